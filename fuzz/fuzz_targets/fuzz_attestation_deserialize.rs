@@ -0,0 +1,16 @@
+//! Deserializes arbitrary bytes as an `Attestation`, like `POST /attestations` does with a
+//! request body. Anything that parses must serialize again.
+//!
+//! Reproduce a crash with the input libFuzzer saved for it:
+//!
+//!     cargo +nightly fuzz run fuzz_attestation_deserialize fuzz/artifacts/fuzz_attestation_deserialize/crash-<hash>
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sdlc_cp_api::model::Attestation;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(attestation) = serde_json::from_slice::<Attestation>(data) {
+        serde_json::to_vec(&attestation).unwrap();
+    }
+});
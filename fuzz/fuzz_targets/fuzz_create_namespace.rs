@@ -0,0 +1,24 @@
+//! Creates a namespace at an arbitrary path. Creation must either succeed, after which the
+//! namespace can be found, or be rejected as an invalid or too deep path.
+//!
+//! Reproduce a crash with the input libFuzzer saved for it:
+//!
+//!     cargo +nightly fuzz run fuzz_create_namespace fuzz/artifacts/fuzz_create_namespace/crash-<hash>
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sdlc_cp_api::services::namespace::{InMemoryNamespaceManager, NamespaceError, NamespaceService, MAX_NAMESPACE_DEPTH};
+
+fuzz_target!(|data: &[u8]| {
+    let path = String::from_utf8_lossy(data);
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    runtime.block_on(async {
+        let mut manager = InMemoryNamespaceManager::new();
+        match manager.create_namespace(&path).await {
+            Ok(()) => assert!(manager.drill_down(&path).await.is_ok()),
+            Err(NamespaceError::InvalidPath) => assert!(InMemoryNamespaceManager::parse_path(&path).is_empty()),
+            Err(NamespaceError::QuotaExceeded { .. }) => assert!(InMemoryNamespaceManager::parse_path(&path).len() > MAX_NAMESPACE_DEPTH),
+            Err(err) => panic!("unexpected error for {:?}: {}", path, err),
+        }
+    });
+});
@@ -0,0 +1,17 @@
+//! Feeds arbitrary bytes through `InMemoryNamespaceManager::parse_path`.
+//!
+//! Reproduce a crash with the input libFuzzer saved for it:
+//!
+//!     cargo +nightly fuzz run fuzz_parse_path fuzz/artifacts/fuzz_parse_path/crash-<hash>
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sdlc_cp_api::services::namespace::InMemoryNamespaceManager;
+
+fuzz_target!(|data: &[u8]| {
+    let path = String::from_utf8_lossy(data);
+    for segment in InMemoryNamespaceManager::parse_path(&path) {
+        assert!(!segment.is_empty());
+        assert!(!segment.contains('/'));
+    }
+});
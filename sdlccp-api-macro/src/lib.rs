@@ -1,17 +1,263 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Attribute, Data, DataEnum, DeriveInput, Fields, GenericParam, LitStr, Type};
 
-#[proc_macro_derive(RegisterSchema)]
+/// Registers the type's JSON schema with the schema generator.
+///
+/// The schema is registered under the type's identifier unless it is overridden with
+/// `#[register_schema(type_name = "...")]`, which is needed when the identifier does not make a
+/// usable file name.
+///
+/// Variants of an enum can be left out of its schema with
+/// `#[register_schema(skip_variants = "MaxAge,RequiredClaims")]`. Tuple variant fields whose
+/// schema may not match what serde writes, like fields serialized `with` a module or of a generic
+/// type, get a warning; skip the variant or give the field a `#[schemars(with = "...")]`.
+///
+/// A generic type is registered with its lifetimes as `'static` and its type and const parameters
+/// set to their defaults, so every parameter needs one; register a concrete newtype otherwise.
+#[proc_macro_derive(RegisterSchema, attributes(register_schema))]
 pub fn register_schema(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let name = &input.ident;
-    
-    let output = quote! {
-        inventory::submit! {
-            crate::SchemaGenerator::new(stringify!(#name), || schemars::schema_for!(#name))
+    let args = RegisterSchemaArgs::parse(&input.attrs)?;
+    let schema_type = schema_type(input)?;
+
+    let type_name = match &args.type_name {
+        Some(type_name) => quote! { #type_name },
+        None => quote! { stringify!(#name) },
+    };
+
+    let (skipped, warnings) = match &input.data {
+        Data::Enum(data) => (skipped_variant_names(input, data, &args)?, tuple_field_warnings(input, data, &args)),
+        _ => {
+            if let Some(skip_variants) = &args.skip_variants {
+                return Err(syn::Error::new_spanned(skip_variants, "`skip_variants` is only supported on enums"));
+            }
+            (Vec::new(), Vec::new())
+        },
+    };
+
+    let generator = if skipped.is_empty() {
+        quote! { || schemars::schema_for!(#schema_type) }
+    } else {
+        quote! {
+            || {
+                let mut root = schemars::schema_for!(#schema_type);
+                let skipped: &[&str] = &[#(#skipped),*];
+                let is_skipped = |value: &schemars::_serde_json::Value| value.as_str().is_some_and(|name| skipped.contains(&name));
+                // Unit variants are listed in an `enum`, the others are objects with the variant as their only property.
+                fn retain_variants(schema: &mut schemars::schema::SchemaObject, is_skipped: &dyn Fn(&schemars::_serde_json::Value) -> bool) {
+                    if let Some(values) = schema.enum_values.as_mut() {
+                        values.retain(|value| !is_skipped(value));
+                    }
+                    let Some(subschemas) = schema.subschemas.as_mut() else { return };
+                    for variants in [&mut subschemas.one_of, &mut subschemas.any_of].into_iter().flatten() {
+                        variants.retain(|variant| match variant {
+                            schemars::schema::Schema::Object(variant) => !variant.object.as_ref().is_some_and(|object| {
+                                object.properties.len() == 1
+                                    && object.properties.keys().all(|key| is_skipped(&schemars::_serde_json::Value::String(key.clone())))
+                            }),
+                            schemars::schema::Schema::Bool(_) => true,
+                        });
+                        for variant in variants.iter_mut() {
+                            if let schemars::schema::Schema::Object(variant) = variant {
+                                retain_variants(variant, is_skipped);
+                            }
+                        }
+                    }
+                }
+                retain_variants(&mut root.schema, &is_skipped);
+                root
+            }
         }
     };
-    
-    output.into()
-}
\ No newline at end of file
+
+    Ok(quote! {
+        #(#warnings)*
+        inventory::submit! {
+            crate::SchemaGenerator::new(#type_name, #generator)
+        }
+    })
+}
+
+/// The concrete type whose schema is registered: `Name` itself, or `Name<...>` with `'static` for
+/// its lifetimes and the defaults of its type and const parameters.
+fn schema_type(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    if input.generics.params.is_empty() {
+        return Ok(quote! { #name });
+    }
+    let mut errors: Option<syn::Error> = None;
+    let mut arguments = Vec::new();
+    for param in &input.generics.params {
+        let (ident, default) = match param {
+            GenericParam::Lifetime(_) => {
+                arguments.push(quote! { 'static });
+                continue;
+            },
+            GenericParam::Type(param) => (&param.ident, param.default.as_ref().map(|default| quote! { #default })),
+            GenericParam::Const(param) => (&param.ident, param.default.as_ref().map(|default| quote! { { #default } })),
+        };
+        match default {
+            Some(default) => arguments.push(default),
+            None => {
+                let error = syn::Error::new_spanned(
+                    param,
+                    format!(
+                        "RegisterSchema needs a concrete type, but `{name}` has no default for `{ident}`; give it a default or register a concrete newtype like `struct My{name}({name}<...>)`"
+                    ),
+                );
+                match &mut errors {
+                    Some(errors) => errors.combine(error),
+                    None => errors = Some(error),
+                }
+            },
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(quote! { #name<#(#arguments),*> }),
+    }
+}
+
+#[derive(Default)]
+struct RegisterSchemaArgs {
+    type_name: Option<LitStr>,
+    skip_variants: Option<LitStr>,
+}
+
+impl RegisterSchemaArgs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut args = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("register_schema")) {
+            attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("type_name") {
+                    &mut args.type_name
+                } else if meta.path.is_ident("skip_variants") {
+                    &mut args.skip_variants
+                } else {
+                    return Err(meta.error("expected `type_name = \"...\"` or `skip_variants = \"...\"`"));
+                };
+                let value = meta.value()?;
+                let lit: syn::Lit = value.parse()?;
+                match lit {
+                    syn::Lit::Str(lit) => *slot = Some(lit),
+                    lit => return Err(syn::Error::new_spanned(lit, "expected a string literal")),
+                }
+                Ok(())
+            })?;
+        }
+        Ok(args)
+    }
+
+    /// The variant identifiers listed in `skip_variants`.
+    fn skip_variants(&self) -> Vec<String> {
+        self.skip_variants.as_ref().map_or_else(Vec::new, |lit| {
+            lit.value().split(',').map(str::trim).filter(|name| !name.is_empty()).map(str::to_string).collect()
+        })
+    }
+}
+
+/// The names the skipped variants have in the schema, which are their serde names.
+fn skipped_variant_names(input: &DeriveInput, data: &DataEnum, args: &RegisterSchemaArgs) -> syn::Result<Vec<String>> {
+    let skip_variants = args.skip_variants();
+    if skip_variants.is_empty() {
+        return Ok(Vec::new());
+    }
+    if serde_value(&input.attrs, "rename_all").is_some() {
+        return Err(syn::Error::new_spanned(&args.skip_variants, "`skip_variants` does not support `#[serde(rename_all = \"...\")]` enums"));
+    }
+    skip_variants
+        .iter()
+        .map(|skipped| {
+            let variant = data.variants.iter().find(|variant| variant.ident == skipped).ok_or_else(|| {
+                syn::Error::new_spanned(&args.skip_variants, format!("`{}` has no variant `{}`", input.ident, skipped))
+            })?;
+            Ok(serde_value(&variant.attrs, "rename").map_or_else(|| skipped.clone(), |rename| rename.value()))
+        })
+        .collect()
+}
+
+/// A warning for every field of a tuple variant whose schema may not describe how it is
+/// serialized. Skipped variants and fields with their own `#[schemars(...)]` schema are left out.
+fn tuple_field_warnings(input: &DeriveInput, data: &DataEnum, args: &RegisterSchemaArgs) -> Vec<proc_macro2::TokenStream> {
+    let skip_variants = args.skip_variants();
+    let mut warnings = Vec::new();
+    for variant in data.variants.iter().filter(|variant| !skip_variants.contains(&variant.ident.to_string())) {
+        let Fields::Unnamed(fields) = &variant.fields else { continue };
+        for field in fields.unnamed.iter().filter(|field| !has_schemars_override(&field.attrs)) {
+            let reason = if ["with", "serialize_with", "deserialize_with"].iter().any(|key| serde_value(&field.attrs, key).is_some()) {
+                "is serialized with custom serde functions"
+            } else if is_generic_parameter(input, &field.ty) {
+                "is a generic parameter"
+            } else {
+                continue;
+            };
+            let note = format!("RegisterSchema: a field of `{}::{}` {}, so its JSON schema may be incomplete", input.ident, variant.ident, reason);
+            let marker = format_ident!("register_schema_{}_{}", input.ident, variant.ident, span = field.ty.span());
+            // Proc macros cannot emit warnings on stable Rust, but using a deprecated item can.
+            warnings.push(quote_spanned! { field.ty.span() =>
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    #[allow(non_upper_case_globals)]
+                    const #marker: () = ();
+                    #marker
+                };
+            });
+        }
+    }
+    warnings
+}
+
+fn has_schemars_override(attrs: &[Attribute]) -> bool {
+    attrs.iter().filter(|attr| attr.path().is_ident("schemars")).any(|attr| {
+        let mut overridden = false;
+        let _ = attr.parse_nested_meta(|meta| {
+            overridden |= meta.path.is_ident("with") || meta.path.is_ident("schema_with");
+            if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+        overridden
+    })
+}
+
+fn is_generic_parameter(input: &DeriveInput, ty: &Type) -> bool {
+    let Type::Path(path) = ty else { return false };
+    path.qself.is_none() && input.generics.type_params().any(|param| path.path.is_ident(&param.ident))
+}
+
+/// The string value of `key` in the `#[serde(...)]` attributes, e.g. `rename`.
+fn serde_value(attrs: &[Attribute], key: &str) -> Option<LitStr> {
+    let mut found = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.input.peek(syn::Token![=]) {
+                let value = meta.value()?.parse::<syn::Expr>()?;
+                if meta.path.is_ident(key) {
+                    if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = value {
+                        found = Some(lit);
+                    }
+                }
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|nested| {
+                    if nested.input.peek(syn::Token![=]) {
+                        nested.value()?.parse::<syn::Expr>()?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        });
+    }
+    found
+}
@@ -0,0 +1,10 @@
+#[test]
+fn register_schema_attributes() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/type_name_override.rs");
+    t.compile_fail("tests/ui/unknown_attribute.rs");
+    t.pass("tests/ui/enum_skip_variants.rs");
+    t.compile_fail("tests/ui/enum_custom_serde_field.rs");
+    t.pass("tests/ui/generic_type_defaults.rs");
+    t.compile_fail("tests/ui/generic_type_without_default.rs");
+}
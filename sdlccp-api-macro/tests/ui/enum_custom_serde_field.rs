@@ -0,0 +1,29 @@
+#![deny(deprecated)]
+
+use schemars::JsonSchema;
+use sdlccp_api_macro::RegisterSchema;
+use serde::Serialize;
+
+pub struct SchemaGenerator;
+
+impl SchemaGenerator {
+    pub const fn new(_type_name: &'static str, _generator: fn() -> schemars::schema::RootSchema) -> Self {
+        Self
+    }
+}
+
+inventory::collect!(SchemaGenerator);
+
+mod seconds {
+    pub fn serialize<S: serde::Serializer>(duration: &std::time::Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_secs())
+    }
+}
+
+#[derive(Serialize, JsonSchema, RegisterSchema)]
+pub enum PolicyRule {
+    MaxAge(#[serde(serialize_with = "seconds::serialize")] std::time::Duration),
+    ApprovedIdentities(Vec<String>),
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use sdlccp_api_macro::RegisterSchema;
+use serde::Serialize;
+
+pub struct SchemaGenerator {
+    pub type_name: &'static str,
+    pub generator: fn() -> schemars::schema::RootSchema,
+}
+
+impl SchemaGenerator {
+    pub const fn new(type_name: &'static str, generator: fn() -> schemars::schema::RootSchema) -> Self {
+        Self { type_name, generator }
+    }
+}
+
+inventory::collect!(SchemaGenerator);
+
+#[derive(Serialize, JsonSchema, RegisterSchema)]
+#[register_schema(skip_variants = "MaxAge, RequiredClaims, Disabled")]
+pub enum PolicyRule {
+    MaxAge(Duration),
+    ApprovedIdentities(Vec<String>),
+    RequiredClaims(HashMap<String, String>),
+    #[serde(rename = "always_pass")]
+    AlwaysPass,
+    Disabled,
+}
+
+fn main() {
+    let generator = inventory::iter::<SchemaGenerator>.into_iter().next().unwrap();
+    assert_eq!(generator.type_name, "PolicyRule");
+    let schema = serde_json::to_string(&(generator.generator)()).unwrap();
+    assert!(schema.contains("ApprovedIdentities"));
+    assert!(schema.contains("always_pass"));
+    assert!(!schema.contains("MaxAge"));
+    assert!(!schema.contains("RequiredClaims"));
+    assert!(!schema.contains("Disabled"));
+}
@@ -0,0 +1,29 @@
+use schemars::JsonSchema;
+use sdlccp_api_macro::RegisterSchema;
+
+pub struct SchemaGenerator {
+    pub type_name: &'static str,
+    pub generator: fn() -> schemars::schema::RootSchema,
+}
+
+impl SchemaGenerator {
+    pub const fn new(type_name: &'static str, generator: fn() -> schemars::schema::RootSchema) -> Self {
+        Self { type_name, generator }
+    }
+}
+
+inventory::collect!(SchemaGenerator);
+
+#[derive(JsonSchema, RegisterSchema)]
+pub struct Page<'a, T = String> {
+    pub cursor: std::borrow::Cow<'a, str>,
+    pub items: Vec<T>,
+}
+
+fn main() {
+    let schemas: Vec<&SchemaGenerator> = inventory::iter::<SchemaGenerator>.into_iter().collect();
+    assert_eq!(schemas.len(), 1);
+    assert_eq!(schemas[0].type_name, "Page");
+    let schema = serde_json::to_value((schemas[0].generator)()).unwrap();
+    assert_eq!(schema["properties"]["items"]["items"]["type"], "string");
+}
@@ -0,0 +1,19 @@
+use schemars::JsonSchema;
+use sdlccp_api_macro::RegisterSchema;
+
+pub struct SchemaGenerator;
+
+impl SchemaGenerator {
+    pub const fn new(_type_name: &'static str, _generator: fn() -> schemars::schema::RootSchema) -> Self {
+        Self
+    }
+}
+
+inventory::collect!(SchemaGenerator);
+
+#[derive(JsonSchema, RegisterSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+use schemars::JsonSchema;
+use sdlccp_api_macro::RegisterSchema;
+
+pub struct SchemaGenerator {
+    pub type_name: &'static str,
+    pub generator: fn() -> schemars::schema::RootSchema,
+}
+
+impl SchemaGenerator {
+    pub const fn new(type_name: &'static str, generator: fn() -> schemars::schema::RootSchema) -> Self {
+        Self { type_name, generator }
+    }
+}
+
+inventory::collect!(SchemaGenerator);
+
+#[derive(JsonSchema)]
+pub struct Release<S> {
+    pub state: S,
+}
+
+#[derive(JsonSchema, RegisterSchema)]
+#[register_schema(type_name = "sdlc_release_dev_draft")]
+pub struct DevDraftRelease(Release<String>);
+
+#[derive(JsonSchema, RegisterSchema)]
+pub struct Policy {
+    pub name: String,
+}
+
+fn main() {
+    let mut names: Vec<&str> = inventory::iter::<SchemaGenerator>.into_iter().map(|schema| schema.type_name).collect();
+    names.sort();
+    assert_eq!(names, ["Policy", "sdlc_release_dev_draft"]);
+}
@@ -0,0 +1,10 @@
+use schemars::JsonSchema;
+use sdlccp_api_macro::RegisterSchema;
+
+#[derive(JsonSchema, RegisterSchema)]
+#[register_schema(file_name = "policy")]
+pub struct Policy {
+    pub name: String,
+}
+
+fn main() {}
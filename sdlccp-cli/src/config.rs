@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::CliError;
+
+/// Server used when neither a flag, the environment nor the config file names one.
+pub const DEFAULT_SERVER_URL: &str = "http://localhost:8080";
+
+/// Settings read from `~/.config/sdlccp/config.toml`. Flags and environment variables override them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CliConfig {
+    pub server_url: Option<String>,
+    /// Bearer token sent with every request.
+    pub token: Option<String>,
+}
+
+impl CliConfig {
+    pub fn path() -> Option<PathBuf> {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/sdlccp/config.toml"))
+    }
+
+    /// The config file, or the defaults if there is none.
+    pub fn load() -> Result<Self, CliError> {
+        let Some(path) = Self::path().filter(|path| path.exists()) else {
+            return Ok(Self::default());
+        };
+        let toml = std::fs::read_to_string(&path).map_err(|err| CliError::Config(format!("cannot read {}: {}", path.display(), err)))?;
+        toml::from_str(&toml).map_err(|err| CliError::Config(format!("{}: {}", path.display(), err)))
+    }
+}
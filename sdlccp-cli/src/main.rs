@@ -0,0 +1,203 @@
+//! Command-line interface to the control plane HTTP API, built on `sdlccp-client`.
+
+use std::fmt;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use sdlc_cp_api::model::{
+    attestation::{ClaimSet, Subject, SubjectType},
+    Attestation, Policy,
+};
+use sdlccp_client::{ClientError, ControlPlaneClient};
+use uuid::Uuid;
+
+use config::{CliConfig, DEFAULT_SERVER_URL};
+use output::{render, render_list, OutputFormat};
+use transition::{Action, TransitionArgs};
+
+mod config;
+mod output;
+mod transition;
+
+#[derive(Debug, Parser)]
+#[command(name = "sdlccp-cli", version, about = "Manage the releases, namespaces, policies and attestations of an SDLC control plane")]
+struct Cli {
+    /// Root URL of the control plane. Falls back to `server_url` in ~/.config/sdlccp/config.toml.
+    #[arg(long, global = true, env = "SDLCCP_SERVER_URL")]
+    server_url: Option<String>,
+
+    /// Bearer token. Falls back to `token` in the config file.
+    #[arg(long, global = true, env = "SDLCCP_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    #[command(subcommand)]
+    Release(ReleaseCommand),
+    #[command(subcommand)]
+    Namespace(NamespaceCommand),
+    #[command(subcommand)]
+    Policy(PolicyCommand),
+    #[command(subcommand)]
+    Attestation(AttestationCommand),
+}
+
+#[derive(Debug, Subcommand)]
+enum ReleaseCommand {
+    Create {
+        #[arg(long)]
+        component_id: Uuid,
+        /// Semantic version of the release.
+        #[arg(long)]
+        version: String,
+        /// Defaults to the subject of the token.
+        #[arg(long)]
+        created_by: Option<String>,
+    },
+    /// Moves a release through its lifecycle.
+    Transition {
+        #[arg(long)]
+        id: Uuid,
+        #[command(flatten)]
+        transition: Box<TransitionArgs>,
+    },
+    Get {
+        #[arg(long)]
+        id: Uuid,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum NamespaceCommand {
+    Create {
+        #[arg(long)]
+        path: String,
+    },
+    /// Lists the top-level namespaces, or those directly below `--path`.
+    List {
+        #[arg(long)]
+        path: Option<String>,
+    },
+    Delete {
+        #[arg(long)]
+        path: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum PolicyCommand {
+    Create {
+        #[arg(long)]
+        name: String,
+        /// Names of the phases the policy applies to.
+        #[arg(long, value_delimiter = ',', required = true)]
+        applies_to: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SubjectTypeArg {
+    Commit,
+    Artifact,
+}
+
+#[derive(Debug, Subcommand)]
+enum AttestationCommand {
+    Create {
+        #[arg(long, value_enum)]
+        subject_type: SubjectTypeArg,
+        #[arg(long)]
+        subject_name: String,
+        #[arg(long)]
+        digest: String,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+    #[error("--{flag} is required for {action}")]
+    MissingFlag { flag: &'static str, action: Action },
+    #[error("Failed to render output: {0}")]
+    Output(String),
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.to_possible_value().expect("no action is skipped");
+        f.write_str(value.get_name())
+    }
+}
+
+impl Cli {
+    /// Flags and environment variables first, then the config file.
+    fn client(&self) -> Result<ControlPlaneClient, CliError> {
+        let config = CliConfig::load()?;
+        let server_url = self.server_url.clone().or(config.server_url).unwrap_or_else(|| DEFAULT_SERVER_URL.to_string());
+        let mut builder = ControlPlaneClient::builder(&server_url);
+        if let Some(token) = self.token.clone().or(config.token) {
+            builder = builder.bearer_token(&token);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+async fn run(cli: Cli) -> Result<String, CliError> {
+    let client = cli.client()?;
+    let output = cli.output;
+    match cli.command {
+        Command::Release(ReleaseCommand::Create { component_id, version, created_by }) => {
+            render(&client.create_release(component_id, &version, created_by.as_deref()).await?, output)
+        },
+        Command::Release(ReleaseCommand::Transition { id, transition }) => {
+            render(&client.transition_release(&id, &transition.into_request()?).await?, output)
+        },
+        Command::Release(ReleaseCommand::Get { id }) => render(&client.get_release(&id).await?, output),
+        Command::Namespace(NamespaceCommand::Create { path }) => {
+            client.create_namespace(&path).await?;
+            Ok(format!("Created namespace {path}"))
+        },
+        Command::Namespace(NamespaceCommand::List { path }) => {
+            let namespaces = match path {
+                Some(path) => client.list_child_namespaces(&path).await?,
+                None => client.list_namespaces().await?,
+            };
+            render_list(&namespaces, output)
+        },
+        Command::Namespace(NamespaceCommand::Delete { path }) => {
+            client.delete_namespace(&path).await?;
+            Ok(format!("Deleted namespace {path}"))
+        },
+        Command::Policy(PolicyCommand::Create { name, applies_to }) => {
+            render(&client.create_policy(&Policy::new(name, applies_to)).await?, output)
+        },
+        Command::Attestation(AttestationCommand::Create { subject_type, subject_name, digest }) => {
+            let type_ = match subject_type {
+                SubjectTypeArg::Commit => SubjectType::Commit,
+                SubjectTypeArg::Artifact => SubjectType::Artifact,
+            };
+            let attestation = Attestation::new(Subject { type_, name: subject_name, digest }, ClaimSet::new());
+            render(&client.store_attestation(&attestation).await?, output)
+        },
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    match run(Cli::parse()).await {
+        Ok(output) => println!("{output}"),
+        Err(err) => {
+            eprintln!("error: {err}");
+            std::process::exit(1);
+        },
+    }
+}
@@ -0,0 +1,101 @@
+use clap::ValueEnum;
+use comfy_table::{presets::NOTHING, Table};
+use sdlc_cp_api::model::{Attestation, Policy, SDLCRelease};
+use serde::Serialize;
+
+use crate::CliError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    Table,
+    Yaml,
+}
+
+/// A resource that can be shown as a row of a table.
+pub trait Tabular {
+    fn header() -> Vec<&'static str>;
+    fn row(&self) -> Vec<String>;
+}
+
+impl Tabular for SDLCRelease {
+    fn header() -> Vec<&'static str> {
+        vec!["ID", "VERSION", "COMPONENT", "PHASE", "STATE", "CREATED BY", "REVISION"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.version.clone(),
+            self.component_id.to_string(),
+            self.phase_name().to_string(),
+            self.state_name().to_string(),
+            self.created_by.clone(),
+            self.revision.to_string(),
+        ]
+    }
+}
+
+impl Tabular for Policy {
+    fn header() -> Vec<&'static str> {
+        vec!["ID", "NAME", "APPLIES TO", "RULES"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.id.to_string(), self.name.clone(), self.applies_to.join(","), self.rules.len().to_string()]
+    }
+}
+
+impl Tabular for Attestation {
+    fn header() -> Vec<&'static str> {
+        vec!["ID", "SUBJECT", "DIGEST", "SIGNATURES"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.id.to_string(), self.subject.name.clone(), self.subject.canonical_digest().to_string(), self.signatures.len().to_string()]
+    }
+}
+
+/// A namespace, by its full path.
+impl Tabular for String {
+    fn header() -> Vec<&'static str> {
+        vec!["NAMESPACE"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.clone()]
+    }
+}
+
+fn table<'a, T: Tabular + 'a>(items: impl IntoIterator<Item = &'a T>) -> String {
+    let mut table = Table::new();
+    table.load_preset(NOTHING).set_header(T::header());
+    for item in items {
+        table.add_row(item.row());
+    }
+    table.to_string()
+}
+
+fn json<T: Serialize + ?Sized>(value: &T) -> Result<String, CliError> {
+    serde_json::to_string_pretty(value).map_err(|err| CliError::Output(err.to_string()))
+}
+
+fn yaml<T: Serialize + ?Sized>(value: &T) -> Result<String, CliError> {
+    serde_yaml::to_string(value).map(|yaml| yaml.trim_end().to_string()).map_err(|err| CliError::Output(err.to_string()))
+}
+
+pub fn render<T: Serialize + Tabular>(item: &T, format: OutputFormat) -> Result<String, CliError> {
+    match format {
+        OutputFormat::Json => json(item),
+        OutputFormat::Table => Ok(table([item])),
+        OutputFormat::Yaml => yaml(item),
+    }
+}
+
+pub fn render_list<T: Serialize + Tabular>(items: &[T], format: OutputFormat) -> Result<String, CliError> {
+    match format {
+        OutputFormat::Json => json(items),
+        OutputFormat::Table => Ok(table(items)),
+        OutputFormat::Yaml => yaml(items),
+    }
+}
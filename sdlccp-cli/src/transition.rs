@@ -0,0 +1,118 @@
+use chrono::Utc;
+use clap::{Args, ValueEnum};
+use sdlc_cp_api::{model::phase::SourceReviewStatus, services::transitions::TransitionRequest};
+
+use crate::CliError;
+
+/// The transitions of `TransitionRequest`, named in kebab case on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Action {
+    StartDevelopment,
+    CompleteDevelopment,
+    StartSourceReview,
+    CompleteSourceReview,
+    StartBuild,
+    CompleteBuild,
+    StartPackaging,
+    CompletePackaging,
+    RequestApproval,
+    RecordApproval,
+    Release,
+    StartDeployment,
+    UpdateCanaryPercentage,
+    CompleteDeployment,
+    Revoke,
+    Fail,
+    Retry,
+}
+
+/// The transition to apply and its arguments. Which flags are required depends on `--action`.
+#[derive(Debug, Args)]
+pub struct TransitionArgs {
+    #[arg(long, value_enum)]
+    action: Action,
+    /// For start-development, start-source-review, start-build and start-packaging.
+    #[arg(long)]
+    started_by: Option<String>,
+    /// A feature of the release, for start-development. Repeat for several.
+    #[arg(long = "feature")]
+    features: Vec<String>,
+    #[arg(long)]
+    commit_hash: Option<String>,
+    #[arg(long)]
+    branch: Option<String>,
+    #[arg(long)]
+    pr_url: Option<String>,
+    /// Who approved the source review, or who records an approval.
+    #[arg(long)]
+    approver: Option<String>,
+    #[arg(long)]
+    comment: Option<String>,
+    #[arg(long)]
+    build_id: Option<String>,
+    /// Whether rebuilding the same source yields an identical artifact, for complete-build.
+    #[arg(long)]
+    reproducible: bool,
+    #[arg(long)]
+    build_tool: Option<String>,
+    #[arg(long)]
+    artifact_hash: Option<String>,
+    #[arg(long)]
+    artifact_url: Option<String>,
+    #[arg(long)]
+    requester: Option<String>,
+    /// An approver whose approval is required, for request-approval. Repeat for several.
+    #[arg(long = "required-approver")]
+    required_approvers: Vec<String>,
+    #[arg(long)]
+    release_notes: Option<String>,
+    /// For start-deployment, which rolls out all at once.
+    #[arg(long)]
+    environment: Option<String>,
+    #[arg(long)]
+    percentage: Option<u8>,
+    /// For revoke and fail.
+    #[arg(long)]
+    reason: Option<String>,
+}
+
+impl TransitionArgs {
+    pub fn into_request(self) -> Result<TransitionRequest, CliError> {
+        let action = self.action;
+        let require = |value: Option<String>, flag: &'static str| value.ok_or(CliError::MissingFlag { flag, action });
+        Ok(match action {
+            Action::StartDevelopment => TransitionRequest::StartDevelopment { started_by: require(self.started_by, "started-by")?, feature_list: self.features },
+            Action::CompleteDevelopment => TransitionRequest::CompleteDevelopment,
+            Action::StartSourceReview => TransitionRequest::StartSourceReview { started_by: require(self.started_by, "started-by")? },
+            Action::CompleteSourceReview => TransitionRequest::CompleteSourceReview {
+                commit_hash: require(self.commit_hash, "commit-hash")?,
+                branch: self.branch.unwrap_or_default(),
+                pr_url: self.pr_url,
+                review_status: SourceReviewStatus::Approved { approver: require(self.approver, "approver")?, approved_at: Utc::now() },
+            },
+            Action::StartBuild => TransitionRequest::StartBuild { started_by: require(self.started_by, "started-by")? },
+            Action::CompleteBuild => TransitionRequest::CompleteBuild {
+                build_id: require(self.build_id, "build-id")?,
+                reproducible: self.reproducible,
+                build_tool: self.build_tool,
+            },
+            Action::StartPackaging => TransitionRequest::StartPackaging { started_by: require(self.started_by, "started-by")? },
+            Action::CompletePackaging => TransitionRequest::CompletePackaging {
+                artifact_hash: require(self.artifact_hash, "artifact-hash")?,
+                artifact_url: require(self.artifact_url, "artifact-url")?,
+                sbom: None,
+            },
+            Action::RequestApproval => TransitionRequest::RequestApproval { requester: require(self.requester, "requester")?, required_approvers: self.required_approvers },
+            Action::RecordApproval => TransitionRequest::RecordApproval { approver: require(self.approver, "approver")?, comment: self.comment },
+            Action::Release => TransitionRequest::Release { release_notes: require(self.release_notes, "release-notes")? },
+            Action::StartDeployment => TransitionRequest::StartDeployment { environment: require(self.environment, "environment")?, rollout_strategy: Default::default() },
+            Action::UpdateCanaryPercentage => TransitionRequest::UpdateCanaryPercentage {
+                percentage: self.percentage.ok_or(CliError::MissingFlag { flag: "percentage", action })?,
+            },
+            Action::CompleteDeployment => TransitionRequest::CompleteDeployment,
+            Action::Revoke => TransitionRequest::Revoke { reason: require(self.reason, "reason")? },
+            Action::Fail => TransitionRequest::Fail { reason: require(self.reason, "reason")? },
+            Action::Retry => TransitionRequest::Retry,
+        })
+    }
+}
@@ -0,0 +1,176 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use assert_cmd::assert::Assert;
+use assert_cmd::Command;
+use axum::{Extension, Router};
+use predicates::prelude::*;
+use predicates::str::contains;
+use sdlc_cp_api::services::attestation::InMemoryAttestationService;
+use sdlc_cp_api::services::audit::InMemoryAuditLogService;
+use sdlc_cp_api::services::auth::{CurrentUser, NAMESPACE_READ, NAMESPACE_WRITE, POLICY_WRITE};
+use sdlc_cp_api::services::controlplane::{attestation_router, namespace_router, policy_router, release_router, NamespaceStore};
+use sdlc_cp_api::services::export::InMemoryExportService;
+use sdlc_cp_api::services::policy_repository::InMemoryPolicyRepository;
+use sdlc_cp_api::services::release_repository::InMemoryReleaseRepository;
+use sdlc_cp_api::services::transitions::TransitionBroadcaster;
+use sdlccp_client::ControlPlaneClient;
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+/// Serves the real namespace, release, policy and attestation routers, called by an operator
+/// holding every role, on a random local port and returns the base URL.
+async fn serve() -> String {
+    let audit = Arc::new(InMemoryAuditLogService::new());
+    let namespaces = Arc::new(NamespaceStore::default());
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let policies = Arc::new(InMemoryPolicyRepository::new());
+    let attestations = Arc::new(InMemoryAttestationService::new());
+    let exports = Arc::new(InMemoryExportService::new(namespaces.clone(), releases.clone(), policies.clone(), attestations.clone()));
+    let operator = CurrentUser {
+        sub: "operator".to_string(),
+        roles: vec![NAMESPACE_READ.to_string(), NAMESPACE_WRITE.to_string(), POLICY_WRITE.to_string()],
+    };
+    let app = Router::new()
+        .nest("/api/v1alpha1/namespaces", namespace_router(namespaces, exports, audit.clone()))
+        .nest("/api/v1alpha1/releases", release_router(releases, TransitionBroadcaster::new(16), audit.clone()))
+        .nest("/api/v1alpha1/policies", policy_router(policies, audit.clone()))
+        .nest("/api/v1alpha1/attestations", attestation_router(attestations, audit))
+        .layer(Extension(operator));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{address}")
+}
+
+/// An empty home directory, so no config file of the user running the tests is read.
+fn home() -> PathBuf {
+    let home = std::env::temp_dir().join(format!("sdlccp-cli-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&home).unwrap();
+    home
+}
+
+/// Runs the CLI off the runtime, which keeps serving requests meanwhile.
+async fn sdlccp(server_url: Option<&str>, home: &Path, args: &[&str]) -> Assert {
+    let mut command = Command::cargo_bin("sdlccp-cli").unwrap();
+    command.env_remove("SDLCCP_SERVER_URL").env_remove("SDLCCP_TOKEN").env("HOME", home).args(args);
+    if let Some(server_url) = server_url {
+        command.env("SDLCCP_SERVER_URL", server_url);
+    }
+    tokio::task::spawn_blocking(move || command.assert()).await.unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_release_lifecycle() {
+    let server = serve().await;
+    let home = home();
+    let component_id = Uuid::new_v4().to_string();
+
+    let created = sdlccp(Some(&server), &home, &["release", "create", "--component-id", &component_id, "--version", "1.0.0", "--created-by", "ci", "--output", "json"])
+        .await
+        .success();
+    let release: serde_json::Value = serde_json::from_slice(&created.get_output().stdout).unwrap();
+    assert_eq!(release["created_by"], "ci");
+    let id = release["id"].as_str().unwrap();
+
+    sdlccp(Some(&server), &home, &["release", "transition", "--id", id, "--action", "start-build"])
+        .await
+        .failure()
+        .stderr(contains("--started-by is required for start-build"));
+    sdlccp(Some(&server), &home, &["release", "transition", "--id", id, "--action", "start-development", "--started-by", "ci", "--feature", "login"])
+        .await
+        .success()
+        .stdout(contains("InProgress"));
+
+    sdlccp(Some(&server), &home, &["release", "get", "--id", id])
+        .await
+        .success()
+        .stdout(contains("PHASE").and(contains("Development")));
+    sdlccp(Some(&server), &home, &["release", "get", "--id", id, "-o", "yaml"])
+        .await
+        .success()
+        .stdout(contains("version: 1.0.0"));
+    sdlccp(Some(&server), &home, &["release", "get", "--id", &Uuid::new_v4().to_string()])
+        .await
+        .failure()
+        .stderr(contains("404"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_namespace_commands() {
+    let server = serve().await;
+    let home = home();
+
+    for path in ["team-a", "team-a/api", "team-a/worker"] {
+        sdlccp(Some(&server), &home, &["namespace", "create", "--path", path]).await.success().stdout(contains(format!("Created namespace {path}")));
+    }
+    sdlccp(Some(&server), &home, &["namespace", "list"]).await.success().stdout(contains("NAMESPACE").and(contains("team-a")));
+    let listed = sdlccp(Some(&server), &home, &["namespace", "list", "--path", "team-a", "--output", "json"]).await.success();
+    let children: Vec<String> = serde_json::from_slice(&listed.get_output().stdout).unwrap();
+    assert_eq!(children, vec!["team-a/api", "team-a/worker"]);
+
+    sdlccp(Some(&server), &home, &["namespace", "delete", "--path", "team-a/api"]).await.success();
+    sdlccp(Some(&server), &home, &["namespace", "delete", "--path", "team-a/api"]).await.failure().stderr(contains("404"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_policy_create() {
+    let server = serve().await;
+    let home = home();
+
+    let created = sdlccp(Some(&server), &home, &["policy", "create", "--name", "Signed builds", "--applies-to", "Build,Deploy", "-o", "json"])
+        .await
+        .success();
+    let policy: serde_json::Value = serde_json::from_slice(&created.get_output().stdout).unwrap();
+    assert_eq!(policy["name"], "Signed builds");
+    assert_eq!(policy["applies_to"], serde_json::json!(["Build", "Deploy"]));
+
+    // The policy was stored by the server, not only echoed back
+    let client = ControlPlaneClient::builder(&server).build().unwrap();
+    let id = policy["id"].as_str().unwrap().parse().unwrap();
+    assert_eq!(client.get_policy(&id).await.unwrap().name, "Signed builds");
+
+    sdlccp(Some(&server), &home, &["policy", "create", "--name", "Reviewed", "--applies-to", "Source"])
+        .await
+        .success()
+        .stdout(contains("APPLIES TO").and(contains("Reviewed")));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_attestation_create() {
+    let server = serve().await;
+    let home = home();
+
+    let created = sdlccp(
+        Some(&server),
+        &home,
+        &["attestation", "create", "--subject-type", "artifact", "--subject-name", "app.tar.gz", "--digest", "sha256:1234", "-o", "json"],
+    )
+    .await
+    .success();
+    let attestation: serde_json::Value = serde_json::from_slice(&created.get_output().stdout).unwrap();
+    assert_eq!(attestation["subject"]["name"], "app.tar.gz");
+
+    let client = ControlPlaneClient::builder(&server).build().unwrap();
+    let id = attestation["id"].as_str().unwrap().parse().unwrap();
+    assert_eq!(client.get_attestation(&id).await.unwrap().subject.digest, "sha256:1234");
+
+    sdlccp(Some(&server), &home, &["attestation", "create", "--subject-type", "commit", "--subject-name", "main", "--digest", "sha1:abcd"])
+        .await
+        .success()
+        .stdout(contains("SUBJECT").and(contains("main")));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_server_url_from_config_file() {
+    let server = serve().await;
+    let home = home();
+    std::fs::create_dir_all(home.join(".config/sdlccp")).unwrap();
+    std::fs::write(home.join(".config/sdlccp/config.toml"), format!("server_url = \"{server}\"\n")).unwrap();
+
+    sdlccp(None, &home, &["namespace", "create", "--path", "from-config"]).await.success();
+    sdlccp(None, &home, &["namespace", "list", "-o", "yaml"]).await.success().stdout(contains("- from-config"));
+
+    std::fs::write(home.join(".config/sdlccp/config.toml"), "server = \"http://localhost\"\n").unwrap();
+    sdlccp(None, &home, &["namespace", "list"]).await.failure().stderr(contains("Invalid configuration"));
+}
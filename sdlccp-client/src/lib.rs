@@ -0,0 +1,262 @@
+//! Typed async HTTP client for the control plane API.
+//!
+//! Each method mirrors one `utoipa`-annotated handler in `sdlc_cp_api::services::controlplane`.
+//! Successful responses are deserialized into the model types; error responses into
+//! [`ErrorBody`], the client-side view of the server's `ApiError`.
+
+use std::time::Duration;
+
+use reqwest::{
+    header::{self, HeaderMap, HeaderValue},
+    Method, StatusCode,
+};
+use sdlc_cp_api::{
+    model::{Attestation, Policy, SDLCRelease},
+    services::{
+        controlplane::{NamespaceCreateRequest, NamespaceGetResponse, NamespaceListResponse, PatchReleaseRequest, ReleaseCreateRequest},
+        policy_enforcement::PolicyEvaluationReport,
+        transitions::TransitionRequest,
+    },
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use url::Url;
+use uuid::Uuid;
+
+/// Path of the versioned API below the server's base URL.
+const API_PREFIX: &str = "api/v1alpha1/";
+
+/// How often a request that failed with a `5xx` status is retried.
+pub const MAX_RETRIES: u32 = 3;
+
+/// Delay before the first retry; it doubles with every further retry.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// The JSON body of an error response, as rendered by the server's `ApiError`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ErrorBody {
+    pub code: String,
+    pub message: String,
+    pub request_id: Option<String>,
+    pub details: Option<serde_json::Value>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("Server returned {status}: {}", body.message)]
+    Api { status: StatusCode, body: ErrorBody },
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+    #[error("Bearer token is not a valid header value")]
+    InvalidBearerToken,
+    #[error("ETag is not a valid header value")]
+    InvalidEtag,
+}
+
+impl ClientError {
+    /// The HTTP status of an error response, if the server answered at all.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ClientError::Api { status, .. } => Some(*status),
+            ClientError::Http(err) => err.status(),
+            _ => None,
+        }
+    }
+}
+
+/// Configures a [`ControlPlaneClient`].
+#[derive(Debug, Clone)]
+pub struct ControlPlaneClientBuilder {
+    base_url: String,
+    bearer_token: Option<String>,
+    timeout: Option<Duration>,
+}
+
+impl ControlPlaneClientBuilder {
+    /// `base_url` is the server root, e.g. `https://controlplane.example.com`, without the API prefix.
+    pub fn new(base_url: &str) -> Self {
+        Self { base_url: base_url.to_string(), bearer_token: None, timeout: None }
+    }
+
+    /// Sent as `Authorization: Bearer <token>` with every request.
+    pub fn bearer_token(mut self, token: &str) -> Self {
+        self.bearer_token = Some(token.to_string());
+        self
+    }
+
+    /// Upper bound for a single attempt, from connecting until the body has been read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn build(self) -> Result<ControlPlaneClient, ClientError> {
+        // Without a trailing slash `Url::join` would replace the last path segment.
+        let mut base_url = Url::parse(&self.base_url)?;
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+
+        let mut headers = HeaderMap::new();
+        if let Some(token) = self.bearer_token {
+            let mut value = HeaderValue::from_str(&format!("Bearer {token}")).map_err(|_| ClientError::InvalidBearerToken)?;
+            value.set_sensitive(true);
+            headers.insert(header::AUTHORIZATION, value);
+        }
+        let mut http = reqwest::Client::builder().default_headers(headers);
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+
+        Ok(ControlPlaneClient { base_url, http: http.build()? })
+    }
+}
+
+/// Client for the control plane HTTP API.
+#[derive(Debug, Clone)]
+pub struct ControlPlaneClient {
+    base_url: Url,
+    http: reqwest::Client,
+}
+
+impl ControlPlaneClient {
+    pub fn builder(base_url: &str) -> ControlPlaneClientBuilder {
+        ControlPlaneClientBuilder::new(base_url)
+    }
+
+    pub async fn create_namespace(&self, path: &str) -> Result<(), ClientError> {
+        let request = NamespaceCreateRequest { namespace: path.to_string() };
+        self.send(Method::POST, "namespaces", Some(&request)).await?;
+        Ok(())
+    }
+
+    /// Lists the top-level namespaces.
+    pub async fn list_namespaces(&self) -> Result<Vec<String>, ClientError> {
+        let response: NamespaceListResponse = self.json(Method::GET, "namespaces", None::<&()>).await?;
+        Ok(response.namespaces)
+    }
+
+    /// Lists the namespaces directly below `path`, by full path.
+    pub async fn list_child_namespaces(&self, path: &str) -> Result<Vec<String>, ClientError> {
+        let path = path.trim_matches('/');
+        let response: NamespaceGetResponse = self.json(Method::GET, &format!("namespaces/{path}"), None::<&()>).await?;
+        let mut children: Vec<String> = response.namespace.children().map(|child| format!("{path}/{}", child.name())).collect();
+        children.sort();
+        Ok(children)
+    }
+
+    pub async fn delete_namespace(&self, path: &str) -> Result<(), ClientError> {
+        self.send(Method::DELETE, &format!("namespaces/{}", path.trim_matches('/')), None::<&()>).await?;
+        Ok(())
+    }
+
+    pub async fn create_policy(&self, policy: &Policy) -> Result<Policy, ClientError> {
+        self.json(Method::POST, "policies", Some(policy)).await
+    }
+
+    pub async fn get_policy(&self, id: &Uuid) -> Result<Policy, ClientError> {
+        self.json(Method::GET, &format!("policies/{id}"), None::<&()>).await
+    }
+
+    /// Stores the attestation and returns it as stored. The server issues it with a new ID and
+    /// timestamp.
+    pub async fn store_attestation(&self, attestation: &Attestation) -> Result<Attestation, ClientError> {
+        self.json(Method::POST, "attestations", Some(attestation)).await
+    }
+
+    pub async fn get_attestation(&self, id: &Uuid) -> Result<Attestation, ClientError> {
+        self.json(Method::GET, &format!("attestations/{id}"), None::<&()>).await
+    }
+
+    /// Creates a release recorded as created by `created_by`, or by the caller if `None`.
+    pub async fn create_release(&self, component_id: Uuid, version: &str, created_by: Option<&str>) -> Result<SDLCRelease, ClientError> {
+        let request = ReleaseCreateRequest { component_id, version: version.to_string(), created_by: created_by.map(str::to_string), sla: None };
+        self.json(Method::POST, "releases", Some(&request)).await
+    }
+
+    pub async fn get_release(&self, id: &Uuid) -> Result<SDLCRelease, ClientError> {
+        self.json(Method::GET, &format!("releases/{id}"), None::<&()>).await
+    }
+
+    /// Applies a transition to the release, whatever its current revision.
+    pub async fn transition_release(&self, id: &Uuid, transition: &TransitionRequest) -> Result<SDLCRelease, ClientError> {
+        self.json(Method::POST, &format!("releases/{id}/transitions"), Some(transition)).await
+    }
+
+    /// Returns the release with its `ETag`, to pass to `patch_release_with_etag`.
+    pub async fn get_release_with_etag(&self, id: &Uuid) -> Result<(SDLCRelease, String), ClientError> {
+        let response = self.send(Method::GET, &format!("releases/{id}"), None::<&()>).await?;
+        let etag = response_etag(&response);
+        Ok((response.json().await?, etag))
+    }
+
+    /// Applies a transition to the release only if it is unchanged since `etag` was read, and
+    /// returns it with its new `ETag`. A concurrent change fails with `412 Precondition Failed`.
+    pub async fn patch_release_with_etag(&self, id: &Uuid, request: &PatchReleaseRequest, etag: &str) -> Result<(SDLCRelease, String), ClientError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(etag).map_err(|_| ClientError::InvalidEtag)?);
+        let response = self.send_with_headers(Method::PATCH, &format!("releases/{id}/phase-transition"), Some(request), headers).await?;
+        let etag = response_etag(&response);
+        Ok((response.json().await?, etag))
+    }
+
+    /// Evaluates the release against one policy. `overall` tells whether it passes.
+    pub async fn apply_policy_to_release(&self, release_id: &Uuid, policy_id: &Uuid) -> Result<PolicyEvaluationReport, ClientError> {
+        self.json(Method::POST, &format!("releases/{release_id}/apply-policy/{policy_id}"), None::<&()>).await
+    }
+
+    /// Evaluates the release against every policy of its component.
+    pub async fn get_policy_report(&self, release_id: &Uuid) -> Result<PolicyEvaluationReport, ClientError> {
+        self.json(Method::GET, &format!("releases/{release_id}/policy-report"), None::<&()>).await
+    }
+
+    async fn json<T: DeserializeOwned>(&self, method: Method, path: &str, body: Option<&impl Serialize>) -> Result<T, ClientError> {
+        Ok(self.send(method, path, body).await?.json().await?)
+    }
+
+    async fn send(&self, method: Method, path: &str, body: Option<&impl Serialize>) -> Result<reqwest::Response, ClientError> {
+        self.send_with_headers(method, path, body, HeaderMap::new()).await
+    }
+
+    /// Sends a request to `path` below the API prefix, retrying with exponential backoff while
+    /// the server answers with a `5xx` status. Any other error status is returned right away.
+    async fn send_with_headers(&self, method: Method, path: &str, body: Option<&impl Serialize>, headers: HeaderMap) -> Result<reqwest::Response, ClientError> {
+        let url = self.base_url.join(API_PREFIX)?.join(path)?;
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            let mut request = self.http.request(method.clone(), url.clone()).headers(headers.clone());
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+            let response = request.send().await?;
+            let status = response.status();
+            if status.is_success() {
+                return Ok(response);
+            }
+            if status.is_server_error() && attempt < MAX_RETRIES {
+                attempt += 1;
+                tracing::warn!(%status, %url, attempt, "Retrying failed request");
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                continue;
+            }
+            return Err(ClientError::Api { status, body: error_body(response).await });
+        }
+    }
+}
+
+fn response_etag(response: &reqwest::Response) -> String {
+    response.headers().get(header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Reads an error response. Bodies that are not an `ApiError`, like the plain-text rejections of
+/// proxies or the rate limiter, are kept as the message under the code `unknown`.
+async fn error_body(response: reqwest::Response) -> ErrorBody {
+    let text = response.text().await.unwrap_or_default();
+    serde_json::from_str(&text).unwrap_or(ErrorBody { code: "unknown".to_string(), message: text, request_id: None, details: None })
+}
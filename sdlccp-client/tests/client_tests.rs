@@ -0,0 +1,192 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::{routing, Extension, Json, Router};
+use sdlc_cp_api::model::attestation::{ClaimSet, Subject, SubjectType};
+use sdlc_cp_api::model::{Attestation, Policy};
+use sdlc_cp_api::services::attestation::InMemoryAttestationService;
+use sdlc_cp_api::services::audit::InMemoryAuditLogService;
+use sdlc_cp_api::services::auth::{CurrentUser, NAMESPACE_READ, NAMESPACE_WRITE, POLICY_WRITE};
+use sdlc_cp_api::services::controlplane::{attestation_router, namespace_router, policy_router, release_router, NamespaceStore, PatchReleaseRequest};
+use sdlc_cp_api::services::export::InMemoryExportService;
+use sdlc_cp_api::services::namespace::InMemoryNamespaceManager;
+use sdlc_cp_api::services::policy_repository::InMemoryPolicyRepository;
+use sdlc_cp_api::services::release_repository::InMemoryReleaseRepository;
+use sdlc_cp_api::services::request_id::RequestIdLayer;
+use sdlc_cp_api::services::transitions::TransitionBroadcaster;
+use sdlccp_client::{ClientError, ControlPlaneClient, MAX_RETRIES};
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Serves `api` under the versioned prefix on a random local port and returns the base URL.
+async fn serve(api: Router) -> String {
+    let app = Router::new().nest("/api/v1alpha1", api).layer(RequestIdLayer);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{address}")
+}
+
+/// The real namespace, release, policy and attestation routers, called by an operator holding
+/// every role.
+fn control_plane() -> Router {
+    let audit = Arc::new(InMemoryAuditLogService::new());
+    let namespaces: Arc<NamespaceStore> = Arc::new(Mutex::new(InMemoryNamespaceManager::new()));
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let policies = Arc::new(InMemoryPolicyRepository::new());
+    let attestations = Arc::new(InMemoryAttestationService::new());
+    let exports = Arc::new(InMemoryExportService::new(namespaces.clone(), releases.clone(), policies.clone(), attestations.clone()));
+    let operator = CurrentUser { sub: "operator".to_string(), roles: vec![NAMESPACE_READ.to_string(), NAMESPACE_WRITE.to_string(), POLICY_WRITE.to_string()] };
+    Router::new()
+        .nest("/namespaces", namespace_router(namespaces, exports, audit.clone()))
+        .nest("/releases", release_router(releases, TransitionBroadcaster::new(16), audit.clone()))
+        .nest("/policies", policy_router(policies, audit.clone()))
+        .nest("/attestations", attestation_router(attestations, audit))
+        .layer(Extension(operator))
+}
+
+#[tokio::test]
+async fn test_namespace_lifecycle() {
+    let client = ControlPlaneClient::builder(&serve(control_plane()).await).build().unwrap();
+
+    client.create_namespace("team-a").await.unwrap();
+    assert_eq!(client.list_namespaces().await.unwrap(), vec!["team-a".to_string()]);
+    client.delete_namespace("team-a").await.unwrap();
+    assert!(client.list_namespaces().await.unwrap().is_empty());
+
+    match client.delete_namespace("team-a").await {
+        Err(ClientError::Api { status, body }) => {
+            assert_eq!(status, StatusCode::NOT_FOUND);
+            assert_eq!(body.code, "namespace_not_found");
+            assert!(body.request_id.is_some());
+        },
+        other => panic!("expected a not found error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_create_release() {
+    let client = ControlPlaneClient::builder(&serve(control_plane()).await)
+        .timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+    let component_id = Uuid::new_v4();
+
+    let release = client.create_release(component_id, "1.2.3", None).await.unwrap();
+
+    assert_eq!(release.component_id, component_id);
+    assert_eq!(release.version, "1.2.3");
+    assert_eq!(release.created_by, "operator");
+
+    let release = client.create_release(component_id, "1.2.4", Some("release-bot")).await.unwrap();
+    assert_eq!(release.created_by, "release-bot");
+    assert_eq!(client.get_release(&release.id).await.unwrap().version, "1.2.4");
+}
+
+#[tokio::test]
+async fn test_patch_release_with_stale_etag_fails() {
+    let client = ControlPlaneClient::builder(&serve(control_plane()).await).build().unwrap();
+    let release = client.create_release(Uuid::new_v4(), "1.2.3", None).await.unwrap();
+    let (_, pipeline_etag) = client.get_release_with_etag(&release.id).await.unwrap();
+    let (_, reviewer_etag) = client.get_release_with_etag(&release.id).await.unwrap();
+
+    let start = PatchReleaseRequest::StartDevelopment { started_by: "ci".to_string(), feature_list: vec!["feature x".to_string()] };
+    let (updated, etag) = client.patch_release_with_etag(&release.id, &start, &pipeline_etag).await.unwrap();
+    assert_eq!(updated.state_name(), "InProgress");
+    assert_ne!(etag, pipeline_etag);
+
+    let revoke = PatchReleaseRequest::Revoke { reason: "not approved".to_string() };
+    let err = client.patch_release_with_etag(&release.id, &revoke, &reviewer_etag).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::PRECONDITION_FAILED));
+}
+
+#[tokio::test]
+async fn test_policy_and_attestation_round_trip() {
+    let client = ControlPlaneClient::builder(&serve(control_plane()).await).build().unwrap();
+
+    let policy = Policy::new("Release Policy".to_string(), vec!["Build".to_string()]);
+    let created = client.create_policy(&policy).await.unwrap();
+    assert_eq!(created.id, policy.id);
+    assert_eq!(client.get_policy(&policy.id).await.unwrap().name, "Release Policy");
+    let err = client.get_policy(&Uuid::new_v4()).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::NOT_FOUND));
+
+    let attestation = Attestation::new(
+        Subject { type_: SubjectType::Artifact, name: "app.tar.gz".to_string(), digest: "sha256:1234".to_string() },
+        ClaimSet::new(),
+    );
+    let stored = client.store_attestation(&attestation).await.unwrap();
+    assert_eq!(stored.subject.name, "app.tar.gz");
+    assert_eq!(client.get_attestation(&stored.id).await.unwrap().subject.digest, "sha256:1234");
+    let Err(err) = client.get_attestation(&Uuid::new_v4()).await else { panic!("expected an unknown attestation to be missing") };
+    assert_eq!(err.status(), Some(StatusCode::NOT_FOUND));
+}
+
+#[tokio::test]
+async fn test_retries_server_errors_with_backoff() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let counter = attempts.clone();
+    let api = Router::new().route("/policies/:id", routing::get(move || async move {
+        // Fails twice before the policy becomes available.
+        if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+            return Err(StatusCode::SERVICE_UNAVAILABLE);
+        }
+        Ok(Json(Policy::new("Release Policy".to_string(), vec!["Build".to_string()])))
+    }));
+    let client = ControlPlaneClient::builder(&serve(api).await).build().unwrap();
+
+    let policy = client.get_policy(&Uuid::new_v4()).await.unwrap();
+
+    assert_eq!(policy.name, "Release Policy");
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_retries() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let counter = attempts.clone();
+    let api = Router::new().route("/releases/:release_id/apply-policy/:policy_id", routing::post(move || async move {
+        counter.fetch_add(1, Ordering::SeqCst);
+        (StatusCode::INTERNAL_SERVER_ERROR, "storage offline")
+    }));
+    let client = ControlPlaneClient::builder(&serve(api).await).build().unwrap();
+
+    let err = client.apply_policy_to_release(&Uuid::new_v4(), &Uuid::new_v4()).await.unwrap_err();
+
+    assert_eq!(err.status(), Some(StatusCode::INTERNAL_SERVER_ERROR));
+    assert!(matches!(err, ClientError::Api { ref body, .. } if body.code == "unknown" && body.message == "storage offline"));
+    assert_eq!(attempts.load(Ordering::SeqCst), MAX_RETRIES + 1);
+}
+
+#[tokio::test]
+async fn test_sends_bearer_token_and_does_not_retry_client_errors() {
+    let attempts = Arc::new(AtomicU32::new(0));
+    let counter = attempts.clone();
+    let attestation = Attestation::new(
+        Subject { type_: SubjectType::Artifact, name: "app.tar.gz".to_string(), digest: "sha256:1234".to_string() },
+        ClaimSet::new(),
+    );
+    let stored = attestation.clone();
+    let api = Router::new().route("/attestations/:id", routing::get(move |headers: HeaderMap| async move {
+        counter.fetch_add(1, Ordering::SeqCst);
+        match headers.get(header::AUTHORIZATION) {
+            Some(value) if value == "Bearer secret" => Ok(Json(stored)),
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }));
+    let base_url = serve(api).await;
+
+    let client = ControlPlaneClient::builder(&base_url).bearer_token("secret").build().unwrap();
+    let found = client.get_attestation(&attestation.id).await.unwrap();
+    assert_eq!(found.id, attestation.id);
+
+    let anonymous = ControlPlaneClient::builder(&base_url).build().unwrap();
+    let Err(err) = anonymous.get_attestation(&attestation.id).await else {
+        panic!("expected the request without a token to be rejected");
+    };
+    assert_eq!(err.status(), Some(StatusCode::UNAUTHORIZED));
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+}
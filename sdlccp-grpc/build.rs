@@ -0,0 +1,10 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Use the vendored protoc so building doesn't depend on a system install.
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    let include = protoc_bin_vendored::include_path()?;
+    tonic_build::configure().compile_protos(
+        &["../proto/sdlccp/v1alpha1/controlplane.proto"],
+        &[std::path::Path::new("../proto"), include.as_path()],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,405 @@
+//! Conversions between the generated protobuf messages and the `sdlc_cp_api` models.
+//!
+//! Model-to-message conversions are infallible. Message-to-model conversions validate IDs and
+//! timestamps and report failures as `InvalidArgument`.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sdlc_cp_api::model::{
+    attestation::{AttestationStatus, ClaimSet, SBOMFormat, Signature, Subject, SubjectType},
+    policy::{PolicyRule, VulnerabilityLevel},
+    Attestation, Policy, ReleaseState, SDLCPhase, SDLCRelease,
+};
+use sdlc_cp_api::services::transitions::TransitionEvent;
+use tonic::Status;
+use uuid::Uuid;
+
+use crate::proto;
+
+pub(crate) fn parse_uuid(field: &str, value: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(value).map_err(|err| Status::invalid_argument(format!("invalid {}: {}", field, err)))
+}
+
+fn timestamp(time: &DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: time.timestamp(),
+        nanos: time.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn date_time(field: &str, time: prost_types::Timestamp) -> Result<DateTime<Utc>, Status> {
+    u32::try_from(time.nanos)
+        .ok()
+        .and_then(|nanos| DateTime::from_timestamp(time.seconds, nanos))
+        .ok_or_else(|| Status::invalid_argument(format!("invalid {}", field)))
+}
+
+fn duration(duration: &Duration) -> prost_types::Duration {
+    prost_types::Duration {
+        seconds: duration.as_secs() as i64,
+        nanos: duration.subsec_nanos() as i32,
+    }
+}
+
+fn std_duration(field: &str, duration: prost_types::Duration) -> Result<Duration, Status> {
+    Duration::try_from(duration).map_err(|err| Status::invalid_argument(format!("invalid {}: {}", field, err)))
+}
+
+impl From<&SDLCRelease> for proto::Release {
+    fn from(release: &SDLCRelease) -> Self {
+        Self {
+            id: release.id.to_string(),
+            component_id: release.component_id.to_string(),
+            version: release.version.clone(),
+            created_by: release.created_by.clone(),
+            created_at: Some(timestamp(&release.created_at)),
+            commit_hash: release.commit_hash.clone(),
+            dependencies: release.dependencies.iter().map(Uuid::to_string).collect(),
+            parent_release_id: release.parent_release_id.map(|id| id.to_string()),
+            release_notes: release.release_notes.clone(),
+            estimated_deploy_time: release.estimated_deploy_time.as_ref().map(timestamp),
+            phase_attestations: release.phase_attestations.iter()
+                .map(|(phase, attestation_id)| (phase.clone(), attestation_id.to_string()))
+                .collect(),
+            state: Some((&release.state).into()),
+            phase: release.phase_name().to_string(),
+            tags: release.list_tags().into_iter().map(str::to_string).collect(),
+            revision: release.revision,
+            applied_policies: {
+                let mut applied_policies: Vec<Uuid> = release.applied_policies.iter().copied().collect();
+                applied_policies.sort();
+                applied_policies.iter().map(Uuid::to_string).collect()
+            },
+        }
+    }
+}
+
+impl From<&ReleaseState> for proto::ReleaseState {
+    fn from(state: &ReleaseState) -> Self {
+        use proto::release_state::{Actor, PartiallyDeployed, State};
+
+        let actor = |name: &String, at: &DateTime<Utc>| Actor { name: name.clone(), at: Some(timestamp(at)) };
+        let state = match state {
+            ReleaseState::Draft => State::Draft(()),
+            ReleaseState::InProgress { started_by, started_at } => State::InProgress(actor(started_by, started_at)),
+            ReleaseState::PolicyCheckPending { requested_by, requested_at } => State::PolicyCheckPending(actor(requested_by, requested_at)),
+            ReleaseState::Releasable { approved_by, approved_at } => State::Releasable(actor(approved_by, approved_at)),
+            ReleaseState::Released { release_notes, release_time } => State::Released(actor(release_notes, release_time)),
+            ReleaseState::PartiallyDeployed { percentage, environment } => State::PartiallyDeployed(PartiallyDeployed {
+                percentage: (*percentage).into(),
+                environment: environment.clone(),
+            }),
+            ReleaseState::Deployed { environment, deployment_time } => State::Deployed(actor(environment, deployment_time)),
+            ReleaseState::Revoked { reason, revocation_time } => State::Revoked(actor(reason, revocation_time)),
+            ReleaseState::Suspended { reason, suspended_at, .. } => State::Suspended(actor(reason, suspended_at)),
+            ReleaseState::Failed { reason, failed_at, .. } => State::Failed(actor(reason, failed_at)),
+            ReleaseState::Custom(name) => State::Custom(name.clone()),
+        };
+        Self { state: Some(state) }
+    }
+}
+
+impl From<&Policy> for proto::Policy {
+    fn from(policy: &Policy) -> Self {
+        Self {
+            id: policy.id.to_string(),
+            name: policy.name.clone(),
+            rules: policy.rules.iter().map(Into::into).collect(),
+            parent_policies: policy.parent_policies.iter().map(Uuid::to_string).collect(),
+            applies_to: policy.applies_to.clone(),
+        }
+    }
+}
+
+impl From<&PolicyRule> for proto::PolicyRule {
+    fn from(rule: &PolicyRule) -> Self {
+        use proto::policy_rule::{ActiveForPhases, ApprovedIdentities, BlockedPackages, ForbiddenClaims, PhaseCompletedWithin, RequiredClaims, Rule, VulnerabilityThreshold};
+
+        let rule = match rule {
+            PolicyRule::MaxAge(max_age) => Rule::MaxAge(duration(max_age)),
+            PolicyRule::ApprovedIdentities(identities) => Rule::ApprovedIdentities(ApprovedIdentities {
+                identities: identities.clone(),
+            }),
+            PolicyRule::RequiredClaims(claims) => Rule::RequiredClaims(RequiredClaims { claims: claims.clone() }),
+            PolicyRule::ForbiddenClaims(claims) => Rule::ForbiddenClaims(ForbiddenClaims { claims: claims.clone() }),
+            PolicyRule::VulnerabilityThreshold(level, max_count) => Rule::VulnerabilityThreshold(VulnerabilityThreshold {
+                level: proto::VulnerabilityLevel::from(level).into(),
+                max_count: *max_count,
+            }),
+            PolicyRule::MaxVulnerabilityAge(max_age) => Rule::MaxVulnerabilityAge(duration(max_age)),
+            PolicyRule::BlockedPackages(packages) => Rule::BlockedPackages(BlockedPackages { packages: packages.clone() }),
+            PolicyRule::RequiresSBOM(format) => Rule::RequiresSbom(proto::SbomFormat::from(format).into()),
+            PolicyRule::RequireReproducibleBuild => Rule::RequireReproducibleBuild(()),
+            PolicyRule::BlockSuspendedReleases => Rule::BlockSuspendedReleases(()),
+            PolicyRule::RequireApprovedSourceReview => Rule::RequireApprovedSourceReview(()),
+            PolicyRule::RequireHealthyRuntime => Rule::RequireHealthyRuntime(()),
+            PolicyRule::RequireTeamOwnership(team_id) => Rule::RequireTeamOwnership(team_id.to_string()),
+            PolicyRule::MinVersion(requirement) => Rule::MinVersion(requirement.to_string()),
+            PolicyRule::RequireReleaseNotes => Rule::RequireReleaseNotes(()),
+            PolicyRule::PhaseCompletedWithin(phase, within) => Rule::PhaseCompletedWithin(PhaseCompletedWithin {
+                phase: phase.name().to_string(),
+                within: Some(duration(within)),
+            }),
+            PolicyRule::NoRevokedAttestations => Rule::NoRevokedAttestations(()),
+            PolicyRule::ActiveForPhases(phases) => Rule::ActiveForPhases(ActiveForPhases {
+                phases: phases.iter().map(|phase| phase.name().to_string()).collect(),
+            }),
+        };
+        Self { rule: Some(rule) }
+    }
+}
+
+impl From<&VulnerabilityLevel> for proto::VulnerabilityLevel {
+    fn from(level: &VulnerabilityLevel) -> Self {
+        match level {
+            VulnerabilityLevel::Low => proto::VulnerabilityLevel::Low,
+            VulnerabilityLevel::Medium => proto::VulnerabilityLevel::Medium,
+            VulnerabilityLevel::High => proto::VulnerabilityLevel::High,
+            VulnerabilityLevel::Critical => proto::VulnerabilityLevel::Critical,
+        }
+    }
+}
+
+impl From<&SBOMFormat> for proto::SbomFormat {
+    fn from(format: &SBOMFormat) -> Self {
+        match format {
+            SBOMFormat::Spdx2 => proto::SbomFormat::Spdx2,
+            SBOMFormat::Spdx3 => proto::SbomFormat::Spdx3,
+            SBOMFormat::CycloneDX14 => proto::SbomFormat::CycloneDx14,
+            SBOMFormat::CycloneDX15 => proto::SbomFormat::CycloneDx15,
+        }
+    }
+}
+
+impl From<proto::SbomFormat> for SBOMFormat {
+    fn from(format: proto::SbomFormat) -> Self {
+        match format {
+            proto::SbomFormat::Spdx2 => SBOMFormat::Spdx2,
+            proto::SbomFormat::Spdx3 => SBOMFormat::Spdx3,
+            proto::SbomFormat::CycloneDx14 => SBOMFormat::CycloneDX14,
+            proto::SbomFormat::CycloneDx15 => SBOMFormat::CycloneDX15,
+        }
+    }
+}
+
+impl TryFrom<proto::Policy> for Policy {
+    type Error = Status;
+
+    fn try_from(policy: proto::Policy) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: if policy.id.is_empty() { Uuid::new_v4() } else { parse_uuid("policy id", &policy.id)? },
+            name: policy.name,
+            rules: policy.rules.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            parent_policies: policy.parent_policies.iter()
+                .map(|id| parse_uuid("parent policy id", id))
+                .collect::<Result<_, _>>()?,
+            applies_to: policy.applies_to,
+        })
+    }
+}
+
+impl TryFrom<proto::PolicyRule> for PolicyRule {
+    type Error = Status;
+
+    fn try_from(rule: proto::PolicyRule) -> Result<Self, Self::Error> {
+        use proto::policy_rule::Rule;
+
+        match rule.rule.ok_or_else(|| Status::invalid_argument("policy rule is empty"))? {
+            Rule::MaxAge(max_age) => Ok(PolicyRule::MaxAge(std_duration("max_age", max_age)?)),
+            Rule::ApprovedIdentities(approved) => Ok(PolicyRule::ApprovedIdentities(approved.identities)),
+            Rule::RequiredClaims(required) => Ok(PolicyRule::RequiredClaims(required.claims)),
+            Rule::ForbiddenClaims(forbidden) => Ok(PolicyRule::ForbiddenClaims(forbidden.claims)),
+            Rule::VulnerabilityThreshold(threshold) => {
+                let level = match threshold.level() {
+                    proto::VulnerabilityLevel::Low => VulnerabilityLevel::Low,
+                    proto::VulnerabilityLevel::Medium => VulnerabilityLevel::Medium,
+                    proto::VulnerabilityLevel::High => VulnerabilityLevel::High,
+                    proto::VulnerabilityLevel::Critical => VulnerabilityLevel::Critical,
+                };
+                Ok(PolicyRule::VulnerabilityThreshold(level, threshold.max_count))
+            }
+            Rule::MaxVulnerabilityAge(max_age) => {
+                Ok(PolicyRule::MaxVulnerabilityAge(std_duration("max_vulnerability_age", max_age)?))
+            }
+            Rule::BlockedPackages(blocked) => Ok(PolicyRule::BlockedPackages(blocked.packages)),
+            Rule::RequiresSbom(format) => {
+                let format = proto::SbomFormat::try_from(format)
+                    .map_err(|_| Status::invalid_argument(format!("unknown SBOM format {}", format)))?;
+                Ok(PolicyRule::RequiresSBOM(format.into()))
+            }
+            Rule::RequireReproducibleBuild(()) => Ok(PolicyRule::RequireReproducibleBuild),
+            Rule::BlockSuspendedReleases(()) => Ok(PolicyRule::BlockSuspendedReleases),
+            Rule::RequireApprovedSourceReview(()) => Ok(PolicyRule::RequireApprovedSourceReview),
+            Rule::RequireHealthyRuntime(()) => Ok(PolicyRule::RequireHealthyRuntime),
+            Rule::RequireTeamOwnership(team_id) => Ok(PolicyRule::RequireTeamOwnership(parse_uuid("require_team_ownership", &team_id)?)),
+            Rule::MinVersion(requirement) => {
+                let requirement = semver::VersionReq::parse(&requirement)
+                    .map_err(|err| Status::invalid_argument(format!("invalid min_version {:?}: {}", requirement, err)))?;
+                Ok(PolicyRule::MinVersion(requirement))
+            }
+            Rule::RequireReleaseNotes(()) => Ok(PolicyRule::RequireReleaseNotes),
+            Rule::NoRevokedAttestations(()) => Ok(PolicyRule::NoRevokedAttestations),
+            Rule::ActiveForPhases(rule) => Ok(PolicyRule::ActiveForPhases(rule.phases.iter().map(|phase| SDLCPhase::from_name(phase)).collect())),
+            Rule::PhaseCompletedWithin(rule) => {
+                let within = rule.within.ok_or_else(|| Status::invalid_argument("phase_completed_within has no duration"))?;
+                Ok(PolicyRule::PhaseCompletedWithin(SDLCPhase::from_name(&rule.phase), std_duration("phase_completed_within", within)?))
+            }
+        }
+    }
+}
+
+impl From<&Attestation> for proto::Attestation {
+    fn from(attestation: &Attestation) -> Self {
+        Self {
+            id: attestation.id.to_string(),
+            subject: Some((&attestation.subject).into()),
+            timestamp: Some(timestamp(&attestation.timestamp)),
+            expiration: attestation.expiration.as_ref().map(timestamp),
+            signatures: attestation.signatures.iter()
+                .map(|signature| proto::Signature {
+                    signer: signature.signer.clone(),
+                    signature: signature.signature.clone(),
+                })
+                .collect(),
+            claims: attestation.claims.iter()
+                .map(|(key, value)| (key.clone(), value.to_string()))
+                .collect(),
+            parent_attestations: attestation.parent_attestations.iter().map(Uuid::to_string).collect(),
+            revocation: match &attestation.status {
+                AttestationStatus::Active => None,
+                AttestationStatus::Revoked { reason, revoked_at, revoked_by } => Some(proto::Revocation {
+                    reason: reason.clone(),
+                    revoked_at: Some(timestamp(revoked_at)),
+                    revoked_by: revoked_by.clone(),
+                }),
+            },
+            release_id: attestation.release_id.as_ref().map(Uuid::to_string),
+            predicate_type: attestation.predicate_type.clone(),
+        }
+    }
+}
+
+impl From<&Subject> for proto::Subject {
+    fn from(subject: &Subject) -> Self {
+        use proto::subject::{Container, Details, GitTag, SbomDocument};
+
+        let (type_, details) = match &subject.type_ {
+            SubjectType::Commit => (proto::SubjectType::Commit, None),
+            SubjectType::Artifact => (proto::SubjectType::Artifact, None),
+            SubjectType::Deployment => (proto::SubjectType::Deployment, None),
+            SubjectType::Container { registry, repository, digest } => (
+                proto::SubjectType::Container,
+                Some(Details::Container(Container {
+                    registry: registry.clone(),
+                    repository: repository.clone(),
+                    digest: digest.clone(),
+                })),
+            ),
+            SubjectType::GitTag { repository_url, tag_name, commit_hash } => (
+                proto::SubjectType::GitTag,
+                Some(Details::GitTag(GitTag {
+                    repository_url: repository_url.clone(),
+                    tag_name: tag_name.clone(),
+                    commit_hash: commit_hash.clone(),
+                })),
+            ),
+            SubjectType::SBOMDocument { format, document_url } => (
+                proto::SubjectType::SbomDocument,
+                Some(Details::SbomDocument(SbomDocument {
+                    format: proto::SbomFormat::from(format).into(),
+                    document_url: document_url.clone(),
+                })),
+            ),
+        };
+        Self {
+            r#type: type_.into(),
+            name: subject.name.clone(),
+            digest: subject.digest.clone(),
+            details,
+        }
+    }
+}
+
+impl TryFrom<proto::Subject> for Subject {
+    type Error = Status;
+
+    fn try_from(subject: proto::Subject) -> Result<Self, Self::Error> {
+        use proto::subject::Details;
+
+        let type_ = match (subject.r#type(), subject.details) {
+            (proto::SubjectType::Commit, _) => SubjectType::Commit,
+            (proto::SubjectType::Artifact, _) => SubjectType::Artifact,
+            (proto::SubjectType::Deployment, _) => SubjectType::Deployment,
+            (proto::SubjectType::Container, Some(Details::Container(container))) => SubjectType::Container {
+                registry: container.registry,
+                repository: container.repository,
+                digest: container.digest,
+            },
+            (proto::SubjectType::GitTag, Some(Details::GitTag(tag))) => SubjectType::GitTag {
+                repository_url: tag.repository_url,
+                tag_name: tag.tag_name,
+                commit_hash: tag.commit_hash,
+            },
+            (proto::SubjectType::SbomDocument, Some(Details::SbomDocument(sbom))) => {
+                SubjectType::SBOMDocument { format: sbom.format().into(), document_url: sbom.document_url }
+            }
+            (type_, _) => {
+                return Err(Status::invalid_argument(format!("subject type {} requires matching details", type_.as_str_name())))
+            }
+        };
+        Ok(Self { type_, name: subject.name, digest: subject.digest })
+    }
+}
+
+impl TryFrom<proto::Attestation> for Attestation {
+    type Error = Status;
+
+    /// An empty `id` or missing `timestamp` is filled in, so clients can submit new attestations
+    /// without generating either themselves.
+    fn try_from(attestation: proto::Attestation) -> Result<Self, Self::Error> {
+        let subject = attestation.subject.ok_or_else(|| Status::invalid_argument("attestation subject is required"))?;
+        let claims = attestation.claims.into_iter()
+            .map(|(key, value)| {
+                serde_json::from_str(&value)
+                    .map(|value| (key.clone(), value))
+                    .map_err(|err| Status::invalid_argument(format!("claim {} is not valid JSON: {}", key, err)))
+            })
+            .collect::<Result<ClaimSet, _>>()?;
+
+        Ok(Self {
+            id: if attestation.id.is_empty() { Uuid::new_v4() } else { parse_uuid("attestation id", &attestation.id)? },
+            subject: subject.try_into()?,
+            timestamp: attestation.timestamp.map(|time| date_time("timestamp", time)).transpose()?.unwrap_or_else(Utc::now),
+            expiration: attestation.expiration.map(|time| date_time("expiration", time)).transpose()?,
+            signatures: attestation.signatures.into_iter()
+                .map(|signature| Signature { signer: signature.signer, signature: signature.signature })
+                .collect(),
+            claims,
+            parent_attestations: attestation.parent_attestations.iter()
+                .map(|id| parse_uuid("parent attestation id", id))
+                .collect::<Result<_, _>>()?,
+            status: match attestation.revocation {
+                None => AttestationStatus::Active,
+                Some(revocation) => AttestationStatus::Revoked {
+                    reason: revocation.reason,
+                    revoked_at: revocation.revoked_at.map(|time| date_time("revoked_at", time)).transpose()?.unwrap_or_else(Utc::now),
+                    revoked_by: revocation.revoked_by,
+                },
+            },
+            release_id: attestation.release_id.as_deref().map(|id| parse_uuid("release id", id)).transpose()?,
+            predicate_type: attestation.predicate_type,
+        })
+    }
+}
+
+impl From<&TransitionEvent> for proto::TransitionEvent {
+    fn from(event: &TransitionEvent) -> Self {
+        Self {
+            release_id: event.release_id.to_string(),
+            from_state: event.from_state.clone(),
+            to_state: event.to_state.clone(),
+            timestamp: Some(timestamp(&event.timestamp)),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! gRPC front end for the SDLC control plane, generated from `proto/sdlccp/v1alpha1`.
+
+// `tonic::Status` is the error type the generated service trait dictates.
+#![allow(clippy::result_large_err)]
+
+mod convert;
+mod service;
+
+pub mod proto {
+    tonic::include_proto!("sdlccp.v1alpha1");
+}
+
+pub use proto::control_plane_client::ControlPlaneClient;
+pub use proto::control_plane_server::ControlPlaneServer;
+pub use service::ControlPlaneGrpcService;
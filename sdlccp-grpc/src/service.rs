@@ -0,0 +1,264 @@
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use sdlc_cp_api::model::{Attestation, Policy, SDLCRelease};
+use sdlc_cp_api::services::{
+    audit::{AuditAction, AuditEvent},
+    auth::{AuthError, CurrentUser, NAMESPACE_READ, NAMESPACE_WRITE, POLICY_WRITE},
+    controlplane::{record_audit_event, AuditStore, ControlPlane, ControlPlaneError, NamespaceStore},
+    namespace::{NamespaceError, NamespaceService},
+    release_repository::{ReleaseRepository, ReleaseRepositoryError},
+    transitions::{TransitionBroadcaster, TransitionEvent},
+};
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::{wrappers::ReceiverStream, Stream};
+use uuid::Uuid;
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::convert::parse_uuid;
+use crate::proto::{
+    self, control_plane_server::ControlPlane as GrpcControlPlane, AttestationId, CreateReleaseRequest,
+    Namespace, NamespaceList, PolicyId, ReleaseId,
+};
+
+/// Serves the `sdlccp.v1alpha1.ControlPlane` gRPC service on top of the same services the HTTP
+/// API uses, recording every mutation in the same audit log.
+///
+/// Namespace and policy calls need the same roles as over HTTP, checked against the
+/// `CurrentUser` an authenticating interceptor adds to the request extensions.
+pub struct ControlPlaneGrpcService {
+    control_plane: Arc<dyn ControlPlane>,
+    releases: Arc<dyn ReleaseRepository>,
+    namespaces: Arc<NamespaceStore>,
+    transitions: TransitionBroadcaster,
+    audit: Arc<AuditStore>,
+}
+
+impl ControlPlaneGrpcService {
+    pub fn new(
+        control_plane: Arc<dyn ControlPlane>,
+        releases: Arc<dyn ReleaseRepository>,
+        namespaces: Arc<NamespaceStore>,
+        transitions: TransitionBroadcaster,
+        audit: Arc<AuditStore>,
+    ) -> Self {
+        Self { control_plane, releases, namespaces, transitions, audit }
+    }
+}
+
+/// The actor recorded on audit events: the authenticated user, or `anonymous` when the service
+/// is served without authentication.
+fn request_actor<T>(request: &Request<T>) -> String {
+    request.extensions().get::<CurrentUser>()
+        .map(|user| user.sub.clone())
+        .unwrap_or_else(|| "anonymous".to_string())
+}
+
+/// Rejects calls whose `CurrentUser` lacks `role`, like the HTTP API's `require_role`: with
+/// `UNAUTHENTICATED` when there is no user at all and `PERMISSION_DENIED` otherwise.
+fn require_role<T>(request: &Request<T>, role: &str) -> Result<(), Status> {
+    match request.extensions().get::<CurrentUser>() {
+        None => Err(Status::unauthenticated(AuthError::MissingToken.to_string())),
+        Some(user) if !user.has_role(role) => Err(Status::permission_denied(AuthError::MissingRole(role.to_string()).to_string())),
+        Some(_) => Ok(()),
+    }
+}
+
+fn control_plane_status(err: ControlPlaneError) -> Status {
+    match err {
+        ControlPlaneError::NoPolicyFound | ControlPlaneError::ReleaseNotFound => Status::not_found(err.to_string()),
+        _ => Status::internal(err.to_string()),
+    }
+}
+
+fn release_status(err: ReleaseRepositoryError) -> Status {
+    match err {
+        ReleaseRepositoryError::NotFound => Status::not_found(err.to_string()),
+        ReleaseRepositoryError::RevisionMismatch { .. } => Status::failed_precondition(err.to_string()),
+        _ => Status::internal(err.to_string()),
+    }
+}
+
+fn namespace_status(err: NamespaceError) -> Status {
+    match err {
+        NamespaceError::NotFound => Status::not_found("Namespace not found"),
+        NamespaceError::AlreadyExists => Status::already_exists("Namespace already exists"),
+        NamespaceError::InvalidPath => Status::invalid_argument("Invalid namespace path"),
+        NamespaceError::PermissionDenied => Status::permission_denied("Permission denied"),
+        NamespaceError::PersistenceError(_) => Status::internal(err.to_string()),
+        NamespaceError::QuotaExceeded { .. } => Status::resource_exhausted(err.to_string()),
+    }
+}
+
+type TransitionStream = Pin<Box<dyn Stream<Item = Result<proto::TransitionEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl GrpcControlPlane for ControlPlaneGrpcService {
+    #[tracing::instrument(skip_all, fields(component_id = %request.get_ref().component_id))]
+    async fn create_release(&self, request: Request<CreateReleaseRequest>) -> Result<Response<proto::Release>, Status> {
+        let actor = request_actor(&request);
+        let request = request.into_inner();
+        let component_id = parse_uuid("component_id", &request.component_id)?;
+        let release = SDLCRelease::new(component_id, request.version, request.created_by)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        self.releases.store_release(release.clone()).await.map_err(release_status)?;
+        record_audit_event(
+            self.audit.as_ref(),
+            AuditEvent::new(actor, AuditAction::CreateRelease, release.id, "release")
+                .with_metadata("version", serde_json::Value::String(release.version.clone())),
+        ).await;
+        tracing::info!(release_id = %release.id, "Created release");
+        Ok(Response::new((&release).into()))
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %request.get_ref().id))]
+    async fn get_release(&self, request: Request<ReleaseId>) -> Result<Response<proto::Release>, Status> {
+        let id = parse_uuid("release id", &request.into_inner().id)?;
+        let release = self.releases.get_release(&id).await
+            .map_err(release_status)?
+            .ok_or_else(|| release_status(ReleaseRepositoryError::NotFound))?;
+        Ok(Response::new((&release).into()))
+    }
+
+    #[tracing::instrument(skip_all, fields(policy_id = %request.get_ref().id))]
+    async fn store_policy(&self, request: Request<proto::Policy>) -> Result<Response<proto::Policy>, Status> {
+        require_role(&request, POLICY_WRITE)?;
+        let actor = request_actor(&request);
+        let policy: Policy = request.into_inner().try_into()?;
+        let response = proto::Policy::from(&policy);
+        let event = AuditEvent::new(actor, AuditAction::CreatePolicy, policy.id, "policy")
+            .with_metadata("name", serde_json::Value::String(policy.name.clone()));
+        self.control_plane.store_policy(policy).await.map_err(control_plane_status)?;
+        record_audit_event(self.audit.as_ref(), event).await;
+        Ok(Response::new(response))
+    }
+
+    #[tracing::instrument(skip_all, fields(policy_id = %request.get_ref().id))]
+    async fn get_policy(&self, request: Request<PolicyId>) -> Result<Response<proto::Policy>, Status> {
+        let id = parse_uuid("policy id", &request.into_inner().id)?;
+        let policy = self.control_plane.get_policy(&id).await
+            .map_err(control_plane_status)?
+            .ok_or_else(|| Status::not_found("Policy not found"))?;
+        Ok(Response::new((&policy).into()))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn store_attestation(&self, request: Request<proto::Attestation>) -> Result<Response<proto::Attestation>, Status> {
+        let actor = request_actor(&request);
+        let attestation: Attestation = request.into_inner().try_into()?;
+        let response = proto::Attestation::from(&attestation);
+        let event = AuditEvent::new(actor, AuditAction::StoreAttestation, attestation.id, "attestation")
+            .with_metadata("subject", serde_json::Value::String(attestation.subject.name.clone()));
+        self.control_plane.store_attestation(attestation).await.map_err(control_plane_status)?;
+        record_audit_event(self.audit.as_ref(), event).await;
+        Ok(Response::new(response))
+    }
+
+    #[tracing::instrument(skip_all, fields(attestation_id = %request.get_ref().id))]
+    async fn get_attestation(&self, request: Request<AttestationId>) -> Result<Response<proto::Attestation>, Status> {
+        let id = parse_uuid("attestation id", &request.into_inner().id)?;
+        let attestation = self.control_plane.get_attestation(&id).await
+            .map_err(control_plane_status)?
+            .ok_or_else(|| Status::not_found("Attestation not found"))?;
+        Ok(Response::new((&attestation).into()))
+    }
+
+    #[tracing::instrument(skip_all, fields(namespace = %request.get_ref().path))]
+    async fn create_namespace(&self, request: Request<Namespace>) -> Result<Response<Namespace>, Status> {
+        require_role(&request, NAMESPACE_WRITE)?;
+        let actor = request_actor(&request);
+        let namespace = request.into_inner();
+        self.namespaces.lock().await.create_namespace(&namespace.path).await.map_err(namespace_status)?;
+        record_audit_event(
+            self.audit.as_ref(),
+            AuditEvent::new(actor, AuditAction::CreateNamespace, Uuid::nil(), "namespace")
+                .with_metadata("path", serde_json::Value::String(namespace.path.clone())),
+        ).await;
+        Ok(Response::new(namespace))
+    }
+
+    #[tracing::instrument(skip_all, fields(namespace = %request.get_ref().path))]
+    async fn list_namespaces(&self, request: Request<Namespace>) -> Result<Response<NamespaceList>, Status> {
+        require_role(&request, NAMESPACE_READ)?;
+        let paths = self.namespaces.lock().await.list_namespaces(&request.into_inner().path).await.map_err(namespace_status)?;
+        Ok(Response::new(NamespaceList { paths }))
+    }
+
+    #[tracing::instrument(skip_all, fields(namespace = %request.get_ref().path))]
+    async fn delete_namespace(&self, request: Request<Namespace>) -> Result<Response<()>, Status> {
+        require_role(&request, NAMESPACE_WRITE)?;
+        let actor = request_actor(&request);
+        let path = request.into_inner().path;
+        self.namespaces.lock().await.delete_namespace(&path).await.map_err(namespace_status)?;
+        record_audit_event(
+            self.audit.as_ref(),
+            AuditEvent::new(actor, AuditAction::DeleteNamespace, Uuid::nil(), "namespace")
+                .with_metadata("path", serde_json::Value::String(path)),
+        ).await;
+        Ok(Response::new(()))
+    }
+
+    type WatchReleaseTransitionsStream = TransitionStream;
+
+    #[tracing::instrument(skip_all)]
+    async fn watch_release_transitions(
+        &self,
+        request: Request<Streaming<ReleaseId>>,
+    ) -> Result<Response<Self::WatchReleaseTransitionsStream>, Status> {
+        let mut inbound = request.into_inner();
+        let mut events = self.transitions.subscribe();
+        let (tx, rx) = mpsc::channel(16);
+
+        tokio::spawn(async move {
+            let mut watched = HashSet::new();
+            // Once the client closes its side, keep streaming for the releases it already sent.
+            let mut inbound_open = true;
+            loop {
+                tokio::select! {
+                    message = inbound.message(), if inbound_open => match message {
+                        Ok(Some(release)) => match parse_uuid("release id", &release.id) {
+                            Ok(id) => {
+                                watched.insert(id);
+                            }
+                            Err(status) => {
+                                let _ = tx.send(Err(status)).await;
+                                return;
+                            }
+                        },
+                        Ok(None) => inbound_open = false,
+                        Err(status) => {
+                            let _ = tx.send(Err(status)).await;
+                            return;
+                        }
+                    },
+                    event = events.recv() => {
+                        if !forward_event(event, &watched, &tx).await {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
+
+/// Sends `event` to the watcher if it is for a watched release. Returns `false` once the watch
+/// should end, either because the watcher went away or no more transitions will be published.
+async fn forward_event(
+    event: Result<TransitionEvent, broadcast::error::RecvError>,
+    watched: &HashSet<uuid::Uuid>,
+    tx: &mpsc::Sender<Result<proto::TransitionEvent, Status>>,
+) -> bool {
+    match event {
+        Ok(event) if watched.contains(&event.release_id) => tx.send(Ok((&event).into())).await.is_ok(),
+        Ok(_) => true,
+        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+            tracing::warn!(skipped, "Transition watcher lagged behind");
+            true
+        }
+        Err(broadcast::error::RecvError::Closed) => false,
+    }
+}
@@ -0,0 +1,237 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use sdlc_cp_api::model::policy::PolicyRule;
+use sdlc_cp_api::model::attestation::{ClaimSet, Subject, SubjectType};
+use sdlc_cp_api::model::{Attestation, Policy, SDLCRelease};
+use sdlc_cp_api::services::{
+    attestation::InMemoryAttestationService,
+    audit::{AuditAction, AuditFilter, AuditLogService, InMemoryAuditLogService},
+    auth::{CurrentUser, NAMESPACE_READ, NAMESPACE_WRITE, POLICY_WRITE},
+    component_repository::InMemoryComponentRepository,
+    controlplane::{ControlPlane, InMemoryControlPlane, NamespaceStore},
+    policy_repository::InMemoryPolicyRepository,
+    release_repository::InMemoryReleaseRepository,
+    transitions::{TransitionBroadcaster, TransitionEvent},
+};
+use sdlccp_grpc::proto::{self, CreateReleaseRequest, Namespace, ReleaseId};
+use sdlccp_grpc::{ControlPlaneClient, ControlPlaneGrpcService, ControlPlaneServer};
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::service::Interceptor;
+use tonic::transport::{Channel, Server};
+use tonic::Code;
+use uuid::Uuid;
+
+struct Fixture {
+    client: ControlPlaneClient<Channel>,
+    transitions: TransitionBroadcaster,
+    policies: Arc<InMemoryPolicyRepository>,
+    control_plane: Arc<InMemoryControlPlane>,
+    audit: Arc<InMemoryAuditLogService>,
+}
+
+fn user(roles: &[&str]) -> CurrentUser {
+    CurrentUser { sub: "operator".to_string(), roles: roles.iter().map(|role| role.to_string()).collect() }
+}
+
+/// Stands in for the server's authenticating interceptor, adding the caller if there is one.
+#[derive(Clone)]
+struct AsUser(Option<CurrentUser>);
+
+impl Interceptor for AsUser {
+    fn call(&mut self, mut request: tonic::Request<()>) -> Result<tonic::Request<()>, tonic::Status> {
+        if let Some(user) = self.0.clone() {
+            request.extensions_mut().insert(user);
+        }
+        Ok(request)
+    }
+}
+
+/// Serves calls as an operator with every role, like the server after authenticating a token.
+async fn start_server() -> Fixture {
+    start_server_as(Some(user(&[NAMESPACE_READ, NAMESPACE_WRITE, POLICY_WRITE]))).await
+}
+
+async fn start_server_as(user: Option<CurrentUser>) -> Fixture {
+    let transitions = TransitionBroadcaster::new(16);
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let policies = Arc::new(InMemoryPolicyRepository::new());
+    let audit = Arc::new(InMemoryAuditLogService::new());
+    let control_plane = Arc::new(InMemoryControlPlane::new(
+        policies.clone(),
+        Arc::new(InMemoryAttestationService::new()),
+        releases.clone(),
+        Arc::new(InMemoryComponentRepository::new()),
+    ));
+    let service = ControlPlaneGrpcService::new(
+        control_plane.clone(),
+        releases,
+        Arc::new(NamespaceStore::default()),
+        transitions.clone(),
+        audit.clone(),
+    );
+
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(
+        Server::builder()
+            .add_service(ControlPlaneServer::with_interceptor(service, AsUser(user)))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+
+    let client = ControlPlaneClient::connect(format!("http://{}", address)).await.unwrap();
+    Fixture { client, transitions, policies, control_plane, audit }
+}
+
+#[tokio::test]
+async fn test_create_and_get_release() {
+    let mut client = start_server().await.client;
+    let component_id = Uuid::new_v4();
+
+    let created = client
+        .create_release(CreateReleaseRequest {
+            component_id: component_id.to_string(),
+            version: "1.0.0".to_string(),
+            created_by: "developer1".to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(created.phase, "Development");
+
+    let fetched = client.get_release(ReleaseId { id: created.id.clone() }).await.unwrap().into_inner();
+    assert_eq!(fetched, created);
+    assert_eq!(fetched.component_id, component_id.to_string());
+    assert_eq!(fetched.version, "1.0.0");
+
+    let missing = client.get_release(ReleaseId { id: Uuid::new_v4().to_string() }).await.unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+    let invalid = client.get_release(ReleaseId { id: "not-a-uuid".to_string() }).await.unwrap_err();
+    assert_eq!(invalid.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_watch_release_transitions() {
+    let Fixture { mut client, transitions, .. } = start_server().await;
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    let event = TransitionEvent::new(&release, "Draft");
+    let unrelated = TransitionEvent::new(&SDLCRelease::new(Uuid::new_v4(), "2.0.0".to_string(), "developer2".to_string()).unwrap(), "Draft");
+
+    let (watch_tx, watch_rx) = tokio::sync::mpsc::channel(1);
+    watch_tx.send(ReleaseId { id: release.id.to_string() }).await.unwrap();
+    let mut stream = client
+        .watch_release_transitions(tokio_stream::wrappers::ReceiverStream::new(watch_rx))
+        .await
+        .unwrap()
+        .into_inner();
+
+    // The watch is registered asynchronously, so keep publishing until it is picked up.
+    let received = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            transitions.publish(unrelated.clone());
+            transitions.publish(event.clone());
+            if let Ok(message) = tokio::time::timeout(Duration::from_millis(20), stream.message()).await {
+                return message.unwrap().unwrap();
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(received.release_id, release.id.to_string());
+    assert_eq!(received.from_state, "Draft");
+    assert_eq!(received.to_state, "InProgress");
+}
+
+#[tokio::test]
+async fn test_policies_stored_over_grpc_are_evaluated_against_releases() {
+    let Fixture { mut client, policies, control_plane, .. } = start_server().await;
+    let component_id = Uuid::new_v4();
+    let release = client
+        .create_release(CreateReleaseRequest {
+            component_id: component_id.to_string(),
+            version: "1.2.0".to_string(),
+            created_by: "developer1".to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let release_id = Uuid::parse_str(&release.id).unwrap();
+
+    let mut policy = Policy::new("Versioning".to_string(), Vec::new());
+    policy.add_rule(PolicyRule::BlockSuspendedReleases);
+    policy.add_rule(PolicyRule::MinVersion(">=1.0.0".parse().unwrap()));
+    client.store_policy(proto::Policy::from(&policy)).await.unwrap();
+    policies.assign_policy(component_id, policy.id).await;
+    assert!(control_plane.apply_policy_to_release(&release_id, &component_id).await.unwrap());
+
+    let mut stricter = Policy::new("Versioning v2".to_string(), Vec::new());
+    stricter.add_rule(PolicyRule::MinVersion(">=2.0.0".parse().unwrap()));
+    client.store_policy(proto::Policy::from(&stricter)).await.unwrap();
+    policies.assign_policy(component_id, stricter.id).await;
+    assert!(!control_plane.apply_policy_to_release(&release_id, &component_id).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_grpc_mutations_are_audited() {
+    let Fixture { mut client, audit, .. } = start_server().await;
+
+    let release = client
+        .create_release(CreateReleaseRequest {
+            component_id: Uuid::new_v4().to_string(),
+            version: "1.0.0".to_string(),
+            created_by: "developer1".to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let policy = Policy::new("Reviewed".to_string(), vec!["Source".to_string()]);
+    client.store_policy(proto::Policy::from(&policy)).await.unwrap();
+    let attestation = Attestation::new(
+        Subject { type_: SubjectType::Artifact, name: "app.tar.gz".to_string(), digest: "sha256:1234".to_string() },
+        ClaimSet::new(),
+    );
+    client.store_attestation(proto::Attestation::from(&attestation)).await.unwrap();
+    client.create_namespace(Namespace { path: "team-a".to_string() }).await.unwrap();
+    client.delete_namespace(Namespace { path: "team-a".to_string() }).await.unwrap();
+    // Failed mutations are not recorded
+    client.delete_namespace(Namespace { path: "team-a".to_string() }).await.unwrap_err();
+
+    let events = audit.query(AuditFilter::default()).await.unwrap();
+    let actions: Vec<AuditAction> = events.iter().map(|event| event.action).collect();
+    assert_eq!(
+        actions,
+        vec![
+            AuditAction::CreateRelease,
+            AuditAction::CreatePolicy,
+            AuditAction::StoreAttestation,
+            AuditAction::CreateNamespace,
+            AuditAction::DeleteNamespace,
+        ]
+    );
+    assert!(events.iter().all(|event| event.actor == "operator"));
+    assert_eq!(events[0].resource_id.to_string(), release.id);
+    assert_eq!(events[1].resource_id, policy.id);
+    assert_eq!(events[2].resource_id, attestation.id);
+    assert_eq!(events[4].metadata["path"], serde_json::json!("team-a"));
+}
+
+#[tokio::test]
+async fn test_namespace_and_policy_calls_require_roles() {
+    let mut anonymous = start_server_as(None).await.client;
+    let status = anonymous.create_namespace(Namespace { path: "team-a".to_string() }).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    let mut reader = start_server_as(Some(user(&[NAMESPACE_READ]))).await.client;
+    reader.list_namespaces(Namespace { path: String::new() }).await.unwrap();
+    let status = reader.create_namespace(Namespace { path: "team-a".to_string() }).await.unwrap_err();
+    assert_eq!(status.code(), Code::PermissionDenied);
+    let status = reader.delete_namespace(Namespace { path: "team-a".to_string() }).await.unwrap_err();
+    assert_eq!(status.code(), Code::PermissionDenied);
+    let policy = Policy::new("Reviewed".to_string(), Vec::new());
+    let status = reader.store_policy(proto::Policy::from(&policy)).await.unwrap_err();
+    assert_eq!(status.code(), Code::PermissionDenied);
+}
@@ -0,0 +1,8 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Compiling the hand-written definitions on every build keeps a broken .proto from being
+    // published. Use the vendored protoc so building doesn't depend on a system install.
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    let include = protoc_bin_vendored::include_path()?;
+    prost_build::compile_protos(&["../proto/sdlccp/v1alpha1/controlplane.proto"], &[std::path::Path::new("../proto"), include.as_path()])?;
+    Ok(())
+}
@@ -0,0 +1,34 @@
+//! Fails if the schemas generated from the models differ from the committed baselines. Run in CI
+//! from the `sdlccp-schema-generator` directory.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use sdlccp_schema_generator::{check_dir, check_file, openapi_document, registered_schemas};
+
+#[derive(Parser, Debug)]
+#[command(about = "Checks the committed JSON schemas and OpenAPI document are up to date")]
+struct Args {
+    /// Directory of the committed JSON schemas.
+    #[arg(long, default_value = "../schemas/json")]
+    json_dir: PathBuf,
+    /// Path of the committed OpenAPI document.
+    #[arg(long, default_value = "../schemas/openapi/openapi.json")]
+    openapi: PathBuf,
+}
+
+fn main() -> std::io::Result<ExitCode> {
+    let args = Args::parse();
+    let mut differences = check_dir(&args.json_dir, &registered_schemas()?)?;
+    differences.extend(check_file(&args.openapi, &openapi_document()?)?);
+    if differences.is_empty() {
+        println!("Schemas match the committed baselines.");
+        return Ok(ExitCode::SUCCESS);
+    }
+    for difference in &differences {
+        eprintln!("{}", difference);
+    }
+    eprintln!("\nIf the change is intended, run `cargo run -- --update` in sdlccp-schema-generator and commit the result.");
+    Ok(ExitCode::FAILURE)
+}
@@ -0,0 +1,188 @@
+//! Compares the schemas generated from the models against the baselines committed under
+//! `schemas/`, so a model change cannot silently alter what downstream consumers rely on.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use sdlc_cp_api::SchemaGenerator;
+use serde_json::Value;
+use utoipa::OpenApi;
+
+/// Name of the file the schema of `type_name` is written to.
+pub fn schema_file_name(type_name: &str, extension: &str) -> String {
+    format!("{}_schema.{}", type_name.to_lowercase(), extension)
+}
+
+/// The JSON schema of every registered type, keyed by the name of its file.
+pub fn registered_schemas() -> std::io::Result<Vec<(String, Value)>> {
+    inventory::iter::<SchemaGenerator>
+        .into_iter()
+        .map(|schema_gen| Ok((schema_file_name(schema_gen.type_name, "json"), serde_json::to_value((schema_gen.generator)())?)))
+        .collect()
+}
+
+pub fn openapi_document() -> std::io::Result<Value> {
+    Ok(serde_json::to_value(sdlc_cp_api::services::controlplane::ControlPlaneAPIDoc::openapi())?)
+}
+
+/// Where a committed document and the generated one disagree, located by JSON pointer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub pointer: String,
+    pub committed: Option<Value>,
+    pub generated: Option<Value>,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |value: &Option<Value>| value.as_ref().map_or_else(|| "absent".to_string(), Value::to_string);
+        let pointer = if self.pointer.is_empty() { "/" } else { &self.pointer };
+        write!(f, "{}: committed {}, generated {}", pointer, show(&self.committed), show(&self.generated))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+    /// Generated but never committed.
+    Missing(PathBuf),
+    /// Committed but no longer generated, e.g. for a removed type.
+    Stale(PathBuf),
+    Changed { path: PathBuf, changes: Vec<Change> },
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difference::Missing(path) => write!(f, "{} is not committed", path.display()),
+            Difference::Stale(path) => write!(f, "{} is committed but no longer generated", path.display()),
+            Difference::Changed { path, changes } => {
+                write!(f, "{} differs:", path.display())?;
+                for change in changes {
+                    write!(f, "\n  {}", change)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Compares the committed document at `path` to `generated`. Only the parsed values are
+/// compared, so formatting and key order do not matter.
+pub fn check_file(path: &Path, generated: &Value) -> std::io::Result<Option<Difference>> {
+    let committed: Value = match std::fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents)?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Some(Difference::Missing(path.to_path_buf()))),
+        Err(err) => return Err(err),
+    };
+    let mut changes = Vec::new();
+    diff_values(String::new(), Some(&committed), Some(generated), &mut changes);
+    Ok((!changes.is_empty()).then(|| Difference::Changed { path: path.to_path_buf(), changes }))
+}
+
+/// Compares every JSON file in `dir` to `generated`, which maps file names to their contents.
+pub fn check_dir(dir: &Path, generated: &[(String, Value)]) -> std::io::Result<Vec<Difference>> {
+    let mut differences = Vec::new();
+    for (file_name, schema) in generated {
+        differences.extend(check_file(&dir.join(file_name), schema)?);
+    }
+    let expected: BTreeSet<&str> = generated.iter().map(|(file_name, _)| file_name.as_str()).collect();
+    let mut committed: Vec<PathBuf> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries.map(|entry| entry.map(|entry| entry.path())).collect::<std::io::Result<_>>()?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => return Err(err),
+    };
+    committed.sort();
+    for path in committed {
+        let is_json = path.extension().is_some_and(|extension| extension == "json");
+        let file_name = path.file_name().and_then(|file_name| file_name.to_str()).unwrap_or_default();
+        if is_json && !expected.contains(file_name) {
+            differences.push(Difference::Stale(path));
+        }
+    }
+    Ok(differences)
+}
+
+fn diff_values(pointer: String, committed: Option<&Value>, generated: Option<&Value>, changes: &mut Vec<Change>) {
+    match (committed, generated) {
+        (Some(Value::Object(committed)), Some(Value::Object(generated))) => {
+            let keys: BTreeSet<&String> = committed.keys().chain(generated.keys()).collect();
+            for key in keys {
+                let child = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+                diff_values(child, committed.get(key), generated.get(key), changes);
+            }
+        },
+        (Some(Value::Array(committed)), Some(Value::Array(generated))) if committed.len() == generated.len() => {
+            for (index, (committed, generated)) in committed.iter().zip(generated).enumerate() {
+                diff_values(format!("{}/{}", pointer, index), Some(committed), Some(generated), changes);
+            }
+        },
+        (committed, generated) if committed != generated => {
+            changes.push(Change { pointer, committed: committed.cloned(), generated: generated.cloned() });
+        },
+        _ => {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schemars::JsonSchema;
+
+    #[allow(dead_code)]
+    #[derive(JsonSchema)]
+    struct Known {
+        name: String,
+        count: u32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(JsonSchema)]
+    #[schemars(rename = "Known")]
+    struct KnownWithTags {
+        name: String,
+        count: u32,
+        tags: Vec<String>,
+    }
+
+    fn generated<T: JsonSchema>() -> Vec<(String, Value)> {
+        vec![(schema_file_name("Known", "json"), serde_json::to_value(schemars::schema_for!(T)).unwrap())]
+    }
+
+    #[test]
+    fn test_check_passes_for_matching_baseline() {
+        let dir = tempfile::tempdir().unwrap();
+        let baseline = generated::<Known>();
+        // Formatting of the committed file does not matter, only its contents.
+        std::fs::write(dir.path().join("known_schema.json"), serde_json::to_string(&baseline[0].1).unwrap()).unwrap();
+
+        assert_eq!(check_dir(dir.path(), &baseline).unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn test_check_reports_changed_missing_and_stale_schemas() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("known_schema.json"), serde_json::to_string_pretty(&generated::<Known>()[0].1).unwrap()).unwrap();
+        std::fs::write(dir.path().join("removed_schema.json"), "{}").unwrap();
+        let mut current = generated::<KnownWithTags>();
+        current.push(("added_schema.json".to_string(), serde_json::json!({})));
+
+        let differences = check_dir(dir.path(), &current).unwrap();
+        assert_eq!(differences.len(), 3);
+        let Difference::Changed { changes, .. } = &differences[0] else { panic!("expected a change, got {:?}", differences[0]) };
+        let pointers: Vec<&str> = changes.iter().map(|change| change.pointer.as_str()).collect();
+        assert_eq!(pointers, vec!["/properties/tags", "/required"]);
+        assert_eq!(changes[0].committed, None);
+        assert_eq!(differences[1], Difference::Missing(dir.path().join("added_schema.json")));
+        assert_eq!(differences[2], Difference::Stale(dir.path().join("removed_schema.json")));
+    }
+
+    #[test]
+    fn test_committed_baselines_are_current() {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let mut differences = check_dir(&manifest_dir.join("../schemas/json"), &registered_schemas().unwrap()).unwrap();
+        differences.extend(check_file(&manifest_dir.join("../schemas/openapi/openapi.json"), &openapi_document().unwrap()).unwrap());
+        let report: Vec<String> = differences.iter().map(ToString::to_string).collect();
+        assert!(differences.is_empty(), "run `cargo run -- --update` in sdlccp-schema-generator:\n{}", report.join("\n"));
+    }
+}
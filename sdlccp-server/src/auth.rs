@@ -0,0 +1,225 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::{
+    body::Body,
+    http::{header, Request},
+    response::{IntoResponse, Response},
+};
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use sdlc_cp_api::services::auth::{AuthError, CurrentUser};
+use serde::Deserialize;
+use tonic::{service::Interceptor, Status};
+use tower::{Layer, Service};
+
+/// How bearer tokens are verified.
+pub struct JwtConfig {
+    key: DecodingKey,
+    validation: Validation,
+}
+
+impl JwtConfig {
+    /// Verifies HS256 tokens signed with `secret`.
+    pub fn hmac(secret: &[u8]) -> Self {
+        Self {
+            key: DecodingKey::from_secret(secret),
+            validation: Validation::new(Algorithm::HS256),
+        }
+    }
+
+    /// Verifies RS256 tokens against a PEM-encoded RSA public key.
+    pub fn rsa_pem(public_key: &[u8]) -> Result<Self, jsonwebtoken::errors::Error> {
+        Ok(Self {
+            key: DecodingKey::from_rsa_pem(public_key)?,
+            validation: Validation::new(Algorithm::RS256),
+        })
+    }
+
+    fn verify(&self, token: &str) -> Result<CurrentUser, AuthError> {
+        let claims = decode::<Claims>(token, &self.key, &self.validation)
+            .map_err(|err| AuthError::InvalidToken(err.to_string()))?
+            .claims;
+        Ok(CurrentUser { sub: claims.sub, roles: claims.roles })
+    }
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+    #[serde(default)]
+    roles: Vec<String>,
+}
+
+/// Authenticates every request with an `Authorization: Bearer <token>` header, adding the
+/// caller as a `CurrentUser` extension or rejecting the request with `401 Unauthorized`.
+#[derive(Clone)]
+pub struct JwtAuthLayer {
+    config: Arc<JwtConfig>,
+}
+
+impl JwtAuthLayer {
+    pub fn new(config: JwtConfig) -> Self {
+        Self { config: Arc::new(config) }
+    }
+
+    /// An interceptor authenticating gRPC calls with the same tokens.
+    pub fn grpc_interceptor(&self) -> GrpcAuthInterceptor {
+        GrpcAuthInterceptor { config: self.config.clone() }
+    }
+}
+
+/// Authenticates every gRPC call with `authorization: Bearer <token>` metadata, adding the caller
+/// as a `CurrentUser` extension for the service's role checks or rejecting the call with
+/// `UNAUTHENTICATED`.
+#[derive(Clone)]
+pub struct GrpcAuthInterceptor {
+    config: Arc<JwtConfig>,
+}
+
+impl Interceptor for GrpcAuthInterceptor {
+    fn call(&mut self, mut request: tonic::Request<()>) -> Result<tonic::Request<()>, Status> {
+        let user = request
+            .metadata()
+            .get(header::AUTHORIZATION.as_str())
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(AuthError::MissingToken)
+            .and_then(|token| self.config.verify(token))
+            .map_err(|err| {
+                tracing::warn!(error = %err, "Rejected unauthenticated gRPC call");
+                Status::unauthenticated(err.to_string())
+            })?;
+        request.extensions_mut().insert(user);
+        Ok(request)
+    }
+}
+
+impl<S> Layer<S> for JwtAuthLayer {
+    type Service = JwtAuth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        JwtAuth { inner, config: self.config.clone() }
+    }
+}
+
+#[derive(Clone)]
+pub struct JwtAuth<S> {
+    inner: S,
+    config: Arc<JwtConfig>,
+}
+
+impl<S> JwtAuth<S> {
+    fn authenticate(&self, request: &Request<Body>) -> Result<CurrentUser, AuthError> {
+        let token = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(AuthError::MissingToken)?;
+        self.config.verify(token)
+    }
+}
+
+impl<S> Service<Request<Body>> for JwtAuth<S>
+where
+    S: Service<Request<Body>, Response = Response>,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<Body>) -> Self::Future {
+        match self.authenticate(&request) {
+            Ok(user) => {
+                request.extensions_mut().insert(user);
+                Box::pin(self.inner.call(request))
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "Rejected unauthenticated request");
+                Box::pin(std::future::ready(Ok(err.into_response())))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::{routing, Extension, Router};
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde::Serialize;
+    use tower::ServiceExt;
+
+    const SECRET: &[u8] = b"test-secret";
+
+    #[derive(Serialize)]
+    struct TestClaims {
+        sub: &'static str,
+        roles: Vec<&'static str>,
+        exp: i64,
+    }
+
+    fn token(algorithm: Algorithm, exp: i64) -> String {
+        let claims = TestClaims { sub: "alice", roles: vec!["namespace:read"], exp };
+        encode(&Header::new(algorithm), &claims, &EncodingKey::from_secret(SECRET)).unwrap()
+    }
+
+    fn in_one_hour() -> i64 {
+        chrono::Utc::now().timestamp() + 3600
+    }
+
+    async fn whoami(Extension(user): Extension<CurrentUser>) -> String {
+        format!("{}:{}", user.sub, user.roles.join(","))
+    }
+
+    async fn call(authorization: Option<String>) -> Response {
+        let router = Router::new()
+            .route("/", routing::get(whoami))
+            .layer(JwtAuthLayer::new(JwtConfig::hmac(SECRET)));
+        let mut request = Request::get("/");
+        if let Some(authorization) = authorization {
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+        router.oneshot(request.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_valid_token_populates_current_user() {
+        let response = call(Some(format!("Bearer {}", token(Algorithm::HS256, in_one_hour())))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "alice:namespace:read");
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_is_rejected() {
+        let expired = chrono::Utc::now().timestamp() - 3600;
+        let response = call(Some(format!("Bearer {}", token(Algorithm::HS256, expired)))).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_algorithm_is_rejected() {
+        let response = call(Some(format!("Bearer {}", token(Algorithm::HS512, in_one_hour())))).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_missing_header_is_rejected() {
+        let response = call(None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+
+        let response = call(Some(format!("Basic {}", token(Algorithm::HS256, in_one_hour())))).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    io,
+    net::{Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+};
+
+use sdlc_cp_api::model::phase::{FeatureListLimits, MAX_FEATURE_DESCRIPTION_LEN, MAX_FEATURE_LIST_SIZE};
+use sdlc_cp_api::services::namespace::MAX_NAMESPACE_DEPTH;
+use serde::{Deserialize, Serialize};
+
+/// Where the versioned HTTP API is mounted.
+pub const API_BASE_PATH: &str = "/api/v1alpha1";
+
+/// Server settings that can come from a `--config` TOML file.
+///
+/// Missing keys keep their default. Environment variables and command line flags override the file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// Address the HTTP and gRPC APIs bind to.
+    pub bind_address: Ipv4Addr,
+    /// Port of the plain HTTP API.
+    pub port: u16,
+    /// Serve the HTTP API over HTTPS instead.
+    pub tls: Option<TlsConfig>,
+    /// URL clients reach the server at, such as `https://sdlccp.example.com`, when it differs from
+    /// the bound address, e.g. behind a reverse proxy. Advertised in the OpenAPI document.
+    pub public_url: Option<String>,
+    /// HMAC secret used to verify HS256 bearer tokens.
+    pub jwt_secret: Option<String>,
+    pub log_level: String,
+    pub cors_allowed_origins: Vec<String>,
+    pub rate_limit_rps: u32,
+    /// OTLP/gRPC collector that spans are exported to.
+    pub otlp_endpoint: String,
+    /// Most features a release can start development with.
+    pub max_feature_list_size: usize,
+    /// Longest feature description, in characters.
+    pub max_feature_description_len: usize,
+    /// Most segments a namespace path may have.
+    pub max_namespace_depth: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TlsConfig {
+    /// PEM certificate chain.
+    pub cert: PathBuf,
+    /// PEM private key for `cert`.
+    pub key: PathBuf,
+    #[serde(default = "default_tls_port")]
+    pub port: u16,
+}
+
+fn default_tls_port() -> u16 {
+    8443
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: Ipv4Addr::UNSPECIFIED,
+            port: 8080,
+            tls: None,
+            public_url: None,
+            jwt_secret: None,
+            log_level: "info".to_string(),
+            cors_allowed_origins: vec!["*".to_string()],
+            rate_limit_rps: 10,
+            otlp_endpoint: "http://localhost:4317".to_string(),
+            max_feature_list_size: MAX_FEATURE_LIST_SIZE,
+            max_feature_description_len: MAX_FEATURE_DESCRIPTION_LEN,
+            max_namespace_depth: MAX_NAMESPACE_DEPTH,
+        }
+    }
+}
+
+impl ServerConfig {
+    pub fn from_toml(toml: &str) -> io::Result<Self> {
+        toml::from_str(toml).map_err(|err| invalid_input(err.to_string()))
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let toml = std::fs::read_to_string(path)
+            .map_err(|err| io::Error::new(err.kind(), format!("cannot read {}: {}", path.display(), err)))?;
+        Self::from_toml(&toml).map_err(|err| invalid_input(format!("{}: {}", path.display(), err)))
+    }
+
+    /// Checks what the types alone cannot. The error message names the offending setting.
+    pub fn validate(&self) -> io::Result<()> {
+        if self.port == 0 {
+            return Err(invalid_input("port must be between 1 and 65535"));
+        }
+        if let Some(tls) = &self.tls {
+            if tls.port == 0 {
+                return Err(invalid_input("tls.port must be between 1 and 65535"));
+            }
+        }
+        if self.rate_limit_rps == 0 {
+            return Err(invalid_input("rate_limit_rps must be greater than zero"));
+        }
+        if self.cors_allowed_origins.is_empty() {
+            return Err(invalid_input("cors_allowed_origins must not be empty"));
+        }
+        if self.public_url.as_deref().is_some_and(|url| !url.starts_with("http://") && !url.starts_with("https://")) {
+            return Err(invalid_input("public_url must be an http:// or https:// URL"));
+        }
+        if self.jwt_secret.as_deref() == Some("") {
+            return Err(invalid_input("jwt_secret must not be empty"));
+        }
+        if self.max_feature_list_size == 0 {
+            return Err(invalid_input("max_feature_list_size must be greater than zero"));
+        }
+        if self.max_feature_description_len == 0 {
+            return Err(invalid_input("max_feature_description_len must be greater than zero"));
+        }
+        if self.max_namespace_depth == 0 {
+            return Err(invalid_input("max_namespace_depth must be greater than zero"));
+        }
+        Ok(())
+    }
+
+    pub fn feature_list_limits(&self) -> FeatureListLimits {
+        FeatureListLimits { max_items: self.max_feature_list_size, max_item_len: self.max_feature_description_len }
+    }
+
+    /// The URL of the versioned API advertised in the OpenAPI document: under `public_url` if set,
+    /// otherwise under `address`, where the HTTP API is bound. A wildcard address is advertised as
+    /// `localhost`.
+    pub fn api_base_url(&self, address: SocketAddr, tls: bool) -> String {
+        if let Some(public_url) = &self.public_url {
+            return format!("{}{}", public_url.trim_end_matches('/'), API_BASE_PATH);
+        }
+        let scheme = if tls { "https" } else { "http" };
+        let host = if address.ip().is_unspecified() { "localhost".to_string() } else { address.ip().to_string() };
+        format!("{}://{}:{}{}", scheme, host, address.port(), API_BASE_PATH)
+    }
+
+    /// The config as TOML, with the JWT secret masked.
+    pub fn to_toml(&self) -> String {
+        let mut printable = self.clone();
+        if printable.jwt_secret.is_some() {
+            printable.jwt_secret = Some("<redacted>".to_string());
+        }
+        toml::to_string(&printable).expect("server config is always representable as TOML")
+    }
+}
+
+fn invalid_input(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_keys_keep_defaults() {
+        let config = ServerConfig::from_toml(r#"
+            port = 9090
+
+            [tls]
+            cert = "/etc/sdlccp/tls.crt"
+            key = "/etc/sdlccp/tls.key"
+        "#).unwrap();
+
+        assert_eq!(config.port, 9090);
+        assert_eq!(config.bind_address, Ipv4Addr::UNSPECIFIED);
+        assert_eq!(config.log_level, "info");
+        assert_eq!(config.tls.unwrap().port, 8443);
+        assert!(ServerConfig::from_toml("").unwrap() == ServerConfig::default());
+    }
+
+    #[test]
+    fn test_invalid_values_are_reported() {
+        let err = ServerConfig::from_toml(r#"bind_address = "300.0.0.1""#).unwrap_err();
+        assert!(err.to_string().contains("bind_address"), "{err}");
+
+        let err = ServerConfig::from_toml("prot = 8080").unwrap_err();
+        assert!(err.to_string().contains("prot"), "{err}");
+
+        let err = ServerConfig { port: 0, ..ServerConfig::default() }.validate().unwrap_err();
+        assert_eq!(err.to_string(), "port must be between 1 and 65535");
+        let err = ServerConfig { max_feature_list_size: 0, ..ServerConfig::default() }.validate().unwrap_err();
+        assert_eq!(err.to_string(), "max_feature_list_size must be greater than zero");
+        assert!(ServerConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_feature_list_limits_are_configurable() {
+        let config = ServerConfig::from_toml("max_feature_list_size = 3\nmax_feature_description_len = 40").unwrap();
+
+        assert_eq!(config.feature_list_limits(), FeatureListLimits { max_items: 3, max_item_len: 40 });
+        assert_eq!(ServerConfig::default().feature_list_limits(), FeatureListLimits::default());
+        assert_eq!(ServerConfig::from_toml("max_namespace_depth = 4").unwrap().max_namespace_depth, 4);
+    }
+
+    #[test]
+    fn test_api_base_url_follows_bound_address() {
+        let config = ServerConfig::default();
+
+        assert_eq!(config.api_base_url(SocketAddr::from(([127, 0, 0, 1], 9090)), false), "http://127.0.0.1:9090/api/v1alpha1");
+        assert_eq!(config.api_base_url(SocketAddr::from((Ipv4Addr::UNSPECIFIED, 8443)), true), "https://localhost:8443/api/v1alpha1");
+
+        let config = ServerConfig { public_url: Some("https://sdlccp.example.com/".to_string()), ..ServerConfig::default() };
+        assert_eq!(config.api_base_url(SocketAddr::from(([127, 0, 0, 1], 9090)), false), "https://sdlccp.example.com/api/v1alpha1");
+        let err = ServerConfig { public_url: Some("sdlccp.example.com".to_string()), ..ServerConfig::default() }.validate().unwrap_err();
+        assert_eq!(err.to_string(), "public_url must be an http:// or https:// URL");
+    }
+
+    #[test]
+    fn test_printed_config_round_trips_without_secret() {
+        let config = ServerConfig { jwt_secret: Some("hunter2".to_string()), ..ServerConfig::default() };
+
+        let printed = config.to_toml();
+
+        assert!(!printed.contains("hunter2"));
+        let parsed = ServerConfig::from_toml(&printed).unwrap();
+        assert_eq!(parsed, ServerConfig { jwt_secret: Some("<redacted>".to_string()), ..config });
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use axum::http::{header, HeaderValue, Method};
+use sdlc_cp_api::services::request_id::X_REQUEST_ID;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+/// The deployment the server runs in. Production refuses settings only meant for development.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Environment {
+    Development,
+    Production,
+}
+
+/// Cross-origin policy for browser clients such as the Swagger UI.
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    /// Origins allowed to call the API, or `*` for any origin.
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<Method>,
+    /// How long browsers may cache a preflight response.
+    pub max_age: Duration,
+}
+
+impl CorsConfig {
+    /// Builds the layer, refusing a wildcard origin in production.
+    pub fn layer(&self, environment: Environment) -> Result<CorsLayer, std::io::Error> {
+        let any_origin = self.allowed_origins.iter().any(|origin| origin == "*");
+        if any_origin && environment == Environment::Production {
+            return Err(invalid_input("SDLCCP_CORS_ALLOWED_ORIGINS must list explicit origins in production"));
+        }
+        let allow_origin = if any_origin {
+            AllowOrigin::any()
+        } else {
+            let origins = self.allowed_origins
+                .iter()
+                .map(|origin| HeaderValue::from_str(origin).map_err(|_| invalid_input(&format!("invalid CORS origin {:?}", origin))))
+                .collect::<Result<Vec<_>, _>>()?;
+            AllowOrigin::list(origins)
+        };
+
+        Ok(CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(self.allowed_methods.clone())
+            .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE, header::IF_MATCH])
+            .expose_headers([header::ETAG, header::RETRY_AFTER, X_REQUEST_ID])
+            .max_age(self.max_age))
+    }
+}
+
+fn invalid_input(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(allowed_origins: &[&str]) -> CorsConfig {
+        CorsConfig {
+            allowed_origins: allowed_origins.iter().map(|origin| origin.to_string()).collect(),
+            allowed_methods: vec![Method::GET],
+            max_age: Duration::from_secs(3600),
+        }
+    }
+
+    #[test]
+    fn test_wildcard_origin_is_refused_in_production() {
+        assert!(config(&["*"]).layer(Environment::Development).is_ok());
+        assert!(config(&["*"]).layer(Environment::Production).is_err());
+        assert!(config(&["https://ui.example.com"]).layer(Environment::Production).is_ok());
+        assert!(config(&["https://ui.example.com\n"]).layer(Environment::Production).is_err());
+    }
+}
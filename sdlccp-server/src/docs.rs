@@ -0,0 +1,172 @@
+use std::io;
+
+use axum::{routing, Json, Router};
+use clap::ValueEnum;
+use utoipa::openapi::OpenApi;
+use utoipa_swagger_ui::{Config, SwaggerUi};
+
+/// Where the OpenAPI document the UIs render is served.
+pub const OPENAPI_PATH: &str = "/api-docs/openapi.json";
+
+/// A UI rendering the OpenAPI document. Swagger UI is always available, the others only when
+/// built with their `ui-*` feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum DocsUi {
+    Swagger,
+    Redoc,
+    Scalar,
+}
+
+impl DocsUi {
+    fn is_compiled_in(self) -> bool {
+        match self {
+            DocsUi::Swagger => true,
+            DocsUi::Redoc => cfg!(feature = "ui-redoc"),
+            DocsUi::Scalar => cfg!(feature = "ui-scalar"),
+        }
+    }
+
+    fn feature(self) -> &'static str {
+        match self {
+            DocsUi::Swagger => "",
+            DocsUi::Redoc => "ui-redoc",
+            DocsUi::Scalar => "ui-scalar",
+        }
+    }
+
+    /// Where the UI is mounted when it is passed to `--extra-ui`.
+    fn default_path(self) -> &'static str {
+        match self {
+            DocsUi::Swagger => "/swagger-ui",
+            DocsUi::Redoc => "/redoc",
+            DocsUi::Scalar => "/scalar",
+        }
+    }
+
+    #[cfg_attr(not(all(feature = "ui-redoc", feature = "ui-scalar")), allow(unused_variables))]
+    fn router(self, path: &str, openapi: &OpenApi) -> Router {
+        match self {
+            // Points at `OPENAPI_PATH` rather than serving its own copy of the document.
+            DocsUi::Swagger => SwaggerUi::new(path.to_string()).config(Config::from(OPENAPI_PATH)).into(),
+            #[cfg(feature = "ui-redoc")]
+            DocsUi::Redoc => {
+                use utoipa_redoc::{Redoc, Servable};
+                Redoc::with_url(path.to_string(), openapi.clone()).into()
+            },
+            #[cfg(feature = "ui-scalar")]
+            DocsUi::Scalar => {
+                use utoipa_scalar::{Scalar, Servable};
+                Scalar::with_url(path.to_string(), openapi.clone()).into()
+            },
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("DocsConfig::new only accepts UIs that are compiled in"),
+        }
+    }
+}
+
+/// Which documentation UIs to serve and where.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocsConfig {
+    /// Each UI with its mount path, the `--ui` one first.
+    uis: Vec<(DocsUi, String)>,
+}
+
+impl DocsConfig {
+    /// Serves `ui` at `path` and each of `extra` at its default path. Fails if a UI was not
+    /// compiled in or two would be mounted at the same path.
+    pub fn new(ui: DocsUi, path: &str, extra: &[DocsUi]) -> io::Result<Self> {
+        if !path.starts_with('/') || path.len() < 2 || path.ends_with('/') {
+            return Err(invalid_input(format!("ui path {path} must start with / and not end with /")));
+        }
+        let mut uis = vec![(ui, path.to_string())];
+        for &extra in extra {
+            if uis.iter().any(|(ui, _)| *ui == extra) {
+                continue;
+            }
+            let path = extra.default_path();
+            if uis.iter().any(|(_, mounted)| mounted == path) {
+                return Err(invalid_input(format!("--extra-ui {extra:?} would be mounted at {path}, which --ui-path already uses")));
+            }
+            uis.push((extra, path.to_string()));
+        }
+        if let Some((ui, _)) = uis.iter().find(|(ui, _)| !ui.is_compiled_in()) {
+            return Err(invalid_input(format!("the {ui:?} UI is not compiled in, rebuild with --features {}", ui.feature())));
+        }
+        Ok(Self { uis })
+    }
+
+    /// Serves `openapi` at `OPENAPI_PATH` along with the UIs rendering it.
+    pub fn router(&self, openapi: OpenApi) -> Router {
+        let router = self.uis.iter().fold(Router::new(), |router, (ui, path)| router.merge(ui.router(path, &openapi)));
+        router.route(OPENAPI_PATH, routing::get(move || async move { Json(openapi) }))
+    }
+}
+
+impl Default for DocsConfig {
+    fn default() -> Self {
+        Self { uis: vec![(DocsUi::Swagger, "/docs".to_string())] }
+    }
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    async fn get_html(router: Router, path: &str) -> String {
+        let response = router.oneshot(Request::get(path).body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{path}");
+        String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap()
+    }
+
+    fn openapi() -> OpenApi {
+        utoipa::openapi::OpenApiBuilder::new().build()
+    }
+
+    #[tokio::test]
+    async fn test_swagger_ui_is_served_at_ui_path() {
+        let router = DocsConfig::new(DocsUi::Swagger, "/docs", &[]).unwrap().router(openapi());
+
+        let html = get_html(router.clone(), "/docs/").await;
+        assert!(html.contains("swagger-ui"), "{html}");
+        let document = get_html(router, OPENAPI_PATH).await;
+        assert!(document.contains("\"openapi\""), "{document}");
+    }
+
+    #[cfg(feature = "ui-redoc")]
+    #[tokio::test]
+    async fn test_redoc_is_served_at_ui_path() {
+        let router = DocsConfig::new(DocsUi::Redoc, "/reference", &[DocsUi::Swagger]).unwrap().router(openapi());
+
+        let html = get_html(router.clone(), "/reference").await;
+        assert!(html.contains("Redoc"), "{html}");
+        assert!(get_html(router, "/swagger-ui/").await.contains("swagger-ui"));
+    }
+
+    #[cfg(feature = "ui-scalar")]
+    #[tokio::test]
+    async fn test_scalar_is_served_as_extra_ui() {
+        let router = DocsConfig::new(DocsUi::Swagger, "/docs", &[DocsUi::Scalar, DocsUi::Scalar]).unwrap().router(openapi());
+
+        let html = get_html(router, "/scalar").await;
+        assert!(html.contains("Scalar"), "{html}");
+    }
+
+    #[test]
+    fn test_invalid_ui_setups_are_rejected() {
+        assert!(DocsConfig::new(DocsUi::Swagger, "docs", &[]).is_err());
+        assert!(DocsConfig::new(DocsUi::Swagger, "/", &[]).is_err());
+        let err = DocsConfig::new(DocsUi::Swagger, "/redoc", &[DocsUi::Redoc]).unwrap_err();
+        assert!(err.to_string().contains("/redoc"), "{err}");
+        if !cfg!(feature = "ui-scalar") {
+            let err = DocsConfig::new(DocsUi::Scalar, "/docs", &[]).unwrap_err();
+            assert_eq!(err.to_string(), "the Scalar UI is not compiled in, rebuild with --features ui-scalar");
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use axum::{extract::State, http::StatusCode, response::IntoResponse, routing, Json, Router};
+use sdlc_cp_api::services::{attestation::AttestationService, controlplane::NamespaceStore};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// How long a readiness probe may wait on a single service before declaring it unavailable.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum HealthStatus {
+    Ok,
+    Unavailable { reason: String },
+}
+
+/// A service that can report whether it is ready to serve requests.
+#[async_trait]
+pub trait HealthChecker: Send + Sync {
+    /// Name the service is reported under in the readiness breakdown.
+    fn name(&self) -> &'static str;
+
+    async fn check(&self) -> HealthStatus;
+}
+
+/// Ready when the namespace store lock can be acquired.
+pub struct NamespaceStoreHealthChecker(pub Arc<NamespaceStore>);
+
+#[async_trait]
+impl HealthChecker for NamespaceStoreHealthChecker {
+    fn name(&self) -> &'static str {
+        "namespaces"
+    }
+
+    async fn check(&self) -> HealthStatus {
+        match tokio::time::timeout(CHECK_TIMEOUT, self.0.lock()).await {
+            Ok(_) => HealthStatus::Ok,
+            Err(_) => HealthStatus::Unavailable { reason: "namespace store lock could not be acquired".to_string() },
+        }
+    }
+}
+
+/// Ready when the attestation store answers a lookup.
+pub struct AttestationServiceHealthChecker(pub Arc<dyn AttestationService>);
+
+#[async_trait]
+impl HealthChecker for AttestationServiceHealthChecker {
+    fn name(&self) -> &'static str {
+        "attestations"
+    }
+
+    async fn check(&self) -> HealthStatus {
+        match tokio::time::timeout(CHECK_TIMEOUT, self.0.get_attestation(&Uuid::nil())).await {
+            Ok(Ok(_)) => HealthStatus::Ok,
+            Ok(Err(err)) => HealthStatus::Unavailable { reason: err.to_string() },
+            Err(_) => HealthStatus::Unavailable { reason: "attestation store did not respond".to_string() },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct HealthResponse {
+    status: &'static str,
+    version: &'static str,
+}
+
+async fn healthz() -> impl IntoResponse {
+    Json(HealthResponse {
+        status: "ok",
+        version: env!("CARGO_PKG_VERSION"),
+    })
+}
+
+#[derive(Serialize)]
+struct ReadinessResponse {
+    status: &'static str,
+    services: BTreeMap<&'static str, HealthStatus>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failing: Vec<&'static str>,
+}
+
+async fn readyz(State(checkers): State<Arc<Vec<Box<dyn HealthChecker>>>>) -> impl IntoResponse {
+    let mut services = BTreeMap::new();
+    let mut failing = Vec::new();
+    for checker in checkers.iter() {
+        let status = checker.check().await;
+        if status != HealthStatus::Ok {
+            failing.push(checker.name());
+        }
+        services.insert(checker.name(), status);
+    }
+
+    let (code, status) = if failing.is_empty() {
+        (StatusCode::OK, "ok")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "unavailable")
+    };
+    (code, Json(ReadinessResponse { status, services, failing }))
+}
+
+pub fn health_router(checkers: Vec<Box<dyn HealthChecker>>) -> Router {
+    Router::new()
+        .route("/healthz", routing::get(healthz))
+        .route("/readyz", routing::get(readyz))
+        .with_state(Arc::new(checkers))
+}
@@ -1,26 +1,746 @@
 use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
-use axum::Router;
-use sdlc_cp_api::services::controlplane;
+use axum::{http::Method, Extension, Router};
+use axum_server::tls_rustls::RustlsConfig;
+use clap::Parser;
+use sdlc_cp_api::model::phase::FeatureListLimits;
+use sdlc_cp_api::services::{
+    attestation::{start_cleanup_task, InMemoryAttestationService},
+    audit::InMemoryAuditLogService,
+    compliance::InMemoryComplianceBaselineService,
+    component_repository::InMemoryComponentRepository,
+    controlplane::{self, InMemoryControlPlane},
+    export::InMemoryExportService,
+    namespace::InMemoryNamespaceManager,
+    policy_repository::InMemoryPolicyRepository,
+    request_id::RequestIdLayer,
+    sla::start_sla_monitor,
+    team_repository::InMemoryTeamRepository,
+    transitions::TransitionBroadcaster,
+    webhook::{spawn_webhook_dispatcher, InMemoryWebhookService},
+};
+use sdlccp_grpc::{ControlPlaneGrpcService, ControlPlaneServer};
 use tokio::net::TcpListener;
-use utoipa_swagger_ui::SwaggerUi;
-use utoipa::OpenApi;
+use tonic::service::interceptor::InterceptedService;
+use tower_http::{cors::CorsLayer, trace::TraceLayer};
+
+mod auth;
+mod config;
+mod cors;
+mod docs;
+mod health;
+mod metrics;
+mod rate_limit;
+mod repository;
+mod shutdown;
+mod snapshot;
+mod telemetry;
+mod tls;
+
+use auth::{GrpcAuthInterceptor, JwtAuthLayer, JwtConfig};
+use config::{ServerConfig, TlsConfig};
+use cors::{CorsConfig, Environment};
+use docs::{DocsConfig, DocsUi};
+use health::{AttestationServiceHealthChecker, NamespaceStoreHealthChecker};
+use rate_limit::{RateLimitConfig, RateLimitLayer};
+use repository::RepositoryConfig;
+use shutdown::{shutdown_signal, Shutdown};
+
+#[derive(Parser, Debug)]
+#[command(version, about = "SDLC Control Plane API server")]
+struct Args {
+    /// TOML file with the server configuration. Environment variables and flags override it.
+    #[arg(long, env = "SDLCCP_CONFIG")]
+    config: Option<PathBuf>,
+    /// Print the effective configuration as TOML and exit.
+    #[arg(long)]
+    print_config: bool,
+    /// Address the HTTP and gRPC APIs bind to. Defaults to `0.0.0.0`.
+    #[arg(long, env = "SDLCCP_BIND_ADDRESS")]
+    bind_address: Option<Ipv4Addr>,
+    /// Port of the plain HTTP API. Defaults to 8080.
+    #[arg(long, env = "SDLCCP_PORT")]
+    port: Option<u16>,
+    /// URL clients reach the server at, advertised in the OpenAPI document. Defaults to one
+    /// derived from the bound address.
+    #[arg(long, env = "SDLCCP_PUBLIC_URL")]
+    public_url: Option<String>,
+    /// Log filter, either a level (`debug`) or a list of directives (`sdlc_cp_api=debug,info`).
+    /// Defaults to `info`.
+    #[arg(long, env = "SDLCCP_LOG_LEVEL")]
+    log_level: Option<String>,
+    /// Port the gRPC API listens on.
+    #[arg(long, env = "SDLCCP_GRPC_PORT", default_value_t = 50051)]
+    grpc_port: u16,
+    /// HMAC secret used to verify HS256 bearer tokens.
+    #[arg(long, env = "SDLCCP_JWT_SECRET", hide_env_values = true)]
+    jwt_secret: Option<String>,
+    /// PEM file holding the RSA public key used to verify RS256 bearer tokens.
+    #[arg(long, env = "SDLCCP_JWT_PUBLIC_KEY")]
+    jwt_public_key: Option<PathBuf>,
+    /// PEM certificate chain to serve HTTPS with instead of plain HTTP.
+    #[arg(long, env = "SDLCCP_TLS_CERT", requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+    /// PEM private key for `--tls-cert`.
+    #[arg(long, env = "SDLCCP_TLS_KEY", requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+    /// Serve HTTPS with a generated self-signed certificate for `localhost`. Development only.
+    #[arg(long, conflicts_with = "tls_cert")]
+    tls_self_signed: bool,
+    /// Port the HTTPS API listens on when TLS is enabled. Defaults to 8443.
+    #[arg(long, env = "SDLCCP_TLS_PORT")]
+    tls_port: Option<u16>,
+    /// Sustained requests per second allowed from one client IP. Defaults to 10.
+    #[arg(long, env = "SDLCCP_RATE_LIMIT_RPS")]
+    rate_limit_rps: Option<u32>,
+    /// Requests one client IP may burst above the sustained rate.
+    #[arg(long, env = "SDLCCP_RATE_LIMIT_BURST", default_value_t = 20)]
+    rate_limit_burst: u32,
+    /// Comma-separated CIDRs of reverse proxies whose `X-Forwarded-For` header is trusted.
+    #[arg(long, env = "SDLCCP_TRUSTED_PROXIES", value_delimiter = ',')]
+    trusted_proxies: Vec<ipnet::IpNet>,
+    /// Deployment environment. `production` refuses development-only settings.
+    #[arg(long, env = "SDLCCP_ENV", value_enum, default_value_t = Environment::Development)]
+    env: Environment,
+    /// Comma-separated origins browsers may call the API from. `*` allows any origin and is
+    /// refused in production. Defaults to `*`.
+    #[arg(long, env = "SDLCCP_CORS_ALLOWED_ORIGINS", value_delimiter = ',')]
+    cors_allowed_origins: Option<Vec<String>>,
+    /// Comma-separated methods allowed in cross-origin requests.
+    #[arg(long, env = "SDLCCP_CORS_ALLOWED_METHODS", value_delimiter = ',', default_value = "GET,POST,PUT,PATCH,DELETE")]
+    cors_allowed_methods: Vec<Method>,
+    /// How long browsers may cache a CORS preflight response.
+    #[arg(long, env = "SDLCCP_CORS_MAX_AGE_SECONDS", default_value_t = 3600)]
+    cors_max_age_seconds: u64,
+    /// How long to wait for in-flight requests after SIGTERM or Ctrl-C before giving up.
+    #[arg(long, env = "SDLCCP_SHUTDOWN_TIMEOUT_SECS", default_value_t = 30)]
+    shutdown_timeout_secs: u64,
+    /// JSON file the namespaces are restored from at startup and saved to periodically and on
+    /// shutdown. Without it namespaces only live in memory.
+    #[arg(long, env = "SDLCCP_NAMESPACE_SNAPSHOT_FILE")]
+    namespace_snapshot_file: Option<PathBuf>,
+    /// How often to save the namespace snapshot.
+    #[arg(long, env = "SDLCCP_NAMESPACE_SNAPSHOT_INTERVAL_SECS", default_value_t = 60)]
+    namespace_snapshot_interval_secs: u64,
+    /// How often to remove expired attestations.
+    #[arg(long, env = "SDLCCP_ATTESTATION_CLEANUP_INTERVAL_SECS", default_value_t = 300)]
+    attestation_cleanup_interval_secs: u64,
+    /// How often to log releases at risk of missing or past their deployment SLA.
+    #[arg(long, env = "SDLCCP_SLA_CHECK_INTERVAL_SECS", default_value_t = 60)]
+    sla_check_interval_secs: u64,
+    /// OTLP/gRPC collector that traces are exported to.
+    #[arg(long, env = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+    /// UI the API documentation is served with. `redoc` and `scalar` need the `ui-redoc` and
+    /// `ui-scalar` features.
+    #[arg(long, env = "SDLCCP_UI", value_enum, default_value_t = DocsUi::Swagger)]
+    ui: DocsUi,
+    /// Path the `--ui` documentation UI is mounted at.
+    #[arg(long, env = "SDLCCP_UI_PATH", default_value = "/docs")]
+    ui_path: String,
+    /// Another documentation UI to serve, at `/swagger-ui`, `/redoc` or `/scalar`. Repeatable.
+    #[arg(long, value_enum)]
+    extra_ui: Vec<DocsUi>,
+}
+
+impl Args {
+    /// The defaults, overridden by the `--config` file, then by environment variables and flags.
+    fn server_config(&self) -> Result<ServerConfig, std::io::Error> {
+        let mut config = match &self.config {
+            Some(path) => ServerConfig::load(path)?,
+            None => ServerConfig::default(),
+        };
+        if let Some(bind_address) = self.bind_address {
+            config.bind_address = bind_address;
+        }
+        if let Some(port) = self.port {
+            config.port = port;
+        }
+        if let Some(public_url) = &self.public_url {
+            config.public_url = Some(public_url.clone());
+        }
+        if let (Some(cert), Some(key)) = (&self.tls_cert, &self.tls_key) {
+            let port = config.tls.as_ref().map_or(8443, |tls| tls.port);
+            config.tls = Some(TlsConfig { cert: cert.clone(), key: key.clone(), port });
+        }
+        if let (Some(port), Some(tls)) = (self.tls_port, &mut config.tls) {
+            tls.port = port;
+        }
+        if let Some(jwt_secret) = &self.jwt_secret {
+            config.jwt_secret = Some(jwt_secret.clone());
+        }
+        if let Some(log_level) = &self.log_level {
+            config.log_level = log_level.clone();
+        }
+        if let Some(origins) = &self.cors_allowed_origins {
+            config.cors_allowed_origins = origins.clone();
+        }
+        if let Some(rate_limit_rps) = self.rate_limit_rps {
+            config.rate_limit_rps = rate_limit_rps;
+        }
+        if let Some(otlp_endpoint) = &self.otlp_endpoint {
+            config.otlp_endpoint = otlp_endpoint.clone();
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn jwt_config(&self, config: &ServerConfig) -> Result<JwtConfig, std::io::Error> {
+        match (&config.jwt_secret, &self.jwt_public_key) {
+            (Some(secret), _) => Ok(JwtConfig::hmac(secret.as_bytes())),
+            (None, Some(path)) => JwtConfig::rsa_pem(&std::fs::read(path)?).map_err(std::io::Error::other),
+            (None, None) => Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "either jwt_secret or --jwt-public-key is required")),
+        }
+    }
+
+    fn rate_limit(&self, config: &ServerConfig) -> Result<RateLimitLayer, std::io::Error> {
+        RateLimitConfig {
+            per_second: config.rate_limit_rps,
+            burst_size: self.rate_limit_burst,
+            trusted_proxies: self.trusted_proxies.clone(),
+        }
+        .layer()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "rate limits must be greater than zero"))
+    }
+
+    fn cors(&self, config: &ServerConfig) -> Result<CorsLayer, std::io::Error> {
+        CorsConfig {
+            allowed_origins: config.cors_allowed_origins.clone(),
+            allowed_methods: self.cors_allowed_methods.clone(),
+            max_age: Duration::from_secs(self.cors_max_age_seconds),
+        }
+        .layer(self.env)
+    }
+
+    fn docs(&self) -> Result<DocsConfig, std::io::Error> {
+        DocsConfig::new(self.ui, &self.ui_path, &self.extra_ui)
+    }
+
+    /// The TLS configuration to serve with, or `None` for plain HTTP.
+    async fn rustls_config(&self, config: &ServerConfig) -> Result<Option<RustlsConfig>, std::io::Error> {
+        if self.tls_self_signed {
+            tracing::warn!("Serving a self-signed certificate; do not use this in production");
+            let certificate = tls::SelfSignedCertificate::generate().map_err(std::io::Error::other)?;
+            return certificate.rustls_config().await.map(Some);
+        }
+        let Some(tls) = &config.tls else {
+            return Ok(None);
+        };
+        let rustls = RustlsConfig::from_pem_file(&tls.cert, &tls.key).await?;
+        tls::reload_on_sighup(rustls.clone(), tls.cert.clone(), tls.key.clone())?;
+        Ok(Some(rustls))
+    }
+
+    fn tls_port(&self, config: &ServerConfig) -> u16 {
+        config.tls.as_ref().map(|tls| tls.port).or(self.tls_port).unwrap_or(8443)
+    }
+}
+
+/// Services shared by the HTTP and gRPC APIs, so both see the same state.
+struct Services {
+    audit: Arc<controlplane::AuditStore>,
+    namespace_store: Arc<controlplane::NamespaceStore>,
+    attestation_service: Arc<InMemoryAttestationService>,
+    releases: Arc<controlplane::ReleaseStore>,
+    policies: Arc<InMemoryPolicyRepository>,
+    components: Arc<InMemoryComponentRepository>,
+    teams: Arc<InMemoryTeamRepository>,
+    webhooks: Arc<InMemoryWebhookService>,
+    control_plane: Arc<InMemoryControlPlane>,
+    compliance_baselines: Arc<InMemoryComplianceBaselineService>,
+    transitions: TransitionBroadcaster,
+    feature_list_limits: FeatureListLimits,
+}
+
+impl Services {
+    /// In-memory services around `releases`, which may live in a database.
+    fn with_releases(releases: Arc<controlplane::ReleaseStore>) -> Self {
+        let attestation_service = Arc::new(InMemoryAttestationService::new());
+        let policies = Arc::new(InMemoryPolicyRepository::new().with_releases(releases.clone()));
+        let components = Arc::new(InMemoryComponentRepository::new());
+        let control_plane = Arc::new(InMemoryControlPlane::new(policies.clone(), attestation_service.clone(), releases.clone(), components.clone()));
+        Self {
+            audit: Arc::new(InMemoryAuditLogService::new()),
+            namespace_store: Arc::new(controlplane::NamespaceStore::default()),
+            compliance_baselines: Arc::new(InMemoryComplianceBaselineService::new(control_plane.clone())),
+            control_plane,
+            attestation_service,
+            releases,
+            policies,
+            components,
+            teams: Arc::new(InMemoryTeamRepository::new()),
+            webhooks: Arc::new(InMemoryWebhookService::new()),
+            transitions: TransitionBroadcaster::new(64),
+            feature_list_limits: FeatureListLimits::default(),
+        }
+    }
+}
+
+/// The HTTP API. Everything under `/api` requires a bearer token; probes, metrics and docs do not.
+/// Everything but the probes and metrics is rate limited per client IP, gets an `X-Request-ID`
+/// and has its duration recorded. CORS applies to all routes. The OpenAPI document names
+/// `api_base_url` as the server, so the docs UI sends its requests there.
+fn app(services: &Services, api_base_url: &str, docs: &DocsConfig, auth: JwtAuthLayer, rate_limit: RateLimitLayer, cors: CorsLayer) -> Router {
+    let openapi = controlplane::ControlPlaneAPIDoc::openapi_with_server_url(api_base_url);
+    let audit = services.audit.clone();
+    let namespace_store = services.namespace_store.clone();
+    let exports = Arc::new(InMemoryExportService::new(
+        namespace_store.clone(),
+        services.releases.clone(),
+        services.policies.clone(),
+        services.attestation_service.clone(),
+    ));
+
+    Router::new()
+        .merge(docs.router(openapi))
+        .merge(
+            Router::new()
+                .nest("/api/v1alpha1/namespaces", controlplane::namespace_router(namespace_store.clone(), exports, audit.clone()))
+                .nest("/api/v1alpha1/components", controlplane::component_router(services.components.clone(), audit.clone()))
+                .nest("/api/v1alpha1/teams", controlplane::team_router(services.teams.clone(), audit.clone()))
+                .nest("/api/v1alpha1/webhooks", controlplane::webhook_router(services.webhooks.clone(), audit.clone()))
+                .nest("/api/v1alpha1/attestations", controlplane::attestation_router(services.attestation_service.clone(), audit.clone()))
+                .nest("/api/v1alpha1/attestation-schemas", controlplane::attestation_schema_router(services.attestation_service.clone()))
+                .nest("/api/v1alpha1/policies", controlplane::policy_router(services.policies.clone(), audit.clone()))
+                .nest(
+                    "/api/v1alpha1/releases",
+                    controlplane::release_router(services.releases.clone(), services.transitions.clone(), audit.clone())
+                        .layer(Extension(services.feature_list_limits))
+                        .merge(controlplane::policy_evaluation_router(services.control_plane.clone()))
+                        .merge(controlplane::compliance_baseline_router(services.compliance_baselines.clone(), audit.clone()))
+                        .merge(controlplane::sbom_router(services.releases.clone(), services.components.clone()))
+                        .merge(controlplane::release_attestation_router(services.releases.clone(), services.attestation_service.clone()))
+                        .merge(controlplane::release_search_router(services.releases.clone(), services.components.clone())),
+                )
+                .nest("/api/v1alpha1/audit", controlplane::audit_router(audit))
+                .layer(auth),
+        )
+        .layer(rate_limit)
+        .layer(RequestIdLayer)
+        .layer(TraceLayer::new_for_http().make_span_with(telemetry::make_span).on_request(telemetry::continue_trace))
+        .layer(axum::middleware::from_fn(metrics::track_request_duration))
+        // Probes and metrics are merged after the rate limit layer so orchestrators and scrapers
+        // are never throttled, and stay outside the versioned API prefix.
+        .merge(health::health_router(vec![
+            Box::new(NamespaceStoreHealthChecker(namespace_store)),
+            Box::new(AttestationServiceHealthChecker(services.attestation_service.clone())),
+        ]))
+        .merge(metrics::metrics_router(services.releases.clone(), services.namespace_store.clone()))
+        // Outermost, so preflight requests are answered without a token or rate limit.
+        .layer(cors)
+}
+
+fn grpc_service(services: &Services, auth: &JwtAuthLayer) -> InterceptedService<ControlPlaneServer<ControlPlaneGrpcService>, GrpcAuthInterceptor> {
+    ControlPlaneServer::with_interceptor(ControlPlaneGrpcService::new(
+        services.control_plane.clone(),
+        services.releases.clone(),
+        services.namespace_store.clone(),
+        services.transitions.clone(),
+        services.audit.clone(),
+    ), auth.grpc_interceptor())
+}
+
+/// Serves the app built by `app` over TLS when configured, otherwise plain HTTP, until
+/// `shutdown` is triggered and the open connections are done. `app` is given the API's URL once
+/// the address is known.
+async fn serve_http(args: &Args, server_config: &ServerConfig, app: impl FnOnce(&str) -> Router, shutdown: Shutdown) -> Result<(), std::io::Error> {
+    tls::install_crypto_provider();
+    if let Some(config) = args.rustls_config(server_config).await? {
+        let address = SocketAddr::from((server_config.bind_address, args.tls_port(server_config)));
+        tracing::info!(%address, "Listening (TLS)");
+        let app = app(&server_config.api_base_url(address, true));
+        let handle = axum_server::Handle::new();
+        let on_shutdown = handle.clone();
+        tokio::spawn(async move {
+            shutdown.triggered().await;
+            // No timeout of its own; `shutdown::drain` bounds the wait.
+            on_shutdown.graceful_shutdown(None);
+        });
+        return tls::serve(address, config, app, handle).await;
+    }
+
+    let address = SocketAddr::from((server_config.bind_address, server_config.port));
+    serve_plain(TcpListener::bind(&address).await?, server_config, app, shutdown).await
+}
+
+/// Serves plain HTTP on `listener`, advertising the address it is actually bound to.
+async fn serve_plain(listener: TcpListener, server_config: &ServerConfig, app: impl FnOnce(&str) -> Router, shutdown: Shutdown) -> Result<(), std::io::Error> {
+    let address = listener.local_addr()?;
+    tracing::info!(%address, "Listening");
+    let app = app(&server_config.api_base_url(address, false));
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(shutdown.triggered())
+        .await
+}
 
 #[tokio::main]
 async fn main() -> Result<(), std::io::Error> {
-    let openapi = controlplane::ControlPlaneAPIDoc::openapi();
-    let app = Router::new()
-        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", openapi))
-        //.merge(Redoc::with_url("/redoc", ApiDoc::openapi()))
-        // There is no need to create `RapiDoc::with_openapi` because the OpenApi is served
-        // via SwaggerUi instead we only make rapidoc to point to the existing doc.
-        //.merge(RapiDoc::new("/api-docs/openapi.json").path("/rapidoc"))
-        // Alternative to above
-        // .merge(RapiDoc::with_openapi("/api-docs/openapi2.json", ApiDoc::openapi()).path("/rapidoc"))
-        //.merge(Scalar::with_url("/scalar", ApiDoc::openapi()))
-        .nest("/api/v1alpha1/namespaces", controlplane::namespace_router());
-
-    let address = SocketAddr::from((Ipv4Addr::UNSPECIFIED, 8080));
-    let listener = TcpListener::bind(&address).await?;
-    axum::serve(listener, app.into_make_service()).await
-}
\ No newline at end of file
+    let args = Args::parse();
+    let config = match args.server_config() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("Invalid configuration: {error}");
+            std::process::exit(2);
+        },
+    };
+    if args.print_config {
+        print!("{}", config.to_toml());
+        return Ok(());
+    }
+    let tracer_provider = telemetry::init(&config.log_level, &config.otlp_endpoint).map_err(std::io::Error::other)?;
+
+    let mut services = Services::with_releases(RepositoryConfig::from_env()?.connect().await?);
+    services.feature_list_limits = config.feature_list_limits();
+    let namespaces = match &args.namespace_snapshot_file {
+        Some(path) => snapshot::load_namespaces(path)?,
+        None => InMemoryNamespaceManager::new(),
+    };
+    services.namespace_store = Arc::new(controlplane::NamespaceStore::new(namespaces.with_max_depth(config.max_namespace_depth)));
+    if let Some(path) = &args.namespace_snapshot_file {
+        let interval = Duration::from_secs(args.namespace_snapshot_interval_secs);
+        snapshot::save_periodically(services.namespace_store.clone(), path.clone(), interval);
+    }
+    spawn_webhook_dispatcher(services.webhooks.clone(), &services.transitions);
+    start_cleanup_task(services.attestation_service.clone(), Duration::from_secs(args.attestation_cleanup_interval_secs));
+    start_sla_monitor(services.releases.clone(), Duration::from_secs(args.sla_check_interval_secs));
+    let auth = JwtAuthLayer::new(args.jwt_config(&config)?);
+    let rate_limit = args.rate_limit(&config)?;
+    let cors = args.cors(&config)?;
+    let docs = args.docs()?;
+
+    let drain_timeout = Duration::from_secs(args.shutdown_timeout_secs);
+    let snapshot_file = args.namespace_snapshot_file.clone();
+    let namespace_store = services.namespace_store.clone();
+
+    let serve = |shutdown: Shutdown| async move {
+        let grpc_address = SocketAddr::from((config.bind_address, args.grpc_port));
+        let grpc = tonic::transport::Server::builder()
+            .add_service(grpc_service(&services, &auth))
+            .serve_with_shutdown(grpc_address, shutdown.clone().triggered());
+        tracing::info!(address = %grpc_address, "gRPC listening");
+
+        let http = serve_http(&args, &config, |api_base_url| app(&services, api_base_url, &docs, auth, rate_limit, cors), shutdown);
+
+        tokio::try_join!(http, async { grpc.await.map_err(std::io::Error::other) }).map(|_| ())
+    };
+
+    let drained = shutdown::drain(serve, shutdown_signal(), drain_timeout).await;
+    if let Some(path) = snapshot_file {
+        snapshot::save_namespaces(&namespace_store, &path).await?;
+        tracing::info!(path = %path.display(), "Saved namespace snapshot");
+    }
+    if let Err(error) = tracer_provider.shutdown() {
+        tracing::warn!(%error, "Failed to flush pending spans");
+    }
+    match drained {
+        Ok(result) => result,
+        Err(_) => {
+            tracing::warn!(timeout_secs = drain_timeout.as_secs(), "Connections still open after the shutdown timeout, closing them");
+            std::process::exit(1);
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::extract::ConnectInfo;
+    use axum::Extension;
+    use sdlc_cp_api::services::release_repository::InMemoryReleaseRepository;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    fn test_auth() -> JwtAuthLayer {
+        JwtAuthLayer::new(JwtConfig::hmac(b"test-secret"))
+    }
+
+    fn test_cors() -> CorsLayer {
+        let cors = CorsConfig {
+            allowed_origins: vec!["https://ui.example.com".to_string()],
+            allowed_methods: vec![Method::GET, Method::POST],
+            max_age: Duration::from_secs(600),
+        };
+        cors.layer(Environment::Production).unwrap()
+    }
+
+    fn app_at(api_base_url: &str) -> Router {
+        let rate_limit = RateLimitConfig { per_second: 1, burst_size: 10, trusted_proxies: Vec::new() };
+        app(&Services::with_releases(Arc::new(InMemoryReleaseRepository::new())), api_base_url, &DocsConfig::default(), test_auth(), rate_limit.layer().unwrap(), test_cors())
+    }
+
+    fn test_app() -> Router {
+        app_at(config::API_BASE_PATH)
+            // What `into_make_service_with_connect_info` inserts for a real connection
+            .layer(Extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 40000)))))
+    }
+
+    async fn preflight(origin: &str) -> axum::response::Response {
+        let request = Request::options("/api/v1alpha1/namespaces")
+            .header("origin", origin)
+            .header("access-control-request-method", "POST")
+            .header("access-control-request-headers", "authorization,content-type")
+            .body(Body::empty())
+            .unwrap();
+        test_app().oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cors_preflight_allows_configured_origin() {
+        let response = preflight("https://ui.example.com").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(headers["access-control-allow-origin"], "https://ui.example.com");
+        assert_eq!(headers["access-control-allow-methods"], "GET,POST");
+        assert_eq!(headers["access-control-max-age"], "600");
+    }
+
+    #[tokio::test]
+    async fn test_cors_preflight_omits_headers_for_other_origins() {
+        let response = preflight("https://evil.example.com").await;
+
+        assert!(response.headers().get("access-control-allow-origin").is_none());
+    }
+
+    #[test]
+    fn test_flags_override_config_file() {
+        let path = std::env::temp_dir().join(format!("sdlccp-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "port = 9090\nlog_level = \"debug\"\nrate_limit_rps = 5\n").unwrap();
+
+        let args = Args::try_parse_from(["sdlccp-server", "--config", path.to_str().unwrap(), "--port", "9191"]).unwrap();
+        let config = args.server_config().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.port, 9191);
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.rate_limit_rps, 5);
+        assert_eq!(config.bind_address, Ipv4Addr::UNSPECIFIED);
+
+        let args = Args::try_parse_from(["sdlccp-server", "--port", "0"]).unwrap();
+        assert!(args.server_config().is_err());
+    }
+
+    #[test]
+    fn test_default_cors_methods_cover_every_api_method() {
+        let args = Args::try_parse_from(["sdlccp-server"]).unwrap();
+        assert_eq!(args.cors_allowed_methods, vec![Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE]);
+    }
+
+    #[tokio::test]
+    async fn test_openapi_server_url_matches_bound_port() {
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (fetched, fetch_done) = tokio::sync::oneshot::channel();
+        let fetch = tokio::spawn(async move {
+            let body = reqwest::get(format!("http://127.0.0.1:{port}/api-docs/openapi.json")).await.unwrap().text().await.unwrap();
+            let _ = fetched.send(());
+            body
+        });
+        let config = ServerConfig::default();
+
+        let serve = |shutdown| serve_plain(listener, &config, app_at, shutdown);
+        let drained = shutdown::drain(serve, async { let _ = fetch_done.await; }, Duration::from_secs(5)).await;
+
+        assert!(matches!(drained, Ok(Ok(()))));
+        let openapi: serde_json::Value = serde_json::from_str(&fetch.await.unwrap()).unwrap();
+        assert_eq!(openapi["servers"][0]["url"], format!("http://127.0.0.1:{port}/api/v1alpha1"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_rejects_requests_over_burst() {
+        let app = test_app();
+        for _ in 0..10 {
+            let response = app
+                .clone()
+                .oneshot(Request::get("/api/v1alpha1/namespaces").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_ne!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        }
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/api/v1alpha1/namespaces").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response.headers()["retry-after"].to_str().unwrap().parse().unwrap();
+        assert!(retry_after >= 1);
+
+        // Probes are exempt
+        let response = app
+            .oneshot(Request::get("/healthz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_api_requires_token_but_probes_do_not() {
+        let app = test_app();
+        let response = app
+            .clone()
+            .oneshot(Request::get("/api/v1alpha1/namespaces").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let request_id = response.headers()["x-request-id"].to_str().unwrap().to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "missing_token");
+        assert_eq!(body["request_id"], request_id);
+
+        let response = app
+            .oneshot(Request::get("/healthz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_every_response_gets_its_own_request_id() {
+        let app = test_app();
+        let mut request_ids = Vec::new();
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(Request::get("/api/v1alpha1/namespaces").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            request_ids.push(response.headers()["x-request-id"].to_str().unwrap().to_string());
+        }
+        assert!(request_ids.iter().all(|request_id| !request_id.is_empty()));
+        assert_ne!(request_ids[0], request_ids[1]);
+    }
+
+    #[tokio::test]
+    async fn test_healthz_reports_version() {
+        let response = test_app()
+            .oneshot(Request::get("/healthz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    }
+
+    #[tokio::test]
+    async fn test_readyz_is_ready_at_startup() {
+        let response = test_app()
+            .oneshot(Request::get("/readyz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["services"]["namespaces"]["status"], "ok");
+        assert_eq!(body["services"]["attestations"]["status"], "ok");
+    }
+
+    #[tokio::test]
+    async fn test_readyz_reports_failing_service() {
+        let store = Arc::new(controlplane::NamespaceStore::default());
+        let router = health::health_router(vec![Box::new(NamespaceStoreHealthChecker(store.clone()))]);
+
+        // Hold the lock so the probe cannot acquire it
+        let _guard = store.lock().await;
+        let response = router
+            .oneshot(Request::get("/readyz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["failing"][0], "namespaces");
+    }
+
+    fn bearer_token() -> String {
+        let claims = serde_json::json!({ "sub": "alice", "roles": [], "exp": chrono::Utc::now().timestamp() + 3600 });
+        let token = jsonwebtoken::encode(&jsonwebtoken::Header::default(), &claims, &jsonwebtoken::EncodingKey::from_secret(b"test-secret")).unwrap();
+        format!("Bearer {}", token)
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_release_transitions() {
+        let app = test_app();
+        let post = |uri: String, body: serde_json::Value| {
+            Request::post(uri)
+                .header("authorization", bearer_token())
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        let response = app
+            .clone()
+            .oneshot(post("/api/v1alpha1/releases".to_string(), serde_json::json!({ "component_id": uuid::Uuid::new_v4(), "version": "1.0.0" })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let release: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+        let transition = serde_json::json!({ "action": "start_development", "started_by": "alice", "feature_list": ["login"] });
+        let response = app
+            .clone()
+            .oneshot(post(format!("/api/v1alpha1/releases/{}/transitions", release["id"].as_str().unwrap()), transition))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(Request::get("/metrics").body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/plain; version=0.0.4");
+        let body = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+        assert!(body.contains("sdlccp_release_transitions_total{"), "{body}");
+        assert!(body.contains("to_phase=\"Development\""), "{body}");
+        assert!(body.contains("sdlccp_request_duration_seconds_bucket{"), "{body}");
+        assert!(body.contains("path=\"/api/v1alpha1/releases/:id/transitions\""), "{body}");
+        assert!(body.contains("sdlccp_active_releases{phase=\"Development\"}"), "{body}");
+        assert!(body.contains("sdlccp_namespace_count"), "{body}");
+    }
+
+    #[tokio::test]
+    async fn test_grpc_calls_are_authenticated() {
+        use sdlccp_grpc::proto::{CreateReleaseRequest, Namespace};
+        use sdlccp_grpc::ControlPlaneClient;
+
+        let services = Services::with_releases(Arc::new(InMemoryReleaseRepository::new()));
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(grpc_service(&services, &test_auth()))
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+        let mut client = ControlPlaneClient::connect(format!("http://{}", address)).await.unwrap();
+        let create_release = || CreateReleaseRequest {
+            component_id: uuid::Uuid::new_v4().to_string(),
+            version: "1.0.0".to_string(),
+            created_by: "alice".to_string(),
+        };
+        fn authenticated<T>(message: T) -> tonic::Request<T> {
+            let mut request = tonic::Request::new(message);
+            request.metadata_mut().insert("authorization", bearer_token().parse().unwrap());
+            request
+        }
+
+        let status = client.create_release(create_release()).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+        let mut forged = tonic::Request::new(create_release());
+        forged.metadata_mut().insert("authorization", "Bearer not-a-token".parse().unwrap());
+        assert_eq!(client.create_release(forged).await.unwrap_err().code(), tonic::Code::Unauthenticated);
+
+        client.create_release(authenticated(create_release())).await.unwrap();
+        // The token carries no roles, so namespace calls are refused like over HTTP
+        let status = client.create_namespace(authenticated(Namespace { path: "team-a".to_string() })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::{Arc, OnceLock},
+    time::Instant,
+};
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing, Router,
+};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use sdlc_cp_api::services::{
+    controlplane::{NamespaceStore, ReleaseStore},
+    metrics::{ATTESTATION_STORE_TOTAL, POLICY_EVALUATIONS_TOTAL, RELEASE_TRANSITIONS_TOTAL},
+};
+use tokio::sync::Mutex;
+
+pub const REQUEST_DURATION_SECONDS: &str = "sdlccp_request_duration_seconds";
+pub const ACTIVE_RELEASES: &str = "sdlccp_active_releases";
+pub const NAMESPACE_COUNT: &str = "sdlccp_namespace_count";
+
+/// Content type of the Prometheus text exposition format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+const REQUEST_DURATION_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Installs the process-wide Prometheus recorder on first use.
+fn recorder() -> PrometheusHandle {
+    static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+    HANDLE
+        .get_or_init(|| {
+            let handle = PrometheusBuilder::new()
+                .set_buckets_for_metric(Matcher::Full(REQUEST_DURATION_SECONDS.to_string()), REQUEST_DURATION_BUCKETS)
+                .expect("buckets are not empty")
+                .install_recorder()
+                .expect("no other metrics recorder is installed");
+            metrics::describe_counter!(RELEASE_TRANSITIONS_TOTAL, "Release transitions applied through the HTTP API");
+            metrics::describe_counter!(POLICY_EVALUATIONS_TOTAL, "Evaluations of a release against its policies");
+            metrics::describe_counter!(ATTESTATION_STORE_TOTAL, "Attestations stored");
+            metrics::describe_histogram!(REQUEST_DURATION_SECONDS, metrics::Unit::Seconds, "HTTP request latency");
+            metrics::describe_gauge!(ACTIVE_RELEASES, "Stored releases per phase");
+            metrics::describe_gauge!(NAMESPACE_COUNT, "Namespaces, at any depth");
+            handle
+        })
+        .clone()
+}
+
+/// Records the duration of every request it wraps, labelled with the route rather than the
+/// concrete path so IDs do not multiply the series.
+pub async fn track_request_duration(request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| "unmatched".to_string(), |path| path.as_str().to_string());
+    let started = Instant::now();
+    let response = next.run(request).await;
+    metrics::histogram!(
+        REQUEST_DURATION_SECONDS,
+        "method" => method,
+        "path" => path,
+        "status_code" => response.status().as_u16().to_string(),
+    )
+    .record(started.elapsed().as_secs_f64());
+    response
+}
+
+#[derive(Clone)]
+struct MetricsState {
+    handle: PrometheusHandle,
+    releases: Arc<ReleaseStore>,
+    namespaces: Arc<NamespaceStore>,
+    /// Phases a gauge was set for, so phases that emptied out drop back to zero.
+    reported_phases: Arc<Mutex<HashSet<String>>>,
+}
+
+impl MetricsState {
+    /// Sets the gauges, which are read from the stores rather than tracked on every change.
+    async fn update_gauges(&self) {
+        let mut releases_per_phase: BTreeMap<String, usize> = BTreeMap::new();
+        match self.releases.list_releases().await {
+            Ok(releases) => {
+                for release in releases {
+                    *releases_per_phase.entry(release.phase_name().to_string()).or_default() += 1;
+                }
+            },
+            Err(err) => tracing::warn!(error = %err, "Failed to count releases"),
+        }
+        let mut reported_phases = self.reported_phases.lock().await;
+        for phase in reported_phases.iter() {
+            if !releases_per_phase.contains_key(phase) {
+                metrics::gauge!(ACTIVE_RELEASES, "phase" => phase.clone()).set(0.0);
+            }
+        }
+        for (phase, count) in releases_per_phase {
+            metrics::gauge!(ACTIVE_RELEASES, "phase" => phase.clone()).set(count as f64);
+            reported_phases.insert(phase);
+        }
+
+        let namespaces = self.namespaces.lock().await.list_all_namespaces().await.len();
+        metrics::gauge!(NAMESPACE_COUNT).set(namespaces as f64);
+    }
+}
+
+async fn render_metrics(State(state): State<MetricsState>) -> impl IntoResponse {
+    state.update_gauges().await;
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], state.handle.render())
+}
+
+/// `GET /metrics` in the Prometheus text format.
+pub fn metrics_router(releases: Arc<ReleaseStore>, namespaces: Arc<NamespaceStore>) -> Router {
+    Router::new()
+        .route("/metrics", routing::get(render_metrics))
+        .with_state(MetricsState { handle: recorder(), releases, namespaces, reported_phases: Arc::default() })
+}
@@ -0,0 +1,157 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use axum::{
+    extract::ConnectInfo,
+    http::{header, HeaderValue, Request, StatusCode},
+    response::{IntoResponse, Response},
+};
+use governor::middleware::NoOpMiddleware;
+use ipnet::IpNet;
+use tower_governor::{governor::GovernorConfigBuilder, key_extractor::KeyExtractor, GovernorError, GovernorLayer};
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// How often idle per-client buckets are dropped from the limiter.
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+pub type RateLimitLayer = GovernorLayer<ClientIpKeyExtractor, NoOpMiddleware>;
+
+/// Keys requests by client IP.
+///
+/// `X-Forwarded-For` is only honoured when the TCP peer is one of the trusted proxies, so clients
+/// cannot pick their own bucket. The header is read right to left and the first address that is
+/// not a trusted proxy is the client.
+#[derive(Debug, Clone, Default)]
+pub struct ClientIpKeyExtractor {
+    trusted_proxies: Arc<Vec<IpNet>>,
+}
+
+impl ClientIpKeyExtractor {
+    pub fn new(trusted_proxies: Vec<IpNet>) -> Self {
+        Self { trusted_proxies: Arc::new(trusted_proxies) }
+    }
+
+    fn is_trusted(&self, ip: &IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|net| net.contains(ip))
+    }
+}
+
+impl KeyExtractor for ClientIpKeyExtractor {
+    type Key = IpAddr;
+
+    fn extract<T>(&self, req: &Request<T>) -> Result<Self::Key, GovernorError> {
+        let peer = req
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(address)| address.ip())
+            .ok_or(GovernorError::UnableToExtractKey)?;
+        if !self.is_trusted(&peer) {
+            return Ok(peer);
+        }
+
+        let forwarded = req
+            .headers()
+            .get_all(X_FORWARDED_FOR)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|hop| hop.trim().parse::<IpAddr>().ok())
+            .collect::<Vec<_>>();
+        Ok(forwarded
+            .iter()
+            .rev()
+            .find(|ip| !self.is_trusted(ip))
+            .or(forwarded.first())
+            .copied()
+            .unwrap_or(peer))
+    }
+}
+
+/// Per-client request quota.
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    /// Sustained requests per second a client may make.
+    pub per_second: u32,
+    /// Requests a client may make in a burst before being limited.
+    pub burst_size: u32,
+    pub trusted_proxies: Vec<IpNet>,
+}
+
+impl RateLimitConfig {
+    /// Builds the limiter layer and starts a task that periodically evicts idle clients.
+    ///
+    /// Returns `None` when either rate is zero.
+    pub fn layer(&self) -> Option<RateLimitLayer> {
+        let replenish_every = Duration::from_secs(1).checked_div(self.per_second)?;
+        let config = GovernorConfigBuilder::default()
+            .period(replenish_every)
+            .burst_size(self.burst_size)
+            .key_extractor(ClientIpKeyExtractor::new(self.trusted_proxies.clone()))
+            .error_handler(too_many_requests)
+            .finish()?;
+
+        let limiter = config.limiter().clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+            loop {
+                interval.tick().await;
+                limiter.retain_recent();
+            }
+        });
+
+        Some(GovernorLayer { config: Arc::new(config) })
+    }
+}
+
+/// Turns a limiter rejection into `429 Too Many Requests` with a `Retry-After` header.
+fn too_many_requests(error: GovernorError) -> Response {
+    match error {
+        GovernorError::TooManyRequests { wait_time, .. } => {
+            // The governor rounds down to whole seconds; never tell a client to retry immediately.
+            let retry_after = HeaderValue::from(wait_time.max(1));
+            (StatusCode::TOO_MANY_REQUESTS, [(header::RETRY_AFTER, retry_after)], "Too Many Requests").into_response()
+        }
+        GovernorError::UnableToExtractKey => {
+            tracing::error!("Rate limiter could not determine the client address");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+        GovernorError::Other { code, msg, .. } => (code, msg.unwrap_or_default()).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+
+    fn request(peer: &str, forwarded_for: Option<&str>) -> Request<Body> {
+        let mut builder = Request::get("/").extension(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 40000)));
+        if let Some(forwarded_for) = forwarded_for {
+            builder = builder.header(X_FORWARDED_FOR, forwarded_for);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn test_forwarded_for_is_ignored_from_untrusted_peer() {
+        let extractor = ClientIpKeyExtractor::new(vec!["10.0.0.0/8".parse().unwrap()]);
+        let key = extractor.extract(&request("203.0.113.7", Some("198.51.100.1"))).unwrap();
+        assert_eq!(key, "203.0.113.7".parse::<IpAddr>().unwrap());
+    }
+
+    #[test]
+    fn test_forwarded_for_skips_trusted_proxies() {
+        let extractor = ClientIpKeyExtractor::new(vec!["10.0.0.0/8".parse().unwrap()]);
+        let key = extractor
+            .extract(&request("10.0.0.2", Some("192.0.2.9, 198.51.100.1, 10.0.0.1")))
+            .unwrap();
+        assert_eq!(key, "198.51.100.1".parse::<IpAddr>().unwrap());
+
+        let key = extractor.extract(&request("10.0.0.2", None)).unwrap();
+        assert_eq!(key, "10.0.0.2".parse::<IpAddr>().unwrap());
+    }
+}
@@ -0,0 +1,88 @@
+use std::{io, sync::Arc};
+
+use sdlc_cp_api::services::{
+    controlplane::ReleaseStore, event_sourcing_release_repository::EventSourcingReleaseRepository,
+    release_repository::InMemoryReleaseRepository,
+};
+
+/// Where releases are stored, chosen with `SDLCCP_BACKEND`: `in-memory` (the default),
+/// `event-sourced` (in memory, keeping every change for `GET /releases/{id}/events`), `sqlite`
+/// or `postgres`. The database backends need the matching `backend-*` feature and take their
+/// connection URL from `SDLCCP_DATABASE_URL`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepositoryConfig {
+    InMemory,
+    EventSourced,
+    #[cfg(feature = "backend-sqlite")]
+    Sqlite { url: String },
+    #[cfg(feature = "backend-postgres")]
+    Postgres { url: String },
+}
+
+impl RepositoryConfig {
+    pub fn from_env() -> Result<Self, io::Error> {
+        let backend = std::env::var("SDLCCP_BACKEND").ok();
+        let url = std::env::var("SDLCCP_DATABASE_URL").ok();
+        Self::from_vars(backend.as_deref(), url)
+    }
+
+    fn from_vars(backend: Option<&str>, url: Option<String>) -> Result<Self, io::Error> {
+        #[cfg(not(any(feature = "backend-sqlite", feature = "backend-postgres")))]
+        let _ = url;
+        #[cfg(any(feature = "backend-sqlite", feature = "backend-postgres"))]
+        let url = || url.ok_or_else(|| invalid_input("SDLCCP_DATABASE_URL is required for a database backend"));
+        match backend.unwrap_or("in-memory") {
+            "in-memory" => Ok(Self::InMemory),
+            "event-sourced" => Ok(Self::EventSourced),
+            #[cfg(feature = "backend-sqlite")]
+            "sqlite" => Ok(Self::Sqlite { url: url()? }),
+            #[cfg(feature = "backend-postgres")]
+            "postgres" => Ok(Self::Postgres { url: url()? }),
+            backend if ["sqlite", "postgres"].contains(&backend) => Err(invalid_input(format!("the {backend} backend is not compiled in, build with --features backend-{backend}"))),
+            backend => Err(invalid_input(format!("unknown SDLCCP_BACKEND {backend:?}, expected in-memory, event-sourced, sqlite or postgres"))),
+        }
+    }
+
+    /// Opens the configured store, creating its tables if they do not exist yet.
+    pub async fn connect(&self) -> Result<Arc<ReleaseStore>, io::Error> {
+        match self {
+            Self::InMemory => Ok(Arc::new(InMemoryReleaseRepository::new())),
+            Self::EventSourced => Ok(Arc::new(EventSourcingReleaseRepository::new())),
+            #[cfg(feature = "backend-sqlite")]
+            Self::Sqlite { url } => {
+                let repository = sdlc_cp_api::services::sqlite_release_repository::SqliteReleaseRepository::connect(url).await.map_err(io::Error::other)?;
+                Ok(Arc::new(repository))
+            },
+            #[cfg(feature = "backend-postgres")]
+            Self::Postgres { url } => {
+                let repository = sdlc_cp_api::services::postgres_release_repository::PostgresReleaseRepository::connect(url).await.map_err(io::Error::other)?;
+                Ok(Arc::new(repository))
+            },
+        }
+    }
+}
+
+fn invalid_input(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_selection() {
+        assert_eq!(RepositoryConfig::from_vars(None, None).unwrap(), RepositoryConfig::InMemory);
+        assert_eq!(RepositoryConfig::from_vars(Some("in-memory"), None).unwrap(), RepositoryConfig::InMemory);
+        assert_eq!(RepositoryConfig::from_vars(Some("event-sourced"), None).unwrap(), RepositoryConfig::EventSourced);
+        assert!(RepositoryConfig::from_vars(Some("mongodb"), None).is_err());
+        #[cfg(feature = "backend-sqlite")]
+        {
+            let url = "sqlite://releases.db".to_string();
+            assert_eq!(RepositoryConfig::from_vars(Some("sqlite"), Some(url.clone())).unwrap(), RepositoryConfig::Sqlite { url });
+            assert!(RepositoryConfig::from_vars(Some("sqlite"), None).is_err());
+        }
+        #[cfg(not(feature = "backend-postgres"))]
+        assert!(RepositoryConfig::from_vars(Some("postgres"), Some("postgres://localhost/sdlccp".to_string())).is_err());
+    }
+}
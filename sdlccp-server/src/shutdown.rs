@@ -0,0 +1,115 @@
+use std::{future::Future, io, time::Duration};
+
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    sync::watch,
+    time::error::Elapsed,
+};
+
+/// Resolves once the process receives Ctrl-C (`SIGINT`) or `SIGTERM`.
+pub async fn shutdown_signal() {
+    let mut terminate = match signal(SignalKind::terminate()) {
+        Ok(terminate) => terminate,
+        Err(error) => {
+            tracing::error!(%error, "Failed to listen for SIGTERM");
+            let _ = tokio::signal::ctrl_c().await;
+            return;
+        },
+    };
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {},
+        _ = terminate.recv() => {},
+    }
+}
+
+/// Tells servers to stop accepting connections. Pass `triggered()` to their graceful shutdown hook.
+#[derive(Clone)]
+pub struct Shutdown(watch::Receiver<bool>);
+
+impl Shutdown {
+    pub async fn triggered(mut self) {
+        // The sender lives until `drain` returns, so an error only means we are done anyway.
+        let _ = self.0.wait_for(|triggered| *triggered).await;
+    }
+}
+
+/// Runs the servers built by `serve` until `signal` resolves, then gives them `drain_timeout` to
+/// finish in-flight requests. Fails with `Elapsed` if they are still busy when it runs out.
+pub async fn drain<F>(serve: impl FnOnce(Shutdown) -> F, signal: impl Future<Output = ()>, drain_timeout: Duration) -> Result<io::Result<()>, Elapsed>
+where
+    F: Future<Output = io::Result<()>>,
+{
+    let (trigger, triggered) = watch::channel(false);
+    let servers = serve(Shutdown(triggered));
+    tokio::pin!(servers);
+
+    tokio::select! {
+        result = &mut servers => return Ok(result),
+        () = signal => {},
+    }
+
+    tracing::info!(timeout_secs = drain_timeout.as_secs(), "Shutting down, draining connections...");
+    let _ = trigger.send(true);
+    tokio::time::timeout(drain_timeout, servers).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+
+    use axum::{routing::get, Router};
+    use tokio::{net::TcpListener, sync::oneshot};
+
+    /// Serves a route that takes `work` to answer and sends one request to it. Shutdown is signalled
+    /// as soon as the request reaches the handler. Returns the drain result and the response.
+    async fn shutdown_during_request(work: Duration, drain_timeout: Duration) -> (Result<io::Result<()>, Elapsed>, reqwest::Result<reqwest::Response>) {
+        let (started, request_in_flight) = oneshot::channel::<()>();
+        let started = Arc::new(Mutex::new(Some(started)));
+        let app = Router::new().route("/slow", get(move || {
+            let started = started.lock().unwrap().take();
+            async move {
+                if let Some(started) = started {
+                    let _ = started.send(());
+                }
+                tokio::time::sleep(work).await;
+                "done"
+            }
+        }));
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let request = tokio::spawn(reqwest::get(format!("http://{address}/slow")));
+        let servers = |shutdown: Shutdown| async move {
+            axum::serve(listener, app).with_graceful_shutdown(shutdown.triggered()).await
+        };
+        let signal = async {
+            request_in_flight.await.unwrap();
+        };
+
+        let drained = drain(servers, signal, drain_timeout).await;
+        (drained, request.await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_request_in_flight_at_shutdown_completes() {
+        tokio::time::pause();
+
+        let (drained, response) = shutdown_during_request(Duration::from_secs(5), Duration::from_secs(30)).await;
+
+        assert!(matches!(drained, Ok(Ok(()))));
+        let response = response.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "done");
+    }
+
+    #[tokio::test]
+    async fn test_drain_gives_up_after_timeout() {
+        tokio::time::pause();
+
+        let (drained, _) = shutdown_during_request(Duration::from_secs(60), Duration::from_secs(30)).await;
+
+        assert!(drained.is_err());
+    }
+}
@@ -0,0 +1,71 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use sdlc_cp_api::services::{controlplane::NamespaceStore, namespace::InMemoryNamespaceManager};
+
+/// Restores the namespaces saved at `path`, or starts empty if there is no snapshot yet.
+pub fn load_namespaces(path: &Path) -> io::Result<InMemoryNamespaceManager> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            tracing::info!(path = %path.display(), "No namespace snapshot yet, starting empty");
+            return Ok(InMemoryNamespaceManager::new());
+        },
+        Err(err) => return Err(err),
+    };
+    let manager = InMemoryNamespaceManager::load_from_reader(io::BufReader::new(file)).map_err(io::Error::other)?;
+    tracing::info!(path = %path.display(), "Loaded namespace snapshot");
+    Ok(manager)
+}
+
+/// Writes the namespaces to `path`. The snapshot goes to a temporary file first and is renamed into
+/// place, so a crash mid-write leaves the previous snapshot intact.
+pub async fn save_namespaces(store: &NamespaceStore, path: &Path) -> io::Result<()> {
+    let mut snapshot = Vec::new();
+    store.lock().await.save_to_writer(&mut snapshot).await.map_err(io::Error::other)?;
+
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".tmp");
+    tokio::fs::write(&partial, snapshot).await?;
+    tokio::fs::rename(&partial, path).await
+}
+
+/// Saves the namespaces every `interval` until the process exits. Failures are logged and retried
+/// on the next tick.
+pub fn save_periodically(store: Arc<NamespaceStore>, path: PathBuf, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(interval);
+        // The first tick fires immediately, when there is nothing new to save.
+        ticks.tick().await;
+        loop {
+            ticks.tick().await;
+            if let Err(error) = save_namespaces(&store, &path).await {
+                tracing::error!(%error, path = %path.display(), "Failed to save namespace snapshot");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sdlc_cp_api::services::namespace::NamespaceService;
+
+    #[tokio::test]
+    async fn test_snapshot_file_survives_restart() {
+        let path = std::env::temp_dir().join(format!("sdlccp-namespaces-{}.json", uuid::Uuid::new_v4()));
+        assert!(load_namespaces(&path).unwrap().list_all_namespaces().await.is_empty());
+
+        let store = NamespaceStore::new(InMemoryNamespaceManager::new());
+        store.lock().await.create_namespace("team-a/payments").await.unwrap();
+        save_namespaces(&store, &path).await.unwrap();
+
+        let restored = load_namespaces(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(restored.list_all_namespaces().await, vec!["team-a".to_string(), "team-a/payments".to_string()]);
+    }
+}
@@ -0,0 +1,82 @@
+use axum::http::{HeaderMap, Request};
+use opentelemetry::{
+    global,
+    propagation::Extractor,
+    trace::{TraceError, TracerProvider as _},
+    Context,
+};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{propagation::TraceContextPropagator, runtime, trace::TracerProvider};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+/// Installs the global subscriber: JSON logs filtered by `log_level`, and spans exported over
+/// OTLP/gRPC to `otlp_endpoint`. Shut the returned provider down to flush pending spans.
+pub fn init(log_level: &str, otlp_endpoint: &str) -> Result<TracerProvider, TraceError> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(otlp_endpoint)
+        .build()?;
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .build();
+    global::set_tracer_provider(provider.clone());
+
+    tracing_subscriber::registry()
+        .with(EnvFilter::new(log_level))
+        .with(tracing_subscriber::fmt::layer().json())
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("sdlccp-server")))
+        .init();
+    Ok(provider)
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+/// The trace context a caller propagated in the W3C `traceparent` and `tracestate` headers.
+pub fn extract_context(headers: &HeaderMap) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+}
+
+/// `TraceLayer` span of one HTTP request.
+pub fn make_span<B>(request: &Request<B>) -> Span {
+    // `RequestIdLayer` fills in `request_id` once it assigned one.
+    tracing::info_span!("http_request", method = %request.method(), uri = %request.uri(), request_id = tracing::field::Empty)
+}
+
+/// `TraceLayer` callback continuing the caller's trace, if it sent one.
+pub fn continue_trace<B>(request: &Request<B>, span: &Span) {
+    span.set_parent(extract_context(request.headers()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TraceContextExt;
+
+    #[test]
+    fn test_extract_context_reads_traceparent() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let mut headers = HeaderMap::new();
+        headers.insert("traceparent", "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".parse().unwrap());
+
+        let context = extract_context(&headers);
+        let span_context = context.span().span_context().clone();
+        assert!(span_context.is_remote());
+        assert_eq!(span_context.trace_id().to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(span_context.span_id().to_string(), "00f067aa0ba902b7");
+
+        assert!(!extract_context(&HeaderMap::new()).span().span_context().is_valid());
+    }
+}
@@ -0,0 +1,91 @@
+use std::{io, net::SocketAddr, path::PathBuf};
+
+use axum::Router;
+use axum_server::{tls_rustls::RustlsConfig, Handle};
+use tokio::signal::unix::{signal, SignalKind};
+
+/// A freshly generated certificate and its private key, both PEM-encoded.
+pub struct SelfSignedCertificate {
+    pub cert_pem: String,
+    pub key_pem: String,
+}
+
+impl SelfSignedCertificate {
+    /// Generates a certificate valid for `localhost`. Only meant for development and tests.
+    pub fn generate() -> Result<Self, rcgen::Error> {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])?;
+        Ok(Self { cert_pem: certified.cert.pem(), key_pem: certified.key_pair.serialize_pem() })
+    }
+
+    pub async fn rustls_config(&self) -> io::Result<RustlsConfig> {
+        RustlsConfig::from_pem(self.cert_pem.clone().into_bytes(), self.key_pem.clone().into_bytes()).await
+    }
+}
+
+/// Selects `ring` as the process-wide rustls crypto provider. Safe to call more than once.
+pub fn install_crypto_provider() {
+    // Fails only if a provider is already installed, which is what we want anyway.
+    let _ = rustls::crypto::ring::default_provider().install_default();
+}
+
+/// Re-reads the certificate and key from disk every time the process receives `SIGHUP`.
+///
+/// A failed reload is logged and the previous certificate stays in use.
+pub fn reload_on_sighup(config: RustlsConfig, cert: PathBuf, key: PathBuf) -> io::Result<()> {
+    let mut hangup = signal(SignalKind::hangup())?;
+    tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            match config.reload_from_pem_file(&cert, &key).await {
+                Ok(()) => tracing::info!(cert = %cert.display(), "Reloaded TLS certificate"),
+                Err(error) => tracing::error!(%error, cert = %cert.display(), "Failed to reload TLS certificate"),
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Serves `app` over HTTPS until the server stops. `handle` exposes the bound address.
+pub async fn serve(address: SocketAddr, config: RustlsConfig, app: Router, handle: Handle) -> io::Result<()> {
+    axum_server::bind_rustls(address, config)
+        .handle(handle)
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+
+    #[tokio::test]
+    async fn test_https_request_with_custom_trust_root() {
+        install_crypto_provider();
+        let certificate = SelfSignedCertificate::generate().unwrap();
+        let config = certificate.rustls_config().await.unwrap();
+        let app = Router::new().route("/hello", get(|| async { "hello over tls" }));
+
+        let handle = Handle::new();
+        tokio::spawn(serve(SocketAddr::from(([127, 0, 0, 1], 0)), config, app, handle.clone()));
+        let address = handle.listening().await.expect("server failed to bind");
+
+        let client = reqwest::Client::builder()
+            .add_root_certificate(reqwest::Certificate::from_pem(certificate.cert_pem.as_bytes()).unwrap())
+            .build()
+            .unwrap();
+        let response = client
+            .get(format!("https://localhost:{}/hello", address.port()))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "hello over tls");
+
+        // Without the self-signed root the handshake is rejected.
+        let untrusted = reqwest::Client::new()
+            .get(format!("https://localhost:{}/hello", address.port()))
+            .send()
+            .await;
+        assert!(untrusted.is_err());
+    }
+}
@@ -1 +1 @@
-pub mod namespace;
\ No newline at end of file
+pub mod namespace;
@@ -3,6 +3,7 @@ pub mod services;
 
 pub use sdlccp_api_macro::RegisterSchema;
 
+#[derive(Clone, Copy)]
 pub struct SchemaGenerator {
     pub type_name: &'static str,
     pub generator: fn() -> schemars::schema::RootSchema,
@@ -19,4 +20,20 @@ inventory::collect!(SchemaGenerator);
 #[cfg(test)]
 mod tests {
     mod model_tests;
+    mod component_tests;
+    mod audit_tests;
+    mod tracing_tests;
+    mod release_tests;
+    mod auth_tests;
+    mod attestation_tests;
+    mod api_error_tests;
+    mod lifecycle_proptests;
+    mod control_plane_tests;
+    mod team_tests;
+    mod webhook_tests;
+    mod export_tests;
+    mod namespace_tests;
+    mod openapi_tests;
+    mod in_memory_tests;
+    mod release_repository_tests;
 }
\ No newline at end of file
@@ -2,33 +2,244 @@ use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use sdlccp_api_macro::RegisterSchema;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use utoipa::ToSchema;
 use uuid::Uuid;
 use std::collections::HashMap;
 
-#[derive(Clone, JsonSchema, RegisterSchema, ToSchema)]
+use super::SDLCRelease;
+
+#[derive(Clone, JsonSchema, RegisterSchema, ToSchema, Serialize, Deserialize)]
 pub struct Attestation {
     pub id: Uuid,
     pub subject: Subject,
     pub timestamp: DateTime<Utc>,
     pub expiration: Option<DateTime<Utc>>,
     pub signatures: Vec<Signature>,
-    pub claims: HashMap<String, serde_json::Value>,
-    pub parent_attestations: Vec<String>, // TODO: This is currently IDs of parent attestations. Should this be a Vec<Uuid>, should this be some other way of referencing?
+    #[schema(value_type = Object)]
+    pub claims: ClaimSet,
+    /// IDs of the attestations this one builds on, see `AttestationService::resolve_chain`.
+    pub parent_attestations: Vec<Uuid>,
+    /// Attestations stored before revocation existed are active.
+    #[serde(default)]
+    pub status: AttestationStatus,
+    /// The release the attestation was recorded for, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub release_id: Option<Uuid>,
+    /// What the claims describe, as in in-toto statements, e.g. `https://slsa.dev/provenance/v0.2`.
+    /// Claims of a predicate type with a registered schema must match it, see `ClaimSchemaRegistry`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub predicate_type: Option<String>,
+}
+
+/// Whether an attestation can still be relied on. A revoked attestation is kept, e.g. for audits,
+/// but fails verification and `PolicyRule::NoRevokedAttestations`.
+#[derive(Debug, Clone, Default, PartialEq, JsonSchema, ToSchema, Serialize, Deserialize)]
+pub enum AttestationStatus {
+    #[default]
+    Active,
+    /// The attestation must no longer be trusted, e.g. because its signing key was compromised.
+    Revoked {
+        reason: String,
+        revoked_at: DateTime<Utc>,
+        revoked_by: String,
+    },
+}
+
+/// The claims of an attestation, keyed by claim name, with typed accessors for the common
+/// value shapes. Serialized as the plain JSON object it wraps.
+///
+/// This replaces the `HashMap<String, serde_json::Value>` attestations used to hold. Build one
+/// from such a map with `ClaimSet::from`; `get`, `insert`, `contains_key` and `iter` keep their
+/// meaning, and `get_string`, `get_bool` and `get_u64` replace matching on the raw value.
+#[derive(Debug, Clone, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ClaimSet(HashMap<String, serde_json::Value>);
+
+impl ClaimSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.0.get(key)
+    }
+
+    /// The claim if it is a JSON string.
+    pub fn get_string(&self, key: &str) -> Option<&str> {
+        self.0.get(key)?.as_str()
+    }
+
+    /// The claim if it is a JSON boolean.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.0.get(key)?.as_bool()
+    }
+
+    /// The claim if it is a non-negative JSON integer.
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.0.get(key)?.as_u64()
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// Sets the claim, returning the value it replaced.
+    pub fn insert(&mut self, key: String, value: serde_json::Value) -> Option<serde_json::Value> {
+        self.0.insert(key, value)
+    }
+
+    pub fn set_string(&mut self, key: &str, value: String) {
+        self.0.insert(key.to_string(), serde_json::Value::String(value));
+    }
+
+    pub fn set_bool(&mut self, key: &str, value: bool) {
+        self.0.insert(key.to_string(), serde_json::Value::Bool(value));
+    }
+
+    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, String, serde_json::Value> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<HashMap<String, serde_json::Value>> for ClaimSet {
+    fn from(claims: HashMap<String, serde_json::Value>) -> Self {
+        Self(claims)
+    }
+}
+
+impl FromIterator<(String, serde_json::Value)> for ClaimSet {
+    fn from_iter<I: IntoIterator<Item = (String, serde_json::Value)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for ClaimSet {
+    type Item = (String, serde_json::Value);
+    type IntoIter = std::collections::hash_map::IntoIter<String, serde_json::Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ClaimSet {
+    type Item = (&'a String, &'a serde_json::Value);
+    type IntoIter = std::collections::hash_map::Iter<'a, String, serde_json::Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
 }
 
 #[derive(Clone, JsonSchema, ToSchema, Serialize, Deserialize)]
 pub struct Subject {
     pub type_: SubjectType,
     pub name: String,
+    /// Digest of the subject. Ignored for `Container` and `GitTag` subjects, which carry their
+    /// own; use `canonical_digest` to read the digest for any subject type.
     pub digest: String,
 }
 
-#[derive(Clone, JsonSchema, ToSchema, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema, ToSchema, Serialize, Deserialize)]
 pub enum SubjectType {
     Commit,
     Artifact,
     Deployment,
+    /// A container image, identified by its registry, repository and manifest digest.
+    Container {
+        registry: String,
+        repository: String,
+        digest: String,
+    },
+    /// A tag in a Git repository and the commit it points at.
+    GitTag {
+        repository_url: String,
+        tag_name: String,
+        commit_hash: String,
+    },
+    /// A software bill of materials describing the release.
+    SBOMDocument {
+        format: SBOMFormat,
+        document_url: String,
+    },
+}
+
+/// The kind of a `SubjectType`, without the details some kinds carry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, ToSchema, Serialize, Deserialize)]
+pub enum SubjectKind {
+    Commit,
+    Artifact,
+    Deployment,
+    Container,
+    GitTag,
+    SBOMDocument,
+}
+
+impl SubjectType {
+    pub fn kind(&self) -> SubjectKind {
+        match self {
+            SubjectType::Commit => SubjectKind::Commit,
+            SubjectType::Artifact => SubjectKind::Artifact,
+            SubjectType::Deployment => SubjectKind::Deployment,
+            SubjectType::Container { .. } => SubjectKind::Container,
+            SubjectType::GitTag { .. } => SubjectKind::GitTag,
+            SubjectType::SBOMDocument { .. } => SubjectKind::SBOMDocument,
+        }
+    }
+}
+
+/// An SBOM document format and the major/minor version of its specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema, RegisterSchema, ToSchema, Serialize, Deserialize)]
+pub enum SBOMFormat {
+    // Unversioned names from before formats carried a version.
+    #[serde(alias = "Spdx")]
+    Spdx2,
+    Spdx3,
+    #[serde(alias = "CycloneDX")]
+    CycloneDX14,
+    CycloneDX15,
+}
+
+impl Subject {
+    /// The digest identifying the subject, whichever field it is stored in.
+    pub fn canonical_digest(&self) -> &str {
+        match &self.type_ {
+            SubjectType::Container { digest, .. } => digest,
+            SubjectType::GitTag { commit_hash, .. } => commit_hash,
+            SubjectType::Commit | SubjectType::Artifact | SubjectType::Deployment | SubjectType::SBOMDocument { .. } => &self.digest,
+        }
+    }
+
+    /// Whether this subject is part of `release`.
+    ///
+    /// Commits and Git tags match the release's commit, artifacts and container images its
+    /// packaged artifact, and deployments its deployment ID. The subject's name alone never
+    /// links it to a release; see `Attestation::release_id` for that.
+    pub fn matches_release(&self, release: &SDLCRelease) -> bool {
+        let details = release.phase_details.as_ref();
+        match &self.type_ {
+            SubjectType::Commit | SubjectType::GitTag { .. } => {
+                release.commit_hash.as_deref() == Some(self.canonical_digest())
+            }
+            SubjectType::Artifact | SubjectType::Container { .. } => details
+                .and_then(|details| details.package_details.as_ref())
+                .is_some_and(|package| package.artifact_hash == self.canonical_digest()),
+            SubjectType::Deployment => details
+                .and_then(|details| details.deploy_details.as_ref())
+                .is_some_and(|deploy| deploy.deployment_id == self.name),
+            // SBOMs are only linked by name.
+            SubjectType::SBOMDocument { .. } => false,
+        }
+    }
 }
 
 #[derive(Clone, JsonSchema, ToSchema, Serialize, Deserialize)]
@@ -38,7 +249,7 @@ pub struct Signature {
 }
 
 impl Attestation {
-    pub fn new(subject: Subject, claims: HashMap<String, serde_json::Value>) -> Self {
+    pub fn new(subject: Subject, claims: ClaimSet) -> Self {
         Self {
             id: uuid::Uuid::new_v4(),
             subject,
@@ -47,10 +258,230 @@ impl Attestation {
             signatures: Vec::new(),
             claims,
             parent_attestations: Vec::new(),
+            status: AttestationStatus::Active,
+            release_id: None,
+            predicate_type: None,
         }
     }
 
+    /// Records the attestation for `release_id`.
+    pub fn for_release(mut self, release_id: Uuid) -> Self {
+        self.release_id = Some(release_id);
+        self
+    }
+
+    /// Sets what the claims describe, see `predicate_type`.
+    pub fn with_predicate_type(mut self, predicate_type: &str) -> Self {
+        self.predicate_type = Some(predicate_type.to_string());
+        self
+    }
+
+    /// Whether the attestation was recorded for `release` or its subject is part of it.
+    pub fn concerns_release(&self, release: &SDLCRelease) -> bool {
+        self.release_id == Some(release.id) || self.subject.matches_release(release)
+    }
+
+    /// SHA-256 over the subject and claims, hex encoded. Attestations that only differ in `id`,
+    /// `timestamp`, signatures or anything else hash the same.
+    pub fn content_hash(&self) -> String {
+        // serde_json's maps keep their keys sorted, so the order claims were added in does not matter.
+        let content = serde_json::json!({ "subject": self.subject, "claims": self.claims });
+        hex::encode(Sha256::digest(content.to_string()))
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        matches!(self.status, AttestationStatus::Revoked { .. })
+    }
+
     pub fn add_signature(&mut self, signer: String, signature: String) {
         self.signatures.push(Signature { signer, signature });
     }
-}
\ No newline at end of file
+
+    /// Checks each claim that has a validator. Missing claims are not an error; use a
+    /// `PolicyRule::RequiredClaims` to demand them.
+    pub fn validate_claims(&self, validators: &[(&'static str, &dyn claims::ClaimValidator)]) -> Vec<ClaimValidationError> {
+        validators.iter()
+            .filter_map(|(claim, validator)| {
+                let value = self.claims.get(claim)?;
+                validator.validate(value).err().map(|reason| ClaimValidationError { claim: claim.to_string(), reason: reason.to_string() })
+            })
+            .collect()
+    }
+}
+
+/// A claim that failed its validator.
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema, ToSchema, Serialize, Deserialize)]
+pub struct ClaimValidationError {
+    pub claim: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for ClaimValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.claim, self.reason)
+    }
+}
+
+/// Well-known claim keys and the shape of their values.
+pub mod claims {
+    use serde_json::Value;
+
+    /// URI identifying the build process, as in SLSA provenance.
+    pub const CLAIM_BUILD_TYPE: &str = "buildType";
+    pub const CLAIM_BUILDER_ID: &str = "builderId";
+    /// URL of the repository the subject was built from.
+    pub const CLAIM_SOURCE_REPO: &str = "sourceRepo";
+    /// Hex SHA-1 or SHA-256 of the commit the subject was built from.
+    pub const CLAIM_SOURCE_COMMIT: &str = "sourceCommit";
+    /// `{"passed": bool, "critical": n, "high": n, "medium": n, "low": n}`, counts optional.
+    pub const CLAIM_VULNERABILITY_SCAN_RESULT: &str = "vulnerabilityScanResult";
+    /// `{"passed": bool, "licenses": ["MIT", ...]}`, licenses optional.
+    pub const CLAIM_LICENSE_SCAN_RESULT: &str = "licenseScanResult";
+
+    pub trait ClaimValidator: Sync {
+        fn validate(&self, value: &Value) -> Result<(), &'static str>;
+    }
+
+    pub struct NonEmptyString;
+
+    impl ClaimValidator for NonEmptyString {
+        fn validate(&self, value: &Value) -> Result<(), &'static str> {
+            match value.as_str() {
+                Some(s) if !s.trim().is_empty() => Ok(()),
+                Some(_) => Err("must not be empty"),
+                None => Err("must be a string"),
+            }
+        }
+    }
+
+    /// An absolute URI, like `https://github.com/sdlcsec/app` or `git+ssh://...`.
+    pub struct Uri;
+
+    impl ClaimValidator for Uri {
+        fn validate(&self, value: &Value) -> Result<(), &'static str> {
+            let uri = value.as_str().ok_or("must be a string")?;
+            let (scheme, rest) = uri.split_once("://").ok_or("must be an absolute URI")?;
+            let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+            if valid_scheme && !rest.is_empty() {
+                Ok(())
+            } else {
+                Err("must be an absolute URI")
+            }
+        }
+    }
+
+    pub struct CommitHash;
+
+    impl ClaimValidator for CommitHash {
+        fn validate(&self, value: &Value) -> Result<(), &'static str> {
+            let hash = value.as_str().ok_or("must be a string")?;
+            if matches!(hash.len(), 40 | 64) && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                Ok(())
+            } else {
+                Err("must be a 40 or 64 character hex commit hash")
+            }
+        }
+    }
+
+    pub struct VulnerabilityScanResult;
+
+    impl ClaimValidator for VulnerabilityScanResult {
+        fn validate(&self, value: &Value) -> Result<(), &'static str> {
+            let result = value.as_object().ok_or("must be an object")?;
+            if !result.get("passed").is_some_and(Value::is_boolean) {
+                return Err("must have a boolean \"passed\"");
+            }
+            let counts_valid = ["critical", "high", "medium", "low"].iter()
+                .filter_map(|severity| result.get(*severity))
+                .all(|count| count.is_u64());
+            if counts_valid {
+                Ok(())
+            } else {
+                Err("severity counts must be non-negative integers")
+            }
+        }
+    }
+
+    pub struct LicenseScanResult;
+
+    impl ClaimValidator for LicenseScanResult {
+        fn validate(&self, value: &Value) -> Result<(), &'static str> {
+            let result = value.as_object().ok_or("must be an object")?;
+            if !result.get("passed").is_some_and(Value::is_boolean) {
+                return Err("must have a boolean \"passed\"");
+            }
+            match result.get("licenses") {
+                None => Ok(()),
+                Some(Value::Array(licenses)) if licenses.iter().all(Value::is_string) => Ok(()),
+                Some(_) => Err("\"licenses\" must be an array of strings"),
+            }
+        }
+    }
+
+    /// The validator of every well-known claim, for `Attestation::validate_claims`.
+    pub const WELL_KNOWN: &[(&str, &dyn ClaimValidator)] = &[
+        (CLAIM_BUILD_TYPE, &Uri),
+        (CLAIM_BUILDER_ID, &NonEmptyString),
+        (CLAIM_SOURCE_REPO, &Uri),
+        (CLAIM_SOURCE_COMMIT, &CommitHash),
+        (CLAIM_VULNERABILITY_SCAN_RESULT, &VulnerabilityScanResult),
+        (CLAIM_LICENSE_SCAN_RESULT, &LicenseScanResult),
+    ];
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use serde_json::json;
+
+        fn check(validator: &dyn ClaimValidator, valid: &[Value], invalid: &[Value]) {
+            for value in valid {
+                assert_eq!(validator.validate(value), Ok(()), "{value} should be valid");
+            }
+            for value in invalid {
+                assert!(validator.validate(value).is_err(), "{value} should be invalid");
+            }
+        }
+
+        #[test]
+        fn test_non_empty_string() {
+            check(&NonEmptyString, &[json!("https://github.com/actions/runner"), json!("ci")], &[json!(""), json!("  "), json!(1), json!(null)]);
+        }
+
+        #[test]
+        fn test_uri() {
+            check(
+                &Uri,
+                &[json!("https://slsa.dev/build/v1"), json!("git+ssh://git@github.com/sdlcsec/app")],
+                &[json!("github.com/sdlcsec/app"), json!("https://"), json!("1http://example.com"), json!({ "uri": "https://example.com" })],
+            );
+        }
+
+        #[test]
+        fn test_commit_hash() {
+            check(
+                &CommitHash,
+                &[json!("a".repeat(40)), json!("0123456789abcdef".repeat(4))],
+                &[json!("abc123"), json!("g".repeat(40)), json!(42)],
+            );
+        }
+
+        #[test]
+        fn test_vulnerability_scan_result() {
+            check(
+                &VulnerabilityScanResult,
+                &[json!({ "passed": true }), json!({ "passed": false, "critical": 1, "high": 0 })],
+                &[json!({}), json!({ "passed": "yes" }), json!({ "passed": true, "high": -1 }), json!({ "passed": true, "low": 1.5 }), json!([])],
+            );
+        }
+
+        #[test]
+        fn test_license_scan_result() {
+            check(
+                &LicenseScanResult,
+                &[json!({ "passed": true }), json!({ "passed": false, "licenses": ["MIT", "GPL-3.0-only"] })],
+                &[json!({ "licenses": ["MIT"] }), json!({ "passed": true, "licenses": "MIT" }), json!({ "passed": true, "licenses": [1] }), json!(true)],
+            );
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! JSON schemas for the claims of attestations with a well-known `predicate_type`.
+//!
+//! Only the parts of JSON Schema needed to describe predicates are checked: `type`, `enum`,
+//! `required`, `properties` and a single `items` schema. Anything else in a registered schema,
+//! like `format` or `$ref`, is ignored.
+
+use std::collections::HashMap;
+
+use schemars::schema::{InstanceType, RootSchema, Schema, SchemaObject, SingleOrVec};
+use serde_json::{json, Value};
+
+use super::attestation::{ClaimSet, ClaimValidationError};
+use super::sbom::CYCLONEDX_PREDICATE_TYPE;
+use super::slsa::SLSA_PROVENANCE_V02;
+
+/// The schema the claims of an attestation must match, keyed by its `predicate_type`.
+#[derive(Debug, Clone, Default)]
+pub struct ClaimSchemaRegistry {
+    schemas: HashMap<String, RootSchema>,
+}
+
+impl ClaimSchemaRegistry {
+    /// A registry without any schemas.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry with the schemas of SLSA provenance v0.2 and CycloneDX BOM predicates.
+    pub fn with_builtin_schemas() -> Self {
+        let mut registry = Self::new();
+        registry.register_schema(SLSA_PROVENANCE_V02, builtin_schema(slsa_provenance_v02_schema()));
+        registry.register_schema(CYCLONEDX_PREDICATE_TYPE, builtin_schema(cyclonedx_bom_schema()));
+        registry
+    }
+
+    /// Validates claims of `predicate_type` against `json_schema` from now on, replacing the
+    /// schema registered before.
+    pub fn register_schema(&mut self, predicate_type: &str, json_schema: RootSchema) {
+        self.schemas.insert(predicate_type.to_string(), json_schema);
+    }
+
+    /// Every predicate type with a schema, sorted.
+    pub fn predicate_types(&self) -> Vec<String> {
+        let mut predicate_types: Vec<String> = self.schemas.keys().cloned().collect();
+        predicate_types.sort();
+        predicate_types
+    }
+
+    /// Where `claims` do not match the schema of `predicate_type`. Nested claims are named by
+    /// their path, like `invocation.configSource.uri` or `materials[0].uri`. Claims of a predicate
+    /// type without a schema are always valid.
+    pub fn validate_claims(&self, predicate_type: &str, claims: &ClaimSet) -> Vec<ClaimValidationError> {
+        let Some(root) = self.schemas.get(predicate_type) else {
+            return Vec::new();
+        };
+        let claims = Value::Object(claims.iter().map(|(key, value)| (key.clone(), value.clone())).collect());
+        let mut errors = Vec::new();
+        check_object(&root.schema, &claims, "", &mut errors);
+        errors
+    }
+}
+
+fn check(schema: &Schema, value: &Value, path: &str, errors: &mut Vec<ClaimValidationError>) {
+    match schema {
+        Schema::Bool(true) => {},
+        Schema::Bool(false) => errors.push(error(path, "is not allowed".to_string())),
+        Schema::Object(schema) => check_object(schema, value, path, errors),
+    }
+}
+
+fn check_object(schema: &SchemaObject, value: &Value, path: &str, errors: &mut Vec<ClaimValidationError>) {
+    if let Some(instance_type) = &schema.instance_type {
+        let types: &[InstanceType] = match instance_type {
+            SingleOrVec::Single(instance_type) => std::slice::from_ref(instance_type.as_ref()),
+            SingleOrVec::Vec(types) => types,
+        };
+        if !types.iter().any(|instance_type| has_type(value, instance_type)) {
+            let names: Vec<String> = types.iter().map(|instance_type| type_name(instance_type).to_string()).collect();
+            errors.push(error(path, format!("must be {}", names.join(" or "))));
+            return;
+        }
+    }
+    if let Some(allowed) = schema.enum_values.as_ref().filter(|allowed| !allowed.contains(value)) {
+        let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+        errors.push(error(path, format!("must be one of {}", allowed.join(", "))));
+    }
+    if let (Some(object), Some(fields)) = (&schema.object, value.as_object()) {
+        for required in object.required.iter().filter(|required| !fields.contains_key(*required)) {
+            errors.push(error(&child_path(path, required), "is required".to_string()));
+        }
+        for (name, property) in &object.properties {
+            if let Some(field) = fields.get(name) {
+                check(property, field, &child_path(path, name), errors);
+            }
+        }
+    }
+    if let (Some(array), Some(elements)) = (&schema.array, value.as_array()) {
+        if let Some(SingleOrVec::Single(items)) = &array.items {
+            for (index, element) in elements.iter().enumerate() {
+                check(items, element, &format!("{path}[{index}]"), errors);
+            }
+        }
+    }
+}
+
+fn has_type(value: &Value, instance_type: &InstanceType) -> bool {
+    match instance_type {
+        InstanceType::Null => value.is_null(),
+        InstanceType::Boolean => value.is_boolean(),
+        InstanceType::Object => value.is_object(),
+        InstanceType::Array => value.is_array(),
+        InstanceType::Number => value.is_number(),
+        InstanceType::String => value.is_string(),
+        InstanceType::Integer => value.is_i64() || value.is_u64(),
+    }
+}
+
+fn type_name(instance_type: &InstanceType) -> &'static str {
+    match instance_type {
+        InstanceType::Null => "null",
+        InstanceType::Boolean => "a boolean",
+        InstanceType::Object => "an object",
+        InstanceType::Array => "an array",
+        InstanceType::Number => "a number",
+        InstanceType::String => "a string",
+        InstanceType::Integer => "an integer",
+    }
+}
+
+fn child_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
+    }
+}
+
+fn error(path: &str, reason: String) -> ClaimValidationError {
+    ClaimValidationError { claim: path.to_string(), reason }
+}
+
+fn builtin_schema(schema: Value) -> RootSchema {
+    serde_json::from_value(schema).expect("built-in claim schemas are valid JSON schemas")
+}
+
+/// The SLSA provenance v0.2 predicate, as `SlsaProvenanceBuilder` lays it out in the claims.
+fn slsa_provenance_v02_schema() -> Value {
+    let digest_set = json!({ "type": "object", "additionalProperties": { "type": "string" } });
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "SLSA provenance v0.2",
+        "type": "object",
+        "required": ["builder", "buildType"],
+        "properties": {
+            "builder": {
+                "type": "object",
+                "required": ["id"],
+                "properties": { "id": { "type": "string" } },
+            },
+            "buildType": { "type": "string" },
+            "invocation": {
+                "type": "object",
+                "properties": {
+                    "configSource": {
+                        "type": "object",
+                        "properties": {
+                            "uri": { "type": "string" },
+                            "digest": digest_set,
+                            "entryPoint": { "type": "string" },
+                        },
+                    },
+                },
+            },
+            "metadata": { "type": "object" },
+            "materials": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["uri"],
+                    "properties": {
+                        "uri": { "type": "string" },
+                        "digest": digest_set,
+                    },
+                },
+            },
+        },
+    })
+}
+
+/// A CycloneDX BOM, with the fields every version of the specification requires.
+fn cyclonedx_bom_schema() -> Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "CycloneDX BOM",
+        "type": "object",
+        "required": ["bomFormat", "specVersion"],
+        "properties": {
+            "bomFormat": { "type": "string", "enum": ["CycloneDX"] },
+            "specVersion": { "type": "string" },
+            "version": { "type": "integer" },
+            "components": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["type", "name"],
+                    "properties": {
+                        "type": { "type": "string" },
+                        "name": { "type": "string" },
+                        "version": { "type": "string" },
+                    },
+                },
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(value: Value) -> ClaimSet {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_cyclonedx_bom_schema() {
+        let registry = ClaimSchemaRegistry::with_builtin_schemas();
+        let valid = claims(json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.4",
+            "components": [{ "type": "application", "name": "app", "version": "1.0.0" }],
+        }));
+        assert!(registry.validate_claims(CYCLONEDX_PREDICATE_TYPE, &valid).is_empty());
+
+        let invalid = claims(json!({ "bomFormat": "SPDX", "components": [{ "type": "library" }] }));
+        let mut errors: Vec<String> = registry.validate_claims(CYCLONEDX_PREDICATE_TYPE, &invalid).iter().map(ToString::to_string).collect();
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "bomFormat: must be one of \"CycloneDX\"".to_string(),
+                "components[0].name: is required".to_string(),
+                "specVersion: is required".to_string(),
+            ]
+        );
+        assert!(registry.validate_claims("https://example.com/unknown", &invalid).is_empty());
+    }
+}
@@ -3,10 +3,17 @@ pub mod phase;
 pub mod state;
 pub mod policy;
 pub mod attestation;
+pub mod claim_schema;
 pub mod sdlc_component;
+pub mod slsa;
+pub mod sbom;
+pub mod sla;
 
-pub use sdlc_release::SDLCRelease;
+pub use sdlc_release::{Approval, ApprovalRequest, ReleaseDiff, ReleaseSnapshot, SDLCRelease, TagError, TransitionRecord, ValidationError};
 pub use phase::SDLCPhase;
 pub use state::ReleaseState;
 pub use policy::Policy;
-pub use attestation::Attestation;
\ No newline at end of file
+pub use attestation::Attestation;
+pub use slsa::SlsaProvenanceBuilder;
+pub use sla::{DeploymentSLA, SLAStatus};
+pub use sdlc_component::{MergeConflict, MergeError, SDLCComponent};
\ No newline at end of file
@@ -5,7 +5,8 @@ use sdlccp_api_macro::RegisterSchema;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use super::policy::Vulnerability;
+use super::attestation::SBOMFormat;
+use super::policy::{Vulnerability, VulnerabilityLevel};
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, RegisterSchema, ToSchema, PartialEq)]
 pub enum SDLCPhase {
@@ -30,6 +31,43 @@ impl SDLCPhase {
             SDLCPhase::Custom(custom_name) => custom_name,
         }
     }
+
+    /// The phase called `name`, the reverse of `name()`. Names of no built-in phase are custom phases.
+    pub fn from_name(name: &str) -> SDLCPhase {
+        match name {
+            "Development" => SDLCPhase::Development,
+            "Source" => SDLCPhase::Source,
+            "Build" => SDLCPhase::Build,
+            "Package" => SDLCPhase::Package,
+            "Deploy" => SDLCPhase::Deploy,
+            "Runtime" => SDLCPhase::Runtime,
+            custom_name => SDLCPhase::Custom(custom_name.to_string()),
+        }
+    }
+}
+
+/// Position of a built-in phase in the lifecycle. Custom phases have none.
+pub fn phase_order(phase: &SDLCPhase) -> Option<u8> {
+    match phase {
+        SDLCPhase::Development => Some(0),
+        SDLCPhase::Source => Some(1),
+        SDLCPhase::Build => Some(2),
+        SDLCPhase::Package => Some(3),
+        SDLCPhase::Deploy => Some(4),
+        SDLCPhase::Runtime => Some(5),
+        SDLCPhase::Custom(_) => None,
+    }
+}
+
+/// Orders the built-in phases by `phase_order`. A custom phase is equal only to itself and
+/// comparable with nothing else, so there is no `Ord`.
+impl PartialOrd for SDLCPhase {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        if self == other {
+            return Some(std::cmp::Ordering::Equal);
+        }
+        phase_order(self)?.partial_cmp(&phase_order(other)?)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, ToSchema, RegisterSchema)]
@@ -40,9 +78,16 @@ pub struct PhaseDetails {
     pub package_details: Option<PackageDetails>,
     pub deploy_details: Option<DeployDetails>,
     pub runtime_details: Option<RuntimeDetails>,
+    #[schema(value_type = Object)]
     pub custom_details: HashMap<String, Option<serde_json::Value>>,
 }
 
+impl Default for PhaseDetails {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PhaseDetails {
     pub fn new() -> Self {
         PhaseDetails {
@@ -55,6 +100,21 @@ impl PhaseDetails {
             custom_details: HashMap::new(),
         }
     }
+
+    /// Drops whatever `phase` recorded so far.
+    pub fn clear(&mut self, phase: &SDLCPhase) {
+        match phase {
+            SDLCPhase::Development => self.development_details = None,
+            SDLCPhase::Source => self.source_details = None,
+            SDLCPhase::Build => self.build_details = None,
+            SDLCPhase::Package => self.package_details = None,
+            SDLCPhase::Deploy => self.deploy_details = None,
+            SDLCPhase::Runtime => self.runtime_details = None,
+            SDLCPhase::Custom(name) => {
+                self.custom_details.remove(name);
+            },
+        }
+    }
 }
 
 
@@ -63,27 +123,126 @@ pub struct DevelopmentDetails {
     pub feature_list: Vec<String>,
 }
 
+/// Default number of features a release can start development with.
+pub const MAX_FEATURE_LIST_SIZE: usize = 100;
+/// Default length, in characters, of a single feature description.
+pub const MAX_FEATURE_DESCRIPTION_LEN: usize = 500;
+
+/// Bounds on the feature list a release starts development with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeatureListLimits {
+    pub max_items: usize,
+    pub max_item_len: usize,
+}
+
+impl Default for FeatureListLimits {
+    fn default() -> Self {
+        Self { max_items: MAX_FEATURE_LIST_SIZE, max_item_len: MAX_FEATURE_DESCRIPTION_LEN }
+    }
+}
+
+impl FeatureListLimits {
+    /// Checks `feature_list` against these limits. Features are counted in characters, not bytes.
+    pub fn validate(&self, feature_list: &[String]) -> Result<(), FeatureListError> {
+        if feature_list.is_empty() {
+            return Err(FeatureListError::EmptyList);
+        }
+        if feature_list.len() > self.max_items {
+            return Err(FeatureListError::TooManyItems { limit: self.max_items });
+        }
+        match feature_list.iter().position(|feature| feature.chars().count() > self.max_item_len) {
+            Some(index) => Err(FeatureListError::ItemTooLong { index, limit: self.max_item_len }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Why a feature list was rejected.
+#[derive(Debug, Clone, PartialEq, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum FeatureListError {
+    #[error("The feature list must not be empty")]
+    EmptyList,
+    #[error("The feature list has more than {limit} features")]
+    TooManyItems { limit: usize },
+    #[error("Feature {index} is longer than {limit} characters")]
+    ItemTooLong { index: usize, limit: usize },
+}
+
 #[derive(Debug, Clone, JsonSchema, RegisterSchema, ToSchema, Serialize, Deserialize)]
 pub struct SourceDetails {
     pub commit_hash: String,
+    /// The branch the reviewed commit is on.
+    #[serde(default)]
+    pub branch: String,
+    #[serde(default)]
+    pub pr_url: Option<String>,
+    #[serde(default)]
+    pub review_status: SourceReviewStatus,
+}
+
+/// Where the review of a release's source stands.
+#[derive(Debug, Clone, Default, PartialEq, JsonSchema, RegisterSchema, ToSchema, Serialize, Deserialize)]
+pub enum SourceReviewStatus {
+    #[default]
+    NotStarted,
+    InReview { reviewer: String, started_at: chrono::DateTime<chrono::Utc> },
+    Approved { approver: String, approved_at: chrono::DateTime<chrono::Utc> },
+    ChangesRequested { reviewer: String, comment: String },
 }
 
 #[derive(Debug, Clone, JsonSchema, RegisterSchema, ToSchema, Serialize, Deserialize)]
 pub struct BuildDetails {
     pub build_id: String,
     pub build_timestamp: chrono::DateTime<chrono::Utc>,
+    /// Whether rebuilding the same source yields a bit-for-bit identical artifact.
+    #[serde(default)]
+    pub reproducible: bool,
+    /// The tool that ran the build, e.g. `bazel 7.1.0`.
+    #[serde(default)]
+    pub build_tool: Option<String>,
+    /// Where the build ran, e.g. a builder image or CI runner label.
+    #[serde(default)]
+    pub build_environment: Option<String>,
 }
 
 #[derive(Debug, Clone, JsonSchema, RegisterSchema, ToSchema, Serialize, Deserialize)]
 pub struct PackageDetails {
     pub artifact_hash: String,
     pub artifact_url: String,
+    /// The bill of materials shipped with the artifact, if one was generated.
+    #[serde(default)]
+    pub sbom: Option<SBOMReference>,
+}
+
+/// Points at an SBOM document stored outside the control plane.
+#[derive(Debug, Clone, PartialEq, JsonSchema, RegisterSchema, ToSchema, Serialize, Deserialize)]
+pub struct SBOMReference {
+    pub format: SBOMFormat,
+    /// Where the SBOM document is stored.
+    pub url: String,
+    /// SHA-256 of the SBOM document.
+    pub digest: String,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(Debug, Clone, JsonSchema, RegisterSchema, ToSchema, Serialize, Deserialize)]
 pub struct DeployDetails {
     pub deployment_id: String,
     pub environment: String,
+    #[serde(default)]
+    pub rollout_strategy: RolloutStrategy,
+}
+
+/// How a deployment replaces the running version.
+#[derive(Debug, Clone, Default, PartialEq, JsonSchema, RegisterSchema, ToSchema, Serialize, Deserialize)]
+pub enum RolloutStrategy {
+    #[default]
+    AllAtOnce,
+    /// Shifts `percentage` of the traffic to the new version, raised step by step with
+    /// `SDLCRelease::update_canary_percentage`.
+    Canary { percentage: u8, increment_interval_secs: u32 },
+    BlueGreen { active_slot: String },
+    Rolling { max_unavailable: u8, max_surge: u8 },
 }
 
 
@@ -92,4 +251,25 @@ pub struct RuntimeDetails {
     pub runtime_id: String,
     pub last_heartbeat: chrono::DateTime<chrono::Utc>,
     pub vulnerabilities: Vec<Vulnerability>,
+    #[serde(default)]
+    pub health_status: HealthStatus,
+    #[serde(default)]
+    pub last_successful_health_check: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Health of a deployed release as last reported by its runtime.
+#[derive(Debug, Clone, Default, PartialEq, JsonSchema, RegisterSchema, ToSchema, Serialize, Deserialize)]
+pub enum HealthStatus {
+    #[default]
+    Unknown,
+    Healthy,
+    Degraded { details: String },
+    Unhealthy { details: String },
+}
+
+impl RuntimeDetails {
+    /// Vulnerabilities at or above `level`, matching how `PolicyRule::VulnerabilityThreshold` counts them.
+    pub fn vulnerabilities_above(&self, level: VulnerabilityLevel) -> Vec<&Vulnerability> {
+        self.vulnerabilities.iter().filter(|vulnerability| vulnerability.severity >= level).collect()
+    }
 }
\ No newline at end of file
@@ -1,30 +1,78 @@
 use std::time::Duration;
 use std::collections::HashMap;
+use std::fmt;
 
 use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use sdlccp_api_macro::RegisterSchema;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use uuid::Uuid;
 
-#[derive(Debug, Clone, JsonSchema, RegisterSchema, ToSchema)]
+use super::attestation::SBOMFormat;
+use super::SDLCPhase;
+
+/// Migration: `id` and `parent_policies` used to be strings. Stored policies whose IDs are UUIDs,
+/// in any of the forms `Uuid::parse_str` accepts, still load; they are written back lowercase and
+/// hyphenated.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, RegisterSchema, ToSchema)]
 pub struct Policy {
-    pub id: String,
+    pub id: Uuid,
     pub name: String,
     pub rules: Vec<PolicyRule>,
-    pub parent_policies: Vec<String>, // Todo: This is currently IDs of parent policies, should this be a Vec<Uuid>, should this be some other way of referencing?
+    /// IDs of the policies this one builds on.
+    pub parent_policies: Vec<Uuid>,
     pub applies_to: Vec<String>, // Todo: This currently is Phase names this policy applies to. Should this be a Vec<Phase>? Should this be some other way of referencing?
 }
 
-#[derive(Debug, Clone, JsonSchema, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
 pub enum PolicyRule {
+    #[schema(value_type = DurationSchema)]
     MaxAge(Duration),
     ApprovedIdentities(Vec<String>),
     RequiredClaims(HashMap<String, String>),
+    /// Fails if an attestation carries any of these claims with this value.
+    ForbiddenClaims(HashMap<String, String>),
     VulnerabilityThreshold(VulnerabilityLevel, u32),
+    /// Fails if any known vulnerability was discovered longer ago than this.
+    #[schema(value_type = DurationSchema)]
+    MaxVulnerabilityAge(Duration),
+    /// Fails if any vulnerability affects one of these packages.
+    BlockedPackages(Vec<String>),
+    /// Fails unless the packaged artifact comes with an SBOM in this format.
+    RequiresSBOM(SBOMFormat),
+    /// Fails unless the build was recorded as reproducible.
+    RequireReproducibleBuild,
+    /// Fails while the release is suspended.
+    BlockSuspendedReleases,
+    /// Fails unless the release's source review was approved.
+    RequireApprovedSourceReview,
+    /// Fails while the runtime reports the release as unhealthy.
+    RequireHealthyRuntime,
+    /// Fails unless the team with this ID owns the released component.
+    RequireTeamOwnership(Uuid),
+    /// Fails unless the release's version satisfies this requirement, e.g. `>=1.2.0`.
+    #[schema(value_type = String)]
+    MinVersion(#[schemars(with = "String")] semver::VersionReq),
+    /// Fails once the release reached the Package phase without release notes.
+    RequireReleaseNotes,
+    /// Fails if the release stayed in the phase for longer than this, or is still in it after that long.
+    PhaseCompletedWithin(SDLCPhase, #[schema(value_type = DurationSchema)] Duration),
+    /// Fails if an attestation about the release, or one it builds on, was revoked.
+    NoRevokedAttestations,
+    /// Limits the policy's other rules to releases in one of these phases; elsewhere they pass.
+    ActiveForPhases(Vec<SDLCPhase>),
 }
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, JsonSchema, ToSchema, Serialize, Deserialize)]
+/// How serde writes a `std::time::Duration`, for the OpenAPI document, which cannot look into it.
+#[derive(ToSchema)]
+pub struct DurationSchema {
+    pub secs: u64,
+    pub nanos: u32,
+}
+
+/// Severity levels, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, JsonSchema, ToSchema, Serialize, Deserialize)]
 pub enum VulnerabilityLevel {
     Low,
     Medium,
@@ -32,10 +80,25 @@ pub enum VulnerabilityLevel {
     Critical,
 }
 
+impl VulnerabilityLevel {
+    /// Maps a CVSS v3.1 base score to its qualitative severity rating.
+    ///
+    /// CVSS rates 0.0 as "None", which has no level here and maps to `Low`.
+    /// Scores outside 0.0–10.0 are clamped.
+    pub fn from_cvss_score(score: f32) -> VulnerabilityLevel {
+        match score {
+            s if s >= 9.0 => VulnerabilityLevel::Critical,
+            s if s >= 7.0 => VulnerabilityLevel::High,
+            s if s >= 4.0 => VulnerabilityLevel::Medium,
+            _ => VulnerabilityLevel::Low,
+        }
+    }
+}
+
 impl Policy {
     pub fn new(name: String, applies_to: Vec<String>) -> Self {
         Self {
-            id: uuid::Uuid::new_v4().to_string(),
+            id: Uuid::new_v4(),
             name,
             rules: Vec::new(),
             parent_policies: Vec::new(),
@@ -48,10 +111,69 @@ impl Policy {
     }
 }
 
+/// Describes what the rule requires, for reports.
+impl fmt::Display for PolicyRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyRule::MaxAge(max_age) => write!(f, "Attestations are at most {}s old", max_age.as_secs()),
+            PolicyRule::ApprovedIdentities(identities) => {
+                write!(f, "Attestations are signed by approved identities only: {}", identities.join(", "))
+            }
+            PolicyRule::RequiredClaims(claims) => {
+                let mut claims: Vec<String> = claims.iter().map(|(claim, value)| format!("{}={}", claim, value)).collect();
+                claims.sort();
+                write!(f, "Attestations carry the claims {}", claims.join(", "))
+            }
+            PolicyRule::ForbiddenClaims(claims) => {
+                let mut claims: Vec<String> = claims.iter().map(|(claim, value)| format!("{}={}", claim, value)).collect();
+                claims.sort();
+                write!(f, "Attestations do not carry the claims {}", claims.join(", "))
+            }
+            PolicyRule::VulnerabilityThreshold(level, max_count) => {
+                write!(f, "At most {} vulnerabilities at or above {:?}", max_count, level)
+            }
+            PolicyRule::MaxVulnerabilityAge(max_age) => {
+                write!(f, "No vulnerability is known for longer than {}s", max_age.as_secs())
+            }
+            PolicyRule::BlockedPackages(packages) => {
+                write!(f, "No vulnerability affects the blocked packages {}", packages.join(", "))
+            }
+            PolicyRule::RequiresSBOM(format) => write!(f, "The package ships an SBOM in {:?} format", format),
+            PolicyRule::RequireReproducibleBuild => write!(f, "The build is reproducible"),
+            PolicyRule::BlockSuspendedReleases => write!(f, "The release is not suspended"),
+            PolicyRule::RequireApprovedSourceReview => write!(f, "The source review is approved"),
+            PolicyRule::RequireHealthyRuntime => write!(f, "The runtime is not unhealthy"),
+            PolicyRule::RequireTeamOwnership(team_id) => write!(f, "The component is owned by team {}", team_id),
+            PolicyRule::MinVersion(requirement) => write!(f, "The version satisfies {}", requirement),
+            PolicyRule::PhaseCompletedWithin(phase, within) => {
+                write!(f, "The {} phase is completed within {}s", phase.name(), within.as_secs())
+            }
+            PolicyRule::RequireReleaseNotes => write!(f, "The release has release notes once packaged"),
+            PolicyRule::NoRevokedAttestations => write!(f, "No attestation in the chain is revoked"),
+            PolicyRule::ActiveForPhases(phases) => {
+                let phases: Vec<&str> = phases.iter().map(SDLCPhase::name).collect();
+                write!(f, "The policy applies in the phases {}", phases.join(", "))
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, RegisterSchema, ToSchema)]
 pub struct Vulnerability {
     pub id: String,
+    /// CVE identifier, when `id` is a scanner-specific identifier or advisory ID.
+    pub cve_id: Option<String>,
     pub severity: VulnerabilityLevel,
+    /// CVSS base score.
+    #[schemars(range(min = 0.0, max = 10.0))]
+    #[schema(minimum = 0.0, maximum = 10.0)]
+    pub cvss_score: Option<f32>,
+    /// CVSS v3.1 vector string, e.g. `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`.
+    pub cvss_vector: Option<String>,
+    /// Package the vulnerability was found in, ideally as a purl.
+    pub affected_package: Option<String>,
+    #[serde(default)]
+    pub fix_available: bool,
     pub description: String,
     pub discovered_at: DateTime<Utc>,
 }
\ No newline at end of file
@@ -0,0 +1,327 @@
+//! SBOM documents generated from a release and its dependency releases.
+//!
+//! Only the parts of CycloneDX 1.4 and SPDX 2.3 the control plane can fill in are modelled:
+//! one component/package per release, its purl and the hash of its packaged artifact.
+
+use chrono::{DateTime, Utc};
+use packageurl::PackageUrl;
+use schemars::JsonSchema;
+use sdlccp_api_macro::RegisterSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::sdlc_component::SDLCComponent;
+use super::sdlc_release::SDLCRelease;
+
+/// `Content-Type` of a `CycloneDxBom` response.
+pub const CYCLONEDX_CONTENT_TYPE: &str = "application/vnd.cyclonedx+json; version=1.4";
+
+/// `predicate_type` of attestations whose claims are a CycloneDX BOM.
+pub const CYCLONEDX_PREDICATE_TYPE: &str = "https://cyclonedx.org/bom";
+
+/// `Content-Type` of an `SpdxDocument` response.
+pub const SPDX_CONTENT_TYPE: &str = "application/spdx+json";
+
+/// A release to list in an SBOM, with its component if that is registered.
+#[derive(Debug, Clone, Copy)]
+pub struct SbomSubject<'a> {
+    pub release: &'a SDLCRelease,
+    pub component: Option<&'a SDLCComponent>,
+}
+
+impl SbomSubject<'_> {
+    /// The component name, or the component ID when the component is not registered.
+    fn name(&self) -> String {
+        self.component.map_or_else(|| self.release.component_id.to_string(), |component| component.name().to_string())
+    }
+
+    fn purl(&self) -> Option<String> {
+        if let Some(SDLCComponent::Unmanaged(unmanaged)) = self.component {
+            if let Some(package_url) = &unmanaged.package_url {
+                return Some(package_url.clone());
+            }
+        }
+        let name = self.name();
+        let mut purl = PackageUrl::new("generic", name.as_str()).ok()?;
+        purl.with_version(self.release.version.as_str());
+        Some(purl.to_string())
+    }
+
+    fn artifact_hash(&self) -> Option<(HashAlgorithm, &str)> {
+        let details = self.release.phase_details.as_ref()?.package_details.as_ref()?;
+        HashAlgorithm::parse(&details.artifact_hash)
+    }
+
+    fn artifact_url(&self) -> Option<&str> {
+        let details = self.release.phase_details.as_ref()?.package_details.as_ref()?;
+        Some(details.artifact_url.as_str())
+    }
+
+    fn is_container(&self) -> bool {
+        matches!(self.component, Some(SDLCComponent::Container(_)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// Splits an `<algorithm>:<hex>` artifact hash. A bare hex digest is taken to be SHA-256.
+    fn parse(artifact_hash: &str) -> Option<(Self, &str)> {
+        let (algorithm, digest) = artifact_hash.split_once(':').unwrap_or(("sha256", artifact_hash));
+        let algorithm = match algorithm.to_ascii_lowercase().replace('-', "").as_str() {
+            "sha1" => Self::Sha1,
+            "sha256" => Self::Sha256,
+            "sha384" => Self::Sha384,
+            "sha512" => Self::Sha512,
+            _ => return None,
+        };
+        Some((algorithm, digest))
+    }
+
+    fn cyclonedx_name(self) -> &'static str {
+        match self {
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+            Self::Sha384 => "SHA-384",
+            Self::Sha512 => "SHA-512",
+        }
+    }
+
+    fn spdx_name(self) -> &'static str {
+        match self {
+            Self::Sha1 => "SHA1",
+            Self::Sha256 => "SHA256",
+            Self::Sha384 => "SHA384",
+            Self::Sha512 => "SHA512",
+        }
+    }
+}
+
+/// A CycloneDX 1.4 JSON document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema, RegisterSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CycloneDxBom {
+    pub bom_format: String,
+    pub spec_version: String,
+    pub serial_number: String,
+    pub version: u32,
+    pub metadata: CycloneDxMetadata,
+    /// One entry per dependency release; the release itself is `metadata.component`.
+    pub components: Vec<CycloneDxComponent>,
+    pub dependencies: Vec<CycloneDxDependency>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct CycloneDxMetadata {
+    pub timestamp: DateTime<Utc>,
+    pub component: CycloneDxComponent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CycloneDxComponentType {
+    Application,
+    Library,
+    Container,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct CycloneDxComponent {
+    #[serde(rename = "type")]
+    pub type_: CycloneDxComponentType,
+    /// The release ID.
+    #[serde(rename = "bom-ref")]
+    pub bom_ref: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purl: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hashes: Vec<CycloneDxHash>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct CycloneDxHash {
+    pub alg: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct CycloneDxDependency {
+    #[serde(rename = "ref")]
+    pub ref_: String,
+    #[serde(rename = "dependsOn")]
+    pub depends_on: Vec<String>,
+}
+
+impl CycloneDxComponent {
+    fn new(subject: SbomSubject<'_>, type_: CycloneDxComponentType) -> Self {
+        Self {
+            type_,
+            bom_ref: subject.release.id.to_string(),
+            name: subject.name(),
+            version: subject.release.version.clone(),
+            purl: subject.purl(),
+            hashes: subject.artifact_hash()
+                .map(|(alg, content)| CycloneDxHash { alg: alg.cyclonedx_name().to_string(), content: content.to_string() })
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+impl CycloneDxBom {
+    /// Describes `release` with `dependencies`, e.g. as returned by `ReleaseRepository::dependency_graph`.
+    pub fn new(release: SbomSubject<'_>, dependencies: &[SbomSubject<'_>]) -> Self {
+        let component_type = |subject: &SbomSubject<'_>| {
+            if subject.is_container() { CycloneDxComponentType::Container } else { CycloneDxComponentType::Library }
+        };
+        Self {
+            bom_format: "CycloneDX".to_string(),
+            spec_version: "1.4".to_string(),
+            serial_number: format!("urn:uuid:{}", Uuid::new_v4()),
+            version: 1,
+            metadata: CycloneDxMetadata {
+                timestamp: Utc::now(),
+                component: CycloneDxComponent::new(release, CycloneDxComponentType::Application),
+            },
+            components: dependencies.iter().map(|dependency| CycloneDxComponent::new(*dependency, component_type(dependency))).collect(),
+            dependencies: std::iter::once(&release).chain(dependencies)
+                .map(|subject| CycloneDxDependency {
+                    ref_: subject.release.id.to_string(),
+                    depends_on: listed_dependencies(subject, dependencies).map(|id| id.to_string()).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The dependencies of `subject` that are part of the document.
+fn listed_dependencies<'a>(subject: &'a SbomSubject<'_>, dependencies: &'a [SbomSubject<'_>]) -> impl Iterator<Item = Uuid> + 'a {
+    subject.release.dependencies.iter()
+        .copied()
+        .filter(|id| dependencies.iter().any(|dependency| dependency.release.id == *id))
+}
+
+/// An SPDX 2.3 JSON document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema, RegisterSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxDocument {
+    pub spdx_version: String,
+    pub data_license: String,
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    pub name: String,
+    pub document_namespace: String,
+    pub creation_info: SpdxCreationInfo,
+    /// The release followed by one entry per dependency release.
+    pub packages: Vec<SpdxPackage>,
+    pub relationships: Vec<SpdxRelationship>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct SpdxCreationInfo {
+    pub created: DateTime<Utc>,
+    pub creators: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxPackage {
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    pub name: String,
+    pub version_info: String,
+    pub download_location: String,
+    pub files_analyzed: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checksums: Vec<SpdxChecksum>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_refs: Vec<SpdxExternalRef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxChecksum {
+    pub algorithm: String,
+    pub checksum_value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxExternalRef {
+    pub reference_category: String,
+    pub reference_type: String,
+    pub reference_locator: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxRelationship {
+    pub spdx_element_id: String,
+    pub relationship_type: String,
+    pub related_spdx_element: String,
+}
+
+fn spdx_package_id(release_id: Uuid) -> String {
+    format!("SPDXRef-Release-{}", release_id)
+}
+
+impl SpdxPackage {
+    fn new(subject: SbomSubject<'_>) -> Self {
+        Self {
+            spdx_id: spdx_package_id(subject.release.id),
+            name: subject.name(),
+            version_info: subject.release.version.clone(),
+            download_location: subject.artifact_url().unwrap_or("NOASSERTION").to_string(),
+            files_analyzed: false,
+            checksums: subject.artifact_hash()
+                .map(|(algorithm, digest)| SpdxChecksum { algorithm: algorithm.spdx_name().to_string(), checksum_value: digest.to_string() })
+                .into_iter()
+                .collect(),
+            external_refs: subject.purl()
+                .map(|purl| SpdxExternalRef {
+                    reference_category: "PACKAGE-MANAGER".to_string(),
+                    reference_type: "purl".to_string(),
+                    reference_locator: purl,
+                })
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+impl SpdxDocument {
+    /// Describes `release` with `dependencies`, e.g. as returned by `ReleaseRepository::dependency_graph`.
+    pub fn new(release: SbomSubject<'_>, dependencies: &[SbomSubject<'_>]) -> Self {
+        let describes = SpdxRelationship {
+            spdx_element_id: "SPDXRef-DOCUMENT".to_string(),
+            relationship_type: "DESCRIBES".to_string(),
+            related_spdx_element: spdx_package_id(release.release.id),
+        };
+        let depends_on = std::iter::once(&release).chain(dependencies).flat_map(|subject| {
+            listed_dependencies(subject, dependencies).map(|dependency_id| SpdxRelationship {
+                spdx_element_id: spdx_package_id(subject.release.id),
+                relationship_type: "DEPENDS_ON".to_string(),
+                related_spdx_element: spdx_package_id(dependency_id),
+            })
+        });
+        Self {
+            spdx_version: "SPDX-2.3".to_string(),
+            data_license: "CC0-1.0".to_string(),
+            spdx_id: "SPDXRef-DOCUMENT".to_string(),
+            name: format!("{}-{}", release.name(), release.release.version),
+            document_namespace: format!("urn:uuid:{}", Uuid::new_v4()),
+            creation_info: SpdxCreationInfo { created: Utc::now(), creators: vec!["Tool: sdlccp".to_string()] },
+            packages: std::iter::once(&release).chain(dependencies).map(|subject| SpdxPackage::new(*subject)).collect(),
+            relationships: std::iter::once(describes).chain(depends_on).collect(),
+        }
+    }
+}
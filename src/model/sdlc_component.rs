@@ -10,6 +10,7 @@ use uuid::Uuid;
 pub enum SDLCComponent {
     Project(Project),   
     Unmanaged(Unmanaged),
+    Container(ContainerImage),
 }
 
 #[derive(Debug, Clone, JsonSchema, ToSchema, Serialize, Deserialize)]
@@ -28,6 +29,28 @@ pub struct Project {
     pub repository_url: Option<String>,
     pub owner: Option<String>,
     pub components: Vec<Uuid>, // References to other SDLCComponents
+    /// The teams that own the project.
+    #[serde(default)]
+    pub teams: Vec<TeamReference>,
+}
+
+#[derive(Debug, Clone, PartialEq, JsonSchema, RegisterSchema, ToSchema, Serialize, Deserialize)]
+pub struct TeamReference {
+    pub id: Uuid,
+    pub name: String,
+    pub contact_email: String,
+}
+
+#[derive(Debug, Clone, JsonSchema, ToSchema, Serialize, Deserialize)]
+pub struct ContainerImage {
+    pub id: Uuid,
+    pub name: String,
+    pub registry: String,
+    pub repository: String,
+    pub tag: Option<String>,
+    pub digest: Option<String>,
+    pub base_image: Option<Box<SDLCComponent>>, // The image this one is built FROM
+    pub metadata: HashMap<String, String>,
 }
 
 impl SDLCComponent {
@@ -35,6 +58,129 @@ impl SDLCComponent {
         match self {
             SDLCComponent::Project(p) => p.name.as_str(),
             SDLCComponent::Unmanaged(u) => u.name.as_str(),
+            SDLCComponent::Container(c) => c.name.as_str(),
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        match self {
+            SDLCComponent::Project(p) => p.id,
+            SDLCComponent::Unmanaged(u) => u.id,
+            SDLCComponent::Container(c) => c.id,
+        }
+    }
+
+    /// The owning teams. Only projects are owned by teams.
+    pub fn teams(&self) -> &[TeamReference] {
+        match self {
+            SDLCComponent::Project(p) => &p.teams,
+            SDLCComponent::Unmanaged(_) | SDLCComponent::Container(_) => &[],
+        }
+    }
+
+    /// Name of the variant, e.g. `Unmanaged`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SDLCComponent::Project(_) => "Project",
+            SDLCComponent::Unmanaged(_) => "Unmanaged",
+            SDLCComponent::Container(_) => "Container",
+        }
+    }
+
+    /// Merges what `other` knows about the same component into this one, e.g. when two teams
+    /// registered it separately. Fields only `other` sets are taken over, as are metadata keys
+    /// and project members and teams this component lacks. The ID stays this component's.
+    ///
+    /// Where both set a field to different values this component's value is kept, and every
+    /// such field is reported in `MergeError::Conflicts`, after the rest was merged.
+    pub fn merge(&mut self, other: &SDLCComponent) -> Result<(), MergeError> {
+        let mut conflicts = Vec::new();
+        match (self, other) {
+            (SDLCComponent::Project(ours), SDLCComponent::Project(theirs)) => {
+                compare("name", &ours.name, &theirs.name, &mut conflicts);
+                merge_option("repository_url", &mut ours.repository_url, &theirs.repository_url, &mut conflicts);
+                merge_option("owner", &mut ours.owner, &theirs.owner, &mut conflicts);
+                for component_id in &theirs.components {
+                    if !ours.components.contains(component_id) {
+                        ours.components.push(*component_id);
+                    }
+                }
+                for team in &theirs.teams {
+                    if !ours.teams.iter().any(|known| known.id == team.id) {
+                        ours.teams.push(team.clone());
+                    }
+                }
+            },
+            (SDLCComponent::Unmanaged(ours), SDLCComponent::Unmanaged(theirs)) => {
+                compare("name", &ours.name, &theirs.name, &mut conflicts);
+                merge_option("repository_url", &mut ours.repository_url, &theirs.repository_url, &mut conflicts);
+                merge_option("package_url", &mut ours.package_url, &theirs.package_url, &mut conflicts);
+                merge_metadata(&mut ours.metadata, &theirs.metadata, &mut conflicts);
+            },
+            (SDLCComponent::Container(ours), SDLCComponent::Container(theirs)) => {
+                compare("name", &ours.name, &theirs.name, &mut conflicts);
+                compare("registry", &ours.registry, &theirs.registry, &mut conflicts);
+                compare("repository", &ours.repository, &theirs.repository, &mut conflicts);
+                merge_option("tag", &mut ours.tag, &theirs.tag, &mut conflicts);
+                merge_option("digest", &mut ours.digest, &theirs.digest, &mut conflicts);
+                if ours.base_image.is_none() {
+                    ours.base_image = theirs.base_image.clone();
+                }
+                merge_metadata(&mut ours.metadata, &theirs.metadata, &mut conflicts);
+            },
+            (ours, theirs) => return Err(MergeError::IncompatibleTypes { ours: ours.kind(), theirs: theirs.kind() }),
+        }
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(MergeError::Conflicts(conflicts))
+        }
+    }
+}
+
+/// A field both components set, to different values, in `SDLCComponent::merge`.
+#[derive(Debug, Clone, PartialEq, JsonSchema, ToSchema, Serialize, Deserialize)]
+pub struct MergeConflict {
+    /// The field, or `metadata.<key>` for a metadata entry.
+    pub key: String,
+    /// The value kept.
+    pub our_value: String,
+    /// The value dropped.
+    pub their_value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum MergeError {
+    #[error("Cannot merge a {theirs} component into a {ours} component")]
+    IncompatibleTypes { ours: &'static str, theirs: &'static str },
+    #[error("{} fields differ between the components", .0.len())]
+    Conflicts(Vec<MergeConflict>),
+}
+
+fn compare(key: &str, ours: &str, theirs: &str, conflicts: &mut Vec<MergeConflict>) {
+    if ours != theirs {
+        conflicts.push(MergeConflict { key: key.to_string(), our_value: ours.to_string(), their_value: theirs.to_string() });
+    }
+}
+
+fn merge_option(key: &str, ours: &mut Option<String>, theirs: &Option<String>, conflicts: &mut Vec<MergeConflict>) {
+    match (ours.as_deref(), theirs) {
+        (None, _) => ours.clone_from(theirs),
+        (Some(our_value), Some(their_value)) => compare(key, our_value, their_value, conflicts),
+        (Some(_), None) => {},
+    }
+}
+
+fn merge_metadata(ours: &mut HashMap<String, String>, theirs: &HashMap<String, String>, conflicts: &mut Vec<MergeConflict>) {
+    let mut keys: Vec<&String> = theirs.keys().collect();
+    // Sorted, so conflicts come out in a stable order.
+    keys.sort();
+    for key in keys {
+        match ours.get(key) {
+            Some(our_value) => compare(&format!("metadata.{}", key), our_value, &theirs[key], conflicts),
+            None => {
+                ours.insert(key.clone(), theirs[key].clone());
+            },
         }
     }
 }
\ No newline at end of file
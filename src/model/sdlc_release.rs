@@ -1,45 +1,211 @@
-use super::sdlc_component::SDLCComponent;
-use super::phase::{BuildDetails, DeployDetails, DevelopmentDetails, PackageDetails, PhaseDetails, SDLCPhase, SourceDetails};
+use super::sla::{DeploymentSLA, SLAStatus};
+use super::phase::{phase_order, BuildDetails, DeployDetails, DevelopmentDetails, FeatureListLimits, HealthStatus, PackageDetails, PhaseDetails, RolloutStrategy, RuntimeDetails, SBOMReference, SDLCPhase, SourceDetails, SourceReviewStatus};
 use super::state::ReleaseState;
+use super::attestation::Attestation;
+use super::sdlc_component::SDLCComponent;
+use crate::services::attestation::{AttestationError, AttestationService};
+use crate::services::component_repository::{ComponentRepository, ComponentRepositoryError};
+use crate::services::transitions::{TransitionAction, TransitionError};
 use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use sdlccp_api_macro::RegisterSchema;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, JsonSchema, ToSchema, Serialize, Deserialize, RegisterSchema)]
 pub struct SDLCRelease {
     pub id: Uuid,
-    pub component: SDLCComponent,
+    /// ID of the `SDLCComponent` this release belongs to.
+    ///
+    /// Migration note: releases used to embed the whole `SDLCComponent`. Components are now
+    /// registered once through the `ComponentRepository` and releases only carry their ID;
+    /// resolve it with `SDLCRelease::resolve_component`.
+    pub component_id: Uuid,
     pub version: String,
     pub created_by: String,
     pub created_at: DateTime<Utc>,
     pub commit_hash: Option<String>,
     pub dependencies: Vec<Uuid>, // IDs of dependent releases
+    /// The release this one was branched from by `clone_for_hotfix`.
+    #[serde(default)]
+    pub parent_release_id: Option<Uuid>,
     pub phase_attestations: HashMap<String, Uuid>, // Todo: this is currently Phase name to attestation ID. Should this be a HashMap<Phase, Uuid>?
     #[schema(value_type = Object)]
     pub state: ReleaseState,
     pub phase: SDLCPhase,
     pub phase_details: Option<PhaseDetails>,
+    /// Free-form labels for grouping releases outside the phase/state taxonomy, e.g. `hotfix` or `lts`.
+    #[serde(default)]
+    pub tags: HashSet<String>,
+    /// Notes published with the release. Set by `release` and kept through every later state.
+    #[serde(default)]
+    pub release_notes: Option<String>,
+    /// How soon the release must be deployed once released, see `sla_status`.
+    #[serde(default)]
+    pub sla: Option<DeploymentSLA>,
+    /// When the release is due to be deployed under its `sla`. Set by `release`.
+    #[serde(default)]
+    pub estimated_deploy_time: Option<DateTime<Utc>>,
+    /// Every phase and state change the release went through, oldest first.
+    #[serde(default)]
+    pub transition_history: Vec<TransitionRecord>,
+    /// The release as it was before each transition, oldest first, to roll back to.
+    #[serde(default)]
+    pub snapshots: Vec<ReleaseSnapshot>,
+    /// Sign-off being collected while the release is `PolicyCheckPending`.
+    #[serde(default)]
+    pub pending_approval: Option<ApprovalRequest>,
+    /// IDs of the policies applied to the release with `POST /releases/{id}/apply-policy/{policy_id}`.
+    #[serde(default)]
+    pub applied_policies: HashSet<Uuid>,
+    /// Bumped by the `ReleaseRepository` on every stored change; served as the `ETag` of the
+    /// release so concurrent writers can detect lost updates. Not to be confused with `version`,
+    /// the version of the released software.
+    #[serde(default)]
+    pub revision: u64,
+}
+
+/// One phase or state change of a release.
+#[derive(Debug, Clone, PartialEq, JsonSchema, ToSchema, Serialize, Deserialize)]
+pub struct TransitionRecord {
+    pub from_phase: SDLCPhase,
+    /// Name of the state left, see `ReleaseState::name`.
+    pub from_state: String,
+    pub to_phase: SDLCPhase,
+    #[schema(value_type = Object)]
+    pub to_state: ReleaseState,
+    pub timestamp: DateTime<Utc>,
+    /// Set for changes the phases and states alone do not explain, like a `Rollback`.
+    #[serde(default)]
+    pub action: Option<TransitionAction>,
+    /// Why the change was made, for changes that take a reason like `exit_custom_state`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Who made the change, for changes that name their actor like `suspend` and `resume`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changed_by: Option<String>,
+}
+
+/// Where a release stood at `taken_at`, for `SDLCRelease::rollback_to`.
+#[derive(Debug, Clone, JsonSchema, ToSchema, Serialize, Deserialize)]
+pub struct ReleaseSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub phase: SDLCPhase,
+    #[schema(value_type = Object)]
+    pub state: ReleaseState,
+    pub phase_details: Option<PhaseDetails>,
+}
+
+/// How one release differs from another, as returned by `SDLCRelease::compare`. Dependencies are
+/// added or removed going from the release compared to the other one.
+#[derive(Debug, Clone, PartialEq, Eq, JsonSchema, ToSchema, Serialize, Deserialize)]
+pub struct ReleaseDiff {
+    pub same_component: bool,
+    pub version_changed: bool,
+    pub phase_changed: bool,
+    /// Whether the releases are in different states, regardless of what each state records.
+    pub state_changed: bool,
+    pub added_dependencies: Vec<Uuid>,
+    pub removed_dependencies: Vec<Uuid>,
+    pub build_id_changed: bool,
+    pub commit_hash_changed: bool,
+    pub artifact_hash_changed: bool,
+}
+
+/// Sign-off a release needs from a set of people before it becomes `Releasable`.
+#[derive(Debug, Clone, PartialEq, JsonSchema, ToSchema, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    pub requester: String,
+    pub required_approvers: Vec<String>,
+    pub obtained_approvals: Vec<Approval>,
+}
+
+#[derive(Debug, Clone, PartialEq, JsonSchema, ToSchema, Serialize, Deserialize)]
+pub struct Approval {
+    pub approver: String,
+    pub approved_at: DateTime<Utc>,
+    pub comment: Option<String>,
+}
+
+/// Longest tag accepted by `SDLCRelease::add_tag`.
+pub const MAX_TAG_LENGTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum TagError {
+    #[error("Invalid tag {0:?}: tags are 1-64 ASCII letters, digits or hyphens")]
+    InvalidTag(String),
+}
+
+/// Why a release is not valid. Serialized without a variant tag, so phase detail errors keep
+/// their `{ "field": ..., "reason": ... }` shape.
+#[derive(Debug, Clone, PartialEq, Serialize, thiserror::Error)]
+#[serde(untagged)]
+pub enum ValidationError {
+    /// A detail the release's phase requires but that is missing or malformed.
+    #[error("{field}: {reason}")]
+    PhaseDetail { field: &'static str, reason: String },
+    /// The version, as given, is not a semantic version.
+    #[error("Invalid version {0:?}: expected a semantic version like 1.2.3")]
+    InvalidSemver(String),
+}
+
+impl ValidationError {
+    fn new(field: &'static str, reason: &str) -> Self {
+        Self::PhaseDetail { field, reason: reason.to_string() }
+    }
+
+    /// The release field the error is about.
+    pub fn field(&self) -> &'static str {
+        match self {
+            Self::PhaseDetail { field, .. } => field,
+            Self::InvalidSemver(_) => "version",
+        }
+    }
+}
+
+/// Whether going from `from` to `to` moves forward in the lifecycle. Never true to or from a
+/// custom phase, which has no place in the order.
+fn advances(from: &SDLCPhase, to: &SDLCPhase) -> bool {
+    to > from
 }
 
 impl SDLCRelease {
-    /// Creates a new SDLCRelease in the Development phase with Draft state.
-    pub fn new(component: SDLCComponent, version: String, created_by: String) -> Self {
+    /// Creates a new SDLCRelease in the Development phase with Draft state. `version` must be a
+    /// semantic version.
+    pub fn new(component_id: Uuid, version: String, created_by: String) -> Result<Self, ValidationError> {
+        if semver::Version::parse(&version).is_err() {
+            return Err(ValidationError::InvalidSemver(version));
+        }
+        Ok(Self::new_unchecked(component_id, version, created_by))
+    }
+
+    /// Like `new`, but takes any `version`, e.g. for fixtures of releases stored before versions
+    /// were checked.
+    pub fn new_unchecked(component_id: Uuid, version: String, created_by: String) -> Self {
         SDLCRelease {
             id: Uuid::new_v4(),
-            component,
+            component_id,
             version,
             created_by,
             created_at: Utc::now(),
             commit_hash: None,
             dependencies: Vec::new(),
+            parent_release_id: None,
             phase_attestations: HashMap::new(),
             phase: SDLCPhase::Development,
             state: ReleaseState::Draft,
             phase_details: Some(PhaseDetails::new()),
+            revision: 0,
+            tags: HashSet::new(),
+            release_notes: None,
+            sla: None,
+            estimated_deploy_time: None,
+            transition_history: Vec::new(),
+            snapshots: Vec::new(),
+            pending_approval: None,
+            applied_policies: HashSet::new(),
         }
     }
 
@@ -60,8 +226,25 @@ impl SDLCRelease {
         self.state.name()
     }
 
-    pub fn component_name(&self) -> &str {
-        &self.component.name()
+    pub fn component_id(&self) -> &Uuid {
+        &self.component_id
+    }
+
+    /// Looks up the component this release references.
+    pub async fn resolve_component(&self, repository: &dyn ComponentRepository) -> Result<Option<SDLCComponent>, ComponentRepositoryError> {
+        repository.get_component(&self.component_id).await
+    }
+
+    /// Looks up the attestations recorded in `phase_attestations`, keyed by phase name. An
+    /// attestation that is no longer stored is an error.
+    pub async fn list_phase_attestations_full(&self, attestation_service: &dyn AttestationService) -> Result<HashMap<String, Attestation>, AttestationError> {
+        let mut attestations = HashMap::with_capacity(self.phase_attestations.len());
+        for (phase, id) in &self.phase_attestations {
+            let attestation = attestation_service.get_attestation(id).await?
+                .ok_or_else(|| AttestationError::RetrievalError(format!("attestation {id} of the {phase} phase is not stored")))?;
+            attestations.insert(phase.clone(), attestation);
+        }
+        Ok(attestations)
     }
 
     pub fn add_dependency(&mut self, dependency_id: Uuid) {
@@ -72,27 +255,157 @@ impl SDLCRelease {
         self.phase_attestations.insert(phase_name, attestation_id);
     }
 
-    /// Starts the Development phase.
-    pub fn start_development(&mut self, started_by: String, feature_list: Vec<String>) -> Result<(), String> {
-        if self.phase == SDLCPhase::Development && matches!(self.state, ReleaseState::Draft) {
-            self.state = ReleaseState::InProgress {
-                started_by,
-                started_at: Utc::now(),
-            };
-            if let Some(details) = &mut self.phase_details {
-                details.development_details = Some(DevelopmentDetails { feature_list });
-            }
-            Ok(())
-        } else {
-            Err("Cannot start development in the current phase and state.".to_string())
+    /// Adds a tag. Adding a tag the release already has is a no-op.
+    pub fn add_tag(&mut self, tag: &str) -> Result<(), TagError> {
+        let valid = !tag.is_empty()
+            && tag.len() <= MAX_TAG_LENGTH
+            && tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !valid {
+            return Err(TagError::InvalidTag(tag.to_string()));
+        }
+        self.tags.insert(tag.to_string());
+        Ok(())
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) {
+        self.tags.remove(tag);
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+
+    /// The release's tags in sorted order.
+    pub fn list_tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = self.tags.iter().map(String::as_str).collect();
+        tags.sort_unstable();
+        tags
+    }
+
+    /// Writes the release notes ahead of `release`, or corrects them afterwards. Allowed in any
+    /// state; `release` replaces notes written before it.
+    pub fn set_release_notes(&mut self, notes: String) {
+        self.release_notes = Some(notes);
+    }
+
+    /// Branches a hotfix off this release, typically one that is `Deployed` or `Revoked`.
+    ///
+    /// The hotfix builds on the same commit, so it starts as a draft in the Source phase. It
+    /// depends on this release and records it as its parent.
+    pub fn clone_for_hotfix(&self, hotfix_version: String, created_by: String) -> Result<SDLCRelease, ValidationError> {
+        let mut hotfix = SDLCRelease::new(self.component_id, hotfix_version, created_by)?;
+        hotfix.commit_hash = self.commit_hash.clone();
+        hotfix.dependencies.push(self.id);
+        hotfix.parent_release_id = Some(self.id);
+        hotfix.phase = SDLCPhase::Source;
+        Ok(hotfix)
+    }
+
+    /// Compares the release to `other`, e.g. a hotfix to the release it was branched from.
+    pub fn compare(&self, other: &SDLCRelease) -> ReleaseDiff {
+        ReleaseDiff {
+            same_component: self.component_id == other.component_id,
+            version_changed: self.version != other.version,
+            phase_changed: self.phase != other.phase,
+            state_changed: self.state.name() != other.state.name(),
+            added_dependencies: other.dependencies.iter().filter(|id| !self.dependencies.contains(id)).copied().collect(),
+            removed_dependencies: self.dependencies.iter().filter(|id| !other.dependencies.contains(id)).copied().collect(),
+            build_id_changed: self.build_id() != other.build_id(),
+            commit_hash_changed: self.commit_hash != other.commit_hash,
+            artifact_hash_changed: self.artifact_hash() != other.artifact_hash(),
+        }
+    }
+
+    fn build_id(&self) -> Option<&str> {
+        self.phase_details.as_ref()?.build_details.as_ref().map(|build| build.build_id.as_str())
+    }
+
+    fn artifact_hash(&self) -> Option<&str> {
+        self.phase_details.as_ref()?.package_details.as_ref().map(|package| package.artifact_hash.as_str())
+    }
+
+    /// Moves the release to `phase` and `state` and records the move in `transition_history`.
+    fn transition_to(&mut self, phase: SDLCPhase, state: ReleaseState) {
+        self.record_transition(phase, state, None);
+    }
+
+    /// Like `transition_to`, also snapshotting the release first. Keeps `phase_details`; callers
+    /// update it themselves.
+    fn record_transition(&mut self, phase: SDLCPhase, state: ReleaseState, action: Option<TransitionAction>) {
+        self.record_transition_with_reason(phase, state, action, None, None);
+    }
+
+    fn record_transition_with_reason(
+        &mut self,
+        phase: SDLCPhase,
+        state: ReleaseState,
+        action: Option<TransitionAction>,
+        reason: Option<String>,
+        changed_by: Option<String>,
+    ) {
+        let snapshot = self.snapshot();
+        self.transition_history.push(TransitionRecord {
+            from_phase: self.phase.clone(),
+            from_state: self.state.name().to_string(),
+            to_phase: phase.clone(),
+            to_state: state.clone(),
+            timestamp: snapshot.taken_at,
+            action,
+            reason,
+            changed_by,
+        });
+        self.snapshots.push(snapshot);
+        self.phase = phase;
+        self.state = state;
+    }
+
+    /// Captures the phase, state and phase details the release has now.
+    pub fn snapshot(&self) -> ReleaseSnapshot {
+        ReleaseSnapshot {
+            taken_at: Utc::now(),
+            phase: self.phase.clone(),
+            state: self.state.clone(),
+            phase_details: self.phase_details.clone(),
+        }
+    }
+
+    /// Restores the phase, state and phase details of `snapshot`, e.g. to redeploy after a bad
+    /// deployment. Only a release that is `Deployed`, `Revoked` or `Failed` can be rolled back.
+    pub fn rollback_to(&mut self, snapshot: ReleaseSnapshot) -> Result<(), TransitionError> {
+        if !matches!(self.state, ReleaseState::Deployed { .. } | ReleaseState::Revoked { .. } | ReleaseState::Failed { .. }) {
+            return Err(TransitionError::InvalidTransition("Only a deployed, revoked or failed release can be rolled back.".to_string()));
+        }
+        tracing::info!(release_id = %self.id, taken_at = %snapshot.taken_at, "Rolling back release");
+        self.record_transition(snapshot.phase, snapshot.state, Some(TransitionAction::Rollback));
+        self.phase_details = snapshot.phase_details;
+        Ok(())
+    }
+
+    /// Starts the Development phase with a feature list within the default limits.
+    pub fn start_development(&mut self, started_by: String, feature_list: Vec<String>) -> Result<(), TransitionError> {
+        self.start_development_with_limits(started_by, feature_list, FeatureListLimits::default())
+    }
+
+    /// Starts the Development phase, rejecting a feature list outside `limits`.
+    pub fn start_development_with_limits(&mut self, started_by: String, feature_list: Vec<String>, limits: FeatureListLimits) -> Result<(), TransitionError> {
+        if self.phase != SDLCPhase::Development || !matches!(self.state, ReleaseState::Draft) {
+            return Err(TransitionError::InvalidTransition("Cannot start development in the current phase and state.".to_string()));
+        }
+        limits.validate(&feature_list).map_err(TransitionError::FeatureListError)?;
+        self.transition_to(SDLCPhase::Development, ReleaseState::InProgress {
+            started_by,
+            started_at: Utc::now(),
+        });
+        if let Some(details) = &mut self.phase_details {
+            details.development_details = Some(DevelopmentDetails { feature_list });
         }
+        Ok(())
     }
 
     /// Completes the Development phase.
     pub fn complete_development(&mut self) -> Result<(), String> {
         if self.phase == SDLCPhase::Development && matches!(self.state, ReleaseState::InProgress { .. }) {
-            self.phase = SDLCPhase::Source;
-            self.state = ReleaseState::Draft;
+            self.transition_to(SDLCPhase::Source, ReleaseState::Draft);
             if let Some(details) = &mut self.phase_details {
                 details.development_details = None;
                 details.source_details = None;
@@ -103,28 +416,46 @@ impl SDLCRelease {
         }
     }
 
-    /// Starts the Source Review phase.
+    /// Starts the Source Review phase, or a re-review after changes were requested.
     pub fn start_source_review(&mut self, started_by: String) -> Result<(), String> {
         if self.phase == SDLCPhase::Source && matches!(self.state, ReleaseState::Draft) {
-            self.state = ReleaseState::InProgress {
-                started_by,
-                started_at: Utc::now(),
-            };
+            let started_at = Utc::now();
+            let review_status = SourceReviewStatus::InReview { reviewer: started_by.clone(), started_at };
+            if let Some(details) = &mut self.phase_details {
+                // A re-review keeps the branch and PR of the previous round.
+                details.source_details.get_or_insert_with(|| SourceDetails {
+                    commit_hash: String::new(),
+                    branch: String::new(),
+                    pr_url: None,
+                    review_status: SourceReviewStatus::NotStarted,
+                }).review_status = review_status;
+            }
+            self.transition_to(SDLCPhase::Source, ReleaseState::InProgress { started_by, started_at });
             Ok(())
         } else {
             Err("Cannot start source review in the current phase and state.".to_string())
         }
     }
 
-    /// Completes the Source Review phase.
-    pub fn complete_source_review(&mut self, commit_hash: String) -> Result<(), String> {
+    /// Completes the Source Review phase with the reviewer's verdict. An approved review moves
+    /// the release on to Build; if changes were requested it goes back to a Source draft so the
+    /// fixed commit can be reviewed again.
+    pub fn complete_source_review(&mut self, commit_hash: String, branch: String, pr_url: Option<String>, review_status: SourceReviewStatus) -> Result<(), String> {
         if self.phase == SDLCPhase::Source && matches!(self.state, ReleaseState::InProgress { .. }) {
-            self.commit_hash = Some(commit_hash.clone());
+            let next_phase = match review_status {
+                SourceReviewStatus::Approved { .. } => SDLCPhase::Build,
+                SourceReviewStatus::ChangesRequested { .. } => SDLCPhase::Source,
+                SourceReviewStatus::NotStarted | SourceReviewStatus::InReview { .. } => {
+                    return Err("A source review completes with an approval or a change request.".to_string());
+                }
+            };
+            if next_phase == SDLCPhase::Build {
+                self.commit_hash = Some(commit_hash.clone());
+            }
             if let Some(details) = &mut self.phase_details {
-                details.source_details = Some(SourceDetails { commit_hash });
+                details.source_details = Some(SourceDetails { commit_hash, branch, pr_url, review_status });
             }
-            self.phase = SDLCPhase::Build;
-            self.state = ReleaseState::Draft;
+            self.transition_to(next_phase, ReleaseState::Draft);
             Ok(())
         } else {
             Err("Cannot complete source review in the current phase and state.".to_string())
@@ -136,10 +467,10 @@ impl SDLCRelease {
     /// Starts the Build phase.
     pub fn start_build(&mut self, started_by: String) -> Result<(), String> {
         if self.phase == SDLCPhase::Build && matches!(self.state, ReleaseState::Draft) {
-            self.state = ReleaseState::InProgress {
+            self.transition_to(SDLCPhase::Build, ReleaseState::InProgress {
                 started_by,
                 started_at: Utc::now(),
-            };
+            });
             Ok(())
         } else {
             Err("Cannot start build in the current phase and state.".to_string())
@@ -147,79 +478,127 @@ impl SDLCRelease {
     }
 
     /// Completes the Build phase.
-    pub fn complete_build(&mut self, build_id: String) -> Result<(), String> {
+    pub fn complete_build(&mut self, build_id: String, reproducible: bool, build_tool: Option<String>) -> Result<(), TransitionError> {
         if self.phase == SDLCPhase::Build && matches!(self.state, ReleaseState::InProgress { .. }) {
             if let Some(details) = &mut self.phase_details {
                 details.build_details = Some(BuildDetails {
                     build_id,
                     build_timestamp: Utc::now(),
+                    reproducible,
+                    build_tool,
+                    build_environment: None,
                 });
             }
-            self.phase = SDLCPhase::Package;
-            self.state = ReleaseState::Draft;
+            self.transition_to(SDLCPhase::Package, ReleaseState::Draft);
             Ok(())
         } else {
-            Err("Cannot complete build in the current phase and state.".to_string())
+            Err(TransitionError::InvalidTransition("Cannot complete build in the current phase and state.".to_string()))
         }
     }
 
     /// Starts the Packaging phase.
     pub fn start_packaging(&mut self, started_by: String) -> Result<(), String> {
         if self.phase == SDLCPhase::Package && matches!(self.state, ReleaseState::Draft) {
-            self.state = ReleaseState::InProgress {
+            self.transition_to(SDLCPhase::Package, ReleaseState::InProgress {
                 started_by,
                 started_at: Utc::now(),
-            };
+            });
             Ok(())
         } else {
             Err("Cannot start packaging in the current phase and state.".to_string())
         }
     }
 
-    /// Completes the Packaging phase.
-    pub fn complete_packaging(&mut self, artifact_hash: String, artifact_url: String) -> Result<(), String> {
+    /// Completes the Packaging phase, recording the SBOM generated for the artifact if there is one.
+    pub fn complete_packaging(&mut self, artifact_hash: String, artifact_url: String, sbom: Option<SBOMReference>) -> Result<(), String> {
         if self.phase == SDLCPhase::Package && matches!(self.state, ReleaseState::InProgress { .. }) {
             if let Some(details) = &mut self.phase_details {
                 details.package_details = Some(PackageDetails {
                     artifact_hash,
                     artifact_url,
+                    sbom,
                 });
             }
-            self.phase = SDLCPhase::Deploy;
-            self.state = ReleaseState::Releasable {
+            self.transition_to(SDLCPhase::Deploy, ReleaseState::Releasable {
                 approved_by: "Auto-Approved".to_string(),
                 approved_at: Utc::now(),
-            };
+            });
             Ok(())
         } else {
             Err("Cannot complete packaging in the current phase and state.".to_string())
         }
     }
 
-    /// Releases the package.
+    /// Releases the package, publishing `release_notes` with it.
     pub fn release(&mut self, release_notes: String) -> Result<(), String> {
         if matches!(self.state, ReleaseState::Releasable { .. }) {
-            self.state = ReleaseState::Released {
-                release_notes,
-                release_time: Utc::now(),
-            };
+            let release_time = Utc::now();
+            self.release_notes = Some(release_notes.clone());
+            self.estimated_deploy_time = self.sla.and_then(|sla| chrono::Duration::from_std(sla.max_time_to_deploy).ok())
+                .and_then(|max_time_to_deploy| release_time.checked_add_signed(max_time_to_deploy));
+            self.transition_to(self.phase.clone(), ReleaseState::Released { release_notes, release_time });
             Ok(())
         } else {
             Err("Cannot release in the current state.".to_string())
         }
     }
 
-    /// Starts the Deployment phase.
-    pub fn start_deployment(&mut self, environment: String) -> Result<(), String> {
-        if self.phase == SDLCPhase::Deploy && matches!(self.state, ReleaseState::Released { .. }) {
-            self.state = ReleaseState::InProgress {
+    /// Where the release stands against its `sla` now.
+    pub fn sla_status(&self) -> SLAStatus {
+        self.sla_status_at(Utc::now())
+    }
+
+    /// Where the release stands against its `sla` at `now`. The clock starts when the release is
+    /// released and stops once it is deployed.
+    pub fn sla_status_at(&self, now: DateTime<Utc>) -> SLAStatus {
+        let (Some(sla), Some(released_at)) = (self.sla, self.awaiting_deployment_since()) else {
+            return SLAStatus::OnTrack;
+        };
+        let elapsed = (now - released_at).to_std().unwrap_or_default();
+        if elapsed > sla.max_time_to_deploy {
+            return SLAStatus::Breached { overdue_by: elapsed - sla.max_time_to_deploy };
+        }
+        if sla.max_time_to_deploy.is_zero() {
+            return SLAStatus::OnTrack;
+        }
+        let percentage_elapsed = (elapsed.as_secs_f64() / sla.max_time_to_deploy.as_secs_f64() * 100.0) as u8;
+        if percentage_elapsed >= sla.notify_at_percent {
+            SLAStatus::AtRisk { percentage_elapsed }
+        } else {
+            SLAStatus::OnTrack
+        }
+    }
+
+    /// When the release was released, as long as it is not deployed yet.
+    fn awaiting_deployment_since(&self) -> Option<DateTime<Utc>> {
+        match &self.state {
+            ReleaseState::Released { release_time, .. } => Some(*release_time),
+            // Deploying, possibly after a failed attempt, but not fully deployed yet
+            ReleaseState::InProgress { .. } | ReleaseState::Failed { .. } | ReleaseState::PartiallyDeployed { .. } if self.phase == SDLCPhase::Deploy => {
+                self.transition_history.iter().rev().find_map(|record| match &record.to_state {
+                    ReleaseState::Released { release_time, .. } => Some(*release_time),
+                    _ => None,
+                })
+            },
+            _ => None,
+        }
+    }
+
+    /// Starts the Deployment phase, or starts it over after a failed deployment was retried.
+    pub fn start_deployment(&mut self, environment: String, rollout_strategy: RolloutStrategy) -> Result<(), String> {
+        if matches!(rollout_strategy, RolloutStrategy::Canary { percentage, .. } if percentage > 100) {
+            return Err("Canary percentage must be at most 100.".to_string());
+        }
+        if self.phase == SDLCPhase::Deploy && matches!(self.state, ReleaseState::Released { .. } | ReleaseState::Draft) {
+            self.transition_to(SDLCPhase::Deploy, ReleaseState::InProgress {
                 started_by: "Deployment System".to_string(),
                 started_at: Utc::now(),
-            };
+            });
             if let Some(details) = &mut self.phase_details {
                 details.deploy_details = Some(DeployDetails {
                     deployment_id: Uuid::new_v4().to_string(),
-                    environment: environment.clone(),
+                    environment,
+                    rollout_strategy,
                 });
             }
             Ok(())
@@ -228,49 +607,447 @@ impl SDLCRelease {
         }
     }
 
-    /// Completes the Deployment phase.
+    /// Completes the Deployment phase. A canary deployment must have reached 100% first.
     pub fn complete_deployment(&mut self) -> Result<(), String> {
-        if self.phase == SDLCPhase::Deploy && matches!(self.state, ReleaseState::InProgress { .. }) {
-            self.phase = SDLCPhase::Runtime;
-            if let Some(details) = &mut self.phase_details {
-                if let Some(deploy_details) = &details.deploy_details {
-                    self.state = ReleaseState::Deployed {
-                        environment: deploy_details.environment.clone(),
-                        deployment_time: Utc::now(),
-                    };
-                } else {
-                    return Err("Deployment details missing.".to_string());
-                }
-            } else {
+        if self.phase == SDLCPhase::Deploy && matches!(self.state, ReleaseState::InProgress { .. } | ReleaseState::PartiallyDeployed { .. }) {
+            let Some(details) = &mut self.phase_details else {
                 return Err("Phase details missing.".to_string());
+            };
+            let Some(deploy_details) = &details.deploy_details else {
+                return Err("Deployment details missing.".to_string());
+            };
+            if let RolloutStrategy::Canary { percentage, .. } = deploy_details.rollout_strategy {
+                if percentage < 100 {
+                    return Err(format!("The canary only serves {}% of the traffic.", percentage));
+                }
             }
+            let environment = deploy_details.environment.clone();
+            // Runtime monitoring fills in vulnerabilities later.
+            details.runtime_details = Some(RuntimeDetails {
+                runtime_id: deploy_details.deployment_id.clone(),
+                last_heartbeat: Utc::now(),
+                vulnerabilities: Vec::new(),
+                health_status: HealthStatus::Unknown,
+                last_successful_health_check: None,
+            });
+            self.transition_to(SDLCPhase::Runtime, ReleaseState::Deployed {
+                environment,
+                deployment_time: Utc::now(),
+            });
             Ok(())
         } else {
             Err("Cannot complete deployment in the current phase and state.".to_string())
         }
     }
 
+    /// Shifts more traffic to a canary deployment. The release is `PartiallyDeployed` until
+    /// `complete_deployment` is called, which requires the canary to be at 100%.
+    pub fn update_canary_percentage(&mut self, new_percentage: u8) -> Result<(), TransitionError> {
+        if self.phase != SDLCPhase::Deploy || !matches!(self.state, ReleaseState::InProgress { .. } | ReleaseState::PartiallyDeployed { .. }) {
+            return Err(TransitionError::InvalidTransition("Only a running deployment can shift traffic.".to_string()));
+        }
+        let Some(deploy_details) = self.phase_details.as_mut().and_then(|details| details.deploy_details.as_mut()) else {
+            return Err(TransitionError::InvalidTransition("Deployment details missing.".to_string()));
+        };
+        let RolloutStrategy::Canary { percentage, .. } = &mut deploy_details.rollout_strategy else {
+            return Err(TransitionError::InvalidTransition("The deployment is not a canary.".to_string()));
+        };
+        if new_percentage <= *percentage || new_percentage > 100 {
+            return Err(TransitionError::InvalidTransition(format!(
+                "Canary percentage must rise from {}% to at most 100%, got {}%.",
+                percentage, new_percentage
+            )));
+        }
+        *percentage = new_percentage;
+        let environment = deploy_details.environment.clone();
+        self.transition_to(SDLCPhase::Deploy, ReleaseState::PartiallyDeployed { percentage: new_percentage, environment });
+        Ok(())
+    }
+
+    /// Records the outcome of a health check of the deployed release.
+    pub fn update_health_status(&mut self, status: HealthStatus) -> Result<(), TransitionError> {
+        if self.phase != SDLCPhase::Runtime || !matches!(self.state, ReleaseState::Deployed { .. }) {
+            return Err(TransitionError::InvalidTransition("Health can only be reported for a deployed release.".to_string()));
+        }
+        let runtime = self.runtime_details_mut()?;
+        if status == HealthStatus::Healthy {
+            runtime.last_successful_health_check = Some(Utc::now());
+        }
+        runtime.health_status = status;
+        Ok(())
+    }
+
+    /// Records a heartbeat from the runtime, which also counts as a successful health check.
+    pub fn record_heartbeat(&mut self) -> Result<(), TransitionError> {
+        if self.phase != SDLCPhase::Runtime || !matches!(self.state, ReleaseState::Deployed { .. }) {
+            return Err(TransitionError::InvalidTransition("Heartbeats can only be recorded for a deployed release.".to_string()));
+        }
+        let runtime = self.runtime_details_mut()?;
+        let now = Utc::now();
+        runtime.last_heartbeat = now;
+        runtime.last_successful_health_check = Some(now);
+        runtime.health_status = HealthStatus::Healthy;
+        Ok(())
+    }
+
+    fn runtime_details_mut(&mut self) -> Result<&mut RuntimeDetails, TransitionError> {
+        self.phase_details.as_mut()
+            .and_then(|details| details.runtime_details.as_mut())
+            .ok_or_else(|| TransitionError::InvalidTransition("Runtime details missing.".to_string()))
+    }
+
     /// Revokes the release.
     pub fn revoke(&mut self, reason: String) -> Result<(), String> {
         if matches!(self.state, ReleaseState::Deployed { .. }) {
-            self.state = ReleaseState::Revoked {
+            self.transition_to(self.phase.clone(), ReleaseState::Revoked {
                 reason,
                 revocation_time: Utc::now(),
-            };
+            });
             Ok(())
         } else {
             Err("Cannot revoke in the current state.".to_string())
         }
     }
 
-    /// Validates the current phase and state.
+    /// Replaces the automatic approval packaging hands out with sign-off from `required_approvers`.
+    /// The release waits in `PolicyCheckPending` until every one of them called `record_approval`.
+    pub fn request_approval(&mut self, requester: String, required_approvers: Vec<String>) -> Result<(), TransitionError> {
+        if self.phase != SDLCPhase::Deploy || !matches!(self.state, ReleaseState::Releasable { .. }) {
+            return Err(TransitionError::InvalidTransition("Approval can only be requested for a releasable package.".to_string()));
+        }
+        if required_approvers.is_empty() {
+            return Err(TransitionError::InvalidTransition("An approval request needs at least one approver.".to_string()));
+        }
+        let requested_at = Utc::now();
+        self.pending_approval = Some(ApprovalRequest {
+            requester: requester.clone(),
+            required_approvers,
+            obtained_approvals: Vec::new(),
+        });
+        self.transition_to(self.phase.clone(), ReleaseState::PolicyCheckPending { requested_by: requester, requested_at });
+        Ok(())
+    }
+
+    /// Records `approver`'s sign-off. The release becomes `Releasable` with the last required one.
+    pub fn record_approval(&mut self, approver: String, comment: Option<String>) -> Result<(), TransitionError> {
+        let Some(request) = self.pending_approval.as_mut().filter(|_| matches!(self.state, ReleaseState::PolicyCheckPending { .. })) else {
+            return Err(TransitionError::InvalidTransition("The release is not awaiting approval.".to_string()));
+        };
+        if !request.required_approvers.contains(&approver) {
+            return Err(TransitionError::InvalidTransition(format!("{} is not a required approver.", approver)));
+        }
+        if request.obtained_approvals.iter().any(|approval| approval.approver == approver) {
+            return Err(TransitionError::InvalidTransition(format!("{} already approved the release.", approver)));
+        }
+        request.obtained_approvals.push(Approval { approver, approved_at: Utc::now(), comment });
+        self.check_approval_complete();
+        Ok(())
+    }
+
+    /// Moves the release to `Releasable` once every required approver signed off, with all of
+    /// them as `approved_by`. Returns whether the approval is complete.
+    pub fn check_approval_complete(&mut self) -> bool {
+        let Some(request) = &self.pending_approval else {
+            return false;
+        };
+        let complete = request.required_approvers.iter()
+            .all(|required| request.obtained_approvals.iter().any(|approval| &approval.approver == required));
+        if !complete {
+            return false;
+        }
+        let approved_by = request.obtained_approvals.iter().map(|approval| approval.approver.as_str()).collect::<Vec<_>>().join(", ");
+        self.pending_approval = None;
+        self.transition_to(self.phase.clone(), ReleaseState::Releasable { approved_by, approved_at: Utc::now() });
+        true
+    }
+
+    /// Pauses a deployed release, e.g. while an incident is investigated. Unlike `revoke`, this
+    /// can be undone with `resume`.
+    pub fn suspend(&mut self, reason: String, suspended_by: String) -> Result<(), TransitionError> {
+        let ReleaseState::Deployed { environment, deployment_time } = &self.state else {
+            return Err(TransitionError::InvalidTransition("Only a deployed release can be suspended.".to_string()));
+        };
+        let suspended = ReleaseState::Suspended {
+            reason: reason.clone(),
+            suspended_by: suspended_by.clone(),
+            suspended_at: Utc::now(),
+            environment: environment.clone(),
+            deployment_time: *deployment_time,
+        };
+        self.record_transition_with_reason(self.phase.clone(), suspended, None, Some(reason), Some(suspended_by));
+        Ok(())
+    }
+
+    /// Puts a suspended release back into the deployment it was suspended from.
+    pub fn resume(&mut self, resumed_by: String) -> Result<(), TransitionError> {
+        let ReleaseState::Suspended { environment, deployment_time, .. } = &self.state else {
+            return Err(TransitionError::InvalidTransition("Only a suspended release can be resumed.".to_string()));
+        };
+        let deployed = ReleaseState::Deployed { environment: environment.clone(), deployment_time: *deployment_time };
+        tracing::info!(release_id = %self.id, %resumed_by, "Resuming suspended release");
+        self.record_transition_with_reason(self.phase.clone(), deployed, None, None, Some(resumed_by));
+        Ok(())
+    }
+
+    /// Marks the work running in the current phase as failed. The `complete_*` methods only accept
+    /// `InProgress`, so a failed release stays put until `retry` is called.
+    pub fn fail(&mut self, reason: String) -> Result<(), TransitionError> {
+        if !matches!(self.state, ReleaseState::InProgress { .. } | ReleaseState::PartiallyDeployed { .. }) {
+            return Err(TransitionError::InvalidTransition("Only a release in progress can fail.".to_string()));
+        }
+        let failed = ReleaseState::Failed { reason, failed_at: Utc::now(), phase: self.phase.clone() };
+        self.transition_to(self.phase.clone(), failed);
+        Ok(())
+    }
+
+    /// Starts the failed phase over: the release goes back to `Draft` in the same phase and
+    /// whatever the failed attempt recorded for that phase is dropped.
+    pub fn retry(&mut self) -> Result<(), TransitionError> {
+        let ReleaseState::Failed { phase, .. } = &self.state else {
+            return Err(TransitionError::InvalidTransition("Only a failed release can be retried.".to_string()));
+        };
+        let phase = phase.clone();
+        if let Some(details) = &mut self.phase_details {
+            details.clear(&phase);
+        }
+        self.transition_to(phase, ReleaseState::Draft);
+        Ok(())
+    }
+
+    /// Parks the release in a team-specific state, e.g. `AwaitingLegalSignOff`, within its current
+    /// phase. Every other transition is blocked until `exit_custom_state` is called.
+    pub fn enter_custom_state(&mut self, name: String) -> Result<(), TransitionError> {
+        if matches!(self.state, ReleaseState::Revoked { .. }) {
+            return Err(TransitionError::InvalidTransition("A revoked release cannot enter a custom state.".to_string()));
+        }
+        Self::validate_custom(&name, &self.phase).map_err(TransitionError::InvalidTransition)?;
+        self.record_transition(self.phase.clone(), ReleaseState::Custom(name), Some(TransitionAction::EnterCustomState));
+        Ok(())
+    }
+
+    /// Leaves the custom state the release is in. The release goes back to `Draft` in the same
+    /// phase, so the phase has to be started over. Runtime has no draft, so a running release
+    /// returns to the state it entered the custom state from instead.
+    pub fn exit_custom_state(&mut self, reason: String) -> Result<(), TransitionError> {
+        if !matches!(self.state, ReleaseState::Custom(_)) {
+            return Err(TransitionError::InvalidTransition("Only a release in a custom state can exit it.".to_string()));
+        }
+        let state = if self.phase == SDLCPhase::Runtime {
+            self.snapshots.iter().rev()
+                .map(|snapshot| &snapshot.state)
+                .find(|state| !matches!(state, ReleaseState::Custom(_)))
+                .cloned()
+                .ok_or_else(|| TransitionError::InvalidTransition("No state to return the release to.".to_string()))?
+        } else {
+            ReleaseState::Draft
+        };
+        self.record_transition_with_reason(self.phase.clone(), state, Some(TransitionAction::ExitCustomState), Some(reason), None);
+        Ok(())
+    }
+
+    /// Whether a release may be in the custom state `state_name` during `phase`. Custom states
+    /// are allowed in every phase, so only the name is checked.
+    pub fn validate_custom(state_name: &str, _phase: &SDLCPhase) -> Result<(), String> {
+        if state_name.trim().is_empty() {
+            return Err("Custom state name must not be empty.".to_string());
+        }
+        Ok(())
+    }
+
+    /// Detours the release into a team-specific phase, e.g. `SecurityAudit`, between two built-in
+    /// phases. Only possible while the current phase has not been started yet.
+    pub fn start_custom_phase(&mut self, phase_name: String, started_by: String) -> Result<(), TransitionError> {
+        if !matches!(self.state, ReleaseState::Draft) {
+            return Err(TransitionError::InvalidTransition("Cannot start a custom phase in the current state.".to_string()));
+        }
+        if phase_name.is_empty() {
+            return Err(TransitionError::InvalidTransition("Custom phase name must not be empty.".to_string()));
+        }
+        let started_at = Utc::now();
+        let details = self.phase_details.get_or_insert_with(PhaseDetails::new);
+        details.custom_details.insert(
+            phase_name.clone(),
+            Some(serde_json::json!({ "started_by": started_by, "started_at": started_at })),
+        );
+        self.transition_to(SDLCPhase::Custom(phase_name), ReleaseState::InProgress { started_by, started_at });
+        Ok(())
+    }
+
+    /// Completes the running custom phase and moves on to `next_phase`, which starts out as a
+    /// draft. Deploy and Runtime cannot be entered this way as they need a released artifact.
+    pub fn complete_custom_phase(&mut self, next_phase: SDLCPhase) -> Result<(), TransitionError> {
+        let SDLCPhase::Custom(phase_name) = &self.phase else {
+            return Err(TransitionError::InvalidTransition("Cannot complete a custom phase outside of one.".to_string()));
+        };
+        if !matches!(self.state, ReleaseState::InProgress { .. }) {
+            return Err(TransitionError::InvalidTransition("Cannot complete a custom phase in the current state.".to_string()));
+        }
+        if matches!(next_phase, SDLCPhase::Deploy | SDLCPhase::Runtime) {
+            return Err(TransitionError::InvalidTransition(format!("Cannot move from a custom phase to {}.", next_phase.name())));
+        }
+        if let Some(Some(serde_json::Value::Object(phase_details))) =
+            self.phase_details.as_mut().and_then(|details| details.custom_details.get_mut(phase_name))
+        {
+            phase_details.insert("completed_at".to_string(), serde_json::json!(Utc::now()));
+        }
+        self.transition_to(next_phase, ReleaseState::Draft);
+        Ok(())
+    }
+
+    /// Checks that the details each phase produces are present once the release got that far:
+    /// the feature list once development started, the commit once source review is done, and so on.
+    ///
+    /// The transition methods always fill these in; this catches releases assembled by hand.
+    pub fn validate_phase_completeness(&self) -> Result<(), Vec<ValidationError>> {
+        let Some(current) = phase_order(&self.phase) else {
+            return Ok(());
+        };
+        let started = !matches!(self.state, ReleaseState::Draft);
+        let in_progress = matches!(self.state, ReleaseState::InProgress { .. } | ReleaseState::PartiallyDeployed { .. });
+        let completed = |phase: &SDLCPhase| phase_order(phase).is_some_and(|order| order < current);
+        let details = self.phase_details.as_ref();
+        let mut errors = Vec::new();
+
+        if self.phase == SDLCPhase::Development && started {
+            match details.and_then(|details| details.development_details.as_ref()) {
+                None => errors.push(ValidationError::new("phase_details.development_details", "required once development has started")),
+                Some(development) if development.feature_list.is_empty() => {
+                    errors.push(ValidationError::new("phase_details.development_details.feature_list", "must not be empty"));
+                },
+                Some(_) => {},
+            }
+        }
+        if completed(&SDLCPhase::Source) && self.commit_hash.is_none() {
+            errors.push(ValidationError::new("commit_hash", "required once source review is complete"));
+        }
+        if completed(&SDLCPhase::Build) {
+            match details.and_then(|details| details.build_details.as_ref()) {
+                None => errors.push(ValidationError::new("phase_details.build_details", "required once the build is complete")),
+                Some(build) if build.build_id.is_empty() => {
+                    errors.push(ValidationError::new("phase_details.build_details.build_id", "must not be empty"));
+                },
+                Some(_) => {},
+            }
+        }
+        if completed(&SDLCPhase::Package) {
+            match details.and_then(|details| details.package_details.as_ref()) {
+                None => errors.push(ValidationError::new("phase_details.package_details", "required once packaging is complete")),
+                Some(package) => {
+                    if let Err(err) = url::Url::parse(&package.artifact_url) {
+                        errors.push(ValidationError::PhaseDetail { field: "phase_details.package_details.artifact_url", reason: format!("not a valid URL: {}", err) });
+                    }
+                    if package.artifact_hash.is_empty() {
+                        errors.push(ValidationError::new("phase_details.package_details.artifact_hash", "must not be empty"));
+                    }
+                },
+            }
+        }
+        if (self.phase == SDLCPhase::Deploy && in_progress) || completed(&SDLCPhase::Deploy) {
+            match details.and_then(|details| details.deploy_details.as_ref()) {
+                None => errors.push(ValidationError::new("phase_details.deploy_details", "required once deployment has started")),
+                Some(deploy) if deploy.environment.is_empty() => {
+                    errors.push(ValidationError::new("phase_details.deploy_details.environment", "must not be empty"));
+                },
+                Some(_) => {},
+            }
+        }
+        if self.phase == SDLCPhase::Runtime && details.and_then(|details| details.runtime_details.as_ref()).is_none() {
+            errors.push(ValidationError::new("phase_details.runtime_details", "required in the Runtime phase"));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Every stay of the release in `phase` according to its transition history, as when it entered
+    /// and, unless it is still there, when it left. A release that started out in `phase` entered it
+    /// when it was created.
+    pub fn phase_stays(&self, phase: &SDLCPhase) -> Vec<(DateTime<Utc>, Option<DateTime<Utc>>)> {
+        let mut stays = Vec::new();
+        let mut entered_at = self.transition_history.first().filter(|record| record.from_phase == *phase).map(|_| self.created_at);
+        for record in &self.transition_history {
+            if record.from_phase == record.to_phase {
+                continue;
+            }
+            if record.to_phase == *phase {
+                entered_at = Some(record.timestamp);
+            } else if record.from_phase == *phase {
+                if let Some(entered) = entered_at.take() {
+                    stays.push((entered, Some(record.timestamp)));
+                }
+            }
+        }
+        stays.extend(entered_at.map(|entered| (entered, None)));
+        stays
+    }
+
+    /// How long the release spent in `phase`, from when it first entered it until it last left it.
+    /// A release that was rolled back out of the phase and re-entered it counts the time in between
+    /// too. `None` if the release never entered the phase or never left it.
+    pub fn phase_duration(&self, phase: &SDLCPhase) -> Option<chrono::Duration> {
+        let stays = self.phase_stays(phase);
+        let first_entered_at = stays.first()?.0;
+        let last_left_at = stays.iter().rev().find_map(|(_, left_at)| *left_at)?;
+        Some(last_left_at - first_entered_at)
+    }
+
+    /// `phase_duration` of every phase the release has left, keyed by phase name.
+    pub fn phase_durations(&self) -> HashMap<String, chrono::Duration> {
+        self.transition_history.iter()
+            .map(|record| &record.from_phase)
+            .filter_map(|phase| Some((phase.name().to_string(), self.phase_duration(phase)?)))
+            .collect()
+    }
+
+    /// How long the release took from entering Development until it first entered Deploy,
+    /// including any custom phases on the way. `None` until it reached Deploy.
+    pub fn total_duration_to_deploy(&self) -> Option<chrono::Duration> {
+        let development_entered_at = self.phase_stays(&SDLCPhase::Development).first()?.0;
+        let deploy_entered_at = self.phase_stays(&SDLCPhase::Deploy).first()?.0;
+        Some(deploy_entered_at - development_entered_at)
+    }
+
+    /// Whether `target_phase` comes after the current phase. Never true to or from a custom phase.
+    pub fn can_advance_to(&self, target_phase: &SDLCPhase) -> bool {
+        advances(&self.phase, target_phase)
+    }
+
+    /// Validates the current phase and state, and that no recorded transition other than a
+    /// rollback went back to an earlier phase.
     pub fn validate(&self) -> Result<(), String> {
+        for record in &self.transition_history {
+            let went_back = advances(&record.to_phase, &record.from_phase);
+            if went_back && record.action != Some(TransitionAction::Rollback) {
+                return Err(format!("Transition from {} back to {}.", record.from_phase.name(), record.to_phase.name()));
+            }
+        }
         match (&self.phase, &self.state) {
             (SDLCPhase::Development, ReleaseState::Draft)
             | (SDLCPhase::Development, ReleaseState::InProgress { .. }) => Ok(()),
             (SDLCPhase::Source, ReleaseState::Draft)
             | (SDLCPhase::Source, ReleaseState::InProgress { .. }) => Ok(()),
-            // Add other valid combinations as needed
+            (SDLCPhase::Build, ReleaseState::Draft)
+            | (SDLCPhase::Build, ReleaseState::InProgress { .. }) => Ok(()),
+            (SDLCPhase::Package, ReleaseState::Draft)
+            | (SDLCPhase::Package, ReleaseState::InProgress { .. }) => Ok(()),
+            // Packaging hands over a releasable artifact; deployment starts once it is released.
+            // A retried deployment starts over from Draft.
+            (SDLCPhase::Deploy, ReleaseState::Releasable { .. })
+            | (SDLCPhase::Deploy, ReleaseState::PolicyCheckPending { .. })
+            | (SDLCPhase::Deploy, ReleaseState::Released { .. })
+            | (SDLCPhase::Deploy, ReleaseState::Draft)
+            | (SDLCPhase::Deploy, ReleaseState::InProgress { .. })
+            | (SDLCPhase::Deploy, ReleaseState::PartiallyDeployed { .. }) => Ok(()),
+            (SDLCPhase::Runtime, ReleaseState::Deployed { .. })
+            | (SDLCPhase::Runtime, ReleaseState::Suspended { .. })
+            | (SDLCPhase::Runtime, ReleaseState::Revoked { .. }) => Ok(()),
+            (SDLCPhase::Custom(_), ReleaseState::Draft)
+            | (SDLCPhase::Custom(_), ReleaseState::InProgress { .. }) => Ok(()),
+            (phase, ReleaseState::Failed { phase: failed_phase, .. }) if phase == failed_phase => Ok(()),
+            (phase, ReleaseState::Custom(name)) => Self::validate_custom(name, phase),
             _ => Err("Invalid phase and state combination.".to_string()),
         }
     }
@@ -317,6 +1094,7 @@ impl<Phase: SDLCPhase, State: ReleaseState> SDLCRelease<Phase, State> {
         match &self.component {
             SDLCComponent::Project(project) => &project.name,
             SDLCComponent::Unmanaged(unmanaged) => &unmanaged.name,
+            SDLCComponent::Container(container) => &container.name,
         }
     }
 
@@ -324,6 +1102,7 @@ impl<Phase: SDLCPhase, State: ReleaseState> SDLCRelease<Phase, State> {
         match self.component {
             SDLCComponent::Project(_) => "Project",
             SDLCComponent::Unmanaged(_) => "Unmanaged",
+            SDLCComponent::Container(_) => "Container",
         }
     }
 
@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use sdlccp_api_macro::RegisterSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// How soon a release must be deployed once it is `Released`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, RegisterSchema, ToSchema)]
+pub struct DeploymentSLA {
+    #[schema(value_type = DurationSchema)]
+    pub max_time_to_deploy: Duration,
+    /// Share of `max_time_to_deploy`, in percent, after which an undeployed release is at risk.
+    /// Above 100 it never is.
+    pub notify_at_percent: u8,
+}
+
+/// Where a release stands against its `DeploymentSLA`, see `SDLCRelease::sla_status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, RegisterSchema, ToSchema)]
+pub enum SLAStatus {
+    /// Not released yet, already deployed, without an SLA, or released recently enough.
+    OnTrack,
+    AtRisk { percentage_elapsed: u8 },
+    Breached {
+        #[schema(value_type = DurationSchema)]
+        overdue_by: Duration,
+    },
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::attestation::{Attestation, ClaimSet, Subject, SubjectType};
+
+/// `predicateType` of SLSA provenance v0.2 statements.
+pub const SLSA_PROVENANCE_V02: &str = "https://slsa.dev/provenance/v0.2";
+
+#[derive(Debug, Clone, Default, Serialize)]
+struct Builder {
+    id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigSource {
+    uri: String,
+    digest: HashMap<String, String>,
+    entry_point: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Invocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    config_source: Option<ConfigSource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parameters: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    environment: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Material {
+    uri: String,
+    digest: HashMap<String, String>,
+}
+
+/// Builds an `Attestation` whose claims are a SLSA provenance v0.2 predicate, as produced by a
+/// SLSA Level 2 build service.
+///
+/// The claims hold the predicate fields (`builder`, `buildType`, `invocation`, `buildConfig`,
+/// `materials`) at the top level, next to `predicateType`.
+#[derive(Debug, Clone)]
+pub struct SlsaProvenanceBuilder {
+    subject_name: String,
+    subject_digest: String,
+    builder: Builder,
+    build_type: String,
+    invocation: Invocation,
+    build_config: Option<serde_json::Value>,
+    materials: Vec<Material>,
+}
+
+impl SlsaProvenanceBuilder {
+    /// Starts a provenance for the artifact `name` with the given digest, e.g. `sha256:...`.
+    pub fn new(subject_name: &str, subject_digest: &str) -> Self {
+        Self {
+            subject_name: subject_name.to_string(),
+            subject_digest: subject_digest.to_string(),
+            builder: Builder::default(),
+            build_type: String::new(),
+            invocation: Invocation::default(),
+            build_config: None,
+            materials: Vec::new(),
+        }
+    }
+
+    /// URI identifying the build platform, e.g. `https://github.com/actions/runner`.
+    pub fn with_builder_id(mut self, id: &str) -> Self {
+        self.builder.id = id.to_string();
+        self
+    }
+
+    /// URI describing how the build was run; defines the shape of `buildConfig`.
+    pub fn with_build_type(mut self, build_type: &str) -> Self {
+        self.build_type = build_type.to_string();
+        self
+    }
+
+    /// Records where the build definition came from and which entry point in it was run.
+    pub fn with_invocation(mut self, config_source_uri: &str, entry_point: &str) -> Self {
+        self.invocation.config_source = Some(ConfigSource {
+            uri: config_source_uri.to_string(),
+            digest: HashMap::new(),
+            entry_point: entry_point.to_string(),
+        });
+        self
+    }
+
+    /// Digest of the config source set with `with_invocation`.
+    pub fn with_config_source_digest(mut self, digest: HashMap<String, String>) -> Self {
+        if let Some(config_source) = &mut self.invocation.config_source {
+            config_source.digest = digest;
+        }
+        self
+    }
+
+    pub fn with_parameters(mut self, parameters: serde_json::Value) -> Self {
+        self.invocation.parameters = Some(parameters);
+        self
+    }
+
+    pub fn with_environment(mut self, environment: serde_json::Value) -> Self {
+        self.invocation.environment = Some(environment);
+        self
+    }
+
+    pub fn with_build_config(mut self, build_config: serde_json::Value) -> Self {
+        self.build_config = Some(build_config);
+        self
+    }
+
+    /// Adds an input to the build, with its digests keyed by algorithm, e.g. `sha1` for a Git commit.
+    pub fn add_material(mut self, uri: &str, digest: HashMap<String, String>) -> Self {
+        self.materials.push(Material { uri: uri.to_string(), digest });
+        self
+    }
+
+    pub fn build(self) -> Attestation {
+        let mut claims = ClaimSet::new();
+        claims.set_string("predicateType", SLSA_PROVENANCE_V02.to_string());
+        claims.insert("builder".to_string(), to_value(&self.builder));
+        claims.set_string("buildType", self.build_type);
+        claims.insert("invocation".to_string(), to_value(&self.invocation));
+        if let Some(build_config) = self.build_config {
+            claims.insert("buildConfig".to_string(), build_config);
+        }
+        claims.insert("materials".to_string(), to_value(&self.materials));
+
+        let subject = Subject { type_: SubjectType::Artifact, name: self.subject_name, digest: self.subject_digest };
+        Attestation::new(subject, claims).with_predicate_type(SLSA_PROVENANCE_V02)
+    }
+}
+
+fn to_value(value: &impl Serialize) -> serde_json::Value {
+    // Only plain structs of strings, maps and JSON values are serialized here, which cannot fail.
+    serde_json::to_value(value).expect("provenance fields serialize to JSON")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provenance_matches_slsa_v02_predicate() {
+        let attestation = SlsaProvenanceBuilder::new("app.tar.gz", "sha256:5678")
+            .with_builder_id("https://github.com/actions/runner")
+            .with_build_type("https://github.com/slsa-framework/slsa-github-generator/generic@v1")
+            .with_invocation("git+https://github.com/sdlcsec/app@refs/heads/main", ".github/workflows/release.yml")
+            .with_config_source_digest(HashMap::from([("sha1".to_string(), "abc123".to_string())]))
+            .with_build_config(serde_json::json!({ "steps": [{ "command": ["make", "release"] }] }))
+            .add_material("git+https://github.com/sdlcsec/app", HashMap::from([("sha1".to_string(), "abc123".to_string())]))
+            .build();
+
+        assert!(matches!(attestation.subject.type_, SubjectType::Artifact));
+        assert_eq!(attestation.subject.name, "app.tar.gz");
+        assert_eq!(attestation.subject.digest, "sha256:5678");
+        assert_eq!(attestation.predicate_type.as_deref(), Some(SLSA_PROVENANCE_V02));
+
+        let json = serde_json::to_value(&attestation).unwrap();
+        let claims = &json["claims"];
+        assert_eq!(claims["predicateType"], SLSA_PROVENANCE_V02);
+        assert_eq!(claims["builder"], serde_json::json!({ "id": "https://github.com/actions/runner" }));
+        assert_eq!(claims["buildType"], "https://github.com/slsa-framework/slsa-github-generator/generic@v1");
+        assert_eq!(
+            claims["invocation"],
+            serde_json::json!({
+                "configSource": {
+                    "uri": "git+https://github.com/sdlcsec/app@refs/heads/main",
+                    "digest": { "sha1": "abc123" },
+                    "entryPoint": ".github/workflows/release.yml",
+                },
+            })
+        );
+        assert_eq!(claims["buildConfig"]["steps"][0]["command"][1], "release");
+        assert_eq!(
+            claims["materials"],
+            serde_json::json!([{ "uri": "git+https://github.com/sdlcsec/app", "digest": { "sha1": "abc123" } }])
+        );
+    }
+}
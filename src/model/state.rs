@@ -4,15 +4,38 @@ use sdlccp_api_macro::RegisterSchema;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use super::phase::SDLCPhase;
+
 /// Represents the state of a release within a phase.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, RegisterSchema, ToSchema, PartialEq)]
 pub enum ReleaseState {
     Draft,
     InProgress { started_by: String, started_at: DateTime<Utc> },
+    /// Waiting for the sign-off requested with `SDLCRelease::request_approval`.
+    PolicyCheckPending { requested_by: String, requested_at: DateTime<Utc> },
+    /// `approved_by` lists every approver, comma separated.
     Releasable { approved_by: String, approved_at: DateTime<Utc> },
+    /// `release_notes` is a copy of `SDLCRelease::release_notes` as released, which outlives this state.
     Released { release_notes: String, release_time: DateTime<Utc> },
+    /// A canary deployment serving `percentage` of the traffic.
+    PartiallyDeployed { percentage: u8, environment: String },
     Deployed { environment: String, deployment_time: DateTime<Utc> },
     Revoked { reason: String, revocation_time: DateTime<Utc> },
+    /// A deployed release paused by operations. Keeps the deployment it was suspended from so
+    /// `SDLCRelease::resume` can restore it.
+    Suspended {
+        reason: String,
+        suspended_by: String,
+        suspended_at: DateTime<Utc>,
+        environment: String,
+        deployment_time: DateTime<Utc>,
+    },
+    /// Work in `phase` failed, e.g. a broken build. `SDLCRelease::retry` starts the phase over.
+    Failed {
+        reason: String,
+        failed_at: DateTime<Utc>,
+        phase: SDLCPhase,
+    },
     Custom(String),
 }
 
@@ -21,10 +44,14 @@ impl ReleaseState {
         match self {
             ReleaseState::Draft => "Draft",
             ReleaseState::InProgress { .. } => "InProgress",
+            ReleaseState::PolicyCheckPending { .. } => "PolicyCheckPending",
             ReleaseState::Releasable { .. } => "Releasable",
             ReleaseState::Released { .. } => "Released",
+            ReleaseState::PartiallyDeployed { .. } => "PartiallyDeployed",
             ReleaseState::Deployed { .. } => "Deployed",
             ReleaseState::Revoked { .. } => "Revoked",
+            ReleaseState::Suspended { .. } => "Suspended",
+            ReleaseState::Failed { .. } => "Failed",
             ReleaseState::Custom(name) => name,
         }
     }
@@ -0,0 +1,278 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use schemars::JsonSchema;
+use sdlccp_api_macro::RegisterSchema;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::model::{MergeError, TagError, ValidationError};
+
+use super::{
+    attestation::AttestationError, audit::AuditError, auth::AuthError, compliance::BaselineError,
+    component_repository::ComponentRepositoryError, controlplane::ControlPlaneError, export::ExportError,
+    namespace::NamespaceError, policy_repository::PolicyRepositoryError,
+    release_repository::ReleaseRepositoryError, request_id::RequestId, team_repository::TeamRepositoryError,
+    transitions::TransitionError, webhook::WebhookError,
+};
+
+/// The JSON body of every error response.
+///
+/// `code` is a stable, machine-readable identifier; `message` is meant for humans and may change.
+/// `request_id` is filled in by `RequestIdLayer` when the router is served behind it.
+#[derive(Debug, Clone, Serialize, JsonSchema, ToSchema, RegisterSchema)]
+pub struct ApiError {
+    #[serde(skip)]
+    status: StatusCode,
+    pub code: &'static str,
+    pub message: String,
+    pub request_id: Option<String>,
+    #[schema(value_type = Option<Object>)]
+    pub details: Option<serde_json::Value>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self { status, code, message: message.into(), request_id: None, details: None }
+    }
+
+    pub fn not_found(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, code, message)
+    }
+
+    pub fn internal(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, code, message)
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn with_request_id(mut self, request_id: &RequestId) -> Self {
+        self.request_id = Some(request_id.to_string());
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        // A copy travels with the response so `RequestIdLayer` can add the request ID to the body.
+        let mut response = (self.status, Json(&self)).into_response();
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+impl From<PolicyRepositoryError> for ApiError {
+    fn from(err: PolicyRepositoryError) -> Self {
+        match err {
+            PolicyRepositoryError::StorageError(_) => Self::internal("policy_storage_failed", err.to_string()),
+            PolicyRepositoryError::RetrievalError(_) => Self::internal("policy_retrieval_failed", err.to_string()),
+            PolicyRepositoryError::NotFound(_) => Self::not_found("policy_not_found", err.to_string()),
+            PolicyRepositoryError::HasDependents(ref release_ids) => {
+                let details = serde_json::json!({ "release_ids": release_ids });
+                Self::new(StatusCode::CONFLICT, "policy_has_dependents", err.to_string()).with_details(details)
+            }
+        }
+    }
+}
+
+impl From<AttestationError> for ApiError {
+    fn from(err: AttestationError) -> Self {
+        match err {
+            AttestationError::StorageError(_) => Self::internal("attestation_storage_failed", err.to_string()),
+            AttestationError::RetrievalError(_) => Self::internal("attestation_retrieval_failed", err.to_string()),
+            AttestationError::VerificationError(_) => Self::new(StatusCode::BAD_REQUEST, "attestation_verification_failed", err.to_string()),
+            AttestationError::NotFound(_) => Self::not_found("attestation_not_found", err.to_string()),
+            AttestationError::InvalidParentAttestation(_) => Self::new(StatusCode::BAD_REQUEST, "invalid_parent_attestation", err.to_string()),
+            AttestationError::CyclicChain(_) => Self::new(StatusCode::UNPROCESSABLE_ENTITY, "cyclic_attestation_chain", err.to_string()),
+            AttestationError::Revoked(_) => Self::new(StatusCode::CONFLICT, "attestation_revoked", err.to_string()),
+            AttestationError::Duplicate { existing_id } => {
+                Self::new(StatusCode::CONFLICT, "duplicate_attestation", err.to_string()).with_details(serde_json::json!({ "existing_id": existing_id }))
+            }
+            AttestationError::InvalidClaims(ref errors) => {
+                let details = serde_json::json!({ "claims": errors });
+                Self::new(StatusCode::BAD_REQUEST, "invalid_claims", err.to_string()).with_details(details)
+            }
+        }
+    }
+}
+
+impl From<ControlPlaneError> for ApiError {
+    fn from(err: ControlPlaneError) -> Self {
+        match err {
+            ControlPlaneError::PolicyRepositoryError(_) => Self::internal("policy_repository_error", err.to_string()),
+            ControlPlaneError::AttestationStorageError(_) => Self::internal("attestation_storage_failed", err.to_string()),
+            ControlPlaneError::NoPolicyFound => Self::not_found("no_policy_found", err.to_string()),
+            ControlPlaneError::ReleaseNotFound => Self::not_found("release_not_found", err.to_string()),
+            ControlPlaneError::ReleaseRepositoryError(_) => Self::internal("release_retrieval_failed", err.to_string()),
+            ControlPlaneError::ComponentRepositoryError(_) => Self::internal("component_retrieval_failed", err.to_string()),
+            ControlPlaneError::PolicyEnforcementError(_) => Self::new(StatusCode::UNPROCESSABLE_ENTITY, "invalid_policy", err.to_string()),
+        }
+    }
+}
+
+impl From<BaselineError> for ApiError {
+    fn from(err: BaselineError) -> Self {
+        match err {
+            BaselineError::ReleaseNotFound => Self::not_found("release_not_found", err.to_string()),
+            BaselineError::NoBaseline(_) => Self::not_found("compliance_baseline_not_found", err.to_string()),
+            BaselineError::EvaluationFailed(_) => Self::internal("compliance_evaluation_failed", err.to_string()),
+        }
+    }
+}
+
+impl From<NamespaceError> for ApiError {
+    fn from(err: NamespaceError) -> Self {
+        let (status, code) = match err {
+            NamespaceError::NotFound => (StatusCode::NOT_FOUND, "namespace_not_found"),
+            NamespaceError::AlreadyExists => (StatusCode::CONFLICT, "namespace_already_exists"),
+            NamespaceError::InvalidPath => (StatusCode::BAD_REQUEST, "invalid_namespace_path"),
+            NamespaceError::PermissionDenied => (StatusCode::FORBIDDEN, "permission_denied"),
+            NamespaceError::PersistenceError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "namespace_persistence_failed"),
+            NamespaceError::QuotaExceeded { limit, actual, quota_type } => {
+                let details = serde_json::json!({ "limit": limit, "actual": actual, "quota_type": quota_type });
+                return Self::new(StatusCode::UNPROCESSABLE_ENTITY, "namespace_quota_exceeded", err.to_string()).with_details(details);
+            },
+        };
+        Self::new(status, code, err.to_string())
+    }
+}
+
+impl From<TransitionError> for ApiError {
+    fn from(err: TransitionError) -> Self {
+        match err {
+            TransitionError::ReleaseNotFound => Self::not_found("release_not_found", err.to_string()),
+            TransitionError::InvalidTransition(_) => Self::new(StatusCode::CONFLICT, "invalid_transition", err.to_string()),
+            TransitionError::StorageError(_) => Self::internal("release_storage_failed", err.to_string()),
+            TransitionError::FeatureListError(ref reason) => {
+                let details = serde_json::to_value(reason).unwrap_or_default();
+                Self::new(StatusCode::UNPROCESSABLE_ENTITY, "invalid_feature_list", err.to_string()).with_details(details)
+            },
+        }
+    }
+}
+
+impl From<ReleaseRepositoryError> for ApiError {
+    fn from(err: ReleaseRepositoryError) -> Self {
+        match err {
+            ReleaseRepositoryError::StorageError(_) => Self::internal("release_storage_failed", err.to_string()),
+            ReleaseRepositoryError::RetrievalError(_) => Self::internal("release_retrieval_failed", err.to_string()),
+            ReleaseRepositoryError::NotFound => Self::not_found("release_not_found", err.to_string()),
+            ReleaseRepositoryError::RevisionMismatch { expected, actual } => {
+                let details = serde_json::json!({ "expected": expected, "actual": actual });
+                Self::new(StatusCode::PRECONDITION_FAILED, "revision_mismatch", err.to_string()).with_details(details)
+            },
+        }
+    }
+}
+
+impl From<ComponentRepositoryError> for ApiError {
+    fn from(err: ComponentRepositoryError) -> Self {
+        match err {
+            ComponentRepositoryError::StorageError(_) => Self::internal("component_storage_failed", err.to_string()),
+            ComponentRepositoryError::RetrievalError(_) => Self::internal("component_retrieval_failed", err.to_string()),
+            ComponentRepositoryError::NotFound => Self::not_found("component_not_found", err.to_string()),
+        }
+    }
+}
+
+impl From<TeamRepositoryError> for ApiError {
+    fn from(err: TeamRepositoryError) -> Self {
+        match err {
+            TeamRepositoryError::StorageError(_) => Self::internal("team_storage_failed", err.to_string()),
+            TeamRepositoryError::RetrievalError(_) => Self::internal("team_retrieval_failed", err.to_string()),
+            TeamRepositoryError::NotFound => Self::not_found("team_not_found", err.to_string()),
+        }
+    }
+}
+
+impl From<WebhookError> for ApiError {
+    fn from(err: WebhookError) -> Self {
+        match err {
+            WebhookError::StorageError(_) => Self::internal("webhook_storage_failed", err.to_string()),
+            WebhookError::RetrievalError(_) => Self::internal("webhook_retrieval_failed", err.to_string()),
+            WebhookError::InvalidUrl(_) => Self::new(StatusCode::BAD_REQUEST, "invalid_webhook_url", err.to_string()),
+            WebhookError::NotFound => Self::not_found("webhook_not_found", err.to_string()),
+        }
+    }
+}
+
+impl From<ExportError> for ApiError {
+    fn from(err: ExportError) -> Self {
+        match err {
+            ExportError::NamespaceNotFound => Self::not_found("namespace_not_found", err.to_string()),
+            ExportError::InvalidArchive(_) => Self::new(StatusCode::BAD_REQUEST, "invalid_export_archive", err.to_string()),
+            ExportError::Conflict { .. } => Self::new(StatusCode::CONFLICT, "import_conflict", err.to_string()),
+            ExportError::RetrievalError(_) => Self::internal("export_retrieval_failed", err.to_string()),
+        }
+    }
+}
+
+impl From<AuditError> for ApiError {
+    fn from(err: AuditError) -> Self {
+        let code = match err {
+            AuditError::StorageError(_) => "audit_storage_failed",
+            AuditError::RetrievalError(_) => "audit_retrieval_failed",
+        };
+        Self::internal(code, err.to_string())
+    }
+}
+
+impl From<TagError> for ApiError {
+    fn from(err: TagError) -> Self {
+        let TagError::InvalidTag(tag) = &err;
+        let details = serde_json::json!({ "tag": tag });
+        Self::new(StatusCode::BAD_REQUEST, "invalid_tag", err.to_string()).with_details(details)
+    }
+}
+
+impl From<ValidationError> for ApiError {
+    fn from(err: ValidationError) -> Self {
+        match err {
+            ValidationError::InvalidSemver(ref version) => {
+                let details = serde_json::json!({ "version": version });
+                Self::new(StatusCode::BAD_REQUEST, "invalid_version", err.to_string()).with_details(details)
+            },
+            ValidationError::PhaseDetail { .. } => {
+                let details = serde_json::json!(&err);
+                Self::new(StatusCode::UNPROCESSABLE_ENTITY, "incomplete_phase_details", err.to_string()).with_details(details)
+            },
+        }
+    }
+}
+
+impl From<MergeError> for ApiError {
+    fn from(err: MergeError) -> Self {
+        match err {
+            MergeError::IncompatibleTypes { ours, theirs } => {
+                let details = serde_json::json!({ "ours": ours, "theirs": theirs });
+                Self::new(StatusCode::UNPROCESSABLE_ENTITY, "incompatible_component_types", err.to_string()).with_details(details)
+            },
+            MergeError::Conflicts(ref conflicts) => {
+                let details = serde_json::json!({ "conflicts": conflicts });
+                Self::new(StatusCode::CONFLICT, "merge_conflicts", err.to_string()).with_details(details)
+            },
+        }
+    }
+}
+
+impl From<AuthError> for ApiError {
+    fn from(err: AuthError) -> Self {
+        match err {
+            AuthError::MissingToken => Self::new(StatusCode::UNAUTHORIZED, "missing_token", err.to_string()),
+            AuthError::InvalidToken(_) => Self::new(StatusCode::UNAUTHORIZED, "invalid_token", err.to_string()),
+            AuthError::MissingRole(ref role) => {
+                let details = serde_json::json!({ "role": role });
+                Self::new(StatusCode::FORBIDDEN, "missing_role", err.to_string()).with_details(details)
+            },
+        }
+    }
+}
@@ -1,21 +1,132 @@
+use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
-use utoipa::ToSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
-use crate::model::attestation::Attestation;
+use crate::model::{attestation::{claims, Attestation, AttestationStatus, ClaimValidationError, SubjectKind}, claim_schema::ClaimSchemaRegistry, SDLCRelease};
+use super::metrics::record_attestation_stored;
 use async_trait::async_trait;
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::RwLock;
 
+/// Criteria for listing attestations. All set fields must match.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AttestationFilter {
+    /// Only return attestations about subjects of this kind.
+    #[param(value_type = Option<SubjectKind>)]
+    pub subject_type: Option<SubjectKind>,
+    /// Only return attestations signed by this identity.
+    pub signer: Option<String>,
+    /// Only return attestations made at or after this time.
+    pub from: Option<DateTime<Utc>>,
+    /// Only return attestations made at or before this time.
+    pub to: Option<DateTime<Utc>>,
+    /// Only return attestations carrying this claim.
+    pub has_claim_key: Option<String>,
+}
+
+impl AttestationFilter {
+    pub fn matches(&self, attestation: &Attestation) -> bool {
+        self.subject_type.is_none_or(|kind| attestation.subject.type_.kind() == kind)
+            && self.signer.as_ref().is_none_or(|signer| attestation.signatures.iter().any(|sig| &sig.signer == signer))
+            && self.from.is_none_or(|from| attestation.timestamp >= from)
+            && self.to.is_none_or(|to| attestation.timestamp <= to)
+            && self.has_claim_key.as_ref().is_none_or(|key| attestation.claims.contains_key(key))
+    }
+}
+
 #[async_trait]
 pub trait AttestationService: Send + Sync {
     async fn store_attestation(&self, attestation: Attestation) -> Result<(), AttestationError>;
+    /// The attestation whether or not it was revoked; check `Attestation::status` before relying
+    /// on it, or use `get_active_attestation`.
     async fn get_attestation(&self, id: &Uuid) -> Result<Option<Attestation>, AttestationError>;
+    /// Attestations recorded for the release, see `Attestation::release_id`.
     async fn get_attestations_for_release(&self, release_id: &Uuid) -> Result<Vec<Attestation>, AttestationError>;
+    /// Attestations recorded for `release` or whose subject is part of it, see
+    /// `Attestation::concerns_release`.
+    async fn get_attestations_concerning_release(&self, release: &SDLCRelease) -> Result<Vec<Attestation>, AttestationError>;
+    async fn list_attestations(&self, filter: AttestationFilter) -> Result<Vec<Attestation>, AttestationError>;
+    /// Fails with `AttestationError::Revoked` if the attestation, or its stored version, is revoked.
     async fn verify_attestation(&self, attestation: &Attestation) -> Result<bool, AttestationError>;
+    /// Marks the attestation as revoked. It stays stored, but no longer verifies.
+    async fn revoke_attestation(&self, id: &Uuid, reason: String, revoked_by: String) -> Result<(), AttestationError>;
+    /// How the removal of expired attestations went so far.
+    async fn cleanup_stats(&self) -> Result<CleanupStats, AttestationError>;
+    /// The predicate types whose claims are validated against a schema when stored, sorted.
+    async fn predicate_types(&self) -> Result<Vec<String>, AttestationError>;
+
+    /// The attestation whose `Attestation::content_hash` is `hash`, if one is stored.
+    async fn find_by_content_hash(&self, hash: &str) -> Result<Option<Attestation>, AttestationError>;
+
+    /// The attestation, unless it was revoked.
+    async fn get_active_attestation(&self, id: &Uuid) -> Result<Option<Attestation>, AttestationError> {
+        Ok(self.get_attestation(id).await?.filter(|attestation| !attestation.is_revoked()))
+    }
+
+    /// The attestation `id` followed by its ancestors, nearest first, following
+    /// `parent_attestations` at most `max_depth` levels up. An ancestor shared by several parents
+    /// is listed once; a chain leading back to itself is an error.
+    async fn resolve_chain(&self, id: &Uuid, max_depth: usize) -> Result<Vec<Attestation>, AttestationError> {
+        let attestation = self.get_attestation(id).await?.ok_or(AttestationError::NotFound(*id))?;
+        let mut parents = HashMap::from([(attestation.id, attestation.parent_attestations.clone())]);
+        let mut level = attestation.parent_attestations.clone();
+        let mut chain = vec![attestation];
+        for _ in 0..max_depth {
+            let mut next_level = Vec::new();
+            for parent_id in level {
+                if parents.contains_key(&parent_id) {
+                    continue;
+                }
+                let parent = self.get_attestation(&parent_id).await?.ok_or(AttestationError::NotFound(parent_id))?;
+                parents.insert(parent.id, parent.parent_attestations.clone());
+                next_level.extend_from_slice(&parent.parent_attestations);
+                chain.push(parent);
+            }
+            level = next_level;
+        }
+        if let Some(cycle) = find_cycle(id, &parents, &mut Vec::new(), &mut HashSet::new()) {
+            return Err(AttestationError::CyclicChain(cycle));
+        }
+        Ok(chain)
+    }
 }
 
-#[derive(Debug, thiserror::Error, JsonSchema, ToSchema)]
+/// Walks the parents of `id` depth first. Returns the IDs around the first cycle found, starting
+/// and ending with the same ID. Attestations missing from `parents` lie beyond the depth limit.
+fn find_cycle(id: &Uuid, parents: &HashMap<Uuid, Vec<Uuid>>, path: &mut Vec<Uuid>, done: &mut HashSet<Uuid>) -> Option<Vec<Uuid>> {
+    if let Some(start) = path.iter().position(|ancestor| ancestor == id) {
+        let mut cycle = path[start..].to_vec();
+        cycle.push(*id);
+        return Some(cycle);
+    }
+    if done.contains(id) {
+        return None;
+    }
+    let parent_ids = parents.get(id)?;
+    path.push(*id);
+    for parent_id in parent_ids {
+        if let Some(cycle) = find_cycle(parent_id, parents, path, done) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+    done.insert(*id);
+    None
+}
+
+/// Totals kept by `InMemoryAttestationService::prune_expired`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct CleanupStats {
+    /// When expired attestations were last pruned, if ever.
+    pub last_run: Option<DateTime<Utc>>,
+    pub total_pruned: u64,
+}
+
+#[derive(Debug, Serialize, thiserror::Error, JsonSchema, ToSchema)]
 pub enum AttestationError {
     #[error("Failed to store attestation: {0}")]
     StorageError(String),
@@ -23,44 +134,376 @@ pub enum AttestationError {
     RetrievalError(String),
     #[error("Failed to verify attestation: {0}")]
     VerificationError(String),
+    #[error("Attestation {0} not found")]
+    NotFound(Uuid),
+    #[error("Invalid parent attestation ID {0:?}")]
+    InvalidParentAttestation(String),
+    #[error("Attestation chain is cyclic: {0:?}")]
+    CyclicChain(Vec<Uuid>),
+    /// Claims that do not match the schema of their predicate type, or well-known claims that
+    /// fail their validator.
+    #[error("Invalid claims: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    InvalidClaims(Vec<ClaimValidationError>),
+    #[error("Attestation {0} is revoked")]
+    Revoked(Uuid),
+    #[error("An attestation with the same subject and claims is already stored as {existing_id}")]
+    Duplicate { existing_id: Uuid },
 }
 
-// Example in-memory implementation for testing
+// Example in-memory implementation for testing. Unless `deduplicate` is turned off, an
+// attestation with the subject and claims of an active stored one is rejected as a duplicate.
+// Claims are validated against the built-in schemas unless others are set with `with_claim_schemas`,
+// and well-known claims against their validators in `claims::WELL_KNOWN`.
+#[derive(Clone)]
 pub struct InMemoryAttestationService {
     attestations: Arc<RwLock<HashMap<Uuid, Attestation>>>,
+    /// The ID of the attestation stored for each content hash. Always locked after `attestations`.
+    content_index: Arc<RwLock<HashMap<String, Uuid>>>,
+    deduplicate: bool,
+    cleanup_stats: Arc<Mutex<CleanupStats>>,
+    claim_schemas: Arc<ClaimSchemaRegistry>,
+}
+
+impl Default for InMemoryAttestationService {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl InMemoryAttestationService {
     pub fn new() -> Self {
         Self {
             attestations: Arc::new(RwLock::new(HashMap::new())),
+            content_index: Arc::new(RwLock::new(HashMap::new())),
+            deduplicate: true,
+            cleanup_stats: Arc::new(Mutex::new(CleanupStats::default())),
+            claim_schemas: Arc::new(ClaimSchemaRegistry::with_builtin_schemas()),
         }
     }
+
+    /// Turns the rejection of duplicates on or off. It is on by default.
+    pub fn with_deduplication(mut self, deduplicate: bool) -> Self {
+        self.deduplicate = deduplicate;
+        self
+    }
+
+    /// Validates the claims of stored attestations against `claim_schemas` instead of the
+    /// built-in schemas.
+    pub fn with_claim_schemas(mut self, claim_schemas: ClaimSchemaRegistry) -> Self {
+        self.claim_schemas = Arc::new(claim_schemas);
+        self
+    }
+
+    /// Removes every attestation whose expiration has passed and returns how many were removed.
+    /// Attestations without an expiration are kept.
+    #[tracing::instrument(skip(self))]
+    pub async fn prune_expired(&self) -> usize {
+        let now = Utc::now();
+        let mut attestations = self.attestations.write().await;
+        let before = attestations.len();
+        attestations.retain(|_, attestation| attestation.expiration.is_none_or(|expiration| expiration >= now));
+        let pruned = before - attestations.len();
+        *self.content_index.write().await = attestations.values().map(|attestation| (attestation.content_hash(), attestation.id)).collect();
+        let mut stats = self.cleanup_stats.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        stats.last_run = Some(now);
+        stats.total_pruned += pruned as u64;
+        tracing::info!(pruned, "Pruned expired attestations");
+        pruned
+    }
+}
+
+/// Spawns a task that prunes the expired attestations of `service` every `interval`, starting
+/// one interval from now. Abort the returned handle to stop it.
+pub fn start_cleanup_task(service: Arc<InMemoryAttestationService>, interval: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(interval);
+        // The first tick fires immediately; give freshly stored attestations a full interval.
+        ticks.tick().await;
+        loop {
+            ticks.tick().await;
+            service.prune_expired().await;
+        }
+    })
 }
 
 #[async_trait]
 impl AttestationService for InMemoryAttestationService {
+    #[tracing::instrument(skip_all, fields(attestation_id = %attestation.id))]
     async fn store_attestation(&self, attestation: Attestation) -> Result<(), AttestationError> {
+        if let Some(predicate_type) = &attestation.predicate_type {
+            let errors = self.claim_schemas.validate_claims(predicate_type, &attestation.claims);
+            if !errors.is_empty() {
+                return Err(AttestationError::InvalidClaims(errors));
+            }
+        }
+        let errors = attestation.validate_claims(claims::WELL_KNOWN);
+        if !errors.is_empty() {
+            return Err(AttestationError::InvalidClaims(errors));
+        }
         let mut attestations = self.attestations.write().await;
+        let mut content_index = self.content_index.write().await;
+        let hash = attestation.content_hash();
+        if self.deduplicate {
+            // Storing an attestation again replaces it, and a revoked attestation may be reissued.
+            let existing = content_index.get(&hash).and_then(|id| attestations.get(id));
+            if let Some(existing) = existing.filter(|existing| existing.id != attestation.id && !existing.is_revoked()) {
+                return Err(AttestationError::Duplicate { existing_id: existing.id });
+            }
+        }
+        if let Some(previous) = attestations.get(&attestation.id) {
+            content_index.remove(&previous.content_hash());
+        }
+        tracing::info!(subject = %attestation.subject.name, "Stored attestation");
+        content_index.insert(hash, attestation.id);
         attestations.insert(attestation.id, attestation);
+        record_attestation_stored();
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(attestation_id = %id))]
     async fn get_attestation(&self, id: &Uuid) -> Result<Option<Attestation>, AttestationError> {
         let attestations = self.attestations.read().await;
         Ok(attestations.get(id).cloned())
     }
 
+    #[tracing::instrument(skip(self))]
+    async fn find_by_content_hash(&self, hash: &str) -> Result<Option<Attestation>, AttestationError> {
+        let attestations = self.attestations.read().await;
+        let content_index = self.content_index.read().await;
+        Ok(content_index.get(hash).and_then(|id| attestations.get(id)).cloned())
+    }
+
+    #[tracing::instrument(skip(self))]
     async fn get_attestations_for_release(&self, release_id: &Uuid) -> Result<Vec<Attestation>, AttestationError> {
         let attestations = self.attestations.read().await;
         Ok(attestations.values()
-            .filter(|att| att.subject.name == release_id.to_string())
+            .filter(|att| att.release_id.as_ref() == Some(release_id))
+            .cloned()
+            .collect())
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %release.id))]
+    async fn get_attestations_concerning_release(&self, release: &SDLCRelease) -> Result<Vec<Attestation>, AttestationError> {
+        let attestations = self.attestations.read().await;
+        Ok(attestations.values()
+            .filter(|att| att.concerns_release(release))
+            .cloned()
+            .collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_attestations(&self, filter: AttestationFilter) -> Result<Vec<Attestation>, AttestationError> {
+        let attestations = self.attestations.read().await;
+        Ok(attestations.values()
+            .filter(|att| filter.matches(att))
             .cloned()
             .collect())
     }
 
-    async fn verify_attestation(&self, _attestation: &Attestation) -> Result<bool, AttestationError> {
+    #[tracing::instrument(skip_all, fields(attestation_id = %attestation.id))]
+    async fn verify_attestation(&self, attestation: &Attestation) -> Result<bool, AttestationError> {
+        // The caller's copy may predate the revocation.
+        let stored_revoked = self.attestations.read().await.get(&attestation.id).is_some_and(Attestation::is_revoked);
+        if attestation.is_revoked() || stored_revoked {
+            return Err(AttestationError::Revoked(attestation.id));
+        }
         // TODO: Implement verification logic
         Ok(true)
     }
+
+    #[tracing::instrument(skip(self, reason))]
+    async fn revoke_attestation(&self, id: &Uuid, reason: String, revoked_by: String) -> Result<(), AttestationError> {
+        let mut attestations = self.attestations.write().await;
+        let attestation = attestations.get_mut(id).ok_or(AttestationError::NotFound(*id))?;
+        if attestation.is_revoked() {
+            return Err(AttestationError::Revoked(*id));
+        }
+        attestation.status = AttestationStatus::Revoked { reason, revoked_at: Utc::now(), revoked_by };
+        tracing::info!("Revoked attestation");
+        Ok(())
+    }
+
+    async fn cleanup_stats(&self) -> Result<CleanupStats, AttestationError> {
+        Ok(self.cleanup_stats.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone())
+    }
+
+    async fn predicate_types(&self) -> Result<Vec<String>, AttestationError> {
+        Ok(self.claim_schemas.predicate_types())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::contract_tests::define_service_contract_tests;
+    use crate::model::attestation::{ClaimSet, SBOMFormat, Subject, SubjectType};
+    use crate::model::phase::PackageDetails;
+
+    fn attestation(type_: SubjectType, name: &str, digest: &str) -> Attestation {
+        Attestation::new(Subject { type_, name: name.to_string(), digest: digest.to_string() }, ClaimSet::new())
+    }
+
+    #[tokio::test]
+    async fn test_get_attestations_concerning_release_matches_every_subject_type() {
+        let service = InMemoryAttestationService::new();
+        let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        release.commit_hash = Some("abc123".to_string());
+        if let Some(details) = &mut release.phase_details {
+            details.package_details = Some(PackageDetails {
+                artifact_hash: "sha256:feed".to_string(),
+                artifact_url: "https://example.com/app.tar.gz".to_string(),
+                sbom: None,
+            });
+        }
+
+        let matching = [
+            attestation(
+                SubjectType::SBOMDocument { format: SBOMFormat::CycloneDX14, document_url: "https://example.com/sbom.json".to_string() },
+                "sbom.json",
+                "sha256:0bad",
+            )
+            .for_release(release.id),
+            attestation(SubjectType::Commit, "app", "abc123"),
+            attestation(SubjectType::Artifact, "app.tar.gz", "sha256:feed"),
+            attestation(
+                SubjectType::Container {
+                    registry: "ghcr.io".to_string(),
+                    repository: "sdlcsec/app".to_string(),
+                    digest: "sha256:feed".to_string(),
+                },
+                "ghcr.io/sdlcsec/app",
+                "",
+            ),
+            attestation(
+                SubjectType::GitTag {
+                    repository_url: "https://github.com/sdlcsec/app".to_string(),
+                    tag_name: "v1.0.0".to_string(),
+                    commit_hash: "abc123".to_string(),
+                },
+                "v1.0.0",
+                "",
+            ),
+        ];
+        let unrelated = [
+            attestation(SubjectType::Commit, "app", "def456"),
+            // Naming the subject after the release does not link it.
+            attestation(SubjectType::Deployment, &release.id.to_string(), ""),
+            // The container digest wins over the redundant subject digest.
+            attestation(
+                SubjectType::Container {
+                    registry: "ghcr.io".to_string(),
+                    repository: "sdlcsec/app".to_string(),
+                    digest: "sha256:beef".to_string(),
+                },
+                "ghcr.io/sdlcsec/app",
+                "sha256:feed",
+            ),
+        ];
+        for attestation in matching.iter().chain(&unrelated) {
+            service.store_attestation(attestation.clone()).await.unwrap();
+        }
+
+        let mut found: Vec<Uuid> = service.get_attestations_concerning_release(&release).await.unwrap().iter().map(|att| att.id).collect();
+        let mut expected: Vec<Uuid> = matching.iter().map(|att| att.id).collect();
+        found.sort();
+        expected.sort();
+        assert_eq!(found, expected);
+        let recorded = service.get_attestations_for_release(&release.id).await.unwrap();
+        assert_eq!(recorded.iter().map(|att| att.id).collect::<Vec<_>>(), vec![matching[0].id]);
+    }
+
+    /// Stores an attestation with the given parents and returns its ID.
+    async fn store_with_parents(service: &InMemoryAttestationService, parent_attestations: Vec<Uuid>) -> Uuid {
+        // A distinct digest each, so the attestations are no duplicates of each other.
+        let mut attestation = attestation(SubjectType::Artifact, "app.tar.gz", &format!("sha256:{}", Uuid::new_v4().simple()));
+        attestation.parent_attestations = parent_attestations;
+        let id = attestation.id;
+        service.store_attestation(attestation).await.unwrap();
+        id
+    }
+
+    fn ids(chain: &[Attestation]) -> Vec<Uuid> {
+        chain.iter().map(|attestation| attestation.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_revoked_attestations_are_kept_but_no_longer_verify() {
+        let service = InMemoryAttestationService::new();
+        let stored = attestation(SubjectType::Artifact, "app.tar.gz", "sha256:feed");
+        service.store_attestation(stored.clone()).await.unwrap();
+        assert!(service.verify_attestation(&stored).await.unwrap());
+
+        service.revoke_attestation(&stored.id, "Signing key leaked".to_string(), "security@example.com".to_string()).await.unwrap();
+        let revoked = service.get_attestation(&stored.id).await.unwrap().unwrap();
+        assert!(matches!(
+            &revoked.status,
+            AttestationStatus::Revoked { reason, revoked_by, .. } if reason == "Signing key leaked" && revoked_by == "security@example.com"
+        ));
+        assert!(service.get_active_attestation(&stored.id).await.unwrap().is_none());
+        // Copies taken before the revocation do not verify either.
+        assert!(matches!(service.verify_attestation(&stored).await, Err(AttestationError::Revoked(id)) if id == stored.id));
+        assert!(matches!(service.verify_attestation(&revoked).await, Err(AttestationError::Revoked(_))));
+
+        assert!(matches!(
+            service.revoke_attestation(&stored.id, "Again".to_string(), "security@example.com".to_string()).await,
+            Err(AttestationError::Revoked(_))
+        ));
+        let missing = Uuid::new_v4();
+        assert!(matches!(
+            service.revoke_attestation(&missing, "Unknown".to_string(), "security@example.com".to_string()).await,
+            Err(AttestationError::NotFound(id)) if id == missing
+        ));
+        let active = attestation(SubjectType::Artifact, "lib.tar.gz", "sha256:beef");
+        service.store_attestation(active.clone()).await.unwrap();
+        assert_eq!(service.get_active_attestation(&active.id).await.unwrap().map(|attestation| attestation.id), Some(active.id));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_linear_chain() {
+        let service = InMemoryAttestationService::new();
+        let mut linear = vec![store_with_parents(&service, Vec::new()).await];
+        for _ in 0..4 {
+            let parent = *linear.last().unwrap();
+            linear.push(store_with_parents(&service, vec![parent]).await);
+        }
+        linear.reverse();
+
+        assert_eq!(ids(&service.resolve_chain(&linear[0], 10).await.unwrap()), linear);
+        assert_eq!(ids(&service.resolve_chain(&linear[0], 2).await.unwrap()), linear[..3].to_vec());
+        assert_eq!(ids(&service.resolve_chain(&linear[0], 0).await.unwrap()), linear[..1].to_vec());
+
+        let missing = Uuid::new_v4();
+        assert!(matches!(service.resolve_chain(&missing, 10).await, Err(AttestationError::NotFound(id)) if id == missing));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_diamond_chain_lists_shared_ancestor_once() {
+        let service = InMemoryAttestationService::new();
+        let grandparent = store_with_parents(&service, Vec::new()).await;
+        let left = store_with_parents(&service, vec![grandparent]).await;
+        let right = store_with_parents(&service, vec![grandparent]).await;
+        let child = store_with_parents(&service, vec![left, right]).await;
+
+        let chain = service.resolve_chain(&child, 10).await.unwrap();
+
+        assert_eq!(ids(&chain), vec![child, left, right, grandparent]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_cyclic_chain_fails() {
+        let service = InMemoryAttestationService::new();
+        let first = store_with_parents(&service, Vec::new()).await;
+        let second = store_with_parents(&service, vec![first]).await;
+        let third = store_with_parents(&service, vec![second]).await;
+        // Close the loop: first -> third -> second -> first.
+        let mut looped = service.get_attestation(&first).await.unwrap().unwrap();
+        looped.parent_attestations = vec![third];
+        service.store_attestation(looped).await.unwrap();
+
+        let result = service.resolve_chain(&third, 10).await;
+
+        assert!(matches!(result, Err(AttestationError::CyclicChain(cycle)) if cycle == vec![third, second, first, third]));
+    }
+
+    define_service_contract_tests!(InMemoryAttestationService::new);
 }
@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// The kind of mutating operation an `AuditEvent` records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub enum AuditAction {
+    CreateRelease,
+    TransitionRelease,
+    TagRelease,
+    UntagRelease,
+    CreatePolicy,
+    DeletePolicy,
+    StoreAttestation,
+    CaptureComplianceBaseline,
+    CreateNamespace,
+    DeleteNamespace,
+    ExportNamespace,
+    ImportNamespace,
+    CopyNamespace,
+    SetNamespaceQuota,
+    CreateComponent,
+    DeleteComponent,
+    MergeComponent,
+    CreateTeam,
+    UpdateTeam,
+    DeleteTeam,
+    RegisterWebhook,
+    DeactivateWebhook,
+}
+
+/// A single entry in the audit trail.
+#[derive(Debug, Clone, Serialize, JsonSchema, ToSchema)]
+pub struct AuditEvent {
+    pub event_id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub actor: String,
+    pub action: AuditAction,
+    pub resource_id: Uuid,
+    #[schema(value_type = String)]
+    pub resource_type: &'static str,
+    #[schema(value_type = Object)]
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+impl AuditEvent {
+    pub fn new(actor: String, action: AuditAction, resource_id: Uuid, resource_type: &'static str) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            actor,
+            action,
+            resource_id,
+            resource_type,
+            metadata: HashMap::new(),
+        }
+    }
+
+    pub fn with_metadata(mut self, key: &str, value: serde_json::Value) -> Self {
+        self.metadata.insert(key.to_string(), value);
+        self
+    }
+}
+
+/// Criteria for querying the audit trail. All set fields must match.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditFilter {
+    /// Only return events recorded at or after this time.
+    pub from: Option<DateTime<Utc>>,
+    /// Only return events recorded at or before this time.
+    pub to: Option<DateTime<Utc>>,
+    /// Only return events performed by this actor.
+    pub actor: Option<String>,
+    /// Only return events of this action type.
+    #[param(value_type = Option<AuditAction>)]
+    pub action: Option<AuditAction>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, event: &AuditEvent) -> bool {
+        self.from.is_none_or(|from| event.timestamp >= from)
+            && self.to.is_none_or(|to| event.timestamp <= to)
+            && self.actor.as_ref().is_none_or(|actor| &event.actor == actor)
+            && self.action.is_none_or(|action| event.action == action)
+    }
+}
+
+#[async_trait]
+pub trait AuditLogService: Send + Sync {
+    async fn record(&self, event: AuditEvent) -> Result<(), AuditError>;
+    async fn query(&self, filter: AuditFilter) -> Result<Vec<AuditEvent>, AuditError>;
+}
+
+#[derive(Debug, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum AuditError {
+    #[error("Failed to record audit event: {0}")]
+    StorageError(String),
+    #[error("Failed to query audit events: {0}")]
+    RetrievalError(String),
+}
+
+// Example in-memory implementation for testing. Events are only ever appended.
+#[derive(Clone)]
+pub struct InMemoryAuditLogService {
+    events: Arc<RwLock<Vec<AuditEvent>>>,
+}
+
+impl Default for InMemoryAuditLogService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryAuditLogService {
+    pub fn new() -> Self {
+        Self {
+            events: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditLogService for InMemoryAuditLogService {
+    async fn record(&self, event: AuditEvent) -> Result<(), AuditError> {
+        let mut events = self.events.write().await;
+        events.push(event);
+        Ok(())
+    }
+
+    async fn query(&self, filter: AuditFilter) -> Result<Vec<AuditEvent>, AuditError> {
+        let events = self.events.read().await;
+        Ok(events.iter()
+            .filter(|event| filter.matches(event))
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_query_by_actor_and_action() {
+        let service = InMemoryAuditLogService::new();
+        service.record(AuditEvent::new("alice".to_string(), AuditAction::CreateNamespace, Uuid::nil(), "namespace")).await.unwrap();
+        service.record(AuditEvent::new("alice".to_string(), AuditAction::DeleteNamespace, Uuid::nil(), "namespace")).await.unwrap();
+        service.record(AuditEvent::new("bob".to_string(), AuditAction::CreateNamespace, Uuid::nil(), "namespace")).await.unwrap();
+
+        let all = service.query(AuditFilter::default()).await.unwrap();
+        assert_eq!(all.len(), 3);
+
+        let by_alice = service.query(AuditFilter { actor: Some("alice".to_string()), ..Default::default() }).await.unwrap();
+        assert_eq!(by_alice.len(), 2);
+
+        let creates_by_alice = service.query(AuditFilter {
+            actor: Some("alice".to_string()),
+            action: Some(AuditAction::CreateNamespace),
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(creates_by_alice.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_query_by_time_range() {
+        let service = InMemoryAuditLogService::new();
+        let before = Utc::now();
+        service.record(AuditEvent::new("alice".to_string(), AuditAction::CreateRelease, Uuid::new_v4(), "release")).await.unwrap();
+        let after = Utc::now();
+
+        let in_range = service.query(AuditFilter { from: Some(before), to: Some(after), ..Default::default() }).await.unwrap();
+        assert_eq!(in_range.len(), 1);
+
+        let too_late = service.query(AuditFilter { from: Some(after + chrono::Duration::seconds(1)), ..Default::default() }).await.unwrap();
+        assert!(too_late.is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::{IntoResponse, Response},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tower::{Layer, Service};
+use utoipa::ToSchema;
+
+use super::api_error::ApiError;
+
+/// Role required to read namespaces.
+pub const NAMESPACE_READ: &str = "namespace:read";
+/// Role required to create or delete namespaces.
+pub const NAMESPACE_WRITE: &str = "namespace:write";
+/// Role required to create or delete policies.
+pub const POLICY_WRITE: &str = "policy:write";
+
+/// The authenticated caller, added to request extensions by the authentication middleware.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CurrentUser {
+    pub sub: String,
+    pub roles: Vec<String>,
+}
+
+impl CurrentUser {
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|granted| granted == role)
+    }
+}
+
+#[derive(Debug, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum AuthError {
+    #[error("Missing bearer token")]
+    MissingToken,
+    #[error("Invalid bearer token: {0}")]
+    InvalidToken(String),
+    #[error("Missing required role: {0}")]
+    MissingRole(String),
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let error = ApiError::from(self);
+        match error.status() {
+            StatusCode::UNAUTHORIZED => ([(header::WWW_AUTHENTICATE, "Bearer")], error).into_response(),
+            _ => error.into_response(),
+        }
+    }
+}
+
+/// Rejects requests whose `CurrentUser` lacks `role`, with `401` when there is no user at all
+/// and `403` otherwise. Apply it with `route_layer` after the authentication middleware.
+pub fn require_role(role: &'static str) -> RequireRoleLayer {
+    RequireRoleLayer { role }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RequireRoleLayer {
+    role: &'static str,
+}
+
+impl<S> Layer<S> for RequireRoleLayer {
+    type Service = RequireRole<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequireRole { inner, role: self.role }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RequireRole<S> {
+    inner: S,
+    role: &'static str,
+}
+
+impl<S> Service<Request<Body>> for RequireRole<S>
+where
+    S: Service<Request<Body>, Response = Response>,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let rejection = match request.extensions().get::<CurrentUser>() {
+            None => Some(AuthError::MissingToken),
+            Some(user) if !user.has_role(self.role) => Some(AuthError::MissingRole(self.role.to_string())),
+            Some(_) => None,
+        };
+        match rejection {
+            Some(rejection) => {
+                tracing::warn!(error = %rejection, "Rejected request");
+                Box::pin(std::future::ready(Ok(rejection.into_response())))
+            },
+            None => Box::pin(self.inner.call(request)),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::model::{SDLCPhase, SDLCRelease};
+
+use super::controlplane::{ControlPlaneError, InMemoryControlPlane};
+use super::policy_enforcement::PolicyEvaluationReport;
+
+/// Which policies a release was checked against in each phase it went through, and how it fared.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct ComplianceReport {
+    pub release_id: Uuid,
+    pub generated_at: DateTime<Utc>,
+    /// Empty if the release's component is not registered.
+    pub component_name: String,
+    pub version: String,
+    /// In the order the release entered the phases.
+    pub phase_summaries: Vec<PhaseSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct PhaseSummary {
+    pub phase: SDLCPhase,
+    /// One report per policy that applies to the phase.
+    pub policies_evaluated: Vec<PolicyEvaluationReport>,
+    /// Whether an attestation was recorded for the phase in `SDLCRelease::phase_attestations`.
+    pub attestations_present: bool,
+    /// When the release first entered the phase, if its history tells.
+    pub phase_started_at: Option<DateTime<Utc>>,
+    /// When the release last left the phase; `None` while it is still there.
+    pub phase_completed_at: Option<DateTime<Utc>>,
+}
+
+impl PhaseSummary {
+    /// The summary of `phase` without any policies evaluated yet.
+    pub fn new(release: &SDLCRelease, phase: SDLCPhase) -> Self {
+        let stays = release.phase_stays(&phase);
+        Self {
+            attestations_present: release.phase_attestations.contains_key(phase.name()),
+            phase_started_at: stays.first().map(|(entered_at, _)| *entered_at),
+            phase_completed_at: stays.last().and_then(|(_, left_at)| *left_at),
+            policies_evaluated: Vec::new(),
+            phase,
+        }
+    }
+
+    /// Whether every policy evaluated for the phase passed.
+    pub fn passed(&self) -> bool {
+        self.policies_evaluated.iter().all(|report| report.overall)
+    }
+}
+
+impl ComplianceReport {
+    /// Every phase the release has been in, including the current one, in the order it first
+    /// entered them.
+    pub fn phases_of(release: &SDLCRelease) -> Vec<SDLCPhase> {
+        let entered = release.transition_history.first().map(|record| &record.from_phase).into_iter()
+            .chain(release.transition_history.iter().map(|record| &record.to_phase))
+            .chain(std::iter::once(&release.phase));
+        let mut phases: Vec<SDLCPhase> = Vec::new();
+        for phase in entered {
+            if !phases.contains(phase) {
+                phases.push(phase.clone());
+            }
+        }
+        phases
+    }
+
+    /// The report as a standalone HTML page with one table row per evaluated policy.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let title = format!("Compliance report for {} {}", escape_html(&self.component_name), escape_html(&self.version));
+        let _ = writeln!(html, "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{title}</title></head>\n<body>");
+        let _ = writeln!(html, "<h1>{title}</h1>\n<p>Release {} generated at {}</p>", self.release_id, self.generated_at.to_rfc3339());
+        let _ = writeln!(html, "<table>\n<tr><th>Phase</th><th>Started</th><th>Completed</th><th>Attestations</th><th>Policy</th><th>Result</th></tr>");
+        for summary in &self.phase_summaries {
+            let phase = escape_html(summary.phase.name());
+            let started = summary.phase_started_at.map(|at| at.to_rfc3339()).unwrap_or_default();
+            let completed = summary.phase_completed_at.map(|at| at.to_rfc3339()).unwrap_or_default();
+            let attestations = if summary.attestations_present { "present" } else { "missing" };
+            let results: Vec<_> = summary.policies_evaluated.iter().flat_map(|report| &report.policy_results).collect();
+            if results.is_empty() {
+                let _ = writeln!(html, "<tr><td>{phase}</td><td>{started}</td><td>{completed}</td><td>{attestations}</td><td>No policies</td><td></td></tr>");
+            }
+            for result in results {
+                let outcome = if result.passed { "passed" } else { "failed" };
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{phase}</td><td>{started}</td><td>{completed}</td><td>{attestations}</td><td>{}</td><td>{outcome}</td></tr>",
+                    escape_html(&result.policy_name),
+                );
+            }
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+        html
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The policy outcome of every phase of a release at a point in time, typically when it was
+/// deployed, to compare later evaluations against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct ComplianceBaseline {
+    pub release_id: Uuid,
+    pub captured_at: DateTime<Utc>,
+    /// Whether every policy applying to the phase passed, keyed by phase name.
+    pub phase_results: HashMap<String, bool>,
+}
+
+impl ComplianceBaseline {
+    pub fn from_report(report: &ComplianceReport) -> Self {
+        Self {
+            release_id: report.release_id,
+            captured_at: report.generated_at,
+            phase_results: report.phase_summaries.iter().map(|summary| (summary.phase.name().to_string(), summary.passed())).collect(),
+        }
+    }
+}
+
+/// How the policy outcomes of a release changed since its baseline was captured.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct DriftReport {
+    pub release_id: Uuid,
+    pub baseline_captured_at: DateTime<Utc>,
+    pub checked_at: DateTime<Utc>,
+    /// Phases that passed in the baseline and fail now, sorted.
+    pub new_failures: Vec<String>,
+}
+
+impl DriftReport {
+    /// Compares the outcomes in `report` against `baseline`. Phases the baseline does not know
+    /// about are not drift.
+    pub fn between(baseline: &ComplianceBaseline, report: &ComplianceReport) -> Self {
+        let mut new_failures: Vec<String> = report.phase_summaries.iter()
+            .filter(|summary| !summary.passed() && baseline.phase_results.get(summary.phase.name()) == Some(&true))
+            .map(|summary| summary.phase.name().to_string())
+            .collect();
+        new_failures.sort();
+        Self { release_id: baseline.release_id, baseline_captured_at: baseline.captured_at, checked_at: report.generated_at, new_failures }
+    }
+
+    pub fn has_drifted(&self) -> bool {
+        !self.new_failures.is_empty()
+    }
+}
+
+/// Captures compliance baselines of releases and checks them for drift.
+#[async_trait]
+pub trait ComplianceBaselineService: Send + Sync {
+    /// Evaluates the release now and keeps the outcome as its baseline, replacing the one before.
+    async fn capture_baseline(&self, release_id: Uuid) -> Result<ComplianceBaseline, BaselineError>;
+    /// The last baseline captured for the release.
+    async fn get_baseline(&self, release_id: Uuid) -> Result<Option<ComplianceBaseline>, BaselineError>;
+    /// Evaluates the release now and reports the phases that fail but passed in `baseline`.
+    async fn check_drift(&self, release_id: Uuid, baseline: &ComplianceBaseline) -> Result<DriftReport, BaselineError>;
+}
+
+#[derive(Debug, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum BaselineError {
+    #[error("Release not found")]
+    ReleaseNotFound,
+    #[error("No compliance baseline captured for release {0}")]
+    NoBaseline(Uuid),
+    #[error("Failed to evaluate release: {0}")]
+    EvaluationFailed(String),
+}
+
+impl From<ControlPlaneError> for BaselineError {
+    fn from(err: ControlPlaneError) -> Self {
+        match err {
+            ControlPlaneError::ReleaseNotFound => BaselineError::ReleaseNotFound,
+            err => BaselineError::EvaluationFailed(err.to_string()),
+        }
+    }
+}
+
+/// Keeps baselines in memory and evaluates releases with `InMemoryControlPlane::compliance_report`.
+pub struct InMemoryComplianceBaselineService {
+    control_plane: Arc<InMemoryControlPlane>,
+    baselines: RwLock<HashMap<Uuid, ComplianceBaseline>>,
+}
+
+impl InMemoryComplianceBaselineService {
+    pub fn new(control_plane: Arc<InMemoryControlPlane>) -> Self {
+        Self { control_plane, baselines: RwLock::new(HashMap::new()) }
+    }
+}
+
+#[async_trait]
+impl ComplianceBaselineService for InMemoryComplianceBaselineService {
+    #[tracing::instrument(skip(self))]
+    async fn capture_baseline(&self, release_id: Uuid) -> Result<ComplianceBaseline, BaselineError> {
+        let report = self.control_plane.compliance_report(&release_id).await?;
+        let baseline = ComplianceBaseline::from_report(&report);
+        self.baselines.write().await.insert(release_id, baseline.clone());
+        tracing::info!(phases = baseline.phase_results.len(), "Captured compliance baseline");
+        Ok(baseline)
+    }
+
+    async fn get_baseline(&self, release_id: Uuid) -> Result<Option<ComplianceBaseline>, BaselineError> {
+        Ok(self.baselines.read().await.get(&release_id).cloned())
+    }
+
+    #[tracing::instrument(skip(self, baseline))]
+    async fn check_drift(&self, release_id: Uuid, baseline: &ComplianceBaseline) -> Result<DriftReport, BaselineError> {
+        let report = self.control_plane.compliance_report(&release_id).await?;
+        let drift = DriftReport::between(baseline, &report);
+        if drift.has_drifted() {
+            tracing::warn!(new_failures = ?drift.new_failures, "Release drifted from its compliance baseline");
+        }
+        Ok(drift)
+    }
+}
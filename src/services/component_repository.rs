@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::model::SDLCComponent;
+
+#[async_trait]
+pub trait ComponentRepository: Send + Sync {
+    async fn store_component(&self, component: SDLCComponent) -> Result<(), ComponentRepositoryError>;
+    async fn get_component(&self, id: &Uuid) -> Result<Option<SDLCComponent>, ComponentRepositoryError>;
+    async fn list_components(&self) -> Result<Vec<SDLCComponent>, ComponentRepositoryError>;
+    async fn search_components(&self, query: &str) -> Result<Vec<SDLCComponent>, ComponentRepositoryError>;
+    async fn delete_component(&self, id: &Uuid) -> Result<(), ComponentRepositoryError>;
+}
+
+#[derive(Debug, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum ComponentRepositoryError {
+    #[error("Failed to store component: {0}")]
+    StorageError(String),
+    #[error("Failed to retrieve component: {0}")]
+    RetrievalError(String),
+    #[error("Component not found")]
+    NotFound,
+}
+
+// Example in-memory implementation for testing
+#[derive(Clone)]
+pub struct InMemoryComponentRepository {
+    components: Arc<RwLock<HashMap<Uuid, SDLCComponent>>>,
+}
+
+impl Default for InMemoryComponentRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryComponentRepository {
+    pub fn new() -> Self {
+        Self {
+            components: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+#[async_trait]
+impl ComponentRepository for InMemoryComponentRepository {
+    async fn store_component(&self, component: SDLCComponent) -> Result<(), ComponentRepositoryError> {
+        let mut components = self.components.write().await;
+        components.insert(component.id(), component);
+        Ok(())
+    }
+
+    async fn get_component(&self, id: &Uuid) -> Result<Option<SDLCComponent>, ComponentRepositoryError> {
+        let components = self.components.read().await;
+        Ok(components.get(id).cloned())
+    }
+
+    async fn list_components(&self) -> Result<Vec<SDLCComponent>, ComponentRepositoryError> {
+        let components = self.components.read().await;
+        Ok(components.values().cloned().collect())
+    }
+
+    async fn search_components(&self, query: &str) -> Result<Vec<SDLCComponent>, ComponentRepositoryError> {
+        let components = self.components.read().await;
+        Ok(components.values()
+            .filter(|component| component.name().contains(query))
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_component(&self, id: &Uuid) -> Result<(), ComponentRepositoryError> {
+        let mut components = self.components.write().await;
+        match components.remove(id) {
+            Some(_) => Ok(()),
+            None => Err(ComponentRepositoryError::NotFound),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::contract_tests::define_service_contract_tests;
+    use crate::model::sdlc_component::{Project, Unmanaged};
+
+    fn project(name: &str) -> SDLCComponent {
+        SDLCComponent::Project(Project {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            repository_url: None,
+            owner: None,
+            components: Vec::new(),
+            teams: Vec::new(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_store_and_get_component() {
+        let repository = InMemoryComponentRepository::new();
+        let component = project("service-a");
+        let id = component.id();
+        repository.store_component(component).await.unwrap();
+
+        let stored = repository.get_component(&id).await.unwrap().unwrap();
+        assert_eq!(stored.name(), "service-a");
+        assert!(repository.get_component(&Uuid::new_v4()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search_and_delete_components() {
+        let repository = InMemoryComponentRepository::new();
+        let component = project("payments-api");
+        let id = component.id();
+        repository.store_component(component).await.unwrap();
+        repository.store_component(project("payments-worker")).await.unwrap();
+        repository.store_component(SDLCComponent::Unmanaged(Unmanaged {
+            id: Uuid::new_v4(),
+            name: "openssl".to_string(),
+            repository_url: None,
+            package_url: None,
+            metadata: HashMap::new(),
+        })).await.unwrap();
+
+        assert_eq!(repository.list_components().await.unwrap().len(), 3);
+        assert_eq!(repository.search_components("payments").await.unwrap().len(), 2);
+
+        repository.delete_component(&id).await.unwrap();
+        assert_eq!(repository.search_components("payments").await.unwrap().len(), 1);
+        assert!(matches!(
+            repository.delete_component(&id).await,
+            Err(ComponentRepositoryError::NotFound)
+        ));
+    }
+
+    define_service_contract_tests!(InMemoryComponentRepository::new);
+}
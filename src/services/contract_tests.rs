@@ -0,0 +1,370 @@
+//! The behavior every backend of a storage service must share, checked by the tests
+//! `define_service_contract_tests!` defines.
+//!
+//! A service takes part by implementing `ContractService` for its trait, with the trait object as
+//! the `Contract` marker; every implementation of that trait then passes the macro its factory,
+//! e.g. `define_service_contract_tests!(InMemoryAttestationService::new)`.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::model::attestation::{ClaimSet, Subject, SubjectType};
+use crate::model::sdlc_component::{Project, TeamReference};
+use crate::model::{Attestation, Policy, SDLCComponent, SDLCRelease};
+
+use super::attestation::{AttestationError, AttestationService};
+use super::component_repository::{ComponentRepository, ComponentRepositoryError};
+use super::policy_repository::{PolicyRepository, PolicyRepositoryError};
+use super::release_repository::{ReleaseRepository, ReleaseRepositoryError};
+use super::team_repository::{TeamRepository, TeamRepositoryError};
+
+/// How many tasks `concurrent_writes` stores items from at once.
+pub(crate) const CONCURRENT_WRITERS: usize = 10;
+
+/// The store, get and delete operations of a service, in terms of the trait `Contract` it
+/// implements.
+#[async_trait]
+pub(crate) trait ContractService<Contract: ?Sized>: Send + Sync + 'static {
+    type Item: Serialize + Clone + Send + Sync + 'static;
+    type Error: Debug + Send + 'static;
+
+    /// A new item with an ID, and content, of its own.
+    fn sample() -> Self::Item;
+    fn id(item: &Self::Item) -> Uuid;
+    async fn store(&self, item: Self::Item) -> Result<(), Self::Error>;
+    async fn get(&self, id: &Uuid) -> Result<Option<Self::Item>, Self::Error>;
+    /// Afterwards, `get` no longer returns the item.
+    async fn delete(&self, id: &Uuid) -> Result<(), Self::Error>;
+}
+
+/// Why a service broke its contract: an error it returned, or a result it should not have.
+#[derive(Debug)]
+pub(crate) enum ContractError<E> {
+    Service(E),
+    Violation(String),
+}
+
+impl<E: Debug> std::fmt::Display for ContractError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContractError::Service(err) => write!(f, "service failed: {err:?}"),
+            ContractError::Violation(violation) => write!(f, "contract violated: {violation}"),
+        }
+    }
+}
+
+impl<E> From<E> for ContractError<E> {
+    fn from(err: E) -> Self {
+        ContractError::Service(err)
+    }
+}
+
+fn ensure<E>(holds: bool, violation: impl FnOnce() -> String) -> Result<(), ContractError<E>> {
+    if holds {
+        Ok(())
+    } else {
+        Err(ContractError::Violation(violation()))
+    }
+}
+
+/// Whether `stored` is `item` as it was stored, compared by its JSON.
+fn same_item<T: Serialize>(stored: &Option<T>, item: &T) -> bool {
+    stored.as_ref().is_some_and(|stored| serde_json::to_value(stored).ok() == serde_json::to_value(item).ok())
+}
+
+/// A stored item is returned by `get` unchanged.
+pub(crate) async fn round_trip<C: ?Sized, S: ContractService<C>>(service: &S) -> Result<(), ContractError<S::Error>> {
+    let item = S::sample();
+    service.store(item.clone()).await?;
+    let stored = service.get(&S::id(&item)).await?;
+    ensure(same_item(&stored, &item), || format!("item {} did not round-trip", S::id(&item)))
+}
+
+/// `get` returns `None`, rather than an error, for an ID never stored.
+pub(crate) async fn missing_id_returns_none<C: ?Sized, S: ContractService<C>>(service: &S) -> Result<(), ContractError<S::Error>> {
+    let id = Uuid::new_v4();
+    let stored = service.get(&id).await?;
+    ensure(stored.is_none(), || format!("unknown ID {id} returned an item"))
+}
+
+/// Items stored from `CONCURRENT_WRITERS` tasks at once are all stored unchanged.
+pub(crate) async fn concurrent_writes<C: ?Sized, S: ContractService<C>>(service: Arc<S>) -> Result<(), ContractError<S::Error>> {
+    let items: Vec<S::Item> = (0..CONCURRENT_WRITERS).map(|_| S::sample()).collect();
+    let writers: Vec<_> = items.iter()
+        .map(|item| {
+            let (service, item) = (service.clone(), item.clone());
+            tokio::spawn(async move { service.store(item).await })
+        })
+        .collect();
+    for writer in writers {
+        writer.await.expect("writer task panicked")?;
+    }
+    for item in &items {
+        let stored = service.get(&S::id(item)).await?;
+        ensure(same_item(&stored, item), || format!("item {} was lost or corrupted by a concurrent write", S::id(item)))?;
+    }
+    Ok(())
+}
+
+/// A deleted item is no longer returned by `get`.
+pub(crate) async fn delete_removes_item<C: ?Sized, S: ContractService<C>>(service: &S) -> Result<(), ContractError<S::Error>> {
+    let item = S::sample();
+    let id = S::id(&item);
+    service.store(item).await?;
+    service.delete(&id).await?;
+    let stored = service.get(&id).await?;
+    ensure(stored.is_none(), || format!("deleted item {id} is still returned"))
+}
+
+/// Defines `mod contract_tests`, running each check of this module against a service made by
+/// `$factory`. With `async`, `$factory` is awaited, and any attributes given after it are added to
+/// every test. With `fails_with`, every check must instead fail with an error of the service
+/// matching the pattern.
+macro_rules! define_service_contract_tests {
+    (@checks $service:expr $(, #[$attr:meta])*) => {
+        mod contract_tests {
+            #[allow(unused_imports)]
+            use super::*;
+            use $crate::services::contract_tests as contract;
+
+            $(#[$attr])*
+            #[tokio::test]
+            async fn test_store_and_get_round_trip() {
+                contract::round_trip(&$service).await.unwrap_or_else(|err| panic!("{err}"));
+            }
+
+            $(#[$attr])*
+            #[tokio::test]
+            async fn test_missing_id_returns_none() {
+                contract::missing_id_returns_none(&$service).await.unwrap_or_else(|err| panic!("{err}"));
+            }
+
+            $(#[$attr])*
+            #[tokio::test]
+            async fn test_concurrent_writes_are_all_stored() {
+                contract::concurrent_writes(std::sync::Arc::new($service)).await.unwrap_or_else(|err| panic!("{err}"));
+            }
+
+            $(#[$attr])*
+            #[tokio::test]
+            async fn test_delete_removes_item() {
+                contract::delete_removes_item(&$service).await.unwrap_or_else(|err| panic!("{err}"));
+            }
+        }
+    };
+    (async $factory:expr $(, #[$attr:meta])*) => {
+        $crate::services::contract_tests::define_service_contract_tests!(@checks $factory().await $(, #[$attr])*);
+    };
+    ($factory:expr, fails_with: $error:pat) => {
+        mod contract_tests {
+            #[allow(unused_imports)]
+            use super::*;
+            use $crate::services::contract_tests::{self as contract, ContractError};
+
+            #[tokio::test]
+            async fn test_store_and_get_round_trip_fails() {
+                let result = contract::round_trip(&$factory()).await;
+                assert!(matches!(result, Err(ContractError::Service($error))), "{result:?}");
+            }
+
+            #[tokio::test]
+            async fn test_missing_id_fails() {
+                let result = contract::missing_id_returns_none(&$factory()).await;
+                assert!(matches!(result, Err(ContractError::Service($error))), "{result:?}");
+            }
+
+            #[tokio::test]
+            async fn test_concurrent_writes_fail() {
+                let result = contract::concurrent_writes(std::sync::Arc::new($factory())).await;
+                assert!(matches!(result, Err(ContractError::Service($error))), "{result:?}");
+            }
+
+            #[tokio::test]
+            async fn test_delete_fails() {
+                let result = contract::delete_removes_item(&$factory()).await;
+                assert!(matches!(result, Err(ContractError::Service($error))), "{result:?}");
+            }
+        }
+    };
+    ($factory:expr) => {
+        $crate::services::contract_tests::define_service_contract_tests!(@checks $factory());
+    };
+}
+
+pub(crate) use define_service_contract_tests;
+
+/// Attestations are never removed; a revoked one is no longer returned by
+/// `get_active_attestation`, which stands in for `get`.
+#[async_trait]
+impl<T: AttestationService + 'static> ContractService<dyn AttestationService> for T {
+    type Item = Attestation;
+    type Error = AttestationError;
+
+    fn sample() -> Attestation {
+        let subject = Subject { type_: SubjectType::Artifact, name: format!("app-{}", Uuid::new_v4()), digest: "sha256:1234".to_string() };
+        let mut claims = ClaimSet::new();
+        claims.set_string("builder", "ci".to_string());
+        Attestation::new(subject, claims)
+    }
+
+    fn id(attestation: &Attestation) -> Uuid {
+        attestation.id
+    }
+
+    async fn store(&self, attestation: Attestation) -> Result<(), AttestationError> {
+        self.store_attestation(attestation).await
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<Attestation>, AttestationError> {
+        self.get_active_attestation(id).await
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), AttestationError> {
+        self.revoke_attestation(id, "Contract test".to_string(), "contract-tests".to_string()).await
+    }
+}
+
+#[async_trait]
+impl<T: ComponentRepository + 'static> ContractService<dyn ComponentRepository> for T {
+    type Item = SDLCComponent;
+    type Error = ComponentRepositoryError;
+
+    fn sample() -> SDLCComponent {
+        let id = Uuid::new_v4();
+        SDLCComponent::Project(Project { id, name: format!("service-{id}"), repository_url: None, owner: None, components: Vec::new(), teams: Vec::new() })
+    }
+
+    fn id(component: &SDLCComponent) -> Uuid {
+        component.id()
+    }
+
+    async fn store(&self, component: SDLCComponent) -> Result<(), ComponentRepositoryError> {
+        self.store_component(component).await
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<SDLCComponent>, ComponentRepositoryError> {
+        self.get_component(id).await
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), ComponentRepositoryError> {
+        self.delete_component(id).await
+    }
+}
+
+/// Deletes with `force`, as no release depends on a sample policy.
+#[async_trait]
+impl<T: PolicyRepository + 'static> ContractService<dyn PolicyRepository> for T {
+    type Item = Policy;
+    type Error = PolicyRepositoryError;
+
+    fn sample() -> Policy {
+        Policy::new(format!("policy-{}", Uuid::new_v4()), vec!["Build".to_string()])
+    }
+
+    fn id(policy: &Policy) -> Uuid {
+        policy.id
+    }
+
+    async fn store(&self, policy: Policy) -> Result<(), PolicyRepositoryError> {
+        self.store_policy(policy).await
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<Policy>, PolicyRepositoryError> {
+        self.get_policy(id).await
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), PolicyRepositoryError> {
+        self.delete_policy(id, true).await
+    }
+}
+
+#[async_trait]
+impl<T: ReleaseRepository + 'static> ContractService<dyn ReleaseRepository> for T {
+    type Item = SDLCRelease;
+    type Error = ReleaseRepositoryError;
+
+    fn sample() -> SDLCRelease {
+        SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "contract-tests".to_string()).expect("sample version is valid")
+    }
+
+    fn id(release: &SDLCRelease) -> Uuid {
+        release.id
+    }
+
+    async fn store(&self, release: SDLCRelease) -> Result<(), ReleaseRepositoryError> {
+        self.store_release(release).await
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<SDLCRelease>, ReleaseRepositoryError> {
+        self.get_release(id).await
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), ReleaseRepositoryError> {
+        self.delete_release(id).await
+    }
+}
+
+#[async_trait]
+impl<T: TeamRepository + 'static> ContractService<dyn TeamRepository> for T {
+    type Item = TeamReference;
+    type Error = TeamRepositoryError;
+
+    fn sample() -> TeamReference {
+        let id = Uuid::new_v4();
+        TeamReference { id, name: format!("team-{id}"), contact_email: format!("team-{id}@example.com") }
+    }
+
+    fn id(team: &TeamReference) -> Uuid {
+        team.id
+    }
+
+    async fn store(&self, team: TeamReference) -> Result<(), TeamRepositoryError> {
+        self.store_team(team).await
+    }
+
+    async fn get(&self, id: &Uuid) -> Result<Option<TeamReference>, TeamRepositoryError> {
+        self.get_team(id).await
+    }
+
+    async fn delete(&self, id: &Uuid) -> Result<(), TeamRepositoryError> {
+        self.delete_team(id).await
+    }
+}
+
+/// A team repository whose backend is down: storing fails with `StorageError`, everything else
+/// with `RetrievalError`.
+#[derive(Default)]
+struct UnavailableTeamRepository;
+
+#[async_trait]
+impl TeamRepository for UnavailableTeamRepository {
+    async fn store_team(&self, _team: TeamReference) -> Result<(), TeamRepositoryError> {
+        Err(TeamRepositoryError::StorageError("connection refused".to_string()))
+    }
+
+    async fn get_team(&self, _id: &Uuid) -> Result<Option<TeamReference>, TeamRepositoryError> {
+        Err(TeamRepositoryError::RetrievalError("connection refused".to_string()))
+    }
+
+    async fn list_teams(&self) -> Result<Vec<TeamReference>, TeamRepositoryError> {
+        Err(TeamRepositoryError::RetrievalError("connection refused".to_string()))
+    }
+
+    async fn list_teams_for_component(&self, _component_id: &Uuid) -> Result<Vec<TeamReference>, TeamRepositoryError> {
+        Err(TeamRepositoryError::RetrievalError("connection refused".to_string()))
+    }
+
+    async fn delete_team(&self, _id: &Uuid) -> Result<(), TeamRepositoryError> {
+        Err(TeamRepositoryError::RetrievalError("connection refused".to_string()))
+    }
+}
+
+// Every check stores first, except looking up a missing ID.
+define_service_contract_tests!(
+    UnavailableTeamRepository::default,
+    fails_with: TeamRepositoryError::StorageError(_) | TeamRepositoryError::RetrievalError(_)
+);
@@ -1,86 +1,249 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::{HashMap, HashSet}, convert::Infallible, io::Read, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use axum::{extract::State, http::request, response::IntoResponse, routing, Json, Router};
+use axum::{extract::{FromRef, Path, Query, State}, http::{header, request, HeaderMap, HeaderName, StatusCode}, response::{sse::{Event, KeepAlive, Sse}, Html, IntoResponse}, routing, Extension, Json, Router};
 use chrono::{DateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
-use utoipa::{OpenApi, ToSchema};
-use uuid::{Timestamp, Uuid};
+use tokio_stream::{wrappers::{errors::BroadcastStreamRecvError, BroadcastStream}, Stream, StreamExt};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+use uuid::Uuid;
 
 use crate::model::{
-    attestation::{Signature, Subject, SubjectType},
+    attestation::{AttestationStatus, ClaimSet, ClaimValidationError, SBOMFormat, Signature, Subject, SubjectKind, SubjectType},
     phase::{
-        BuildDetails, DeployDetails, DevelopmentDetails, PackageDetails, PhaseDetails,
-        RuntimeDetails, SourceDetails,
+        BuildDetails, DeployDetails, DevelopmentDetails, FeatureListError, FeatureListLimits, HealthStatus, PackageDetails,
+        PhaseDetails, RolloutStrategy, RuntimeDetails, SBOMReference, SourceDetails, SourceReviewStatus,
     },
-    policy::{Policy, PolicyRule, Vulnerability, VulnerabilityLevel},
-    sdlc_component::{Project, SDLCComponent, Unmanaged},
-    Attestation, ReleaseState, SDLCPhase, SDLCRelease,
+    policy::{DurationSchema, Policy, PolicyRule, Vulnerability, VulnerabilityLevel},
+    sbom::{
+        CycloneDxBom, CycloneDxComponent, CycloneDxComponentType, CycloneDxDependency, CycloneDxHash, CycloneDxMetadata,
+        SbomSubject, SpdxChecksum, SpdxCreationInfo, SpdxDocument, SpdxExternalRef, SpdxPackage, SpdxRelationship,
+        CYCLONEDX_CONTENT_TYPE, SPDX_CONTENT_TYPE,
+    },
+    sdlc_component::{ContainerImage, MergeConflict, Project, SDLCComponent, TeamReference, Unmanaged},
+    sla::{DeploymentSLA, SLAStatus},
+    Approval, ApprovalRequest, Attestation, ReleaseDiff, ReleaseSnapshot, ReleaseState, SDLCPhase, SDLCRelease, TagError, TransitionRecord,
 };
 
-use super::{attestation::AttestationError, namespace::{InMemoryNamespaceManager, NamespaceManager, NamespaceNode}, policy_repository::PolicyRepositoryError};
+use super::{api_error::ApiError, attestation::{AttestationError, AttestationFilter, AttestationService, CleanupStats, InMemoryAttestationService}, auth::{require_role, CurrentUser, NAMESPACE_READ, NAMESPACE_WRITE, POLICY_WRITE}, audit::{AuditAction, AuditError, AuditEvent, AuditFilter, AuditLogService}, compliance::{BaselineError, ComplianceBaseline, ComplianceBaselineService, ComplianceReport, DriftReport, PhaseSummary}, component_repository::{ComponentRepository, ComponentRepositoryError, InMemoryComponentRepository}, event_sourcing_release_repository::{ReleaseEvent, ReleaseEventKind}, export::{ConflictStrategy, ExportError, ExportService, ImportCounts, ImportReport, NamespaceExport}, metrics::{record_policy_evaluation, record_transition}, namespace::{InMemoryNamespaceManager, NamespaceEvent, NamespaceEventType, NamespaceNode, NamespaceQuota, NamespaceService, NamespaceStats}, policy_enforcement::{PolicyEnforcementError, PolicyEnforcementService, PolicyEvaluationReport, PolicyResult, PolicyViolation, RuleResult}, policy_repository::{InMemoryPolicyRepository, PolicyRepository, PolicyRepositoryError}, release_repository::{ReleaseFilter, ReleaseRepository, ReleaseRepositoryError}, sla::{sla_breaches_at, SLABreach}, team_repository::{TeamRepository, TeamRepositoryError}, transitions::{TransitionAction, TransitionBroadcaster, TransitionError, TransitionEvent, TransitionRequest}, webhook::{WebhookError, WebhookPayload, WebhookService, WebhookSubscription}};
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
         create_policy,
         get_policy,
+        list_policies,
+        delete_policy,
         get_policies_for_component,
         create_attestation,
+        list_attestations,
+        get_attestation_cleanup_stats,
+        list_attestation_schemas,
         get_attestation,
         get_attestations_for_release,
+        get_release_phase_attestations,
         apply_policy_to_release,
-        create_namespace
+        get_policy_report,
+        get_compliance_report,
+        capture_compliance_baseline,
+        get_compliance_drift,
+        dry_run_policy,
+        create_namespace,
+        list_namespaces,
+        search_namespaces,
+        watch_namespaces,
+        get_namespace,
+        delete_namespace,
+        export_namespace,
+        import_namespace,
+        copy_namespace,
+        list_namespace_subtree,
+        get_namespace_stats,
+        get_namespace_quota,
+        set_namespace_quota,
+        create_component,
+        list_components,
+        get_component,
+        delete_component,
+        merge_component,
+        create_team,
+        list_teams,
+        get_team,
+        update_team,
+        delete_team,
+        register_webhook,
+        list_webhooks,
+        deactivate_webhook,
+        create_release,
+        create_releases_batch,
+        list_releases,
+        search_releases,
+        get_release,
+        get_release_lineage,
+        get_release_notes,
+        get_release_phase_durations,
+        get_sla_breaches,
+        get_release_events,
+        get_release_snapshots,
+        get_release_diff,
+        get_release_sbom,
+        add_release_tag,
+        remove_release_tag,
+        transition_release,
+        patch_release_phase,
+        watch_release,
+        query_audit_log
     ),
     components(schemas(
         SDLCComponent,
         Project,
         Unmanaged,
+        ContainerImage,
+        MergeConflict,
         SDLCRelease,
+        ReleaseSnapshot,
+        ReleaseDiff,
         ReleaseState,
+        TransitionRecord,
+        CycloneDxBom,
+        CycloneDxMetadata,
+        CycloneDxComponent,
+        CycloneDxComponentType,
+        CycloneDxHash,
+        CycloneDxDependency,
+        SpdxDocument,
+        SpdxCreationInfo,
+        SpdxPackage,
+        SpdxChecksum,
+        SpdxExternalRef,
+        SpdxRelationship,
+        SbomOutputFormat,
+        ApprovalRequest,
+        Approval,
         VulnerabilityLevel,
+        DurationSchema,
         Attestation,
+        AttestationStatus,
         AttestationError,
+        CleanupStats,
+        ClaimValidationError,
         Policy,
         PolicyRepositoryError,
         Signature,
         Subject,
         SubjectType,
+        SubjectKind,
+        SBOMFormat,
         PolicyRule,
+        PolicyViolation,
+        PolicyEvaluationReport,
+        ComplianceReport,
+        PhaseSummary,
+        ComplianceBaseline,
+        DriftReport,
+        PolicyResult,
+        RuleResult,
+        PolicyDryRunRequest,
+        PolicyEnforcementError,
         ControlPlaneError,
+        ApiError,
         SDLCPhase,
         PhaseDetails,
         RuntimeDetails,
+        HealthStatus,
         SourceDetails,
+        SourceReviewStatus,
         DevelopmentDetails,
+        FeatureListError,
         DeployDetails,
+        RolloutStrategy,
         BuildDetails,
         PackageDetails,
+        SBOMReference,
         Vulnerability,
         AttestationCreateRequest,
         NamespaceCreateRequest,
         NamespaceCreateResponse,
-        NamespaceCreateError,
+        NamespaceCopyRequest,
         NamespaceDeleteResponse,
-        NamespaceDeleteError,
         NamespaceListResponse,
-        NamespaceListError,
         NamespaceGetResponse,
-        NamespaceGetError,
-        NamespaceSearchError,
+        NamespaceListRecursiveResponse,
+        ReleaseListResponse,
+        ReleaseSearchResponse,
+        ReleaseNotesResponse,
+        ReleaseDetailResponse,
+        DeploymentSLA,
+        SLAStatus,
+        SLABreach,
+        ReleaseEventsResponse,
+        ReleaseEvent,
+        ReleaseEventKind,
+        NamespaceStats,
+        NamespaceQuota,
+        NamespaceNode,
+        NamespaceEvent,
+        NamespaceEventType,
+        NamespaceExport,
+        ConflictStrategy,
+        ImportCounts,
+        ImportReport,
+        ExportError,
+        ComponentRepositoryError,
+        TeamReference,
+        TeamRepositoryError,
+        AuditAction,
+        AuditEvent,
+        AuditError,
+        ReleaseCreateRequest,
+        BatchCreateReleasesRequest,
+        BatchCreateReleasesResponse,
+        BatchItemError,
+        ReleaseRepositoryError,
+        TagError,
+        TransitionRequest,
+        TransitionEvent,
+        TransitionAction,
+        TransitionError,
+        WebhookSubscription,
+        WebhookSubscriptionRequest,
+        WebhookPayload,
+        WebhookError,
     )),
     tags(
         (name = "policies", description = "Policy management endpoints"),
         (name = "attestations", description = "Attestation management endpoints"),
-        (name = "releases", description = "Release management endpoints")
+        (name = "releases", description = "Release management endpoints"),
+        (name = "namespaces", description = "Namespace export and import endpoints"),
+        (name = "components", description = "Component management endpoints"),
+        (name = "teams", description = "Team management endpoints"),
+        (name = "webhooks", description = "Webhook subscription endpoints"),
+        (name = "audit", description = "Audit trail endpoints")
+    ),
+    servers(
+        (url = "/api/v1alpha1", description = "Local")
     )
 )]
 pub struct ControlPlaneAPIDoc;
 
+impl ControlPlaneAPIDoc {
+    /// The OpenAPI document with `url` in place of the relative default server URL, so clients
+    /// that do not resolve it against the document's location, such as code generators, can reach
+    /// the API.
+    pub fn openapi_with_server_url(url: &str) -> utoipa::openapi::OpenApi {
+        let mut openapi = Self::openapi();
+        for server in openapi.servers.iter_mut().flatten() {
+            server.url = url.to_string();
+        }
+        openapi
+    }
+}
+
 #[async_trait]
-pub trait ControlPlane {
+pub trait ControlPlane: Send + Sync {
     async fn apply_policy_to_release(
         &self,
         release_id: &Uuid,
@@ -100,6 +263,218 @@ pub enum ControlPlaneError {
     AttestationStorageError(String),
     #[error("No policy found for component")]
     NoPolicyFound,
+    #[error("Release not found")]
+    ReleaseNotFound,
+    #[error("Release repository error: {0}")]
+    ReleaseRepositoryError(String),
+    #[error("Component repository error: {0}")]
+    ComponentRepositoryError(String),
+    #[error("Policy cannot be evaluated: {0}")]
+    PolicyEnforcementError(String),
+}
+
+/// `ControlPlane` over the in-memory repositories that evaluates every policy of the release's
+/// component against the release and its attestations.
+#[derive(Clone)]
+pub struct InMemoryControlPlane {
+    policies: Arc<InMemoryPolicyRepository>,
+    attestations: Arc<InMemoryAttestationService>,
+    releases: Arc<ReleaseStore>,
+    components: Arc<InMemoryComponentRepository>,
+}
+
+impl InMemoryControlPlane {
+    pub fn new(
+        policies: Arc<InMemoryPolicyRepository>,
+        attestations: Arc<InMemoryAttestationService>,
+        releases: Arc<ReleaseStore>,
+        components: Arc<InMemoryComponentRepository>,
+    ) -> Self {
+        Self { policies, attestations, releases, components }
+    }
+
+    /// Evaluates the release against every policy of its component, or only against `policy_id`.
+    #[tracing::instrument(skip_all, fields(release_id = %release_id, policy_id = ?policy_id, component_id = tracing::field::Empty))]
+    pub async fn evaluate_release(&self, release_id: &Uuid, policy_id: Option<&Uuid>) -> Result<PolicyEvaluationReport, ControlPlaneError> {
+        let release = self.releases.get_release(release_id).await
+            .map_err(|err| ControlPlaneError::ReleaseRepositoryError(err.to_string()))?
+            .ok_or(ControlPlaneError::ReleaseNotFound)?;
+        tracing::Span::current().record("component_id", tracing::field::display(release.component_id));
+        let policies = match policy_id {
+            Some(policy_id) => self.policies.get_policy(policy_id).await
+                .map_err(|err| ControlPlaneError::PolicyRepositoryError(err.to_string()))?
+                .into_iter()
+                .collect(),
+            None => self.policies.get_policies_for_component(&release.component_id).await
+                .map_err(|err| ControlPlaneError::PolicyRepositoryError(err.to_string()))?,
+        };
+        if policies.is_empty() {
+            return Err(ControlPlaneError::NoPolicyFound);
+        }
+        let (attestations, ancestors) = self.release_attestations(&release, &policies).await?;
+        let component = release.resolve_component(self.components.as_ref()).await
+            .map_err(|err| ControlPlaneError::ComponentRepositoryError(err.to_string()))?;
+
+        let enforcement = PolicyEnforcementService::new();
+        let evaluated_at = Utc::now();
+        let policy_results: Vec<PolicyResult> = policies.iter()
+            .map(|policy| enforcement.evaluate_policy_at(policy, &release, component.as_ref(), &attestations, &ancestors, evaluated_at))
+            .collect();
+        for result in policy_results.iter().filter(|result| !result.passed) {
+            tracing::info!(policy_id = %result.policy_id, "Release violates policy");
+        }
+        tracing::debug!(policies = policy_results.len(), "Evaluated release");
+        Ok(PolicyEvaluationReport {
+            release_id: release.id,
+            evaluated_at,
+            overall: policy_results.iter().all(|result| result.passed),
+            policy_results,
+        })
+    }
+
+    /// Evaluates the release against `policy_id` and records the policy in the release's
+    /// `applied_policies`.
+    #[tracing::instrument(skip_all, fields(release_id = %release_id, policy_id = %policy_id))]
+    pub async fn apply_policy(&self, release_id: &Uuid, policy_id: &Uuid) -> Result<PolicyEvaluationReport, ControlPlaneError> {
+        let report = self.evaluate_release(release_id, Some(policy_id)).await?;
+        let mut release = self.releases.get_release(release_id).await
+            .map_err(|err| ControlPlaneError::ReleaseRepositoryError(err.to_string()))?
+            .ok_or(ControlPlaneError::ReleaseNotFound)?;
+        if release.applied_policies.insert(*policy_id) {
+            let revision = release.revision;
+            self.releases.update_release(release, revision).await
+                .map_err(|err| ControlPlaneError::ReleaseRepositoryError(err.to_string()))?;
+        }
+        Ok(report)
+    }
+
+    /// Evaluates the release, for every phase it has been in, against each stored policy that
+    /// applies to that phase. Like `dry_run_policy`, the metrics are not touched.
+    #[tracing::instrument(skip_all, fields(release_id = %release_id))]
+    pub async fn compliance_report(&self, release_id: &Uuid) -> Result<ComplianceReport, ControlPlaneError> {
+        let release = self.releases.get_release(release_id).await
+            .map_err(|err| ControlPlaneError::ReleaseRepositoryError(err.to_string()))?
+            .ok_or(ControlPlaneError::ReleaseNotFound)?;
+        let component = release.resolve_component(self.components.as_ref()).await
+            .map_err(|err| ControlPlaneError::ComponentRepositoryError(err.to_string()))?;
+        let mut phases = Vec::new();
+        for phase in ComplianceReport::phases_of(&release) {
+            let policies = self.policies.get_policies_by_phase(&phase).await
+                .map_err(|err| ControlPlaneError::PolicyRepositoryError(err.to_string()))?;
+            phases.push((phase, policies));
+        }
+        let all_policies: Vec<Policy> = phases.iter().flat_map(|(_, policies)| policies.iter().cloned()).collect();
+        let (attestations, ancestors) = self.release_attestations(&release, &all_policies).await?;
+
+        let enforcement = PolicyEnforcementService::new();
+        let generated_at = Utc::now();
+        let phase_summaries = phases.into_iter()
+            .map(|(phase, policies)| {
+                let mut summary = PhaseSummary::new(&release, phase);
+                summary.policies_evaluated = policies.iter()
+                    .map(|policy| {
+                        let result = enforcement.evaluate_policy_at(policy, &release, component.as_ref(), &attestations, &ancestors, generated_at);
+                        PolicyEvaluationReport { release_id: release.id, evaluated_at: generated_at, overall: result.passed, policy_results: vec![result] }
+                    })
+                    .collect();
+                summary
+            })
+            .collect();
+        Ok(ComplianceReport {
+            release_id: release.id,
+            generated_at,
+            component_name: component.map(|component| component.name().to_string()).unwrap_or_default(),
+            version: release.version,
+            phase_summaries,
+        })
+    }
+
+    /// Evaluates the policy in `request` against the release without storing it, together with
+    /// every policy of the release's component if `apply_to_all` is set. Neither the release nor
+    /// the metrics are touched.
+    #[tracing::instrument(skip_all, fields(release_id = %release_id, policy_id = %request.policy.id))]
+    pub async fn dry_run_policy(&self, release_id: &Uuid, request: PolicyDryRunRequest) -> Result<PolicyEvaluationReport, ControlPlaneError> {
+        let release = self.releases.get_release(release_id).await
+            .map_err(|err| ControlPlaneError::ReleaseRepositoryError(err.to_string()))?
+            .ok_or(ControlPlaneError::ReleaseNotFound)?;
+        let tested_id = request.policy.id;
+        let mut policies = vec![request.policy];
+        if request.apply_to_all {
+            let assigned = self.policies.get_policies_for_component(&release.component_id).await
+                .map_err(|err| ControlPlaneError::PolicyRepositoryError(err.to_string()))?;
+            // The policy under test replaces its stored version rather than being evaluated twice.
+            policies.extend(assigned.into_iter().filter(|policy| policy.id != tested_id));
+        }
+        let (attestations, ancestors) = self.release_attestations(&release, &policies).await?;
+        let component = release.resolve_component(self.components.as_ref()).await
+            .map_err(|err| ControlPlaneError::ComponentRepositoryError(err.to_string()))?;
+        PolicyEnforcementService::new()
+            .dry_run_evaluate_all(&policies, &release, component.as_ref(), &attestations, &ancestors)
+            .map_err(|err| ControlPlaneError::PolicyEnforcementError(err.to_string()))
+    }
+
+    /// The attestations about the release, and the attestations they build on that are not about
+    /// the release themselves. The chains are only resolved if one of `policies` checks them.
+    async fn release_attestations(&self, release: &SDLCRelease, policies: &[Policy]) -> Result<(Vec<Attestation>, Vec<Attestation>), ControlPlaneError> {
+        let attestations = self.attestations.get_attestations_concerning_release(release).await
+            .map_err(|err| ControlPlaneError::AttestationStorageError(err.to_string()))?;
+        let checks_chains = policies.iter().flat_map(|policy| &policy.rules).any(|rule| matches!(rule, PolicyRule::NoRevokedAttestations));
+        if !checks_chains {
+            return Ok((attestations, Vec::new()));
+        }
+        let mut seen: HashSet<Uuid> = attestations.iter().map(|attestation| attestation.id).collect();
+        let mut ancestors = Vec::new();
+        for attestation in attestations.iter().filter(|attestation| !attestation.parent_attestations.is_empty()) {
+            let chain = self.attestations.resolve_chain(&attestation.id, MAX_ATTESTATION_CHAIN_DEPTH).await
+                .map_err(|err| ControlPlaneError::AttestationStorageError(err.to_string()))?;
+            ancestors.extend(chain.into_iter().skip(1).filter(|ancestor| seen.insert(ancestor.id)));
+        }
+        Ok((attestations, ancestors))
+    }
+}
+
+/// How many levels of parents `PolicyRule::NoRevokedAttestations` follows.
+pub const MAX_ATTESTATION_CHAIN_DEPTH: usize = 32;
+
+#[async_trait]
+impl ControlPlane for InMemoryControlPlane {
+    /// Passes only if no policy is violated by the release or by any attestation about it.
+    /// `component_id` is ignored in favour of the component the stored release belongs to.
+    #[tracing::instrument(skip(self))]
+    async fn apply_policy_to_release(
+        &self,
+        release_id: &Uuid,
+        _component_id: &Uuid,
+    ) -> Result<bool, ControlPlaneError> {
+        Ok(self.evaluate_release(release_id, None).await?.overall)
+    }
+
+    #[tracing::instrument(skip_all, fields(policy_id = %policy.id))]
+    async fn store_policy(&self, policy: Policy) -> Result<(), ControlPlaneError> {
+        let rules = policy.rules.len();
+        self.policies.store_policy(policy).await
+            .map_err(|err| ControlPlaneError::PolicyRepositoryError(err.to_string()))?;
+        tracing::debug!(rules, "Stored policy");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(policy_id = %id))]
+    async fn get_policy(&self, id: &Uuid) -> Result<Option<Policy>, ControlPlaneError> {
+        self.policies.get_policy(id).await
+            .map_err(|err| ControlPlaneError::PolicyRepositoryError(err.to_string()))
+    }
+
+    #[tracing::instrument(skip_all, fields(attestation_id = %attestation.id))]
+    async fn store_attestation(&self, attestation: Attestation) -> Result<(), ControlPlaneError> {
+        self.attestations.store_attestation(attestation).await
+            .map_err(|err| ControlPlaneError::AttestationStorageError(err.to_string()))
+    }
+
+    #[tracing::instrument(skip_all, fields(attestation_id = %id))]
+    async fn get_attestation(&self, id: &Uuid) -> Result<Option<Attestation>, ControlPlaneError> {
+        self.attestations.get_attestation(id).await
+            .map_err(|err| ControlPlaneError::AttestationStorageError(err.to_string()))
+    }
 }
 
 #[utoipa::path(
@@ -108,13 +483,27 @@ pub enum ControlPlaneError {
     request_body = Policy,
     responses(
         (status = 201, description = "Policy created successfully", body = Policy),
-        (status = 400, description = "Invalid policy data", body = PolicyRepositoryError)
+        (status = 400, description = "Invalid policy data", body = ApiError),
+        (status = 401, description = "Not authenticated", body = ApiError),
+        (status = 403, description = "The caller lacks the policy:write role", body = ApiError),
+        (status = 500, description = "Policy could not be stored", body = ApiError)
     ),
     tag = "policies"
 )]
-pub async fn create_policy(_policy: Policy) -> Result<Policy, PolicyRepositoryError> {
-    // Implementation here
-    unimplemented!()
+#[tracing::instrument(skip_all, fields(policy_id = %policy.id))]
+pub async fn create_policy(State(policies): State<Arc<PolicyStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Json(policy): Json<Policy>,
+) -> Result<(StatusCode, Json<Policy>), ApiError> {
+    policies.store_policy(policy.clone()).await?;
+    record_audit_event(
+        audit.as_ref(),
+        AuditEvent::new(request_actor(&user), AuditAction::CreatePolicy, policy.id, "policy")
+            .with_metadata("name", serde_json::Value::String(policy.name.clone())),
+    ).await;
+    tracing::info!("Created policy");
+    Ok((StatusCode::CREATED, Json(policy)))
 }
 
 #[utoipa::path(
@@ -122,16 +511,109 @@ pub async fn create_policy(_policy: Policy) -> Result<Policy, PolicyRepositoryEr
     path = "/policies/{id}",
     responses(
         (status = 200, description = "Policy found", body = Policy),
-        (status = 404, description = "Policy not found", body = PolicyRepositoryError)
+        (status = 404, description = "Policy not found", body = ApiError)
     ),
     params(
         ("id" = Uuid, Path, description = "Policy ID")
     ),
     tag = "policies"
 )]
-pub async fn get_policy(_id: Uuid) -> Result<Policy, PolicyRepositoryError> {
-    // Implementation here
-    unimplemented!()
+#[tracing::instrument(skip_all, fields(policy_id = %id))]
+pub async fn get_policy(State(policies): State<Arc<PolicyStore>>,
+Path(id): Path<Uuid>,
+) -> Result<Json<Policy>, ApiError> {
+    let policy = policies.get_policy(&id).await?.ok_or(PolicyRepositoryError::NotFound(id))?;
+    Ok(Json(policy))
+}
+
+#[derive(Clone, Default, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PolicyListQuery {
+    /// Only return policies that apply to the phase with this name, e.g. `Source` or the name of
+    /// a custom phase.
+    pub phase: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/policies",
+    responses(
+        (status = 200, description = "Every policy, or those applying to the phase", body = Vec<Policy>),
+        (status = 400, description = "The phase is blank", body = ApiError),
+        (status = 500, description = "Policies could not be retrieved", body = ApiError)
+    ),
+    params(
+        PolicyListQuery
+    ),
+    tag = "policies"
+)]
+#[tracing::instrument(skip_all, fields(phase = ?query.phase))]
+pub async fn list_policies(State(policies): State<Arc<PolicyStore>>,
+Query(query): Query<PolicyListQuery>,
+) -> Result<Json<Vec<Policy>>, ApiError> {
+    let policies = match query.phase.as_deref() {
+        Some(phase) if phase.trim().is_empty() => {
+            return Err(ApiError::new(StatusCode::BAD_REQUEST, "invalid_phase", "The phase must not be blank"));
+        }
+        Some(phase) => policies.get_policies_by_phase(&SDLCPhase::from_name(phase)).await?,
+        None => policies.list_policies().await?,
+    };
+    Ok(Json(policies))
+}
+
+#[derive(Clone, Default, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PolicyDeleteQuery {
+    /// Also delete a policy that was applied to releases, removing it from their
+    /// `applied_policies`.
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/policies/{id}",
+    responses(
+        (status = 204, description = "Policy deleted"),
+        (status = 401, description = "Not authenticated", body = ApiError),
+        (status = 403, description = "The caller lacks the policy:write role", body = ApiError),
+        (status = 404, description = "Policy not found", body = ApiError),
+        (status = 409, description = "The policy was applied to releases and `force` is not set", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Policy ID"),
+        PolicyDeleteQuery
+    ),
+    tag = "policies"
+)]
+#[tracing::instrument(skip_all, fields(policy_id = %id, force = query.force))]
+pub async fn delete_policy(State(policies): State<Arc<PolicyStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Path(id): Path<Uuid>,
+Query(query): Query<PolicyDeleteQuery>,
+) -> Result<StatusCode, ApiError> {
+    policies.delete_policy(&id, query.force).await?;
+    record_audit_event(
+        audit.as_ref(),
+        AuditEvent::new(request_actor(&user), AuditAction::DeletePolicy, id, "policy")
+            .with_metadata("force", serde_json::Value::Bool(query.force)),
+    ).await;
+    tracing::info!("Deleted policy");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub type PolicyStore = dyn PolicyRepository;
+
+/// Creating and deleting policies requires the `policy:write` role; reading them does not.
+pub fn policy_router(policies: Arc<PolicyStore>, audit: Arc<AuditStore>) -> Router {
+    Router::new()
+        .route("/", routing::get(list_policies)
+            .merge(routing::post(create_policy).route_layer(require_role(POLICY_WRITE))))
+        .route("/:id", routing::get(get_policy)
+            .merge(routing::delete(delete_policy).route_layer(require_role(POLICY_WRITE))))
+        .layer(Extension(audit))
+        .with_state(policies)
 }
 
 #[utoipa::path(
@@ -139,7 +621,7 @@ pub async fn get_policy(_id: Uuid) -> Result<Policy, PolicyRepositoryError> {
     path = "/components/{id}/policies",
     responses(
         (status = 200, description = "Policies found", body = Vec<Policy>),
-        (status = 404, description = "Component not found", body = PolicyRepositoryError)
+        (status = 404, description = "Component not found", body = ApiError)
     ),
     params(
         ("id" = Uuid, Path, description = "Component ID")
@@ -156,33 +638,63 @@ pub struct AttestationCreateRequest {
     pub subject: Subject,
     pub expiration: Option<DateTime<Utc>>,
     pub signatures: Vec<Signature>,
-    pub claims: HashMap<String, serde_json::Value>,
-    pub parent_attestations: Vec<String>, // Use strings for client-provided UUIDs
+    #[schema(value_type = Object)]
+    pub claims: ClaimSet,
+    pub parent_attestations: Vec<String>, // Use strings for client-provided UUIDs, parsed by `into_attestation`
+    /// The release the attestation is recorded for.
+    #[serde(default)]
+    pub release_id: Option<Uuid>,
+    /// What the claims describe, e.g. `https://slsa.dev/provenance/v0.2`.
+    #[serde(default)]
+    pub predicate_type: Option<String>,
+}
+
+impl AttestationCreateRequest {
+    /// The attestation to store, issued now. Fails if a parent ID is not a UUID; the claims are
+    /// validated when it is stored.
+    pub fn into_attestation(self) -> Result<Attestation, AttestationError> {
+        Ok(Attestation {
+            id: uuid::Uuid::new_v4(),
+            subject: self.subject,
+            timestamp: Utc::now(),
+            expiration: self.expiration,
+            signatures: self.signatures,
+            claims: self.claims,
+            parent_attestations: self.parent_attestations.iter()
+                .map(|id| Uuid::parse_str(id).map_err(|_| AttestationError::InvalidParentAttestation(id.clone())))
+                .collect::<Result<_, _>>()?,
+            status: AttestationStatus::Active,
+            release_id: self.release_id,
+            predicate_type: self.predicate_type,
+        })
+    }
 }
 
 #[utoipa::path(
     post,
     path = "/attestations",
-    request_body = Attestation,
+    request_body = AttestationCreateRequest,
     responses(
         (status = 201, description = "Attestation created successfully", body = Attestation),
-        (status = 400, description = "Invalid attestation data", body = AttestationError)
+        (status = 400, description = "Invalid attestation data", body = ApiError),
+        (status = 409, description = "An attestation with the same subject and claims is already stored", body = ApiError)
     ),
     tag = "attestations"
 )]
-pub async fn create_attestation(
-    _attestation: AttestationCreateRequest,
-) -> Result<Attestation, AttestationError> {
-    // Implementation here
-    Ok(Attestation {
-        id: uuid::Uuid::new_v4(),
-        subject: todo!(),
-        timestamp: todo!(),
-        expiration: todo!(),
-        signatures: todo!(),
-        claims: todo!(),
-        parent_attestations: todo!(),
-    })
+#[tracing::instrument(skip_all)]
+pub async fn create_attestation(State(attestations): State<Arc<AttestationStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Json(request): Json<AttestationCreateRequest>,
+) -> Result<(StatusCode, Json<Attestation>), ApiError> {
+    let attestation = request.into_attestation()?;
+    attestations.store_attestation(attestation.clone()).await?;
+    record_audit_event(
+        audit.as_ref(),
+        AuditEvent::new(request_actor(&user), AuditAction::StoreAttestation, attestation.id, "attestation")
+            .with_metadata("subject", serde_json::Value::String(attestation.subject.name.clone())),
+    ).await;
+    Ok((StatusCode::CREATED, Json(attestation)))
 }
 
 #[utoipa::path(
@@ -190,44 +702,173 @@ pub async fn create_attestation(
     path = "/attestations/{id}",
     responses(
         (status = 200, description = "Attestation found", body = Attestation),
-        (status = 404, description = "Attestation not found", body = AttestationError)
+        (status = 404, description = "Attestation not found", body = ApiError)
     ),
     params(
         ("id" = Uuid, Path, description = "Attestation ID")
     ),
     tag = "attestations"
 )]
-pub async fn get_attestation(_id: Uuid) -> Result<Attestation, AttestationError> {
-    // Implementation here
-    unimplemented!()
+#[tracing::instrument(skip_all, fields(attestation_id = %id))]
+pub async fn get_attestation(State(attestations): State<Arc<AttestationStore>>,
+Path(id): Path<Uuid>,
+) -> Result<Json<Attestation>, ApiError> {
+    let attestation = attestations.get_attestation(&id).await?.ok_or(AttestationError::NotFound(id))?;
+    Ok(Json(attestation))
 }
 
 #[utoipa::path(
     get,
     path = "/releases/{id}/attestations",
     responses(
-        (status = 200, description = "Attestations found", body = Vec<Attestation>),
-        (status = 404, description = "Release not found", body = AttestationError)
+        (status = 200, description = "Attestations recorded for the release", body = Vec<Attestation>),
+        (status = 404, description = "Release not found", body = ApiError),
+        (status = 500, description = "Release or attestations could not be retrieved", body = ApiError)
     ),
     params(
         ("id" = Uuid, Path, description = "Release ID")
     ),
     tag = "releases"
 )]
-pub async fn get_attestations_for_release(_id: Uuid) -> Result<Vec<Attestation>, AttestationError> {
-    // Implementation here
-    unimplemented!()
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn get_attestations_for_release(State(releases): State<Arc<ReleaseStore>>,
+Extension(attestations): Extension<Arc<AttestationStore>>,
+Path(id): Path<Uuid>,
+) -> Result<Json<Vec<Attestation>>, ApiError> {
+    releases.get_release(&id).await?.ok_or(ReleaseRepositoryError::NotFound)?;
+    let mut attestations = attestations.get_attestations_for_release(&id).await?;
+    attestations.sort_by_key(|attestation| attestation.timestamp);
+    Ok(Json(attestations))
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}/phase-attestations",
+    responses(
+        (status = 200, description = "The attestation recorded for each phase, by phase name", body = HashMap<String, Attestation>),
+        (status = 404, description = "Release not found", body = ApiError),
+        (status = 500, description = "Release or one of its phase attestations could not be retrieved", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn get_release_phase_attestations(State(releases): State<Arc<ReleaseStore>>,
+Extension(attestations): Extension<Arc<AttestationStore>>,
+Path(id): Path<Uuid>,
+) -> Result<Json<HashMap<String, Attestation>>, ApiError> {
+    let release = releases.get_release(&id).await?.ok_or(ReleaseRepositoryError::NotFound)?;
+    Ok(Json(release.list_phase_attestations_full(attestations.as_ref()).await?))
+}
+
+/// Attestation routes, nested below `/releases` next to the `release_router`.
+pub fn release_attestation_router(releases: Arc<ReleaseStore>, attestations: Arc<AttestationStore>) -> Router {
+    Router::new()
+        .route("/:id/attestations", routing::get(get_attestations_for_release))
+        .route("/:id/phase-attestations", routing::get(get_release_phase_attestations))
+        .layer(Extension(attestations))
+        .with_state(releases)
+}
+
+pub type AttestationStore = dyn AttestationService;
+
+#[utoipa::path(
+    get,
+    path = "/attestations",
+    responses(
+        (status = 200, description = "Attestations matching the filter", body = Vec<Attestation>),
+        (status = 500, description = "Attestations could not be retrieved", body = ApiError)
+    ),
+    params(
+        AttestationFilter
+    ),
+    tag = "attestations"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn list_attestations(State(attestations): State<Arc<AttestationStore>>,
+Query(filter): Query<AttestationFilter>,
+) -> impl IntoResponse {
+    match attestations.list_attestations(filter).await {
+        Ok(attestations) => Ok(Json(attestations)),
+        Err(err) => Err(ApiError::from(err)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/attestations/cleanup-stats",
+    responses(
+        (status = 200, description = "When expired attestations were last pruned and how many were removed in total", body = CleanupStats),
+        (status = 500, description = "Statistics could not be retrieved", body = ApiError)
+    ),
+    tag = "attestations"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn get_attestation_cleanup_stats(State(attestations): State<Arc<AttestationStore>>) -> Result<Json<CleanupStats>, ApiError> {
+    Ok(Json(attestations.cleanup_stats().await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/attestation-schemas",
+    responses(
+        (status = 200, description = "Predicate types whose claims are validated against a registered schema, sorted", body = Vec<String>),
+        (status = 500, description = "Predicate types could not be retrieved", body = ApiError)
+    ),
+    tag = "attestations"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn list_attestation_schemas(State(attestations): State<Arc<AttestationStore>>) -> Result<Json<Vec<String>>, ApiError> {
+    Ok(Json(attestations.predicate_types().await?))
+}
+
+/// Routes nested at `/attestation-schemas`, next to the `attestation_router`.
+pub fn attestation_schema_router(attestations: Arc<AttestationStore>) -> Router {
+    Router::new()
+        .route("/", routing::get(list_attestation_schemas))
+        .with_state(attestations)
+}
+
+pub fn attestation_router(attestations: Arc<AttestationStore>, audit: Arc<AuditStore>) -> Router {
+    Router::new()
+        .route("/", routing::get(list_attestations).post(create_attestation))
+        .route("/cleanup-stats", routing::get(get_attestation_cleanup_stats))
+        .route("/:id", routing::get(get_attestation))
+        .layer(Extension(audit))
+        .with_state(attestations)
 }
 
 pub type NamespaceStore = Mutex<InMemoryNamespaceManager>;
 
+pub type AuditStore = dyn AuditLogService;
+
+/// The actor recorded on audit events: the authenticated user, or `anonymous` when the router
+/// is served without authentication.
+fn request_actor(user: &Option<Extension<CurrentUser>>) -> String {
+    user.as_ref()
+        .map(|Extension(user)| user.sub.clone())
+        .unwrap_or_else(|| "anonymous".to_string())
+}
+
+/// Records `event`, logging instead of failing when the audit log is unavailable.
+pub async fn record_audit_event(audit: &AuditStore, event: AuditEvent) {
+    // Auditing must never turn a successful mutation into a failed request.
+    if let Err(err) = audit.record(event).await {
+        tracing::warn!(error = %err, "Failed to record audit event");
+    }
+}
+
+pub type PolicyEvaluationStore = InMemoryControlPlane;
+
 #[utoipa::path(
     post,
     path = "/releases/{release_id}/apply-policy/{policy_id}",
     responses(
-        (status = 200, description = "Policy applied successfully", body = bool),
-        (status = 400, description = "Invalid policy or release data", body = ControlPlaneError),
-        (status = 404, description = "Release or policy not found", body = ControlPlaneError)
+        (status = 200, description = "Policy applied successfully and recorded in the release's `applied_policies`", body = PolicyEvaluationReport),
+        (status = 404, description = "Release or policy not found", body = ApiError),
+        (status = 500, description = "Policy could not be evaluated", body = ApiError)
     ),
     params(
         ("release_id" = Uuid, Path, description = "Release ID"),
@@ -235,92 +876,292 @@ pub type NamespaceStore = Mutex<InMemoryNamespaceManager>;
     ),
     tag = "releases"
 )]
-pub async fn apply_policy_to_release(
-    _release_id: Uuid,
-    _policy_id: Uuid,
-) -> Result<bool, ControlPlaneError> {
-    // Implementation here
-    unimplemented!()
+#[tracing::instrument(skip_all, fields(release_id = %release_id, policy_id = %policy_id))]
+pub async fn apply_policy_to_release(State(control_plane): State<Arc<PolicyEvaluationStore>>,
+Path((release_id, policy_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<PolicyEvaluationReport>, ApiError> {
+    let report = control_plane.apply_policy(&release_id, &policy_id).await?;
+    record_policy_evaluation(report.overall);
+    Ok(Json(report))
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}/policy-report",
+    responses(
+        (status = 200, description = "The release evaluated against every policy of its component", body = PolicyEvaluationReport),
+        (status = 404, description = "Release not found or its component has no policy", body = ApiError),
+        (status = 500, description = "Policies could not be evaluated", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn get_policy_report(State(control_plane): State<Arc<PolicyEvaluationStore>>,
+Path(id): Path<Uuid>,
+) -> Result<Json<PolicyEvaluationReport>, ApiError> {
+    let report = control_plane.evaluate_release(&id, None).await?;
+    record_policy_evaluation(report.overall);
+    Ok(Json(report))
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}/compliance-report",
+    responses(
+        (status = 200, description = "The release evaluated, phase by phase, against the policies applying to each phase",
+            content(("application/json" = ComplianceReport), ("text/html" = String))),
+        (status = 404, description = "Release not found", body = ApiError),
+        (status = 500, description = "Policies could not be evaluated", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID"),
+        ("Accept" = Option<String>, Header, description = "`text/html` for a table instead of JSON")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn get_compliance_report(State(control_plane): State<Arc<PolicyEvaluationStore>>,
+Path(id): Path<Uuid>,
+headers: HeaderMap,
+) -> Result<axum::response::Response, ApiError> {
+    let report = control_plane.compliance_report(&id).await?;
+    let wants_html = headers.get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .is_some_and(|accept| accept.split(',').any(|media_type| media_type.trim().starts_with("text/html")));
+    Ok(if wants_html { Html(report.to_html()).into_response() } else { Json(report).into_response() })
+}
+
+/// A policy to try against a release before storing or assigning it.
+#[derive(Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct PolicyDryRunRequest {
+    pub policy: Policy,
+    /// Also evaluate every policy assigned to the release's component.
+    #[serde(default)]
+    pub apply_to_all: bool,
+}
+
+#[utoipa::path(
+    post,
+    path = "/releases/{id}/policy-dry-run",
+    request_body = PolicyDryRunRequest,
+    responses(
+        (status = 200, description = "How the release would fare against the policy; nothing is stored", body = PolicyEvaluationReport),
+        (status = 404, description = "Release not found", body = ApiError),
+        (status = 422, description = "The policy has no rules", body = ApiError),
+        (status = 500, description = "Policies could not be evaluated", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn dry_run_policy(State(control_plane): State<Arc<PolicyEvaluationStore>>,
+Path(id): Path<Uuid>,
+Json(request): Json<PolicyDryRunRequest>,
+) -> Result<Json<PolicyEvaluationReport>, ApiError> {
+    Ok(Json(control_plane.dry_run_policy(&id, request).await?))
+}
+
+/// Policy evaluation routes, nested below `/releases` next to the `release_router`.
+pub fn policy_evaluation_router(control_plane: Arc<PolicyEvaluationStore>) -> Router {
+    Router::new()
+        .route("/:id/apply-policy/:policy_id", routing::post(apply_policy_to_release))
+        .route("/:id/policy-report", routing::get(get_policy_report))
+        .route("/:id/policy-dry-run", routing::post(dry_run_policy))
+        .route("/:id/compliance-report", routing::get(get_compliance_report))
+        .with_state(control_plane)
+}
+
+pub type ComplianceBaselineStore = dyn ComplianceBaselineService;
+
+#[utoipa::path(
+    post,
+    path = "/releases/{id}/compliance-baseline",
+    responses(
+        (status = 201, description = "The release evaluated now, kept as its baseline in place of any earlier one", body = ComplianceBaseline),
+        (status = 404, description = "Release not found", body = ApiError),
+        (status = 500, description = "Policies could not be evaluated", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn capture_compliance_baseline(State(baselines): State<Arc<ComplianceBaselineStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<ComplianceBaseline>), ApiError> {
+    let baseline = baselines.capture_baseline(id).await?;
+    record_audit_event(
+        audit.as_ref(),
+        AuditEvent::new(request_actor(&user), AuditAction::CaptureComplianceBaseline, id, "release")
+            .with_metadata("captured_at", serde_json::Value::String(baseline.captured_at.to_rfc3339())),
+    ).await;
+    Ok((StatusCode::CREATED, Json(baseline)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}/compliance-baseline/drift",
+    responses(
+        (status = 200, description = "The phases that fail now but passed in the release's baseline", body = DriftReport),
+        (status = 404, description = "Release not found or no baseline captured for it", body = ApiError),
+        (status = 500, description = "Policies could not be evaluated", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn get_compliance_drift(State(baselines): State<Arc<ComplianceBaselineStore>>,
+Path(id): Path<Uuid>,
+) -> Result<Json<DriftReport>, ApiError> {
+    let baseline = baselines.get_baseline(id).await?.ok_or(BaselineError::NoBaseline(id))?;
+    Ok(Json(baselines.check_drift(id, &baseline).await?))
 }
 
+/// Compliance baseline routes, nested below `/releases` next to the `release_router`.
+pub fn compliance_baseline_router(baselines: Arc<ComplianceBaselineStore>, audit: Arc<AuditStore>) -> Router {
+    Router::new()
+        .route("/:id/compliance-baseline", routing::post(capture_compliance_baseline))
+        .route("/:id/compliance-baseline/drift", routing::get(get_compliance_drift))
+        .layer(Extension(audit))
+        .with_state(baselines)
+}
 
 #[utoipa::path(
     post,
-    path = "/",
+    path = "/namespaces",
     request_body = NamespaceCreateRequest,
     responses(
-        (status = 201, description = "Namespace created successfully", body = NamespaceCreateResponse),
-        (status = 400, description = "Invalid namespace data", body = NamespaceCreateError)
+        (status = 200, description = "Namespace created successfully, along with any missing parents", body = NamespaceCreateResponse),
+        (status = 400, description = "Invalid namespace path", body = ApiError),
+        (status = 409, description = "Namespace already exists", body = ApiError)
     ),
-    tag = ""
+    tag = "namespaces"
 )]
+#[tracing::instrument(skip_all, fields(namespace = %namespace_create_request.namespace))]
 pub async fn create_namespace(State(store): State<Arc<NamespaceStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
 Json(namespace_create_request): Json<NamespaceCreateRequest>,
-) -> impl IntoResponse { //Result<Json<NamespaceCreateResponse>, Json<NamespaceCreateError>> {
+) -> Result<Json<NamespaceCreateResponse>, ApiError> {
     let namespace_create_result = store.lock().await.create_namespace(namespace_create_request.namespace.as_str()).await;
     match namespace_create_result {
-        Ok(_) => Ok(Json(NamespaceCreateResponse)),
-        Err(_) => Err(Json(NamespaceCreateError::InvalidPath)),
+        Ok(_) => {
+            record_audit_event(
+                audit.as_ref(),
+                AuditEvent::new(request_actor(&user), AuditAction::CreateNamespace, Uuid::nil(), "namespace")
+                    .with_metadata("path", serde_json::Value::String(namespace_create_request.namespace)),
+            ).await;
+            Ok(Json(NamespaceCreateResponse {}))
+        },
+        Err(err) => {
+            tracing::warn!(error = ?err, "Failed to create namespace");
+            Err(ApiError::from(err))
+        },
     }
 }
 
-#[derive(Clone, Deserialize, JsonSchema, ToSchema)]
+#[derive(Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
 pub struct NamespaceCreateRequest {
     pub namespace: String
 }
 
 #[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
-pub struct NamespaceCreateResponse;
-
-#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
-pub enum NamespaceCreateError {
-    InvalidPath,
-}
+pub struct NamespaceCreateResponse {}
 
 #[utoipa::path(
     get,
-    path = "/",
+    path = "/namespaces",
     responses(
-        (status = 201, description = "Namespace created successfully", body = NamespaceListResponse),
-        (status = 400, description = "Invalid namespace data", body = NamespaceListError)
+        (status = 200, description = "The top-level namespaces", body = NamespaceListResponse)
     ),
-    tag = ""
+    tag = "namespaces"
 )]
-pub async fn list_namespaces(State(store): State<Arc<NamespaceStore>>) -> impl IntoResponse {
-    let namespace_list_result = store.lock().await.list_namespaces("").await;
-    match namespace_list_result {
-        Ok(namespaces) => Ok(Json(NamespaceListResponse { namespaces })),
-        Err(_) => Err(Json(NamespaceListError::InvalidPath)),
-    }
+#[tracing::instrument(skip_all)]
+pub async fn list_namespaces(State(store): State<Arc<NamespaceStore>>) -> Result<Json<NamespaceListResponse>, ApiError> {
+    let namespaces = store.lock().await.list_namespaces("").await?;
+    Ok(Json(NamespaceListResponse { namespaces }))
 }
 
 #[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
-struct NamespaceListResponse {
-    namespaces: Vec<String>
+pub struct NamespaceListResponse {
+    pub namespaces: Vec<String>
 }
 
-#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
-enum NamespaceListError {
-    InvalidPath,
+#[derive(Clone, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NamespaceSearchQuery {
+    /// Text the name of a namespace must contain.
+    pub q: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/namespaces/search",
+    responses(
+        (status = 200, description = "Full paths of the namespaces whose name contains the query", body = NamespaceListResponse),
+        (status = 400, description = "The query is missing", body = ApiError)
+    ),
+    params(
+        NamespaceSearchQuery
+    ),
+    tag = "namespaces"
+)]
+#[tracing::instrument(skip_all, fields(query = %query.q))]
 pub async fn search_namespaces(State(store): State<Arc<NamespaceStore>>,
-query: String) -> impl IntoResponse {
-    let namespace_search_result = store.lock().await.search_namespaces(query.as_str()).await;
-    match namespace_search_result {
-        Ok(namespaces) => Ok(Json(NamespaceListResponse { namespaces })),
-        Err(_) => Err(Json(NamespaceSearchError::InvalidPath)),
-    }
+Query(query): Query<NamespaceSearchQuery>,
+) -> Result<Json<NamespaceListResponse>, ApiError> {
+    let namespaces = store.lock().await.search_namespaces(query.q.as_str()).await?;
+    Ok(Json(NamespaceListResponse { namespaces }))
 }
 
-#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
-enum NamespaceSearchError {
-    InvalidPath,
+#[utoipa::path(
+    get,
+    path = "/namespaces/events",
+    responses(
+        (status = 200, description = "Stream of `namespace` events carrying a NamespaceEvent", body = NamespaceEvent, content_type = "text/event-stream")
+    ),
+    tag = "namespaces"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn watch_namespaces(State(store): State<Arc<NamespaceStore>>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // Subscribe before returning so no change after this point is missed.
+    let events = BroadcastStream::new(store.lock().await.subscribe()).filter_map(|event| match event {
+        Ok(event) => Some(Ok(Event::default().event("namespace").json_data(event).ok()?)),
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+            tracing::warn!(skipped, "Namespace watcher lagged behind");
+            None
+        },
+    });
+    Sse::new(events).keep_alive(KeepAlive::new().interval(WATCH_KEEPALIVE_INTERVAL))
 }
 
+#[utoipa::path(
+    get,
+    path = "/namespaces/{path}",
+    responses(
+        (status = 200, description = "The namespace and everything below it", body = NamespaceGetResponse),
+        (status = 400, description = "Invalid namespace path", body = ApiError),
+        (status = 404, description = "Namespace not found", body = ApiError)
+    ),
+    params(
+        ("path" = String, Path, description = "Namespace path")
+    ),
+    tag = "namespaces"
+)]
+#[tracing::instrument(skip_all)]
 pub async fn get_namespace(State(store): State<Arc<NamespaceStore>>,
 request: request::Parts,
-) -> impl IntoResponse {
+) -> Result<Json<NamespaceGetResponse>, ApiError> {
     let namespace_path = request
         .uri
         .path()
@@ -330,55 +1171,1687 @@ request: request::Parts,
         .map(|s| s.to_string())
         .collect::<Vec<String>>()
         .join("/");
-    let namespace_drill_down_result = store.lock().await.drill_down(namespace_path.as_str()).await;
-    match namespace_drill_down_result {
-        Ok(namespace) => Ok(Json(NamespaceGetResponse { namespace })),
-        Err(_) => Err(Json(NamespaceGetError::NamespaceNotFound)),
-    }
+    let namespace = store.lock().await.drill_down(namespace_path.as_str()).await?;
+    Ok(Json(NamespaceGetResponse { namespace }))
 }
 
-#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
-struct NamespaceGetResponse {
-    namespace: Arc<NamespaceNode>
+#[derive(Clone, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NamespaceSubtreeQuery {
+    /// How many levels below the namespace to list. Unlimited if absent.
+    pub max_depth: Option<usize>,
 }
 
 #[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
-enum NamespaceGetError {
-    NamespaceNotFound
+pub struct NamespaceListRecursiveResponse {
+    /// Full paths from the root, sorted so parents come first.
+    pub namespaces: Vec<String>,
+    /// Whether `max_depth` left out deeper namespaces.
+    pub truncated: bool,
 }
 
-pub async fn delete_namespace(State(store): State<Arc<NamespaceStore>>,
-request: request::Parts,
-) -> impl IntoResponse {
-    let namespace_path = request
-        .uri
-        .path()
-        .trim_matches('/')
-        .split('/')
-        .filter(|s| !s.is_empty())
-        .map(|s| s.to_string())
-        .collect::<Vec<String>>()
-        .join("/");
-    let namespace_delete_result = store.lock().await.delete_namespace(namespace_path.as_str()).await;
-    match namespace_delete_result {
-        Ok(_) => Ok(Json(NamespaceDeleteResponse)),
-        Err(_) => Err(Json(NamespaceDeleteError::NamespaceNotFound)),
-    }
+#[utoipa::path(
+    get,
+    path = "/namespaces/{path}/subtree",
+    responses(
+        (status = 200, description = "Every namespace below the namespace", body = NamespaceListRecursiveResponse),
+        (status = 404, description = "Namespace not found", body = ApiError)
+    ),
+    params(
+        ("path" = String, Path, description = "Namespace path, empty for the whole tree"),
+        NamespaceSubtreeQuery
+    ),
+    tag = "namespaces"
+)]
+#[tracing::instrument(skip_all, fields(namespace = %namespace_path))]
+pub async fn list_namespace_subtree(store: &NamespaceStore,
+namespace_path: &str,
+query: NamespaceSubtreeQuery,
+) -> Result<Json<NamespaceListRecursiveResponse>, ApiError> {
+    let manager = store.lock().await;
+    let node = manager.drill_down(namespace_path).await?;
+    let namespaces = manager.list_all_namespaces_recursive(namespace_path, query.max_depth).await?;
+    let truncated = query.max_depth.is_some_and(|max_depth| node.height() > max_depth);
+    Ok(Json(NamespaceListRecursiveResponse { namespaces, truncated }))
 }
 
-#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
-struct NamespaceDeleteResponse;
-
-#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
-enum NamespaceDeleteError {
-    NamespaceNotFound
+#[utoipa::path(
+    get,
+    path = "/namespaces/{path}/stats",
+    responses(
+        (status = 200, description = "Counts describing the namespace subtree", body = NamespaceStats),
+        (status = 404, description = "Namespace not found", body = ApiError)
+    ),
+    params(
+        ("path" = String, Path, description = "Namespace path, empty for the whole tree")
+    ),
+    tag = "namespaces"
+)]
+#[tracing::instrument(skip_all, fields(namespace = %namespace_path))]
+pub async fn get_namespace_stats(store: &NamespaceStore, namespace_path: &str) -> Result<Json<NamespaceStats>, ApiError> {
+    Ok(Json(store.lock().await.namespace_stats(namespace_path).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/namespaces/{path}/quota",
+    responses(
+        (status = 200, description = "The quota of the namespace, without limits if none was set", body = NamespaceQuota),
+        (status = 404, description = "Namespace not found", body = ApiError)
+    ),
+    params(
+        ("path" = String, Path, description = "Namespace path, empty for the root")
+    ),
+    tag = "namespaces"
+)]
+#[tracing::instrument(skip_all, fields(namespace = %namespace_path))]
+pub async fn get_namespace_quota(store: &NamespaceStore, namespace_path: &str) -> Result<Json<NamespaceQuota>, ApiError> {
+    Ok(Json(store.lock().await.quota(namespace_path).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = "/namespaces/{path}/quota",
+    request_body = NamespaceQuota,
+    responses(
+        (status = 200, description = "Quota set; it only limits namespaces created from now on", body = NamespaceQuota),
+        (status = 404, description = "Namespace not found", body = ApiError)
+    ),
+    params(
+        ("path" = String, Path, description = "Namespace path, empty for the root")
+    ),
+    tag = "namespaces"
+)]
+#[tracing::instrument(skip_all, fields(namespace = %request.uri.path()))]
+pub async fn set_namespace_quota(State(store): State<Arc<NamespaceStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+request: request::Parts,
+Json(quota): Json<NamespaceQuota>,
+) -> Result<Json<NamespaceQuota>, ApiError> {
+    let namespace_path = request_namespace_path(&request);
+    let Some(namespace_path) = strip_action(&namespace_path, "quota") else {
+        return Err(ApiError::new(StatusCode::METHOD_NOT_ALLOWED, "method_not_allowed", "Only quotas can be put on a namespace"));
+    };
+    store.lock().await.set_quota(namespace_path, quota.clone()).await?;
+    record_audit_event(
+        audit.as_ref(),
+        AuditEvent::new(request_actor(&user), AuditAction::SetNamespaceQuota, Uuid::nil(), "namespace")
+            .with_metadata("path", serde_json::Value::String(namespace_path.to_string())),
+    ).await;
+    Ok(Json(quota))
+}
+
+#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
+pub struct NamespaceGetResponse {
+    #[schema(value_type = NamespaceNode)]
+    pub namespace: Arc<NamespaceNode>
+}
+
+#[utoipa::path(
+    delete,
+    path = "/namespaces/{path}",
+    responses(
+        (status = 200, description = "Namespace and everything below it deleted", body = NamespaceDeleteResponse),
+        (status = 400, description = "Invalid namespace path", body = ApiError),
+        (status = 404, description = "Namespace not found", body = ApiError)
+    ),
+    params(
+        ("path" = String, Path, description = "Namespace path")
+    ),
+    tag = "namespaces"
+)]
+#[tracing::instrument(skip_all, fields(namespace = %request.uri.path()))]
+pub async fn delete_namespace(State(store): State<Arc<NamespaceStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+request: request::Parts,
+) -> Result<Json<NamespaceDeleteResponse>, ApiError> {
+    let namespace_path = request
+        .uri
+        .path()
+        .trim_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>()
+        .join("/");
+    let namespace_delete_result = store.lock().await.delete_namespace(namespace_path.as_str()).await;
+    match namespace_delete_result {
+        Ok(_) => {
+            record_audit_event(
+                audit.as_ref(),
+                AuditEvent::new(request_actor(&user), AuditAction::DeleteNamespace, Uuid::nil(), "namespace")
+                    .with_metadata("path", serde_json::Value::String(namespace_path)),
+            ).await;
+            Ok(Json(NamespaceDeleteResponse {}))
+        },
+        Err(err) => {
+            tracing::warn!(error = ?err, "Failed to delete namespace");
+            Err(ApiError::from(err))
+        },
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
+pub struct NamespaceDeleteResponse {}
+
+pub type ExportStore = dyn ExportService;
+
+/// The namespace path of a request to the `/*namespace_path` route.
+fn request_namespace_path(request: &request::Parts) -> String {
+    InMemoryNamespaceManager::parse_path(request.uri.path()).join("/")
+}
+
+#[derive(Clone, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NamespaceExportQuery {
+    /// Gzip the archive.
+    #[serde(default)]
+    pub compressed: bool,
+}
+
+#[derive(Clone, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NamespaceImportQuery {
+    /// What to do with items that already exist. Defaults to `fail`.
+    #[serde(default)]
+    #[param(value_type = Option<ConflictStrategy>)]
+    pub conflict_strategy: ConflictStrategy,
+}
+
+#[utoipa::path(
+    get,
+    path = "/namespaces/{path}/export",
+    responses(
+        (status = 200, description = "Archive of the namespace subtree", content(
+            ("application/json" = NamespaceExport),
+            ("application/gzip" = Vec<u8>)
+        )),
+        (status = 404, description = "Namespace not found", body = ApiError)
+    ),
+    params(
+        ("path" = String, Path, description = "Namespace path, empty for the whole tree"),
+        NamespaceExportQuery
+    ),
+    tag = "namespaces"
+)]
+#[tracing::instrument(skip_all, fields(namespace = %namespace_path))]
+pub async fn export_namespace(exports: &ExportStore,
+audit: &AuditStore,
+user: &Option<Extension<CurrentUser>>,
+namespace_path: &str,
+query: NamespaceExportQuery,
+) -> Result<axum::response::Response, ApiError> {
+    let export = exports.export_namespace(namespace_path).await?;
+    record_audit_event(
+        audit,
+        AuditEvent::new(request_actor(user), AuditAction::ExportNamespace, Uuid::nil(), "namespace")
+            .with_metadata("path", serde_json::Value::String(namespace_path.to_string())),
+    ).await;
+    if !query.compressed {
+        return Ok(Json(export).into_response());
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    serde_json::to_writer(&mut encoder, &export)
+        .map_err(|err| ExportError::RetrievalError(err.to_string()))?;
+    let archive = encoder.finish().map_err(|err| ExportError::RetrievalError(err.to_string()))?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/gzip"),
+            (header::CONTENT_DISPOSITION, "attachment; filename=\"namespace-export.json.gz\""),
+        ],
+        archive,
+    ).into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/namespaces/{path}/import",
+    request_body(content = NamespaceExport, description = "An archive from the export endpoint, gzipped or not"),
+    responses(
+        (status = 200, description = "Archive imported", body = ImportReport),
+        (status = 400, description = "Archive could not be read", body = ApiError),
+        (status = 409, description = "An item already exists and the conflict strategy is `fail`", body = ApiError)
+    ),
+    params(
+        ("path" = String, Path, description = "Namespace path to recreate the exported namespaces below"),
+        NamespaceImportQuery
+    ),
+    tag = "namespaces"
+)]
+#[tracing::instrument(skip_all, fields(namespace = %namespace_path))]
+pub async fn import_namespace(exports: &ExportStore,
+audit: &AuditStore,
+user: &Option<Extension<CurrentUser>>,
+namespace_path: &str,
+query: NamespaceImportQuery,
+body: &[u8],
+) -> Result<Json<ImportReport>, ApiError> {
+    let mut archive = Vec::new();
+    let json = if body.starts_with(&[0x1f, 0x8b]) {
+        GzDecoder::new(body).read_to_end(&mut archive)
+            .map_err(|err| ExportError::InvalidArchive(err.to_string()))?;
+        archive.as_slice()
+    } else {
+        body
+    };
+    let export: NamespaceExport = serde_json::from_slice(json).map_err(|err| ExportError::InvalidArchive(err.to_string()))?;
+    let report = exports.import_namespace(namespace_path, export, query.conflict_strategy).await?;
+    record_audit_event(
+        audit,
+        AuditEvent::new(request_actor(user), AuditAction::ImportNamespace, Uuid::nil(), "namespace")
+            .with_metadata("path", serde_json::Value::String(namespace_path.to_string())),
+    ).await;
+    Ok(Json(report))
+}
+
+#[derive(Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct NamespaceCopyRequest {
+    /// Path to copy the namespace to. Must not exist yet, nor be inside the namespace.
+    pub destination: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/namespaces/{path}/copy",
+    request_body = NamespaceCopyRequest,
+    responses(
+        (status = 201, description = "Namespace copied with everything below it, creating any missing parents of the destination", body = NamespaceCreateResponse),
+        (status = 400, description = "Invalid path, or the destination is inside the namespace", body = ApiError),
+        (status = 404, description = "Namespace not found", body = ApiError),
+        (status = 409, description = "The destination already exists", body = ApiError),
+        (status = 422, description = "The copy would exceed a namespace quota", body = ApiError)
+    ),
+    params(
+        ("path" = String, Path, description = "Namespace path to copy")
+    ),
+    tag = "namespaces"
+)]
+#[tracing::instrument(skip_all, fields(namespace = %namespace_path, destination = %copy_request.destination))]
+pub async fn copy_namespace(store: &NamespaceStore,
+audit: &AuditStore,
+user: &Option<Extension<CurrentUser>>,
+namespace_path: &str,
+copy_request: NamespaceCopyRequest,
+) -> Result<(StatusCode, Json<NamespaceCreateResponse>), ApiError> {
+    store.lock().await.copy_namespace(namespace_path, &copy_request.destination).await?;
+    record_audit_event(
+        audit,
+        AuditEvent::new(request_actor(user), AuditAction::CopyNamespace, Uuid::nil(), "namespace")
+            .with_metadata("path", serde_json::Value::String(namespace_path.to_string()))
+            .with_metadata("destination", serde_json::Value::String(copy_request.destination)),
+    ).await;
+    Ok((StatusCode::CREATED, Json(NamespaceCreateResponse {})))
+}
+
+/// Serves `POST /{path}/import` and `POST /{path}/copy`, as the catch-all route cannot be
+/// followed by a fixed segment.
+#[tracing::instrument(skip_all)]
+async fn post_namespace_action(State(store): State<Arc<NamespaceStore>>,
+Extension(exports): Extension<Arc<ExportStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Query(query): Query<NamespaceImportQuery>,
+request: request::Parts,
+body: axum::body::Bytes,
+) -> Result<axum::response::Response, ApiError> {
+    let namespace_path = request_namespace_path(&request);
+    if let Some(path) = strip_action(&namespace_path, "import") {
+        return import_namespace(exports.as_ref(), audit.as_ref(), &user, path, query, &body).await.map(IntoResponse::into_response);
+    }
+    if let Some(path) = strip_action(&namespace_path, "copy") {
+        let copy_request: NamespaceCopyRequest = serde_json::from_slice(&body)
+            .map_err(|err| ApiError::new(StatusCode::BAD_REQUEST, "invalid_request_body", err.to_string()))?;
+        return copy_namespace(store.as_ref(), audit.as_ref(), &user, path, copy_request).await.map(IntoResponse::into_response);
+    }
+    Err(ApiError::new(StatusCode::METHOD_NOT_ALLOWED, "method_not_allowed", "Only imports and copies can be posted to a namespace"))
+}
+
+/// `path` without its last segment, if that segment is `action`.
+fn strip_action<'a>(path: &'a str, action: &str) -> Option<&'a str> {
+    path.strip_suffix(action)
+        .filter(|parent| parent.is_empty() || parent.ends_with('/'))
+        .map(|parent| parent.trim_end_matches('/'))
 }
 
-pub fn namespace_router() -> Router {
-    let store = Arc::new(NamespaceStore::default());
+/// Serves `GET /{path}` and `GET /{path}/{export,subtree,stats,quota}`, as the catch-all route
+/// cannot be followed by a fixed segment.
+#[tracing::instrument(skip_all)]
+async fn get_namespace_or_export(State(store): State<Arc<NamespaceStore>>,
+Extension(exports): Extension<Arc<ExportStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Query(query): Query<NamespaceExportQuery>,
+Query(subtree_query): Query<NamespaceSubtreeQuery>,
+request: request::Parts,
+) -> Result<axum::response::Response, ApiError> {
+    let namespace_path = request_namespace_path(&request);
+    if let Some(path) = strip_action(&namespace_path, "export") {
+        return export_namespace(exports.as_ref(), audit.as_ref(), &user, path, query).await;
+    }
+    if let Some(path) = strip_action(&namespace_path, "subtree") {
+        return list_namespace_subtree(store.as_ref(), path, subtree_query).await.map(IntoResponse::into_response);
+    }
+    if let Some(path) = strip_action(&namespace_path, "stats") {
+        return get_namespace_stats(store.as_ref(), path).await.map(IntoResponse::into_response);
+    }
+    if let Some(path) = strip_action(&namespace_path, "quota") {
+        return get_namespace_quota(store.as_ref(), path).await.map(IntoResponse::into_response);
+    }
+    get_namespace(State(store), request).await.map(IntoResponse::into_response)
+}
+
+/// Namespace routes. A namespace whose last segment is `export`, `import`, `copy`, `subtree`,
+/// `stats` or `quota` is taken for the endpoint of that name of its parent.
+pub fn namespace_router(store: Arc<NamespaceStore>, exports: Arc<ExportStore>, audit: Arc<AuditStore>) -> Router {
     Router::new()
-        .route("/", routing::get(list_namespaces).post(create_namespace))
-        .route("/search", routing::get(search_namespaces))
-        .route("/*namespace_path", routing::get(get_namespace).delete(delete_namespace))
+        .route("/", routing::get(list_namespaces)
+            .route_layer(require_role(NAMESPACE_READ))
+            .merge(routing::post(create_namespace).route_layer(require_role(NAMESPACE_WRITE))))
+        .route("/search", routing::get(search_namespaces).route_layer(require_role(NAMESPACE_READ)))
+        .route("/events", routing::get(watch_namespaces).route_layer(require_role(NAMESPACE_READ)))
+        .route("/*namespace_path", routing::get(get_namespace_or_export)
+            .route_layer(require_role(NAMESPACE_READ))
+            .merge(routing::delete(delete_namespace)
+                .post(post_namespace_action)
+                .put(set_namespace_quota)
+                .route_layer(require_role(NAMESPACE_WRITE))))
+        .layer(Extension(exports))
+        .layer(Extension(audit))
         .with_state(store)
-}
\ No newline at end of file
+}
+
+pub type ComponentStore = dyn ComponentRepository;
+
+#[utoipa::path(
+    post,
+    path = "/components",
+    request_body = SDLCComponent,
+    responses(
+        (status = 201, description = "Component created successfully", body = SDLCComponent),
+        (status = 500, description = "Component could not be stored", body = ApiError)
+    ),
+    tag = "components"
+)]
+#[tracing::instrument(skip_all, fields(component_id = %component.id()))]
+pub async fn create_component(State(repository): State<Arc<ComponentStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Json(component): Json<SDLCComponent>,
+) -> impl IntoResponse {
+    match repository.store_component(component.clone()).await {
+        Ok(_) => {
+            record_audit_event(
+                audit.as_ref(),
+                AuditEvent::new(request_actor(&user), AuditAction::CreateComponent, component.id(), "component")
+                    .with_metadata("name", serde_json::Value::String(component.name().to_string())),
+            ).await;
+            tracing::info!("Created component");
+            Ok((StatusCode::CREATED, Json(component)))
+        },
+        Err(err) => {
+            tracing::warn!(error = %err, "Failed to create component");
+            Err(ApiError::from(err))
+        },
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/components",
+    responses(
+        (status = 200, description = "Components found", body = Vec<SDLCComponent>),
+        (status = 500, description = "Components could not be retrieved", body = ApiError)
+    ),
+    params(
+        ComponentSearchQuery
+    ),
+    tag = "components"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn list_components(State(repository): State<Arc<ComponentStore>>,
+Query(search): Query<ComponentSearchQuery>,
+) -> impl IntoResponse {
+    let component_list_result = match search.q {
+        Some(query) => repository.search_components(query.as_str()).await,
+        None => repository.list_components().await,
+    };
+    match component_list_result {
+        Ok(components) => Ok(Json(components)),
+        Err(err) => Err(ApiError::from(err)),
+    }
+}
+
+#[derive(Clone, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ComponentSearchQuery {
+    /// Only return components whose name contains this string.
+    pub q: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/components/{id}",
+    responses(
+        (status = 200, description = "Component found", body = SDLCComponent),
+        (status = 404, description = "Component not found", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Component ID")
+    ),
+    tag = "components"
+)]
+#[tracing::instrument(skip_all, fields(component_id = %id))]
+pub async fn get_component(State(repository): State<Arc<ComponentStore>>,
+Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match repository.get_component(&id).await {
+        Ok(Some(component)) => Ok(Json(component)),
+        Ok(None) => Err(ApiError::from(ComponentRepositoryError::NotFound)),
+        Err(err) => Err(ApiError::from(err)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/components/{id}",
+    responses(
+        (status = 204, description = "Component deleted successfully"),
+        (status = 404, description = "Component not found", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Component ID")
+    ),
+    tag = "components"
+)]
+#[tracing::instrument(skip_all, fields(component_id = %id))]
+pub async fn delete_component(State(repository): State<Arc<ComponentStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match repository.delete_component(&id).await {
+        Ok(_) => {
+            record_audit_event(
+                audit.as_ref(),
+                AuditEvent::new(request_actor(&user), AuditAction::DeleteComponent, id, "component"),
+            ).await;
+            tracing::info!("Deleted component");
+            Ok(StatusCode::NO_CONTENT)
+        },
+        Err(err) => {
+            tracing::warn!(error = %err, "Failed to delete component");
+            Err(ApiError::from(err))
+        },
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/components/{id}/merge",
+    request_body(content = SDLCComponent, description = "The same component as registered elsewhere"),
+    responses(
+        (status = 200, description = "Component merged and stored", body = SDLCComponent),
+        (status = 404, description = "Component not found", body = ApiError),
+        (status = 409, description = "Both components set some fields differently; nothing was stored", body = ApiError),
+        (status = 422, description = "The components are of different kinds", body = ApiError),
+        (status = 500, description = "Component could not be stored", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Component ID")
+    ),
+    tag = "components"
+)]
+#[tracing::instrument(skip_all, fields(component_id = %id, other_component_id = %other.id()))]
+pub async fn merge_component(State(repository): State<Arc<ComponentStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Path(id): Path<Uuid>,
+Json(other): Json<SDLCComponent>,
+) -> Result<Json<SDLCComponent>, ApiError> {
+    let mut component = repository.get_component(&id).await?.ok_or(ComponentRepositoryError::NotFound)?;
+    if let Err(err) = component.merge(&other) {
+        tracing::warn!(error = %err, "Rejected component merge");
+        return Err(ApiError::from(err));
+    }
+    repository.store_component(component.clone()).await?;
+    record_audit_event(
+        audit.as_ref(),
+        AuditEvent::new(request_actor(&user), AuditAction::MergeComponent, id, "component")
+            .with_metadata("merged_from", serde_json::Value::String(other.id().to_string())),
+    ).await;
+    tracing::info!("Merged component");
+    Ok(Json(component))
+}
+
+pub fn component_router(repository: Arc<ComponentStore>, audit: Arc<AuditStore>) -> Router {
+    Router::new()
+        .route("/", routing::get(list_components).post(create_component))
+        .route("/:id", routing::get(get_component).delete(delete_component))
+        .route("/:id/merge", routing::post(merge_component))
+        .layer(Extension(audit))
+        .with_state(repository)
+}
+
+pub type TeamStore = dyn TeamRepository;
+
+#[derive(Clone, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TeamListQuery {
+    /// Only return the teams owning this component.
+    pub component_id: Option<Uuid>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/teams",
+    request_body = TeamReference,
+    responses(
+        (status = 201, description = "Team created successfully", body = TeamReference),
+        (status = 500, description = "Team could not be stored", body = ApiError)
+    ),
+    tag = "teams"
+)]
+#[tracing::instrument(skip_all, fields(team_id = %team.id))]
+pub async fn create_team(State(repository): State<Arc<TeamStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Json(team): Json<TeamReference>,
+) -> Result<(StatusCode, Json<TeamReference>), ApiError> {
+    repository.store_team(team.clone()).await?;
+    record_audit_event(
+        audit.as_ref(),
+        AuditEvent::new(request_actor(&user), AuditAction::CreateTeam, team.id, "team")
+            .with_metadata("name", serde_json::Value::String(team.name.clone())),
+    ).await;
+    tracing::info!("Created team");
+    Ok((StatusCode::CREATED, Json(team)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/teams",
+    responses(
+        (status = 200, description = "Teams found", body = Vec<TeamReference>),
+        (status = 500, description = "Teams could not be retrieved", body = ApiError)
+    ),
+    params(
+        TeamListQuery
+    ),
+    tag = "teams"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn list_teams(State(repository): State<Arc<TeamStore>>,
+Query(query): Query<TeamListQuery>,
+) -> Result<Json<Vec<TeamReference>>, ApiError> {
+    let teams = match query.component_id {
+        Some(component_id) => repository.list_teams_for_component(&component_id).await?,
+        None => repository.list_teams().await?,
+    };
+    Ok(Json(teams))
+}
+
+#[utoipa::path(
+    get,
+    path = "/teams/{id}",
+    responses(
+        (status = 200, description = "Team found", body = TeamReference),
+        (status = 404, description = "Team not found", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Team ID")
+    ),
+    tag = "teams"
+)]
+#[tracing::instrument(skip_all, fields(team_id = %id))]
+pub async fn get_team(State(repository): State<Arc<TeamStore>>,
+Path(id): Path<Uuid>,
+) -> Result<Json<TeamReference>, ApiError> {
+    let team = repository.get_team(&id).await?.ok_or(TeamRepositoryError::NotFound)?;
+    Ok(Json(team))
+}
+
+#[utoipa::path(
+    put,
+    path = "/teams/{id}",
+    request_body = TeamReference,
+    responses(
+        (status = 200, description = "Team updated successfully", body = TeamReference),
+        (status = 400, description = "The body's ID does not match the path", body = ApiError),
+        (status = 404, description = "Team not found", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Team ID")
+    ),
+    tag = "teams"
+)]
+#[tracing::instrument(skip_all, fields(team_id = %id))]
+pub async fn update_team(State(repository): State<Arc<TeamStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Path(id): Path<Uuid>,
+Json(team): Json<TeamReference>,
+) -> Result<Json<TeamReference>, ApiError> {
+    if team.id != id {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, "team_id_mismatch", format!("Team ID {} does not match the path", team.id)));
+    }
+    repository.get_team(&id).await?.ok_or(TeamRepositoryError::NotFound)?;
+    repository.store_team(team.clone()).await?;
+    record_audit_event(
+        audit.as_ref(),
+        AuditEvent::new(request_actor(&user), AuditAction::UpdateTeam, id, "team"),
+    ).await;
+    tracing::info!("Updated team");
+    Ok(Json(team))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/teams/{id}",
+    responses(
+        (status = 204, description = "Team deleted successfully"),
+        (status = 404, description = "Team not found", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Team ID")
+    ),
+    tag = "teams"
+)]
+#[tracing::instrument(skip_all, fields(team_id = %id))]
+pub async fn delete_team(State(repository): State<Arc<TeamStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    repository.delete_team(&id).await?;
+    record_audit_event(
+        audit.as_ref(),
+        AuditEvent::new(request_actor(&user), AuditAction::DeleteTeam, id, "team"),
+    ).await;
+    tracing::info!("Deleted team");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn team_router(repository: Arc<TeamStore>, audit: Arc<AuditStore>) -> Router {
+    Router::new()
+        .route("/", routing::get(list_teams).post(create_team))
+        .route("/:id", routing::get(get_team).put(update_team).delete(delete_team))
+        .layer(Extension(audit))
+        .with_state(repository)
+}
+
+pub type WebhookStore = dyn WebhookService;
+
+#[derive(Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct WebhookSubscriptionRequest {
+    pub url: String,
+    pub events: Vec<TransitionAction>,
+    /// Key of the HMAC-SHA256 signature sent in `X-SDLCCP-Signature`.
+    pub secret: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    request_body = WebhookSubscriptionRequest,
+    responses(
+        (status = 201, description = "Webhook registered successfully", body = WebhookSubscription),
+        (status = 400, description = "Webhook URL is invalid", body = ApiError),
+        (status = 500, description = "Webhook could not be stored", body = ApiError)
+    ),
+    tag = "webhooks"
+)]
+#[tracing::instrument(skip_all, fields(url = %request.url))]
+pub async fn register_webhook(State(webhooks): State<Arc<WebhookStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Json(request): Json<WebhookSubscriptionRequest>,
+) -> Result<(StatusCode, Json<WebhookSubscription>), ApiError> {
+    let subscription = WebhookSubscription::new(request.url, request.events, request.secret);
+    webhooks.register_subscription(subscription.clone()).await?;
+    record_audit_event(
+        audit.as_ref(),
+        AuditEvent::new(request_actor(&user), AuditAction::RegisterWebhook, subscription.id, "webhook")
+            .with_metadata("url", serde_json::Value::String(subscription.url.clone())),
+    ).await;
+    tracing::info!(webhook_id = %subscription.id, "Registered webhook");
+    Ok((StatusCode::CREATED, Json(subscription)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    responses(
+        (status = 200, description = "Webhooks found", body = Vec<WebhookSubscription>),
+        (status = 500, description = "Webhooks could not be retrieved", body = ApiError)
+    ),
+    tag = "webhooks"
+)]
+pub async fn list_webhooks(State(webhooks): State<Arc<WebhookStore>>,
+) -> Result<Json<Vec<WebhookSubscription>>, ApiError> {
+    Ok(Json(webhooks.list_subscriptions().await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    responses(
+        (status = 204, description = "Webhook deactivated successfully"),
+        (status = 404, description = "Webhook not found", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Webhook subscription ID")
+    ),
+    tag = "webhooks"
+)]
+#[tracing::instrument(skip_all, fields(webhook_id = %id))]
+pub async fn deactivate_webhook(State(webhooks): State<Arc<WebhookStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    webhooks.deactivate_subscription(&id).await?;
+    record_audit_event(
+        audit.as_ref(),
+        AuditEvent::new(request_actor(&user), AuditAction::DeactivateWebhook, id, "webhook"),
+    ).await;
+    tracing::info!("Deactivated webhook");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn webhook_router(webhooks: Arc<WebhookStore>, audit: Arc<AuditStore>) -> Router {
+    Router::new()
+        .route("/", routing::get(list_webhooks).post(register_webhook))
+        .route("/:id", routing::delete(deactivate_webhook))
+        .layer(Extension(audit))
+        .with_state(webhooks)
+}
+
+pub type ReleaseStore = dyn ReleaseRepository;
+
+/// How often an idle watch stream sends a keepalive comment.
+const WATCH_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// State shared by the release handlers.
+#[derive(Clone)]
+pub struct ReleaseApiState {
+    pub releases: Arc<ReleaseStore>,
+    pub transitions: TransitionBroadcaster,
+}
+
+impl FromRef<ReleaseApiState> for Arc<ReleaseStore> {
+    fn from_ref(state: &ReleaseApiState) -> Self {
+        state.releases.clone()
+    }
+}
+
+impl FromRef<ReleaseApiState> for TransitionBroadcaster {
+    fn from_ref(state: &ReleaseApiState) -> Self {
+        state.transitions.clone()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct ReleaseCreateRequest {
+    pub component_id: Uuid,
+    pub version: String,
+    /// Who the release is recorded as created by. Defaults to the caller; the audit log
+    /// records the caller either way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sla: Option<DeploymentSLA>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/releases",
+    request_body = ReleaseCreateRequest,
+    responses(
+        (status = 201, description = "Release created successfully", body = SDLCRelease),
+        (status = 400, description = "The version is not a semantic version", body = ApiError),
+        (status = 500, description = "Release could not be stored", body = ApiError)
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(component_id = %release_create_request.component_id))]
+pub async fn create_release(State(releases): State<Arc<ReleaseStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Json(release_create_request): Json<ReleaseCreateRequest>,
+) -> impl IntoResponse {
+    let actor = request_actor(&user);
+    let created_by = release_create_request.created_by.unwrap_or_else(|| actor.clone());
+    let mut release = SDLCRelease::new(release_create_request.component_id, release_create_request.version, created_by)?;
+    release.sla = release_create_request.sla;
+    match releases.store_release(release.clone()).await {
+        Ok(_) => {
+            record_audit_event(
+                audit.as_ref(),
+                AuditEvent::new(actor, AuditAction::CreateRelease, release.id, "release")
+                    .with_metadata("version", serde_json::Value::String(release.version.clone())),
+            ).await;
+            tracing::info!(release_id = %release.id, "Created release");
+            Ok((StatusCode::CREATED, Json(release)))
+        },
+        Err(err) => {
+            tracing::warn!(error = %err, "Failed to create release");
+            Err(ApiError::from(err))
+        },
+    }
+}
+
+/// How many releases one `POST /releases/batch` may create.
+pub const MAX_RELEASE_BATCH_SIZE: usize = 100;
+
+#[derive(Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct BatchCreateReleasesRequest {
+    /// At most 100 releases.
+    pub releases: Vec<ReleaseCreateRequest>,
+    /// Stop at the first invalid release and create none of them. Otherwise every valid release
+    /// is created and the invalid ones are reported.
+    #[serde(default)]
+    pub fail_fast: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct BatchCreateReleasesResponse {
+    /// The releases created, in request order.
+    pub created: Vec<SDLCRelease>,
+    pub errors: Vec<BatchItemError>,
+}
+
+/// Why a release of a batch was not created.
+#[derive(Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct BatchItemError {
+    /// Position of the release in the request.
+    pub index: usize,
+    pub error: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/releases/batch",
+    request_body = BatchCreateReleasesRequest,
+    responses(
+        (status = 201, description = "Every release was created", body = BatchCreateReleasesResponse),
+        (status = 207, description = "The valid releases were created, the others are listed in `errors`", body = BatchCreateReleasesResponse),
+        (status = 400, description = "No release was created, see `errors`", body = BatchCreateReleasesResponse),
+        (status = 413, description = "The batch holds more than 100 releases", body = ApiError),
+        (status = 500, description = "Releases could not be stored; none were", body = ApiError)
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(count = request.releases.len(), fail_fast = request.fail_fast))]
+pub async fn create_releases_batch(State(releases): State<Arc<ReleaseStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Json(request): Json<BatchCreateReleasesRequest>,
+) -> Result<(StatusCode, Json<BatchCreateReleasesResponse>), ApiError> {
+    if request.releases.len() > MAX_RELEASE_BATCH_SIZE {
+        return Err(ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "batch_too_large",
+            format!("A batch holds at most {} releases, not {}", MAX_RELEASE_BATCH_SIZE, request.releases.len()),
+        ));
+    }
+    let actor = request_actor(&user);
+    let mut valid = Vec::new();
+    let mut errors = Vec::new();
+    for (index, item) in request.releases.into_iter().enumerate() {
+        let created_by = item.created_by.unwrap_or_else(|| actor.clone());
+        match SDLCRelease::new(item.component_id, item.version, created_by) {
+            Ok(release) => valid.push(SDLCRelease { sla: item.sla, ..release }),
+            Err(err) => {
+                errors.push(BatchItemError { index, error: err.to_string() });
+                if request.fail_fast {
+                    break;
+                }
+            },
+        }
+    }
+    // Everything is validated before anything is stored, and the repository stores the batch
+    // all or nothing, so failing fast leaves nothing behind to roll back.
+    if request.fail_fast && !errors.is_empty() {
+        valid.clear();
+    }
+    let created = if valid.is_empty() {
+        Vec::new()
+    } else {
+        releases.store_releases(valid).await.inspect_err(|err| tracing::warn!(error = %err, "Failed to create releases"))?
+    };
+    for release in &created {
+        record_audit_event(
+            audit.as_ref(),
+            AuditEvent::new(actor.clone(), AuditAction::CreateRelease, release.id, "release")
+                .with_metadata("version", serde_json::Value::String(release.version.clone())),
+        ).await;
+    }
+    tracing::info!(created = created.len(), failed = errors.len(), "Created releases");
+    let status = match (created.is_empty(), errors.is_empty()) {
+        (_, true) => StatusCode::CREATED,
+        (true, false) => StatusCode::BAD_REQUEST,
+        (false, false) => StatusCode::MULTI_STATUS,
+    };
+    Ok((status, Json(BatchCreateReleasesResponse { created, errors })))
+}
+
+/// How many releases a page holds unless `limit` says otherwise.
+const DEFAULT_RELEASE_PAGE_SIZE: usize = 100;
+
+#[derive(Clone, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReleasePageQuery {
+    /// How many releases to return at most, at least one. Defaults to 100.
+    pub limit: Option<usize>,
+    /// The `next_cursor` of the previous page. Starts from the oldest release if absent.
+    pub cursor: Option<Uuid>,
+}
+
+#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
+pub struct ReleaseListResponse {
+    /// Oldest first.
+    pub releases: Vec<SDLCRelease>,
+    /// Pass as `cursor` to get the next page. Absent on the last page.
+    pub next_cursor: Option<Uuid>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases",
+    responses(
+        (status = 200, description = "A page of the releases matching the filter", body = ReleaseListResponse),
+        (status = 400, description = "The cursor is not a release matching the filter", body = ApiError),
+        (status = 500, description = "Releases could not be retrieved", body = ApiError)
+    ),
+    params(
+        ReleaseFilter,
+        ReleasePageQuery
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn list_releases(State(releases): State<Arc<ReleaseStore>>,
+Query(filter): Query<ReleaseFilter>,
+Query(page): Query<ReleasePageQuery>,
+) -> Result<Json<ReleaseListResponse>, ApiError> {
+    let matching = releases.list_releases_filtered(filter).await?;
+    let start = match page.cursor {
+        Some(cursor) => match matching.iter().position(|release| release.id == cursor) {
+            Some(position) => position + 1,
+            None => return Err(ApiError::new(StatusCode::BAD_REQUEST, "invalid_cursor", format!("Release {} does not match the filter", cursor))),
+        },
+        None => 0,
+    };
+    let limit = page.limit.unwrap_or(DEFAULT_RELEASE_PAGE_SIZE).max(1);
+    let releases: Vec<SDLCRelease> = matching.into_iter().skip(start).take(limit.saturating_add(1)).collect();
+    let next_cursor = (releases.len() > limit).then(|| releases[limit - 1].id);
+    Ok(Json(ReleaseListResponse { releases: releases.into_iter().take(limit).collect(), next_cursor }))
+}
+
+/// How many releases a search returns unless `limit` says otherwise.
+const DEFAULT_RELEASE_SEARCH_LIMIT: usize = 20;
+
+#[derive(Clone, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReleaseSearchQuery {
+    /// Text to look for in the version, component name, commit hash (as a prefix) and tags.
+    pub q: String,
+    /// How many releases to return at most, at least one. Defaults to 20.
+    pub limit: Option<usize>,
+}
+
+#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
+pub struct ReleaseSearchResponse {
+    /// Releases matching the most fields first, newest first among equals.
+    pub releases: Vec<SDLCRelease>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/search",
+    responses(
+        (status = 200, description = "The releases matching the query, best match first", body = ReleaseSearchResponse),
+        (status = 400, description = "The query is missing or blank", body = ApiError),
+        (status = 500, description = "Releases or components could not be retrieved", body = ApiError)
+    ),
+    params(
+        ReleaseSearchQuery
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(query = %query.q))]
+pub async fn search_releases(State(releases): State<Arc<ReleaseStore>>,
+Extension(components): Extension<Arc<ComponentStore>>,
+Query(query): Query<ReleaseSearchQuery>,
+) -> Result<Json<ReleaseSearchResponse>, ApiError> {
+    if query.q.trim().is_empty() {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, "invalid_query", "The search query must not be blank"));
+    }
+    let component_ids = components.search_components(&query.q).await?.iter().map(SDLCComponent::id).collect();
+    let limit = query.limit.unwrap_or(DEFAULT_RELEASE_SEARCH_LIMIT).max(1);
+    let releases = releases.search_releases(&query.q, &component_ids, limit).await?;
+    Ok(Json(ReleaseSearchResponse { releases }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}",
+    responses(
+        (status = 200, description = "Release found, with how long it spent in each phase", body = ReleaseDetailResponse),
+        (status = 404, description = "Release not found", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn get_release(State(releases): State<Arc<ReleaseStore>>,
+Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match releases.get_release(&id).await {
+        Ok(Some(release)) => Ok((release_etag(&release), Json(ReleaseDetailResponse::new(release)))),
+        Ok(None) => Err(ApiError::from(ReleaseRepositoryError::NotFound)),
+        Err(err) => Err(ApiError::from(err)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}/lineage",
+    responses(
+        (status = 200, description = "The release followed by the releases it was branched from, nearest first", body = Vec<SDLCRelease>),
+        (status = 404, description = "Release not found", body = ApiError),
+        (status = 500, description = "Releases could not be retrieved", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn get_release_lineage(State(releases): State<Arc<ReleaseStore>>,
+Path(id): Path<Uuid>,
+) -> Result<Json<Vec<SDLCRelease>>, ApiError> {
+    let mut lineage: Vec<SDLCRelease> = Vec::new();
+    let mut next = Some(id);
+    while let Some(release_id) = next {
+        // Releases never get a parent assigned after the fact, but a hand-edited store could loop.
+        if lineage.iter().any(|release| release.id == release_id) {
+            break;
+        }
+        match releases.get_release(&release_id).await? {
+            Some(release) => {
+                next = release.parent_release_id;
+                lineage.push(release);
+            },
+            None if lineage.is_empty() => return Err(ApiError::from(ReleaseRepositoryError::NotFound)),
+            None => {
+                tracing::warn!(parent_release_id = %release_id, "Parent release is missing, lineage ends early");
+                break;
+            },
+        }
+    }
+    Ok(Json(lineage))
+}
+
+/// A release with the time it spent in its phases, computed from its transition history.
+#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
+pub struct ReleaseDetailResponse {
+    #[serde(flatten)]
+    pub release: SDLCRelease,
+    /// Seconds spent in each phase the release has left, keyed by phase name.
+    pub phase_durations: HashMap<String, i64>,
+    /// Seconds from entering Development to first entering Deploy; absent until then.
+    pub total_duration_to_deploy_secs: Option<i64>,
+}
+
+impl ReleaseDetailResponse {
+    pub fn new(release: SDLCRelease) -> Self {
+        Self {
+            phase_durations: phase_duration_secs(&release),
+            total_duration_to_deploy_secs: release.total_duration_to_deploy().map(|duration| duration.num_seconds()),
+            release,
+        }
+    }
+}
+
+fn phase_duration_secs(release: &SDLCRelease) -> HashMap<String, i64> {
+    release.phase_durations().into_iter().map(|(phase, duration)| (phase, duration.num_seconds())).collect()
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}/phase-durations",
+    responses(
+        (status = 200, description = "Seconds spent in each phase the release has left, keyed by phase name", body = HashMap<String, i64>),
+        (status = 404, description = "Release not found", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn get_release_phase_durations(State(releases): State<Arc<ReleaseStore>>,
+Path(id): Path<Uuid>,
+) -> Result<Json<HashMap<String, i64>>, ApiError> {
+    let release = releases.get_release(&id).await?.ok_or(ReleaseRepositoryError::NotFound)?;
+    Ok(Json(phase_duration_secs(&release)))
+}
+
+#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
+pub struct ReleaseNotesResponse {
+    pub release_id: Uuid,
+    /// Absent until the notes are written or the release is released.
+    pub release_notes: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}/release-notes",
+    responses(
+        (status = 200, description = "The release's notes, kept in every state after it was released", body = ReleaseNotesResponse),
+        (status = 404, description = "Release not found", body = ApiError),
+        (status = 500, description = "Release could not be retrieved", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn get_release_notes(State(releases): State<Arc<ReleaseStore>>,
+Path(id): Path<Uuid>,
+) -> Result<Json<ReleaseNotesResponse>, ApiError> {
+    let release = releases.get_release(&id).await?.ok_or(ReleaseRepositoryError::NotFound)?;
+    Ok(Json(ReleaseNotesResponse { release_id: release.id, release_notes: release.release_notes }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/sla-breaches",
+    responses(
+        (status = 200, description = "Every release past its deployment SLA, longest overdue first", body = Vec<SLABreach>),
+        (status = 500, description = "Releases could not be listed", body = ApiError)
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn get_sla_breaches(State(releases): State<Arc<ReleaseStore>>) -> Result<Json<Vec<SLABreach>>, ApiError> {
+    Ok(Json(sla_breaches_at(releases.as_ref(), Utc::now()).await?))
+}
+
+#[derive(Clone, Deserialize, Serialize, JsonSchema, ToSchema)]
+pub struct ReleaseEventsResponse {
+    pub release_id: Uuid,
+    /// Oldest first.
+    pub events: Vec<ReleaseEvent>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}/events",
+    responses(
+        (status = 200, description = "Every change made to the release, including its deletion", body = ReleaseEventsResponse),
+        (status = 404, description = "Release not found", body = ApiError),
+        (status = 501, description = "The release store only keeps the latest state of each release", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn get_release_events(State(releases): State<Arc<ReleaseStore>>,
+Path(id): Path<Uuid>,
+) -> Result<Json<ReleaseEventsResponse>, ApiError> {
+    let events = releases.event_log(&id).await?.ok_or_else(|| {
+        ApiError::new(StatusCode::NOT_IMPLEMENTED, "event_log_unavailable", "The release store does not keep an event log")
+    })?;
+    if events.is_empty() {
+        return Err(ReleaseRepositoryError::NotFound.into());
+    }
+    Ok(Json(ReleaseEventsResponse { release_id: id, events }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}/snapshots",
+    responses(
+        (status = 200, description = "The release as it was before each of its transitions, oldest first", body = Vec<ReleaseSnapshot>),
+        (status = 404, description = "Release not found", body = ApiError),
+        (status = 500, description = "Release could not be retrieved", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn get_release_snapshots(State(releases): State<Arc<ReleaseStore>>,
+Path(id): Path<Uuid>,
+) -> Result<Json<Vec<ReleaseSnapshot>>, ApiError> {
+    match releases.get_release(&id).await? {
+        Some(release) => Ok(Json(release.snapshots)),
+        None => Err(ApiError::from(ReleaseRepositoryError::NotFound)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}/diff/{other_id}",
+    responses(
+        (status = 200, description = "How the other release differs from this one", body = ReleaseDiff),
+        (status = 404, description = "Either release not found", body = ApiError),
+        (status = 500, description = "Releases could not be retrieved", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID"),
+        ("other_id" = Uuid, Path, description = "ID of the release to compare it to")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id, other_release_id = %other_id))]
+pub async fn get_release_diff(State(releases): State<Arc<ReleaseStore>>,
+Path((id, other_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ReleaseDiff>, ApiError> {
+    let release = releases.get_release(&id).await?.ok_or(ReleaseRepositoryError::NotFound)?;
+    let other = releases.get_release(&other_id).await?.ok_or(ReleaseRepositoryError::NotFound)?;
+    Ok(Json(release.compare(&other)))
+}
+
+/// Document format served by `GET /releases/{id}/sbom`.
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SbomOutputFormat {
+    /// CycloneDX 1.4 JSON.
+    #[default]
+    CycloneDx,
+    /// SPDX 2.3 JSON.
+    Spdx,
+}
+
+#[derive(Clone, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SbomQuery {
+    #[serde(default)]
+    pub format: SbomOutputFormat,
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}/sbom",
+    responses(
+        (status = 200, description = "SBOM listing every release the release depends on, directly or transitively", content(
+            ("application/vnd.cyclonedx+json; version=1.4" = CycloneDxBom),
+            ("application/spdx+json" = SpdxDocument)
+        )),
+        (status = 404, description = "Release not found", body = ApiError),
+        (status = 500, description = "Releases or components could not be retrieved", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID"),
+        SbomQuery
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn get_release_sbom(State(releases): State<Arc<ReleaseStore>>,
+Extension(components): Extension<Arc<ComponentStore>>,
+Path(id): Path<Uuid>,
+Query(query): Query<SbomQuery>,
+) -> Result<axum::response::Response, ApiError> {
+    let release = releases.get_release(&id).await?.ok_or(ReleaseRepositoryError::NotFound)?;
+    let dependencies = releases.dependency_graph(&id).await?;
+    let mut resolved: HashMap<Uuid, Option<SDLCComponent>> = HashMap::new();
+    for component_id in std::iter::once(&release).chain(&dependencies).map(|release| release.component_id) {
+        if let std::collections::hash_map::Entry::Vacant(entry) = resolved.entry(component_id) {
+            entry.insert(components.get_component(&component_id).await?);
+        }
+    }
+    let subject = |release| SbomSubject {
+        release,
+        component: resolved.get(&release.component_id).and_then(Option::as_ref),
+    };
+    let dependencies: Vec<SbomSubject> = dependencies.iter().map(subject).collect();
+    Ok(match query.format {
+        SbomOutputFormat::CycloneDx => {
+            ([(header::CONTENT_TYPE, CYCLONEDX_CONTENT_TYPE)], Json(CycloneDxBom::new(subject(&release), &dependencies))).into_response()
+        },
+        SbomOutputFormat::Spdx => {
+            ([(header::CONTENT_TYPE, SPDX_CONTENT_TYPE)], Json(SpdxDocument::new(subject(&release), &dependencies))).into_response()
+        },
+    })
+}
+
+/// SBOM routes, nested below `/releases` next to the `release_router`.
+pub fn sbom_router(releases: Arc<ReleaseStore>, components: Arc<ComponentStore>) -> Router {
+    Router::new()
+        .route("/:id/sbom", routing::get(get_release_sbom))
+        .layer(Extension(components))
+        .with_state(releases)
+}
+
+/// Release search, nested below `/releases` next to the `release_router`. Kept apart because
+/// it also needs the components, to match on their names.
+pub fn release_search_router(releases: Arc<ReleaseStore>, components: Arc<ComponentStore>) -> Router {
+    Router::new()
+        .route("/search", routing::get(search_releases))
+        .layer(Extension(components))
+        .with_state(releases)
+}
+
+#[utoipa::path(
+    put,
+    path = "/releases/{id}/tags/{tag}",
+    responses(
+        (status = 200, description = "Tag added", body = SDLCRelease),
+        (status = 400, description = "Tag is not 1-64 ASCII letters, digits or hyphens", body = ApiError),
+        (status = 404, description = "Release not found", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID"),
+        ("tag" = String, Path, description = "Tag to add")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id, tag = %tag))]
+pub async fn add_release_tag(State(releases): State<Arc<ReleaseStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Path((id, tag)): Path<(Uuid, String)>,
+) -> Result<Json<SDLCRelease>, ApiError> {
+    let mut release = match releases.get_release(&id).await {
+        Ok(Some(release)) => release,
+        Ok(None) => return Err(ApiError::from(ReleaseRepositoryError::NotFound)),
+        Err(err) => return Err(ApiError::from(err)),
+    };
+    release.add_tag(&tag)?;
+    update_release_tags(releases.as_ref(), audit.as_ref(), &user, release, AuditAction::TagRelease, tag).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/releases/{id}/tags/{tag}",
+    responses(
+        (status = 200, description = "Tag removed, or the release did not carry it", body = SDLCRelease),
+        (status = 404, description = "Release not found", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID"),
+        ("tag" = String, Path, description = "Tag to remove")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id, tag = %tag))]
+pub async fn remove_release_tag(State(releases): State<Arc<ReleaseStore>>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+Path((id, tag)): Path<(Uuid, String)>,
+) -> Result<Json<SDLCRelease>, ApiError> {
+    let mut release = match releases.get_release(&id).await {
+        Ok(Some(release)) => release,
+        Ok(None) => return Err(ApiError::from(ReleaseRepositoryError::NotFound)),
+        Err(err) => return Err(ApiError::from(err)),
+    };
+    release.remove_tag(&tag);
+    update_release_tags(releases.as_ref(), audit.as_ref(), &user, release, AuditAction::UntagRelease, tag).await
+}
+
+/// Stores a release whose tags changed and records the change in the audit trail.
+async fn update_release_tags(releases: &ReleaseStore,
+audit: &AuditStore,
+user: &Option<Extension<CurrentUser>>,
+release: SDLCRelease,
+action: AuditAction,
+tag: String,
+) -> Result<Json<SDLCRelease>, ApiError> {
+    let revision = release.revision;
+    let release = match releases.update_release(release, revision).await {
+        Ok(release) => release,
+        Err(err) => {
+            tracing::warn!(error = %err, "Failed to store release tags");
+            return Err(ApiError::from(err));
+        },
+    };
+    record_audit_event(
+        audit,
+        AuditEvent::new(request_actor(user), action, release.id, "release")
+            .with_metadata("tag", serde_json::Value::String(tag)),
+    ).await;
+    Ok(Json(release))
+}
+
+/// The `ETag` of a release: its revision, quoted.
+fn release_etag(release: &SDLCRelease) -> [(HeaderName, String); 1] {
+    [(header::ETAG, format!("\"{}\"", release.revision))]
+}
+
+/// The revision a client expects, from its `If-Match: "<revision>"` header.
+fn if_match_revision(headers: &HeaderMap) -> Result<u64, ApiError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Err(ApiError::new(StatusCode::PRECONDITION_REQUIRED, "if_match_required", "Send the release's ETag in an If-Match header"));
+    };
+    value.to_str().ok()
+        .map(|value| value.trim().trim_start_matches("W/").trim_matches('"'))
+        .and_then(|revision| revision.parse().ok())
+        .ok_or_else(|| ApiError::new(StatusCode::BAD_REQUEST, "invalid_if_match", "If-Match must be an ETag returned for the release"))
+}
+
+#[utoipa::path(
+    post,
+    path = "/releases/{id}/transitions",
+    request_body = TransitionRequest,
+    responses(
+        (status = 200, description = "Transition applied", body = SDLCRelease),
+        (status = 404, description = "Release not found", body = ApiError),
+        (status = 409, description = "Transition not allowed in the current phase and state", body = ApiError),
+        (status = 412, description = "Release changed while the transition was applied", body = ApiError),
+        (status = 422, description = "Release is missing details its phase requires, or the feature list is outside the configured limits", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn transition_release(State(releases): State<Arc<ReleaseStore>>,
+State(transitions): State<TransitionBroadcaster>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+limits: Option<Extension<FeatureListLimits>>,
+Path(id): Path<Uuid>,
+Json(transition): Json<TransitionRequest>,
+) -> impl IntoResponse {
+    let limits = limits.map(|Extension(limits)| limits).unwrap_or_default();
+    let release = apply_release_transition(releases.as_ref(), &transitions, audit.as_ref(), &user, limits, id, transition, None).await?;
+    Ok::<_, ApiError>((release_etag(&release), Json(release)))
+}
+
+/// Body of `PATCH /releases/{id}/phase-transition`, the same tagged union `POST /transitions` takes.
+pub type PatchReleaseRequest = TransitionRequest;
+
+#[utoipa::path(
+    patch,
+    path = "/releases/{id}/phase-transition",
+    request_body = TransitionRequest,
+    responses(
+        (status = 200, description = "Transition applied", body = SDLCRelease),
+        (status = 400, description = "If-Match is not a release ETag", body = ApiError),
+        (status = 404, description = "Release not found", body = ApiError),
+        (status = 409, description = "Transition not allowed in the current phase and state", body = ApiError),
+        (status = 412, description = "Release changed since the ETag in If-Match was read", body = ApiError),
+        (status = 422, description = "Release is missing details its phase requires, or the feature list is outside the configured limits", body = ApiError),
+        (status = 428, description = "If-Match header missing", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID"),
+        ("If-Match" = String, Header, description = "ETag of the release the transition was based on")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+#[allow(clippy::too_many_arguments)]
+pub async fn patch_release_phase(State(releases): State<Arc<ReleaseStore>>,
+State(transitions): State<TransitionBroadcaster>,
+Extension(audit): Extension<Arc<AuditStore>>,
+user: Option<Extension<CurrentUser>>,
+limits: Option<Extension<FeatureListLimits>>,
+Path(id): Path<Uuid>,
+headers: HeaderMap,
+Json(request): Json<PatchReleaseRequest>,
+) -> impl IntoResponse {
+    let expected_revision = if_match_revision(&headers)?;
+    let limits = limits.map(|Extension(limits)| limits).unwrap_or_default();
+    let release = apply_release_transition(releases.as_ref(), &transitions, audit.as_ref(), &user, limits, id, request, Some(expected_revision)).await?;
+    Ok::<_, ApiError>((release_etag(&release), Json(release)))
+}
+
+/// Applies `transition` to a stored release, records and publishes it. With an expected
+/// revision the release must still be at it; without one it must not change while the
+/// transition is applied. A feature list outside `limits` is rejected before the release is read.
+#[allow(clippy::too_many_arguments)]
+async fn apply_release_transition(releases: &ReleaseStore,
+transitions: &TransitionBroadcaster,
+audit: &AuditStore,
+user: &Option<Extension<CurrentUser>>,
+limits: FeatureListLimits,
+id: Uuid,
+transition: TransitionRequest,
+expected_revision: Option<u64>,
+) -> Result<SDLCRelease, ApiError> {
+    if let TransitionRequest::StartDevelopment { feature_list, .. } = &transition {
+        if let Err(err) = limits.validate(feature_list) {
+            tracing::warn!(error = %err, "Rejected feature list");
+            return Err(ApiError::from(TransitionError::FeatureListError(err)));
+        }
+    }
+    let mut release = match releases.get_release(&id).await {
+        Ok(Some(release)) => release,
+        Ok(None) => return Err(ApiError::from(TransitionError::ReleaseNotFound)),
+        Err(err) => return Err(ApiError::from(TransitionError::StorageError(err.to_string()))),
+    };
+    let expected_revision = expected_revision.unwrap_or(release.revision);
+    if release.revision != expected_revision {
+        tracing::warn!(expected_revision, revision = release.revision, "Rejected transition of a stale release");
+        return Err(ApiError::from(ReleaseRepositoryError::RevisionMismatch { expected: expected_revision, actual: release.revision }));
+    }
+    // Refuse to build on a release whose earlier phases left their details incomplete.
+    if let Err(errors) = release.validate_phase_completeness() {
+        tracing::warn!(?errors, "Rejected transition of an incomplete release");
+        return Err(ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, "incomplete_phase_details", "Release is missing details its phase requires")
+            .with_details(serde_json::json!(errors)));
+    }
+    let from_phase = release.phase_name().to_string();
+    let event = match transition.apply_with_limits(&mut release, limits) {
+        Ok(event) => event,
+        Err(err) => {
+            tracing::warn!(error = %err, "Rejected release transition");
+            return Err(ApiError::from(err));
+        },
+    };
+    let release = match releases.update_release(release, expected_revision).await {
+        Ok(release) => release,
+        Err(err) => {
+            tracing::warn!(error = %err, "Failed to store release transition");
+            return Err(ApiError::from(err));
+        },
+    };
+
+    record_audit_event(
+        audit,
+        AuditEvent::new(request_actor(user), AuditAction::TransitionRelease, id, "release")
+            .with_metadata("from_state", serde_json::Value::String(event.from_state.clone()))
+            .with_metadata("to_state", serde_json::Value::String(event.to_state.clone())),
+    ).await;
+    tracing::info!(from_state = %event.from_state, to_state = %event.to_state, "Transitioned release");
+    record_transition(&from_phase, release.phase_name(), &event);
+    transitions.publish(event);
+    Ok(release)
+}
+
+#[utoipa::path(
+    get,
+    path = "/releases/{id}/watch",
+    responses(
+        (status = 200, description = "Stream of `transition` events carrying a TransitionEvent", body = TransitionEvent, content_type = "text/event-stream"),
+        (status = 404, description = "Release not found", body = ApiError)
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Release ID")
+    ),
+    tag = "releases"
+)]
+#[tracing::instrument(skip_all, fields(release_id = %id))]
+pub async fn watch_release(State(releases): State<Arc<ReleaseStore>>,
+State(transitions): State<TransitionBroadcaster>,
+Path(id): Path<Uuid>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    match releases.get_release(&id).await {
+        Ok(Some(_)) => {},
+        Ok(None) => return Err(ApiError::from(ReleaseRepositoryError::NotFound)),
+        Err(err) => return Err(ApiError::from(err)),
+    }
+
+    // Subscribe before returning so no transition after this point is missed.
+    let events = BroadcastStream::new(transitions.subscribe()).filter_map(move |event| match event {
+        Ok(event) if event.release_id == id => Some(Ok(Event::default().event("transition").json_data(event).ok()?)),
+        Ok(_) => None,
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+            tracing::warn!(skipped, "Release watcher lagged behind");
+            None
+        },
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::new().interval(WATCH_KEEPALIVE_INTERVAL)))
+}
+
+pub fn release_router(releases: Arc<ReleaseStore>, transitions: TransitionBroadcaster, audit: Arc<AuditStore>) -> Router {
+    Router::new()
+        .route("/", routing::post(create_release).get(list_releases))
+        .route("/batch", routing::post(create_releases_batch))
+        .route("/sla-breaches", routing::get(get_sla_breaches))
+        .route("/:id", routing::get(get_release))
+        .route("/:id/lineage", routing::get(get_release_lineage))
+        .route("/:id/release-notes", routing::get(get_release_notes))
+        .route("/:id/phase-durations", routing::get(get_release_phase_durations))
+        .route("/:id/events", routing::get(get_release_events))
+        .route("/:id/snapshots", routing::get(get_release_snapshots))
+        .route("/:id/diff/:other_id", routing::get(get_release_diff))
+        .route("/:id/tags/:tag", routing::put(add_release_tag).delete(remove_release_tag))
+        .route("/:id/transitions", routing::post(transition_release))
+        .route("/:id/phase-transition", routing::patch(patch_release_phase))
+        .route("/:id/watch", routing::get(watch_release))
+        .layer(Extension(audit))
+        .with_state(ReleaseApiState { releases, transitions })
+}
+
+#[utoipa::path(
+    get,
+    path = "/audit",
+    responses(
+        (status = 200, description = "Audit events matching the filter", body = Vec<AuditEvent>),
+        (status = 500, description = "Audit events could not be retrieved", body = ApiError)
+    ),
+    params(
+        AuditFilter
+    ),
+    tag = "audit"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn query_audit_log(State(audit): State<Arc<AuditStore>>,
+Query(filter): Query<AuditFilter>,
+) -> impl IntoResponse {
+    match audit.query(filter).await {
+        Ok(events) => Ok(Json(events)),
+        Err(err) => Err(ApiError::from(err)),
+    }
+}
+
+pub fn audit_router(audit: Arc<AuditStore>) -> Router {
+    Router::new()
+        .route("/", routing::get(query_audit_log))
+        .with_state(audit)
+}
@@ -0,0 +1,337 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::model::{ReleaseSnapshot, SDLCRelease, TransitionRecord};
+
+use super::release_repository::{ReleaseFilter, ReleaseRepository, ReleaseRepositoryError};
+
+/// One entry of the journal kept by `EventSourcingReleaseRepository`.
+#[derive(Debug, Clone, JsonSchema, ToSchema, Serialize, Deserialize)]
+pub struct ReleaseEvent {
+    pub release_id: Uuid,
+    /// Position in the journal, counting every release's events from zero.
+    pub sequence: u64,
+    pub recorded_at: DateTime<Utc>,
+    /// Revision of the release after this event.
+    pub revision: u64,
+    pub kind: ReleaseEventKind,
+}
+
+/// What happened to the release. Only `Created` carries a whole release; every later event
+/// carries just what changed, so the release is rebuilt by replaying its events in order.
+#[derive(Debug, Clone, JsonSchema, ToSchema, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ReleaseEventKind {
+    /// The release was stored for the first time.
+    Created { release: Box<SDLCRelease> },
+    /// The release made one transition.
+    Transitioned(Box<TransitionRecord>),
+    /// Fields of the release changed other than by a transition, e.g. it was tagged. Each
+    /// changed field maps to its new JSON value, or `null` if it was removed.
+    Updated {
+        #[schema(value_type = Object)]
+        changes: serde_json::Map<String, serde_json::Value>,
+    },
+    Deleted,
+}
+
+impl ReleaseEvent {
+    /// Replays this event on `release`, the release as of the event before. `None` if the release
+    /// does not exist afterwards.
+    fn apply(&self, release: Option<SDLCRelease>) -> Result<Option<SDLCRelease>, ReleaseRepositoryError> {
+        let mut release = match (&self.kind, release) {
+            (ReleaseEventKind::Created { release }, _) => release.as_ref().clone(),
+            (ReleaseEventKind::Deleted, _) => return Ok(None),
+            (ReleaseEventKind::Transitioned(record), Some(release)) => replay_transition(release, record),
+            (ReleaseEventKind::Updated { changes }, Some(release)) => apply_changes(release, changes)?,
+            (_, None) => return Err(ReleaseRepositoryError::RetrievalError(format!("Event {} changes a release that does not exist", self.sequence))),
+        };
+        release.revision = self.revision;
+        Ok(Some(release))
+    }
+}
+
+/// `release` after making the transition `record` describes, snapshotted first like the
+/// transition itself did.
+fn replay_transition(mut release: SDLCRelease, record: &TransitionRecord) -> SDLCRelease {
+    release.snapshots.push(ReleaseSnapshot {
+        taken_at: record.timestamp,
+        phase: release.phase.clone(),
+        state: release.state.clone(),
+        phase_details: release.phase_details.clone(),
+    });
+    release.transition_history.push(record.clone());
+    release.phase = record.to_phase.clone();
+    release.state = record.to_state.clone();
+    release
+}
+
+fn apply_changes(release: SDLCRelease, changes: &serde_json::Map<String, serde_json::Value>) -> Result<SDLCRelease, ReleaseRepositoryError> {
+    let mut fields = to_fields(&release)?;
+    for (field, value) in changes {
+        if value.is_null() {
+            fields.remove(field);
+        } else {
+            fields.insert(field.clone(), value.clone());
+        }
+    }
+    serde_json::from_value(serde_json::Value::Object(fields)).map_err(|err| ReleaseRepositoryError::RetrievalError(err.to_string()))
+}
+
+/// The fields of `after` that differ from `before`, as `apply_changes` takes them. The revision
+/// is left out, every event carries it.
+fn changed_fields(before: &SDLCRelease, after: &SDLCRelease) -> Result<serde_json::Map<String, serde_json::Value>, ReleaseRepositoryError> {
+    let (mut before, after) = (to_fields(before)?, to_fields(after)?);
+    let mut changes: serde_json::Map<String, serde_json::Value> = after.into_iter()
+        .filter(|(field, value)| before.remove(field).as_ref() != Some(value))
+        .collect();
+    changes.extend(before.into_iter().map(|(field, _)| (field, serde_json::Value::Null)));
+    changes.remove("revision");
+    Ok(changes)
+}
+
+fn to_fields(release: &SDLCRelease) -> Result<serde_json::Map<String, serde_json::Value>, ReleaseRepositoryError> {
+    match serde_json::to_value(release) {
+        Ok(serde_json::Value::Object(fields)) => Ok(fields),
+        Ok(_) => Err(ReleaseRepositoryError::StorageError("A release must serialize to an object".to_string())),
+        Err(err) => Err(ReleaseRepositoryError::StorageError(err.to_string())),
+    }
+}
+
+/// Keeps an append-only journal of `ReleaseEvent`s instead of the latest state of each release,
+/// and rebuilds a release by folding its events. Answers what a release looked like at any
+/// earlier time, for compliance audits.
+#[derive(Clone, Default)]
+pub struct EventSourcingReleaseRepository {
+    events: Arc<RwLock<Vec<ReleaseEvent>>>,
+}
+
+impl EventSourcingReleaseRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The release as of `at`, folding only the events recorded until then. `None` if it did not
+    /// exist yet or was deleted.
+    pub async fn get_release_at(&self, id: Uuid, at: DateTime<Utc>) -> Result<Option<SDLCRelease>, ReleaseRepositoryError> {
+        let events = self.events.read().await;
+        fold(events.iter().filter(|event| event.release_id == id && event.recorded_at <= at))
+    }
+
+    /// Every event of the release, oldest first.
+    pub async fn get_event_log(&self, id: Uuid) -> Vec<ReleaseEvent> {
+        let events = self.events.read().await;
+        events.iter().filter(|event| event.release_id == id).cloned().collect()
+    }
+
+    /// Every release that exists now.
+    async fn current_releases(&self) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let events = self.events.read().await;
+        let mut releases: HashMap<Uuid, Option<SDLCRelease>> = HashMap::new();
+        for event in events.iter() {
+            let release = releases.remove(&event.release_id).flatten();
+            releases.insert(event.release_id, event.apply(release)?);
+        }
+        Ok(releases.into_values().flatten().collect())
+    }
+}
+
+/// Replays `events`, all of one release, in order from the beginning.
+fn fold<'a>(events: impl Iterator<Item = &'a ReleaseEvent>) -> Result<Option<SDLCRelease>, ReleaseRepositoryError> {
+    events.into_iter().try_fold(None, |release, event| event.apply(release))
+}
+
+fn current(events: &[ReleaseEvent], id: Uuid) -> Result<Option<SDLCRelease>, ReleaseRepositoryError> {
+    fold(events.iter().filter(|event| event.release_id == id))
+}
+
+fn append(events: &mut Vec<ReleaseEvent>, release_id: Uuid, recorded_at: DateTime<Utc>, revision: u64, kind: ReleaseEventKind) {
+    let sequence = events.len() as u64;
+    events.push(ReleaseEvent { release_id, sequence, recorded_at, revision, kind });
+}
+
+/// Appends the events storing `release` with the revision after the one it replaces: one per
+/// transition it made since, then one for any other change. Returns the release as stored.
+fn record_release(events: &mut Vec<ReleaseEvent>, mut release: SDLCRelease) -> Result<SDLCRelease, ReleaseRepositoryError> {
+    let recorded_at = Utc::now();
+    let Some(previous) = current(events, release.id)? else {
+        append(events, release.id, recorded_at, release.revision, ReleaseEventKind::Created { release: Box::new(release.clone()) });
+        return Ok(release);
+    };
+    release.revision = previous.revision + 1;
+    let records = release.transition_history.get(previous.transition_history.len()..).unwrap_or_default();
+    let mut replayed = previous;
+    for record in records {
+        replayed = replay_transition(replayed, record);
+        append(events, release.id, recorded_at, release.revision, ReleaseEventKind::Transitioned(Box::new(record.clone())));
+    }
+    let changes = changed_fields(&replayed, &release)?;
+    // Every store bumps the revision, so it leaves an event even if nothing else changed.
+    if !changes.is_empty() || records.is_empty() {
+        append(events, release.id, recorded_at, release.revision, ReleaseEventKind::Updated { changes });
+    }
+    Ok(release)
+}
+
+#[async_trait]
+impl ReleaseRepository for EventSourcingReleaseRepository {
+    #[tracing::instrument(skip_all, fields(release_id = %release.id))]
+    async fn store_release(&self, release: SDLCRelease) -> Result<(), ReleaseRepositoryError> {
+        let mut events = self.events.write().await;
+        let stored = record_release(&mut events, release)?;
+        tracing::debug!(revision = stored.revision, "Stored release");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(count = releases.len()))]
+    async fn store_releases(&self, releases: Vec<SDLCRelease>) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let mut events = self.events.write().await;
+        let journaled = events.len();
+        let stored: Result<Vec<SDLCRelease>, ReleaseRepositoryError> = releases.into_iter().map(|release| record_release(&mut events, release)).collect();
+        if stored.is_err() {
+            // All or none: drop the events of the releases recorded before the failure.
+            events.truncate(journaled);
+        }
+        stored
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %release.id, expected_revision))]
+    async fn update_release(&self, release: SDLCRelease, expected_revision: u64) -> Result<SDLCRelease, ReleaseRepositoryError> {
+        let mut events = self.events.write().await;
+        let actual = current(&events, release.id)?.ok_or(ReleaseRepositoryError::NotFound)?.revision;
+        if actual != expected_revision {
+            return Err(ReleaseRepositoryError::RevisionMismatch { expected: expected_revision, actual });
+        }
+        let stored = record_release(&mut events, release)?;
+        tracing::debug!(revision = stored.revision, "Updated release");
+        Ok(stored)
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %id))]
+    async fn get_release(&self, id: &Uuid) -> Result<Option<SDLCRelease>, ReleaseRepositoryError> {
+        let events = self.events.read().await;
+        current(&events, *id)
+    }
+
+    /// Records that the release was deleted. Its earlier events stay in the journal.
+    #[tracing::instrument(skip_all, fields(release_id = %id))]
+    async fn delete_release(&self, id: &Uuid) -> Result<(), ReleaseRepositoryError> {
+        let mut events = self.events.write().await;
+        let release = current(&events, *id)?.ok_or(ReleaseRepositoryError::NotFound)?;
+        append(&mut events, *id, Utc::now(), release.revision, ReleaseEventKind::Deleted);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases(&self) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        self.current_releases().await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases_for_component(&self, component_id: &Uuid) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        Ok(self.current_releases().await?.into_iter().filter(|release| &release.component_id == component_id).collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases_by_tag(&self, tag: &str) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        Ok(self.current_releases().await?.into_iter().filter(|release| release.tags.contains(tag)).collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases_filtered(&self, filter: ReleaseFilter) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let mut matching: Vec<SDLCRelease> = self.current_releases().await?.into_iter().filter(|release| filter.matches(release)).collect();
+        matching.sort_by_key(|release| (release.created_at, release.id));
+        Ok(matching)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn event_log(&self, id: &Uuid) -> Result<Option<Vec<ReleaseEvent>>, ReleaseRepositoryError> {
+        Ok(Some(self.get_event_log(*id).await))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::contract_tests::define_service_contract_tests;
+    use std::collections::HashSet;
+
+    fn event(sequence: u64, revision: u64, kind: ReleaseEventKind) -> ReleaseEvent {
+        ReleaseEvent { release_id: Uuid::nil(), sequence, recorded_at: Utc::now(), revision, kind }
+    }
+
+    #[test]
+    fn test_fold_replays_events_in_order() {
+        let created = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        let mut release = created.clone();
+        release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+        release.complete_development().unwrap();
+        let (started, completed) = (release.transition_history[0].clone(), release.transition_history[1].clone());
+        let mut changes = serde_json::Map::new();
+        changes.insert("tags".to_string(), serde_json::json!(["candidate"]));
+        changes.insert("release_notes".to_string(), serde_json::json!("First cut"));
+        let events = vec![
+            event(0, 0, ReleaseEventKind::Created { release: Box::new(created) }),
+            event(1, 1, ReleaseEventKind::Transitioned(Box::new(started.clone()))),
+            event(2, 1, ReleaseEventKind::Transitioned(Box::new(completed.clone()))),
+            event(3, 2, ReleaseEventKind::Updated { changes }),
+        ];
+
+        let rebuilt = fold(events[..2].iter()).unwrap().unwrap();
+        assert_eq!(rebuilt.phase, started.to_phase);
+        assert_eq!(rebuilt.state_name(), "InProgress");
+
+        let rebuilt = fold(events.iter()).unwrap().unwrap();
+        assert_eq!(rebuilt.phase, release.phase);
+        assert_eq!(rebuilt.state, release.state);
+        assert_eq!(rebuilt.transition_history, release.transition_history);
+        assert_eq!(rebuilt.snapshots.len(), 2);
+        assert_eq!(rebuilt.snapshots[1].state, started.to_state);
+        assert_eq!(rebuilt.tags, HashSet::from(["candidate".to_string()]));
+        assert_eq!(rebuilt.release_notes.as_deref(), Some("First cut"));
+        assert_eq!(rebuilt.revision, 2);
+
+        // A `null` change removes the field again.
+        let mut changes = serde_json::Map::new();
+        changes.insert("release_notes".to_string(), serde_json::Value::Null);
+        let mut events = events;
+        events.push(event(4, 3, ReleaseEventKind::Updated { changes }));
+        assert!(fold(events.iter()).unwrap().unwrap().release_notes.is_none());
+
+        events.push(event(5, 3, ReleaseEventKind::Deleted));
+        assert!(fold(events.iter()).unwrap().is_none());
+        // Replaying out of order, a change to a release that does not exist yet, fails.
+        assert!(matches!(fold(events[1..].iter()), Err(ReleaseRepositoryError::RetrievalError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_journal_keeps_changes_not_snapshots() {
+        let repository = EventSourcingReleaseRepository::new();
+        let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        repository.store_release(release.clone()).await.unwrap();
+        release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+        release.add_tag("candidate").unwrap();
+        repository.store_release(release.clone()).await.unwrap();
+
+        let log = repository.get_event_log(release.id).await;
+        assert_eq!(log.len(), 3);
+        assert!(matches!(&log[1].kind, ReleaseEventKind::Transitioned(record) if record.from_state == "Draft"));
+        let ReleaseEventKind::Updated { changes } = &log[2].kind else { panic!("{:?}", log[2].kind) };
+        // Starting development records its feature list; the transition itself is not repeated.
+        assert_eq!(changes.keys().collect::<Vec<_>>(), vec!["phase_details", "tags"]);
+        assert_eq!(log.iter().map(|event| event.revision).collect::<Vec<_>>(), vec![0, 1, 1]);
+
+        let stored = repository.get_release(&release.id).await.unwrap().unwrap();
+        release.revision = 1;
+        assert_eq!(serde_json::to_value(&stored).unwrap(), serde_json::to_value(&release).unwrap());
+    }
+
+    define_service_contract_tests!(EventSourcingReleaseRepository::new);
+}
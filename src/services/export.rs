@@ -0,0 +1,214 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::model::{Attestation, Policy, SDLCRelease};
+
+use super::attestation::{AttestationFilter, AttestationService};
+use super::namespace::{InMemoryNamespaceManager, NamespaceError, NamespaceNode, NamespaceService};
+use super::policy_repository::PolicyRepository;
+use super::release_repository::ReleaseRepository;
+
+/// A portable archive of a namespace subtree, for moving data between control plane instances.
+///
+/// Releases, policies and attestations are not scoped to namespaces yet, so every export
+/// carries all of them.
+#[derive(Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct NamespaceExport {
+    pub exported_at: DateTime<Utc>,
+    pub namespace_path: String,
+    /// The namespaces directly below `namespace_path`, each with its own subtree.
+    pub namespaces: Vec<NamespaceNode>,
+    pub releases: Vec<SDLCRelease>,
+    pub policies: Vec<Policy>,
+    pub attestations: Vec<Attestation>,
+}
+
+/// What to do with an imported item whose ID, or path, already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStrategy {
+    /// Keep the existing item.
+    Skip,
+    /// Replace the existing item. Existing namespaces are kept, as they carry no data.
+    Overwrite,
+    /// Reject the whole import before anything is changed.
+    #[default]
+    Fail,
+}
+
+/// `created` includes items that overwrote an existing one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct ImportCounts {
+    pub created: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct ImportReport {
+    pub namespaces: ImportCounts,
+    pub releases: ImportCounts,
+    pub policies: ImportCounts,
+    pub attestations: ImportCounts,
+}
+
+#[async_trait]
+pub trait ExportService: Send + Sync {
+    async fn export_namespace(&self, path: &str) -> Result<NamespaceExport, ExportError>;
+    /// Recreates the exported namespaces below `path` and stores the exported items.
+    async fn import_namespace(&self, path: &str, export: NamespaceExport, conflict_strategy: ConflictStrategy) -> Result<ImportReport, ExportError>;
+}
+
+#[derive(Debug, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum ExportError {
+    #[error("Namespace not found")]
+    NamespaceNotFound,
+    #[error("Invalid export archive: {0}")]
+    InvalidArchive(String),
+    #[error("{kind} {id} already exists")]
+    Conflict { kind: String, id: String },
+    #[error("Failed to read data to export: {0}")]
+    RetrievalError(String),
+}
+
+impl From<NamespaceError> for ExportError {
+    fn from(err: NamespaceError) -> Self {
+        match err {
+            NamespaceError::NotFound => ExportError::NamespaceNotFound,
+            err => ExportError::RetrievalError(err.to_string()),
+        }
+    }
+}
+
+/// `ExportService` over the in-memory stores the server runs with.
+#[derive(Clone)]
+pub struct InMemoryExportService {
+    namespaces: Arc<Mutex<InMemoryNamespaceManager>>,
+    releases: Arc<dyn ReleaseRepository>,
+    policies: Arc<dyn PolicyRepository>,
+    attestations: Arc<dyn AttestationService>,
+}
+
+impl InMemoryExportService {
+    pub fn new(
+        namespaces: Arc<Mutex<InMemoryNamespaceManager>>,
+        releases: Arc<dyn ReleaseRepository>,
+        policies: Arc<dyn PolicyRepository>,
+        attestations: Arc<dyn AttestationService>,
+    ) -> Self {
+        Self { namespaces, releases, policies, attestations }
+    }
+
+    async fn existing_ids(&self) -> Result<(HashSet<Uuid>, HashSet<Uuid>, HashSet<Uuid>), ExportError> {
+        let releases = self.releases.list_releases().await.map_err(|err| ExportError::RetrievalError(err.to_string()))?;
+        let policies = self.policies.list_policies().await.map_err(|err| ExportError::RetrievalError(err.to_string()))?;
+        let attestations = self.attestations.list_attestations(AttestationFilter::default()).await
+            .map_err(|err| ExportError::RetrievalError(err.to_string()))?;
+        Ok((
+            releases.into_iter().map(|release| release.id).collect(),
+            policies.into_iter().map(|policy| policy.id).collect(),
+            attestations.into_iter().map(|attestation| attestation.id).collect(),
+        ))
+    }
+}
+
+/// The full path of `relative` below `base`.
+fn join_path(base: &str, relative: &str) -> String {
+    let base = base.trim_matches('/');
+    if base.is_empty() { relative.to_string() } else { format!("{}/{}", base, relative) }
+}
+
+/// Counts one item whose ID `exists` already, and tells whether to store it.
+fn should_store(exists: bool, conflict_strategy: ConflictStrategy, counts: &mut ImportCounts) -> bool {
+    if exists && conflict_strategy == ConflictStrategy::Skip {
+        counts.skipped += 1;
+        return false;
+    }
+    true
+}
+
+/// Counts the outcome of storing one item.
+fn count_stored<E: std::fmt::Display>(result: Result<(), E>, kind: &str, counts: &mut ImportCounts) {
+    match result {
+        Ok(()) => counts.created += 1,
+        Err(err) => {
+            tracing::warn!(kind, "Failed to import item: {}", err);
+            counts.failed += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl ExportService for InMemoryExportService {
+    #[tracing::instrument(skip(self))]
+    async fn export_namespace(&self, path: &str) -> Result<NamespaceExport, ExportError> {
+        let node = self.namespaces.lock().await.drill_down(path).await?;
+        let mut namespaces: Vec<NamespaceNode> = node.children().map(|child| child.as_ref().clone()).collect();
+        namespaces.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(NamespaceExport {
+            exported_at: Utc::now(),
+            namespace_path: path.trim_matches('/').to_string(),
+            namespaces,
+            releases: self.releases.list_releases().await.map_err(|err| ExportError::RetrievalError(err.to_string()))?,
+            policies: self.policies.list_policies().await.map_err(|err| ExportError::RetrievalError(err.to_string()))?,
+            attestations: self.attestations.list_attestations(AttestationFilter::default()).await
+                .map_err(|err| ExportError::RetrievalError(err.to_string()))?,
+        })
+    }
+
+    #[tracing::instrument(skip(self, export))]
+    async fn import_namespace(&self, path: &str, export: NamespaceExport, conflict_strategy: ConflictStrategy) -> Result<ImportReport, ExportError> {
+        let mut namespace_manager = self.namespaces.lock().await;
+        let existing_namespaces: HashSet<String> = namespace_manager.list_all_namespaces().await.into_iter().collect();
+        let namespace_paths: Vec<String> = export.namespaces.iter()
+            .flat_map(|node| std::iter::once(node.name().to_string())
+                .chain(node.descendant_paths().into_iter().map(|descendant| format!("{}/{}", node.name(), descendant))))
+            .map(|relative| join_path(path, &relative))
+            .collect();
+        let (existing_releases, existing_policies, existing_attestations) = self.existing_ids().await?;
+
+        if conflict_strategy == ConflictStrategy::Fail {
+            let conflict = namespace_paths.iter().find(|path| existing_namespaces.contains(*path)).map(|path| ("Namespace", path.clone()))
+                .or_else(|| export.releases.iter().find(|release| existing_releases.contains(&release.id)).map(|release| ("Release", release.id.to_string())))
+                .or_else(|| export.policies.iter().find(|policy| existing_policies.contains(&policy.id)).map(|policy| ("Policy", policy.id.to_string())))
+                .or_else(|| export.attestations.iter().find(|attestation| existing_attestations.contains(&attestation.id)).map(|attestation| ("Attestation", attestation.id.to_string())));
+            if let Some((kind, id)) = conflict {
+                return Err(ExportError::Conflict { kind: kind.to_string(), id });
+            }
+        }
+
+        let mut report = ImportReport::default();
+        for namespace_path in namespace_paths {
+            if existing_namespaces.contains(&namespace_path) {
+                report.namespaces.skipped += 1;
+                continue;
+            }
+            count_stored(namespace_manager.create_namespace(&namespace_path).await, "namespace", &mut report.namespaces);
+        }
+        drop(namespace_manager);
+        for release in export.releases {
+            if should_store(existing_releases.contains(&release.id), conflict_strategy, &mut report.releases) {
+                count_stored(self.releases.store_release(release).await, "release", &mut report.releases);
+            }
+        }
+        for policy in export.policies {
+            if should_store(existing_policies.contains(&policy.id), conflict_strategy, &mut report.policies) {
+                count_stored(self.policies.store_policy(policy).await, "policy", &mut report.policies);
+            }
+        }
+        for attestation in export.attestations {
+            if should_store(existing_attestations.contains(&attestation.id), conflict_strategy, &mut report.attestations) {
+                count_stored(self.attestations.store_attestation(attestation).await, "attestation", &mut report.attestations);
+            }
+        }
+        tracing::info!(?report, "Imported namespace");
+        Ok(report)
+    }
+}
@@ -0,0 +1,29 @@
+//! Counters of what the control plane does, recorded through the `metrics` facade. They are
+//! only kept when the binary installs a recorder, as `sdlccp-server` does to serve `/metrics`.
+
+use super::transitions::TransitionEvent;
+
+pub const RELEASE_TRANSITIONS_TOTAL: &str = "sdlccp_release_transitions_total";
+pub const POLICY_EVALUATIONS_TOTAL: &str = "sdlccp_policy_evaluations_total";
+pub const ATTESTATION_STORE_TOTAL: &str = "sdlccp_attestation_store_total";
+
+/// Counts a release leaving `from_phase` for `to_phase` through `event`.
+pub fn record_transition(from_phase: &str, to_phase: &str, event: &TransitionEvent) {
+    metrics::counter!(
+        RELEASE_TRANSITIONS_TOTAL,
+        "from_phase" => from_phase.to_string(),
+        "to_phase" => to_phase.to_string(),
+        "from_state" => event.from_state.clone(),
+        "to_state" => event.to_state.clone(),
+    )
+    .increment(1);
+}
+
+/// Counts one evaluation of a release against its policies.
+pub fn record_policy_evaluation(passed: bool) {
+    metrics::counter!(POLICY_EVALUATIONS_TOTAL, "result" => if passed { "pass" } else { "fail" }).increment(1);
+}
+
+pub fn record_attestation_stored() {
+    metrics::counter!(ATTESTATION_STORE_TOTAL).increment(1);
+}
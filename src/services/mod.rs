@@ -1,4 +1,25 @@
+pub mod api_error;
 pub mod attestation;
+pub mod audit;
+pub mod auth;
+pub mod compliance;
+pub mod component_repository;
+#[cfg(test)]
+pub(crate) mod contract_tests;
 pub mod controlplane;
+pub mod event_sourcing_release_repository;
+pub mod export;
+pub mod metrics;
+pub mod policy_enforcement;
 pub mod policy_repository;
-pub mod namespace;
\ No newline at end of file
+#[cfg(feature = "backend-postgres")]
+pub mod postgres_release_repository;
+pub mod namespace;
+pub mod release_repository;
+pub mod request_id;
+pub mod sla;
+#[cfg(feature = "backend-sqlite")]
+pub mod sqlite_release_repository;
+pub mod team_repository;
+pub mod transitions;
+pub mod webhook;
@@ -1,14 +1,16 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use sdlccp_api_macro::RegisterSchema;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 
+/// A tree of namespaces addressed by `/`-separated paths.
 #[async_trait]
-pub trait NamespaceManager {
+pub trait NamespaceService {
     /// Asynchronously creates a new namespace at the specified path.
     async fn create_namespace(&mut self, path: &str) -> Result<(), NamespaceError>;
 
@@ -23,24 +25,201 @@ pub trait NamespaceManager {
 
     /// Asynchronously retrieves the namespace hierarchy starting from the specified path.
     async fn drill_down(&self, path: &str) -> Result<Arc<NamespaceNode>, NamespaceError>;
+
+    /// Asynchronously lists the full path of every namespace below the specified path, down to
+    /// `max_depth` levels below it, or all of them if `max_depth` is `None`.
+    async fn list_all_namespaces_recursive(&self, path: &str, max_depth: Option<usize>) -> Result<Vec<String>, NamespaceError>;
+
+    /// Asynchronously summarizes the subtree at the specified path.
+    async fn namespace_stats(&self, path: &str) -> Result<NamespaceStats, NamespaceError>;
+
+    /// Asynchronously copies the namespace at `source_path`, with everything below it, to
+    /// `dest_path`, creating any missing parents. The destination must not exist yet and must
+    /// not be inside the source.
+    async fn copy_namespace(&mut self, source_path: &str, dest_path: &str) -> Result<(), NamespaceError>;
+}
+
+/// The former name of `NamespaceService`, with the same methods. Implementations keep working
+/// through the blanket `NamespaceService` impl.
+#[deprecated(note = "implement `NamespaceService` instead, which every `NamespaceManager` also implements")]
+#[async_trait]
+pub trait NamespaceManager {
+    async fn create_namespace(&mut self, path: &str) -> Result<(), NamespaceError>;
+    async fn list_namespaces(&self, path: &str) -> Result<Vec<String>, NamespaceError>;
+    async fn delete_namespace(&mut self, path: &str) -> Result<(), NamespaceError>;
+    async fn search_namespaces(&self, query: &str) -> Result<Vec<String>, NamespaceError>;
+    async fn drill_down(&self, path: &str) -> Result<Arc<NamespaceNode>, NamespaceError>;
+    async fn list_all_namespaces_recursive(&self, path: &str, max_depth: Option<usize>) -> Result<Vec<String>, NamespaceError>;
+    async fn namespace_stats(&self, path: &str) -> Result<NamespaceStats, NamespaceError>;
+    async fn copy_namespace(&mut self, source_path: &str, dest_path: &str) -> Result<(), NamespaceError>;
+}
+
+#[async_trait]
+#[allow(deprecated)]
+impl<T: NamespaceManager + Send + Sync> NamespaceService for T {
+    async fn create_namespace(&mut self, path: &str) -> Result<(), NamespaceError> {
+        NamespaceManager::create_namespace(self, path).await
+    }
+
+    async fn list_namespaces(&self, path: &str) -> Result<Vec<String>, NamespaceError> {
+        NamespaceManager::list_namespaces(self, path).await
+    }
+
+    async fn delete_namespace(&mut self, path: &str) -> Result<(), NamespaceError> {
+        NamespaceManager::delete_namespace(self, path).await
+    }
+
+    async fn search_namespaces(&self, query: &str) -> Result<Vec<String>, NamespaceError> {
+        NamespaceManager::search_namespaces(self, query).await
+    }
+
+    async fn drill_down(&self, path: &str) -> Result<Arc<NamespaceNode>, NamespaceError> {
+        NamespaceManager::drill_down(self, path).await
+    }
+
+    async fn list_all_namespaces_recursive(&self, path: &str, max_depth: Option<usize>) -> Result<Vec<String>, NamespaceError> {
+        NamespaceManager::list_all_namespaces_recursive(self, path, max_depth).await
+    }
+
+    async fn namespace_stats(&self, path: &str) -> Result<NamespaceStats, NamespaceError> {
+        NamespaceManager::namespace_stats(self, path).await
+    }
+
+    async fn copy_namespace(&mut self, source_path: &str, dest_path: &str) -> Result<(), NamespaceError> {
+        NamespaceManager::copy_namespace(self, source_path, dest_path).await
+    }
 }
 
 #[derive(Debug, Clone, ToSchema, Serialize, Deserialize, JsonSchema, RegisterSchema)]
 pub struct NamespaceNode {
     name: String,
+    #[schema(value_type = HashMap<String, NamespaceNode>)]
     children: HashMap<String, Arc<NamespaceNode>>,
+    /// Unknown for the root and for namespaces restored from snapshots that predate it.
+    #[serde(default)]
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    quota: Option<NamespaceQuota>,
+}
+
+/// Limits on the namespaces that can be created below a namespace, enforced by
+/// `create_namespace`. Each limit left at `None` is not enforced.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema, JsonSchema)]
+pub struct NamespaceQuota {
+    /// How many namespaces may be directly below it.
+    pub max_children: Option<usize>,
+    /// How many namespaces may be below it at any depth.
+    pub max_total_descendants: Option<usize>,
+    /// How many levels of namespaces may be below it.
+    pub max_depth: Option<usize>,
+}
+
+/// Counts describing a namespace subtree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema, JsonSchema)]
+pub struct NamespaceStats {
+    pub path: String,
+    pub direct_children: usize,
+    /// Every namespace below `path`, at any depth.
+    pub total_descendants: usize,
+    /// How many levels of namespaces are below `path`.
+    pub max_depth: usize,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// What changed about a namespace, identified by its full path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema, JsonSchema)]
+pub enum NamespaceEventType {
+    Created(String),
+    Deleted(String),
+    Renamed { old_path: String, new_path: String },
+    /// Something other than its path changed, like its quota.
+    MetadataChanged(String),
+}
+
+/// A change to the namespace tree, see `InMemoryNamespaceManager::subscribe`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema, JsonSchema)]
+pub struct NamespaceEvent {
+    pub event_type: NamespaceEventType,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl NamespaceEvent {
+    pub fn new(event_type: NamespaceEventType) -> Self {
+        Self { event_type, timestamp: Utc::now() }
+    }
+}
+
+impl NamespaceNode {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    /// The quota set on this namespace, if any. Quotas of its ancestors apply as well.
+    pub fn quota(&self) -> Option<&NamespaceQuota> {
+        self.quota.as_ref()
+    }
+
+    pub fn children(&self) -> impl Iterator<Item = &Arc<NamespaceNode>> {
+        self.children.values()
+    }
+
+    /// The path of every namespace below this one, relative to it, sorted so parents come first.
+    pub fn descendant_paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        for child in self.children.values() {
+            paths.push(child.name.clone());
+            paths.extend(child.descendant_paths().into_iter().map(|path| format!("{}/{}", child.name, path)));
+        }
+        paths.sort();
+        paths
+    }
+
+    /// How many levels of namespaces are below this one.
+    pub fn height(&self) -> usize {
+        self.children.values().map(|child| child.height() + 1).max().unwrap_or(0)
+    }
+
+    /// How many levels the subtree rooted here spans, counting this namespace.
+    pub fn depth(&self) -> usize {
+        1 + self.children.values().map(|child| child.depth()).max().unwrap_or(0)
+    }
 }
 
-#[derive(Debug, Clone, ToSchema, JsonSchema, RegisterSchema)]
+#[derive(Debug, Clone, thiserror::Error, ToSchema, JsonSchema, RegisterSchema)]
 pub enum NamespaceError {
+    #[error("Namespace not found")]
     NotFound,
+    #[error("Namespace already exists")]
     AlreadyExists,
+    #[error("Invalid namespace path")]
     InvalidPath,
+    #[error("Permission denied")]
     PermissionDenied,
+    #[error("Failed to persist namespaces: {0}")]
+    PersistenceError(String),
+    /// `quota_type` names the `NamespaceQuota` field that was exceeded, or is
+    /// `max_namespace_depth` for the limit of the whole manager.
+    #[error("Namespace quota {quota_type} of {limit} exceeded, would be {actual}")]
+    QuotaExceeded { limit: usize, actual: usize, quota_type: &'static str },
 }
 
+/// How many levels of namespaces an `InMemoryNamespaceManager` allows unless configured otherwise.
+pub const MAX_NAMESPACE_DEPTH: usize = 16;
+
+/// How many namespace events a slow subscriber may fall behind before it misses some.
+const NAMESPACE_EVENT_CAPACITY: usize = 64;
+
+#[derive(Clone)]
 pub struct InMemoryNamespaceManager {
     root: Arc<RwLock<Arc<NamespaceNode>>>,
+    /// How many segments the path of a namespace may have.
+    max_depth: usize,
+    /// Shared by clones, so subscribers hear about changes made through any of them.
+    events: broadcast::Sender<NamespaceEvent>,
 }
 
 impl Default for InMemoryNamespaceManager {
@@ -51,15 +230,85 @@ impl Default for InMemoryNamespaceManager {
 
 impl InMemoryNamespaceManager {
     pub fn new() -> Self {
+        Self::with_root(NamespaceNode {
+            name: "".to_string(),
+            children: HashMap::new(),
+            created_at: None,
+            quota: None,
+        })
+    }
+
+    fn with_root(root: NamespaceNode) -> Self {
         Self {
-            root: Arc::new(RwLock::new(Arc::new(NamespaceNode {
-                name: "".to_string(),
-                children: HashMap::new(),
-            }))),
+            root: Arc::new(RwLock::new(Arc::new(root))),
+            max_depth: MAX_NAMESPACE_DEPTH,
+            events: broadcast::channel(NAMESPACE_EVENT_CAPACITY).0,
+        }
+    }
+
+    /// Limits namespace paths to `max_depth` segments. Namespaces already deeper are kept; it
+    /// only stops new ones from being created.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Writes the whole namespace tree as JSON.
+    pub async fn save_to_writer<W: std::io::Write>(&self, writer: W) -> Result<(), NamespaceError> {
+        let root = self.root.read().await;
+        serde_json::to_writer(writer, &**root).map_err(|err| NamespaceError::PersistenceError(err.to_string()))
+    }
+
+    /// Restores a manager from the JSON written by `save_to_writer`.
+    pub fn load_from_reader<R: std::io::Read>(reader: R) -> Result<Self, NamespaceError> {
+        let root: NamespaceNode = serde_json::from_reader(reader).map_err(|err| NamespaceError::PersistenceError(err.to_string()))?;
+        Ok(Self::with_root(root))
+    }
+
+    /// Receives an event for every namespace created, deleted or otherwise changed from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<NamespaceEvent> {
+        self.events.subscribe()
+    }
+
+    /// Having no subscribers is not an error.
+    fn publish(&self, event_type: NamespaceEventType) {
+        let _ = self.events.send(NamespaceEvent::new(event_type));
+    }
+
+    /// The full path of every namespace, sorted.
+    pub async fn list_all_namespaces(&self) -> Vec<String> {
+        let hierarchy = self.root.read().await;
+        let mut namespaces = Vec::new();
+        self.search_recursive(&hierarchy, "", "", &mut namespaces);
+        namespaces.sort();
+        namespaces
+    }
+
+    /// Sets the quota of the namespace at `path`, or of the root if `path` is empty. Namespaces
+    /// already beyond the quota are kept; it only stops new ones from being created.
+    pub async fn set_quota(&mut self, path: &str, quota: NamespaceQuota) -> Result<(), NamespaceError> {
+        let mut hierarchy = self.root.write().await;
+        let mut current_node = Arc::make_mut(&mut hierarchy);
+        for part in Self::parse_path(path) {
+            current_node = Arc::make_mut(current_node.children.get_mut(&part).ok_or(NamespaceError::NotFound)?);
         }
+        current_node.quota = Some(quota);
+        tracing::info!(namespace = path, "Set namespace quota");
+        self.publish(NamespaceEventType::MetadataChanged(Self::parse_path(path).join("/")));
+        Ok(())
+    }
+
+    /// The quota set on the namespace at `path`, with no limits if none was set.
+    pub async fn quota(&self, path: &str) -> Result<NamespaceQuota, NamespaceError> {
+        Ok(self.drill_down(path).await?.quota.clone().unwrap_or_default())
     }
 
-    fn parse_path(path: &str) -> Vec<String> {
+    /// Splits a namespace path into its segments, ignoring leading, trailing and repeated slashes.
+    pub fn parse_path(path: &str) -> Vec<String> {
         path.trim_matches('/')
             .split('/')
             .filter(|s| !s.is_empty())
@@ -69,17 +318,27 @@ impl InMemoryNamespaceManager {
 }
 
 #[async_trait]
-impl NamespaceManager for InMemoryNamespaceManager {
+impl NamespaceService for InMemoryNamespaceManager {
+    #[tracing::instrument(skip(self))]
     async fn create_namespace(&mut self, path: &str) -> Result<(), NamespaceError> {
         let parts = Self::parse_path(path);
         if parts.is_empty() {
+            tracing::warn!(error = ?NamespaceError::InvalidPath, "Rejected namespace creation");
             return Err(NamespaceError::InvalidPath);
         }
     
         let mut root_guard = self.root.write().await;
+        if let Err(err) = self.check_max_depth(&root_guard, &parts, 0).and_then(|()| Self::check_quotas(&root_guard, &parts, (0, 0))) {
+            tracing::warn!(error = ?err, "Rejected namespace creation");
+            return Err(err);
+        }
         let mut current_node = Arc::make_mut(&mut *root_guard);
+        let mut created = Vec::new();
     
-        for part in parts {
+        for (depth, part) in parts.iter().enumerate() {
+            if !current_node.children.contains_key(part) {
+                created.push(parts[..=depth].join("/"));
+            }
             current_node = Arc::make_mut(
                 current_node
                     .children
@@ -87,13 +346,21 @@ impl NamespaceManager for InMemoryNamespaceManager {
                     .or_insert_with(|| Arc::new(NamespaceNode {
                         name: part.clone(),
                         children: HashMap::new(),
+                        created_at: Some(Utc::now()),
+                        quota: None,
                     })),
             );
         }
     
+        tracing::info!(namespace = path, "Created namespace");
+        // Parents first, so subscribers learn of every namespace before its children.
+        for path in created {
+            self.publish(NamespaceEventType::Created(path));
+        }
         Ok(())
     }
 
+    #[tracing::instrument(skip(self))]
     async fn list_namespaces(&self, path: &str) -> Result<Vec<String>, NamespaceError> {
         let parts = Self::parse_path(path);
         let hierarchy = self.root.read().await;
@@ -117,6 +384,7 @@ impl NamespaceManager for InMemoryNamespaceManager {
         Ok(namespaces)
     }
 
+    #[tracing::instrument(skip(self))]
     async fn search_namespaces(&self, query: &str) -> Result<Vec<String>, NamespaceError> {
         let hierarchy = self.root.read().await;
         let mut results = Vec::new();
@@ -124,9 +392,11 @@ impl NamespaceManager for InMemoryNamespaceManager {
         Ok(results)
     }
 
+    #[tracing::instrument(skip(self))]
     async fn delete_namespace(&mut self, path: &str) -> Result<(), NamespaceError> {
         let parts = Self::parse_path(path);
         if parts.is_empty() {
+            tracing::warn!(error = ?NamespaceError::InvalidPath, "Rejected namespace deletion");
             return Err(NamespaceError::InvalidPath);
         }
 
@@ -134,12 +404,13 @@ impl NamespaceManager for InMemoryNamespaceManager {
         let mut current_node = Arc::make_mut(&mut hierarchy);
 
         for part in &parts[..parts.len() - 1] {
-            current_node = Arc::make_mut(
-                current_node
-                    .children
-                    .get_mut(part)
-                    .ok_or(NamespaceError::NotFound)?,
-            );
+            current_node = match current_node.children.get_mut(part) {
+                Some(node) => Arc::make_mut(node),
+                None => {
+                    tracing::warn!(error = ?NamespaceError::NotFound, "Namespace to delete does not exist");
+                    return Err(NamespaceError::NotFound);
+                }
+            };
         }
 
         let removed = current_node
@@ -147,11 +418,19 @@ impl NamespaceManager for InMemoryNamespaceManager {
             .remove(&parts.last().unwrap().to_string());
 
         match removed {
-            Some(_) => Ok(()),
-            None => Err(NamespaceError::NotFound),
+            Some(_) => {
+                tracing::info!(namespace = path, "Deleted namespace");
+                self.publish(NamespaceEventType::Deleted(parts.join("/")));
+                Ok(())
+            }
+            None => {
+                tracing::warn!(error = ?NamespaceError::NotFound, "Namespace to delete does not exist");
+                Err(NamespaceError::NotFound)
+            }
         }
     }
 
+    #[tracing::instrument(skip(self))]
     async fn drill_down(&self, path: &str) -> Result<Arc<NamespaceNode>, NamespaceError> {
         let parts = Self::parse_path(path);
         let hierarchy = self.root.read().await;
@@ -168,9 +447,161 @@ impl NamespaceManager for InMemoryNamespaceManager {
 
         Ok(current_node)
     }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_all_namespaces_recursive(&self, path: &str, max_depth: Option<usize>) -> Result<Vec<String>, NamespaceError> {
+        let node = self.drill_down(path).await?;
+        let mut namespaces = Vec::new();
+        self.collect_recursive(&node, &Self::parse_path(path).join("/"), max_depth, &mut namespaces);
+        namespaces.sort();
+        Ok(namespaces)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn copy_namespace(&mut self, source_path: &str, dest_path: &str) -> Result<(), NamespaceError> {
+        let source = Self::parse_path(source_path);
+        let dest = Self::parse_path(dest_path);
+        if source.is_empty() || dest.is_empty() || dest.starts_with(&source) {
+            tracing::warn!(error = ?NamespaceError::InvalidPath, "Rejected namespace copy");
+            return Err(NamespaceError::InvalidPath);
+        }
+
+        let mut root_guard = self.root.write().await;
+        let mut copy = source
+            .iter()
+            .try_fold(&*root_guard, |node, part| node.children.get(part))
+            .ok_or(NamespaceError::NotFound)?
+            .as_ref()
+            .clone();
+        if dest.iter().try_fold(&*root_guard, |node, part| node.children.get(part)).is_some() {
+            return Err(NamespaceError::AlreadyExists);
+        }
+        if let Err(err) = self.check_max_depth(&root_guard, &dest, copy.height()).and_then(|()| Self::check_quotas(&root_guard, &dest, Self::subtree_size(&copy))) {
+            tracing::warn!(error = ?err, "Rejected namespace copy");
+            return Err(err);
+        }
+
+        let (name, parents) = dest.split_last().expect("dest is not empty");
+        let mut current_node = Arc::make_mut(&mut *root_guard);
+        let mut created = Vec::new();
+        for (depth, part) in parents.iter().enumerate() {
+            if !current_node.children.contains_key(part) {
+                created.push(dest[..=depth].join("/"));
+            }
+            current_node = Arc::make_mut(current_node.children.entry(part.clone()).or_insert_with(|| Arc::new(NamespaceNode {
+                name: part.clone(),
+                children: HashMap::new(),
+                created_at: Some(Utc::now()),
+                quota: None,
+            })));
+        }
+        // Children are shared until either copy changes, as `Arc::make_mut` clones them then.
+        copy.name = name.clone();
+        current_node.children.insert(name.clone(), Arc::new(copy));
+
+        tracing::info!(source = source_path, destination = dest_path, "Copied namespace");
+        created.push(dest.join("/"));
+        for path in created {
+            self.publish(NamespaceEventType::Created(path));
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn namespace_stats(&self, path: &str) -> Result<NamespaceStats, NamespaceError> {
+        let node = self.drill_down(path).await?;
+        let (total_descendants, max_depth) = Self::subtree_size(&node);
+        Ok(NamespaceStats {
+            path: Self::parse_path(path).join("/"),
+            direct_children: node.children.len(),
+            total_descendants,
+            max_depth,
+            created_at: node.created_at,
+        })
+    }
 }
 
 impl InMemoryNamespaceManager {
+    /// Checks that creating `parts` below `root`, with a subtree `below_depth` levels deep under
+    /// the last of them, keeps every new namespace within `max_depth` levels of the root.
+    fn check_max_depth(&self, root: &NamespaceNode, parts: &[String], below_depth: usize) -> Result<(), NamespaceError> {
+        let mut node = root;
+        for part in parts {
+            match node.children.get(part) {
+                Some(child) => node = child,
+                None if parts.len() + below_depth > self.max_depth => {
+                    return Err(NamespaceError::QuotaExceeded {
+                        limit: self.max_depth,
+                        actual: parts.len() + below_depth,
+                        quota_type: "max_namespace_depth",
+                    });
+                },
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that creating `parts` below `root`, with a subtree of `below` namespaces as deep
+    /// as `below_depth` under the last of them, stays within the quota of every namespace on
+    /// the way. Creating a namespace that already exists never exceeds a quota.
+    fn check_quotas(root: &Arc<NamespaceNode>, parts: &[String], (below, below_depth): (usize, usize)) -> Result<(), NamespaceError> {
+        let mut existing = vec![root];
+        for part in parts {
+            match existing[existing.len() - 1].children.get(part) {
+                Some(child) => existing.push(child),
+                None => break,
+            }
+        }
+        let created = parts.len() + 1 - existing.len();
+        if created == 0 {
+            return Ok(());
+        }
+        for (level, node) in existing.iter().enumerate() {
+            let Some(quota) = &node.quota else {
+                continue;
+            };
+            let check = |limit: Option<usize>, actual: usize, quota_type: &'static str| match limit {
+                Some(limit) if actual > limit => Err(NamespaceError::QuotaExceeded { limit, actual, quota_type }),
+                _ => Ok(()),
+            };
+            // Only the deepest existing namespace gets a new direct child.
+            if level == existing.len() - 1 {
+                check(quota.max_children, node.children.len() + 1, "max_children")?;
+            }
+            check(quota.max_total_descendants, Self::subtree_size(node).0 + created + below, "max_total_descendants")?;
+            check(quota.max_depth, parts.len() - level + below_depth, "max_depth")?;
+        }
+        Ok(())
+    }
+
+    /// Depth-first, the number of namespaces below `node` and how many levels deep they go.
+    fn subtree_size(node: &NamespaceNode) -> (usize, usize) {
+        node.children.values().fold((0, 0), |(count, depth), child| {
+            let (child_count, child_depth) = Self::subtree_size(child);
+            (count + child_count + 1, depth.max(child_depth + 1))
+        })
+    }
+
+    /// Depth-first, pushes the full path of every namespace below `node`, whose own path is
+    /// `path`, that is at most `remaining_depth` levels down.
+    fn collect_recursive(
+        &self,
+        node: &Arc<NamespaceNode>,
+        path: &str,
+        remaining_depth: Option<usize>,
+        results: &mut Vec<String>,
+    ) {
+        if remaining_depth == Some(0) {
+            return;
+        }
+        for child in node.children.values() {
+            let child_path = if path.is_empty() { child.name.clone() } else { format!("{}/{}", path, child.name) };
+            results.push(child_path.clone());
+            self.collect_recursive(child, &child_path, remaining_depth.map(|depth| depth - 1), results);
+        }
+    }
+
     fn search_recursive(
         &self,
         node: &Arc<NamespaceNode>,
@@ -223,6 +654,92 @@ mod tests {
         assert!(result.unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_snapshot_round_trip() {
+        let mut manager = InMemoryNamespaceManager::new();
+        for i in 0..100 {
+            manager.create_namespace(&format!("team-{}/service-{}", i % 10, i)).await.unwrap();
+        }
+
+        let mut snapshot = Vec::new();
+        manager.save_to_writer(&mut snapshot).await.unwrap();
+        let restored = InMemoryNamespaceManager::load_from_reader(snapshot.as_slice()).unwrap();
+
+        let namespaces = manager.list_all_namespaces().await;
+        assert_eq!(namespaces.len(), 110);
+        assert_eq!(restored.list_all_namespaces().await, namespaces);
+        assert!(matches!(
+            InMemoryNamespaceManager::load_from_reader(&b"{\"name\":"[..]),
+            Err(NamespaceError::PersistenceError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_waits_for_writes_through_clones() {
+        let mut manager = InMemoryNamespaceManager::new();
+        manager.create_namespace("team-a").await.unwrap();
+        let clone = manager.clone();
+        let write = clone.root.write().await;
+
+        let save = tokio::spawn(async move {
+            let mut snapshot = Vec::new();
+            manager.save_to_writer(&mut snapshot).await.map(|()| snapshot)
+        });
+        tokio::task::yield_now().await;
+        assert!(!save.is_finished());
+        drop(write);
+
+        let snapshot = save.await.unwrap().unwrap();
+        let restored = InMemoryNamespaceManager::load_from_reader(snapshot.as_slice()).unwrap();
+        assert_eq!(restored.list_all_namespaces().await, vec!["team-a"]);
+    }
+
+    #[tokio::test]
+    async fn test_list_all_namespaces_recursive() {
+        let mut manager = InMemoryNamespaceManager::new();
+        manager.create_namespace("a/b/c/d").await.unwrap();
+        manager.create_namespace("a/b/e").await.unwrap();
+        manager.create_namespace("a/f").await.unwrap();
+        manager.create_namespace("g").await.unwrap();
+
+        assert_eq!(manager.list_all_namespaces_recursive("a", Some(2)).await.unwrap(), vec!["a/b", "a/b/c", "a/b/e", "a/f"]);
+        assert_eq!(
+            manager.list_all_namespaces_recursive("/a/", None).await.unwrap(),
+            vec!["a/b", "a/b/c", "a/b/c/d", "a/b/e", "a/f"]
+        );
+        assert_eq!(manager.list_all_namespaces_recursive("", Some(1)).await.unwrap(), vec!["a", "g"]);
+        assert!(manager.list_all_namespaces_recursive("a", Some(0)).await.unwrap().is_empty());
+        assert!(matches!(manager.list_all_namespaces_recursive("missing", None).await, Err(NamespaceError::NotFound)));
+
+        let root = manager.drill_down("").await.unwrap();
+        assert_eq!(root.height(), 4);
+        assert_eq!(manager.drill_down("a/b/c/d").await.unwrap().height(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_namespace_stats() {
+        let mut manager = InMemoryNamespaceManager::new();
+        let before = Utc::now();
+        for path in ["org/team-a/api/v1", "org/team-a/api/v2", "org/team-a/worker", "org/team-b", "sandbox"] {
+            manager.create_namespace(path).await.unwrap();
+        }
+
+        let stats = manager.namespace_stats("/org/").await.unwrap();
+        assert_eq!(stats.path, "org");
+        assert_eq!(stats.direct_children, 2);
+        assert_eq!(stats.total_descendants, 6);
+        assert_eq!(stats.max_depth, 3);
+        assert!(stats.created_at.is_some_and(|created_at| created_at >= before && created_at <= Utc::now()));
+
+        let root = manager.namespace_stats("").await.unwrap();
+        assert_eq!((root.path.as_str(), root.direct_children, root.total_descendants, root.max_depth), ("", 2, 8, 4));
+        assert_eq!(root.created_at, None);
+
+        let leaf = manager.namespace_stats("org/team-a/api/v2").await.unwrap();
+        assert_eq!((leaf.direct_children, leaf.total_descendants, leaf.max_depth), (0, 0, 0));
+        assert!(matches!(manager.namespace_stats("org/team-c").await, Err(NamespaceError::NotFound)));
+    }
+
     #[tokio::test]
     async fn test_search_namespaces() {
         let mut manager = InMemoryNamespaceManager::new();
@@ -236,4 +753,213 @@ mod tests {
         assert!(results.contains(&"search/test2".to_string()));
         assert!(results.contains(&"other/test3".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_namespace_quotas() {
+        let mut manager = InMemoryNamespaceManager::new();
+        manager.create_namespace("org").await.unwrap();
+        manager.set_quota("org", NamespaceQuota { max_children: Some(2), ..NamespaceQuota::default() }).await.unwrap();
+        manager.create_namespace("org/a").await.unwrap();
+        manager.create_namespace("org/b").await.unwrap();
+        assert!(matches!(
+            manager.create_namespace("org/c").await,
+            Err(NamespaceError::QuotaExceeded { limit: 2, actual: 3, quota_type: "max_children" })
+        ));
+        // Existing namespaces and grandchildren do not count against `max_children`.
+        manager.create_namespace("org/a").await.unwrap();
+        manager.create_namespace("org/a/x").await.unwrap();
+
+        manager.set_quota("", NamespaceQuota { max_total_descendants: Some(5), max_depth: Some(3), ..NamespaceQuota::default() }).await.unwrap();
+        assert!(matches!(
+            manager.create_namespace("org/a/x/y").await,
+            Err(NamespaceError::QuotaExceeded { limit: 3, actual: 4, quota_type: "max_depth" })
+        ));
+        manager.create_namespace("org/a/y").await.unwrap();
+        assert!(matches!(
+            manager.create_namespace("other").await,
+            Err(NamespaceError::QuotaExceeded { limit: 5, actual: 6, quota_type: "max_total_descendants" })
+        ));
+        // A rejected creation leaves no partial path behind.
+        assert_eq!(manager.list_all_namespaces().await.len(), 5);
+
+        assert_eq!(manager.quota("org").await.unwrap().max_children, Some(2));
+        assert_eq!(manager.quota("org/a").await.unwrap(), NamespaceQuota::default());
+        assert!(matches!(manager.set_quota("missing", NamespaceQuota::default()).await, Err(NamespaceError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_max_namespace_depth() {
+        let mut manager = InMemoryNamespaceManager::new().with_max_depth(3);
+        manager.create_namespace("a/b/c").await.unwrap();
+        assert!(matches!(
+            manager.create_namespace("a/b/c/d").await,
+            Err(NamespaceError::QuotaExceeded { limit: 3, actual: 4, quota_type: "max_namespace_depth" })
+        ));
+        // Copies count with everything below them.
+        assert!(matches!(
+            manager.copy_namespace("a", "x/y").await,
+            Err(NamespaceError::QuotaExceeded { limit: 3, actual: 4, quota_type: "max_namespace_depth" })
+        ));
+        manager.copy_namespace("a/b", "x").await.unwrap();
+
+        let root = manager.drill_down("").await.unwrap();
+        assert_eq!(root.depth(), 4);
+        assert_eq!(manager.drill_down("a/b/c").await.unwrap().depth(), 1);
+        assert_eq!(InMemoryNamespaceManager::new().max_depth(), MAX_NAMESPACE_DEPTH);
+    }
+
+    #[tokio::test]
+    async fn test_copy_namespace() {
+        let mut manager = InMemoryNamespaceManager::new();
+        manager.create_namespace("templates/service/ci").await.unwrap();
+        manager.create_namespace("templates/service/docs").await.unwrap();
+        manager.set_quota("templates/service", NamespaceQuota { max_children: Some(4), ..NamespaceQuota::default() }).await.unwrap();
+        let original = manager.drill_down("templates/service").await.unwrap();
+
+        manager.copy_namespace("templates/service", "teams/payments/api").await.unwrap();
+        let copy = manager.drill_down("teams/payments/api").await.unwrap();
+        assert_eq!(copy.name(), "api");
+        assert_eq!(copy.descendant_paths(), vec!["ci", "docs"]);
+        assert_eq!(copy.created_at(), original.created_at());
+        assert_eq!(copy.quota(), original.quota());
+
+        // The copies change independently.
+        manager.create_namespace("teams/payments/api/ci/nightly").await.unwrap();
+        assert_eq!(manager.drill_down("templates/service").await.unwrap().descendant_paths(), vec!["ci", "docs"]);
+
+        assert!(matches!(manager.copy_namespace("templates/service", "teams/payments/api").await, Err(NamespaceError::AlreadyExists)));
+        assert!(matches!(manager.copy_namespace("templates/missing", "teams/other").await, Err(NamespaceError::NotFound)));
+        assert!(matches!(manager.copy_namespace("", "teams/other").await, Err(NamespaceError::InvalidPath)));
+    }
+
+    #[tokio::test]
+    async fn test_copy_namespace_into_itself_is_rejected() {
+        let mut manager = InMemoryNamespaceManager::new();
+        manager.create_namespace("org/team/app").await.unwrap();
+        for dest in ["org/team", "org/team/app", "org/team/app/copy", "/org/team/new/"] {
+            assert!(matches!(manager.copy_namespace("org/team", dest).await, Err(NamespaceError::InvalidPath)), "{}", dest);
+        }
+        // A sibling that only shares a name prefix is not inside the source.
+        manager.copy_namespace("org/team", "org/team-copy").await.unwrap();
+        // Nor is an ancestor's other child, or a copy of the parent into a new root.
+        manager.copy_namespace("org/team/app", "org/app").await.unwrap();
+        manager.copy_namespace("org", "archive/org").await.unwrap();
+        assert_eq!(manager.list_all_namespaces_recursive("archive", None).await.unwrap().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_copy_namespace_respects_quotas() {
+        let mut manager = InMemoryNamespaceManager::new();
+        manager.create_namespace("template/a/b").await.unwrap();
+        manager.create_namespace("team").await.unwrap();
+        manager.set_quota("team", NamespaceQuota { max_depth: Some(2), ..NamespaceQuota::default() }).await.unwrap();
+        assert!(matches!(
+            manager.copy_namespace("template", "team/copy").await,
+            Err(NamespaceError::QuotaExceeded { limit: 2, actual: 3, quota_type: "max_depth" })
+        ));
+        // Every copied namespace counts, not just the one at the destination.
+        manager.set_quota("team", NamespaceQuota { max_total_descendants: Some(1), ..NamespaceQuota::default() }).await.unwrap();
+        assert!(matches!(
+            manager.copy_namespace("template/a", "team/copy").await,
+            Err(NamespaceError::QuotaExceeded { limit: 1, actual: 2, quota_type: "max_total_descendants" })
+        ));
+        assert!(matches!(manager.drill_down("team/copy").await, Err(NamespaceError::NotFound)));
+        manager.set_quota("team", NamespaceQuota { max_total_descendants: Some(2), ..NamespaceQuota::default() }).await.unwrap();
+        manager.copy_namespace("template/a", "team/copy").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_namespace_events() {
+        let mut manager = InMemoryNamespaceManager::new();
+        let mut events = manager.clone().subscribe();
+
+        manager.create_namespace("/a/b/").await.unwrap();
+        manager.set_quota("a", NamespaceQuota::default()).await.unwrap();
+        manager.copy_namespace("a/b", "c").await.unwrap();
+        manager.delete_namespace("a").await.unwrap();
+        assert!(manager.delete_namespace("a").await.is_err());
+
+        let mut received = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            received.push(event.event_type);
+        }
+        assert_eq!(received, vec![
+            NamespaceEventType::Created("a".to_string()),
+            NamespaceEventType::Created("a/b".to_string()),
+            NamespaceEventType::MetadataChanged("a".to_string()),
+            NamespaceEventType::Created("c".to_string()),
+            NamespaceEventType::Deleted("a".to_string()),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_implicitly_created_namespaces_each_get_an_event() {
+        let mut manager = InMemoryNamespaceManager::new();
+        manager.create_namespace("org").await.unwrap();
+        let mut events = manager.clone().subscribe();
+
+        manager.create_namespace("org/payments/api").await.unwrap();
+        manager.create_namespace("org/payments").await.unwrap();
+        manager.copy_namespace("org/payments", "archive/2024/payments").await.unwrap();
+
+        let mut received = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            received.push(event.event_type);
+        }
+        // Existing namespaces are not announced again.
+        assert_eq!(received, vec![
+            NamespaceEventType::Created("org/payments".to_string()),
+            NamespaceEventType::Created("org/payments/api".to_string()),
+            NamespaceEventType::Created("archive".to_string()),
+            NamespaceEventType::Created("archive/2024".to_string()),
+            NamespaceEventType::Created("archive/2024/payments".to_string()),
+        ]);
+    }
+
+    /// An implementation written against the deprecated trait.
+    struct LegacyNamespaces(InMemoryNamespaceManager);
+
+    #[allow(deprecated)]
+    #[async_trait]
+    impl NamespaceManager for LegacyNamespaces {
+        async fn create_namespace(&mut self, path: &str) -> Result<(), NamespaceError> {
+            NamespaceService::create_namespace(&mut self.0, path).await
+        }
+        async fn list_namespaces(&self, path: &str) -> Result<Vec<String>, NamespaceError> {
+            NamespaceService::list_namespaces(&self.0, path).await
+        }
+        async fn delete_namespace(&mut self, path: &str) -> Result<(), NamespaceError> {
+            NamespaceService::delete_namespace(&mut self.0, path).await
+        }
+        async fn search_namespaces(&self, query: &str) -> Result<Vec<String>, NamespaceError> {
+            NamespaceService::search_namespaces(&self.0, query).await
+        }
+        async fn drill_down(&self, path: &str) -> Result<Arc<NamespaceNode>, NamespaceError> {
+            NamespaceService::drill_down(&self.0, path).await
+        }
+        async fn list_all_namespaces_recursive(&self, path: &str, max_depth: Option<usize>) -> Result<Vec<String>, NamespaceError> {
+            NamespaceService::list_all_namespaces_recursive(&self.0, path, max_depth).await
+        }
+        async fn namespace_stats(&self, path: &str) -> Result<NamespaceStats, NamespaceError> {
+            NamespaceService::namespace_stats(&self.0, path).await
+        }
+        async fn copy_namespace(&mut self, source_path: &str, dest_path: &str) -> Result<(), NamespaceError> {
+            NamespaceService::copy_namespace(&mut self.0, source_path, dest_path).await
+        }
+    }
+
+    async fn create_and_drill_down(service: &mut impl NamespaceService) -> Result<Arc<NamespaceNode>, NamespaceError> {
+        service.create_namespace("org/team").await?;
+        service.drill_down("org").await
+    }
+
+    #[tokio::test]
+    async fn test_namespace_manager_implementations_are_namespace_services() {
+        let mut legacy = LegacyNamespaces(InMemoryNamespaceManager::new());
+
+        let org = create_and_drill_down(&mut legacy).await.unwrap();
+
+        assert!(org.children.contains_key("team"));
+        assert_eq!(legacy.0.list_all_namespaces().await, vec!["org".to_string(), "org/team".to_string()]);
+    }
 }
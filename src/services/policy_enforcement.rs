@@ -0,0 +1,1127 @@
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use uuid::Uuid;
+
+use crate::model::{
+    attestation::{AttestationStatus, SBOMFormat},
+    phase::{HealthStatus, RuntimeDetails, SourceReviewStatus},
+    policy::{PolicyRule, Vulnerability, VulnerabilityLevel},
+    Attestation, Policy, ReleaseState, SDLCComponent, SDLCPhase, SDLCRelease,
+};
+
+/// A rule a release failed, with enough detail to tell the operator what to fix.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema, ToSchema)]
+pub enum PolicyViolation {
+    VulnerabilityThresholdExceeded {
+        level: VulnerabilityLevel,
+        max_count: u32,
+        found: usize,
+    },
+    VulnerabilityTooOld {
+        vulnerability_id: String,
+        discovered_at: DateTime<Utc>,
+    },
+    BlockedPackage {
+        vulnerability_id: String,
+        package: String,
+    },
+    AttestationTooOld {
+        attestation_id: Uuid,
+        timestamp: DateTime<Utc>,
+    },
+    UnsignedAttestation {
+        attestation_id: Uuid,
+    },
+    UnapprovedSigner {
+        attestation_id: Uuid,
+        signer: String,
+    },
+    /// The claim is missing, when `actual` is `None`, or holds a different value.
+    ClaimMismatch {
+        attestation_id: Uuid,
+        claim: String,
+        expected: String,
+        actual: Option<String>,
+    },
+    ForbiddenClaim {
+        attestation_id: Uuid,
+        claim: String,
+        value: String,
+    },
+    /// The package has no SBOM, or one in another format.
+    MissingSBOM {
+        required: SBOMFormat,
+        found: Option<SBOMFormat>,
+    },
+    /// The build is not reproducible, or the release has no build recorded yet.
+    NonReproducibleBuild {
+        build_id: Option<String>,
+        message: String,
+    },
+    ReleaseSuspended {
+        reason: String,
+        suspended_by: String,
+    },
+    /// The source review is missing or was not approved.
+    UnapprovedSourceReview {
+        review_status: Option<SourceReviewStatus>,
+    },
+    UnhealthyRuntime {
+        runtime_id: String,
+        details: String,
+    },
+    /// The component is not owned by the team, or it is not registered at all.
+    MissingTeamOwnership {
+        component_id: Uuid,
+        team_id: Uuid,
+    },
+    /// The release reached `phase` with no release notes, or only blank ones.
+    MissingReleaseNotes {
+        phase: SDLCPhase,
+    },
+    /// The version does not satisfy the requirement, or is no semantic version at all.
+    VersionRequirementNotMet {
+        version: String,
+        requirement: String,
+    },
+    /// The release stayed in `phase` for `took`, longer than `limit`. `left_at` is `None` while it
+    /// is still there.
+    PhaseTookTooLong {
+        phase: SDLCPhase,
+        entered_at: DateTime<Utc>,
+        left_at: Option<DateTime<Utc>>,
+        #[schema(value_type = DurationSchema)]
+        took: Duration,
+        #[schema(value_type = DurationSchema)]
+        limit: Duration,
+    },
+    RevokedAttestation {
+        attestation_id: Uuid,
+        reason: String,
+        revoked_by: String,
+    },
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyViolation::VulnerabilityThresholdExceeded { level, max_count, found } => {
+                write!(f, "{} vulnerabilities at or above {:?}, at most {} allowed", found, level, max_count)
+            }
+            PolicyViolation::VulnerabilityTooOld { vulnerability_id, discovered_at } => {
+                write!(f, "Vulnerability {} has been known since {}", vulnerability_id, discovered_at)
+            }
+            PolicyViolation::BlockedPackage { vulnerability_id, package } => {
+                write!(f, "Vulnerability {} affects blocked package {}", vulnerability_id, package)
+            }
+            PolicyViolation::AttestationTooOld { attestation_id, timestamp } => {
+                write!(f, "Attestation {} was made at {}", attestation_id, timestamp)
+            }
+            PolicyViolation::UnsignedAttestation { attestation_id } => write!(f, "Attestation {} is not signed", attestation_id),
+            PolicyViolation::UnapprovedSigner { attestation_id, signer } => {
+                write!(f, "Attestation {} is signed by unapproved identity {}", attestation_id, signer)
+            }
+            PolicyViolation::ClaimMismatch { attestation_id, claim, expected, actual: None } => {
+                write!(f, "Attestation {} does not carry the claim {}, {} expected", attestation_id, claim, expected)
+            }
+            PolicyViolation::ClaimMismatch { attestation_id, claim, expected, actual: Some(actual) } => {
+                write!(f, "Attestation {} has the claim {}={}, {} expected", attestation_id, claim, actual, expected)
+            }
+            PolicyViolation::ForbiddenClaim { attestation_id, claim, value } => {
+                write!(f, "Attestation {} carries the forbidden claim {}={}", attestation_id, claim, value)
+            }
+            PolicyViolation::MissingSBOM { required, found: None } => write!(f, "The package has no SBOM, {:?} required", required),
+            PolicyViolation::MissingSBOM { required, found: Some(found) } => {
+                write!(f, "The package has a {:?} SBOM, {:?} required", found, required)
+            }
+            PolicyViolation::NonReproducibleBuild { message, .. } => f.write_str(message),
+            PolicyViolation::ReleaseSuspended { reason, suspended_by } => {
+                write!(f, "The release was suspended by {}: {}", suspended_by, reason)
+            }
+            PolicyViolation::UnapprovedSourceReview { review_status: None } => write!(f, "The release has no source review"),
+            PolicyViolation::UnapprovedSourceReview { review_status: Some(status) } => {
+                write!(f, "The source review is not approved: {:?}", status)
+            }
+            PolicyViolation::UnhealthyRuntime { runtime_id, details } => write!(f, "Runtime {} is unhealthy: {}", runtime_id, details),
+            PolicyViolation::MissingTeamOwnership { component_id, team_id } => {
+                write!(f, "Component {} is not owned by team {}", component_id, team_id)
+            }
+            PolicyViolation::MissingReleaseNotes { phase } => {
+                write!(f, "The release has no release notes in the {} phase", phase.name())
+            }
+            PolicyViolation::VersionRequirementNotMet { version, requirement } => {
+                write!(f, "Version {} does not satisfy {}", version, requirement)
+            }
+            PolicyViolation::PhaseTookTooLong { phase, left_at: Some(_), took, limit, .. } => {
+                write!(f, "The release spent {} in the {} phase, at most {} allowed", hours(took), phase.name(), hours(limit))
+            }
+            PolicyViolation::PhaseTookTooLong { phase, left_at: None, took, limit, .. } => {
+                write!(f, "The release has been in the {} phase for {}, at most {} allowed", phase.name(), hours(took), hours(limit))
+            }
+            PolicyViolation::RevokedAttestation { attestation_id, reason, revoked_by } => {
+                write!(f, "Attestation {} was revoked by {}: {}", attestation_id, revoked_by, reason)
+            }
+        }
+    }
+}
+
+/// How a release fared against every policy it was evaluated against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct PolicyEvaluationReport {
+    pub release_id: Uuid,
+    pub evaluated_at: DateTime<Utc>,
+    /// Whether every policy passed.
+    pub overall: bool,
+    pub policy_results: Vec<PolicyResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct PolicyResult {
+    pub policy_id: Uuid,
+    pub policy_name: String,
+    /// Whether every rule of the policy passed.
+    pub passed: bool,
+    pub rule_results: Vec<RuleResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct RuleResult {
+    pub rule_description: String,
+    pub passed: bool,
+    /// The violations found, or why the rule passed.
+    pub details: String,
+}
+
+/// Why a policy could not be dry-run.
+#[derive(Debug, Clone, PartialEq, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum PolicyEnforcementError {
+    /// A policy without rules passes every release, so evaluating it tells nothing.
+    #[error("Policy {0} has no rules to evaluate")]
+    EmptyPolicy(Uuid),
+}
+
+/// Evaluates policy rules against what a release reports about itself and against the
+/// attestations made about it.
+///
+/// `evaluate` covers the rules that look at the release's version, build, package and runtime details;
+/// `MaxAge`, `ApprovedIdentities`, `RequiredClaims`, `ForbiddenClaims` and `NoRevokedAttestations` always pass there.
+/// `evaluate_attestation` covers those five and passes the rest. Only the evaluations that see a
+/// release honor `ActiveForPhases`. `evaluate_component` covers `RequireTeamOwnership`, which needs
+/// the released component rather than the release.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PolicyEnforcementService;
+
+impl PolicyEnforcementService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns every violation of `policy` by `release`. An empty list means the release passes.
+    pub fn evaluate(&self, policy: &Policy, release: &SDLCRelease) -> Vec<PolicyViolation> {
+        self.evaluate_at(policy, release, Utc::now())
+    }
+
+    /// Like `evaluate`, with vulnerability ages measured at `now`.
+    pub fn evaluate_at(&self, policy: &Policy, release: &SDLCRelease, now: DateTime<Utc>) -> Vec<PolicyViolation> {
+        if !active_in(policy, &release.phase) {
+            return Vec::new();
+        }
+        policy.rules.iter().flat_map(|rule| self.evaluate_rule(rule, release, now)).collect()
+    }
+
+    /// Evaluates `policy` rule by rule against `release`, its component and the attestations about
+    /// it. A release whose component is not registered fails the component rules.
+    pub fn evaluate_policy(&self, policy: &Policy, release: &SDLCRelease, component: Option<&SDLCComponent>, attestations: &[Attestation]) -> PolicyResult {
+        self.evaluate_policy_at(policy, release, component, attestations, &[], Utc::now())
+    }
+
+    /// Like `evaluate_policy`, with ages measured at `now`. `ancestors` are the attestations that
+    /// `attestations` build on; only `NoRevokedAttestations` looks at them.
+    pub fn evaluate_policy_at(&self, policy: &Policy, release: &SDLCRelease, component: Option<&SDLCComponent>, attestations: &[Attestation], ancestors: &[Attestation], now: DateTime<Utc>) -> PolicyResult {
+        let active = active_in(policy, &release.phase);
+        let rule_results: Vec<RuleResult> = policy.rules.iter()
+            .map(|rule| {
+                if !active {
+                    return RuleResult {
+                        rule_description: rule.to_string(),
+                        passed: true,
+                        details: format!("Policy is not active in the {} phase; rule skipped", release.phase.name()),
+                    };
+                }
+                if matches!(rule, PolicyRule::PhaseCompletedWithin(..)) && release.transition_history.is_empty() {
+                    // Releases stored before transitions were recorded cannot tell how long a phase took.
+                    return RuleResult {
+                        rule_description: rule.to_string(),
+                        passed: true,
+                        details: "No history available; rule skipped".to_string(),
+                    };
+                }
+                let violations: Vec<String> = self.evaluate_rule(rule, release, now).into_iter()
+                    .chain(self.evaluate_component_rule(rule, release.component_id, component))
+                    .chain(attestations.iter().flat_map(|attestation| self.evaluate_attestation_rule(rule, attestation, now)))
+                    .chain(ancestors.iter().flat_map(|ancestor| self.evaluate_ancestor_rule(rule, ancestor)))
+                    .map(|violation| violation.to_string())
+                    .collect();
+                RuleResult {
+                    rule_description: rule.to_string(),
+                    passed: violations.is_empty(),
+                    details: if violations.is_empty() { "No violations found".to_string() } else { violations.join("; ") },
+                }
+            })
+            .collect();
+        PolicyResult {
+            policy_id: policy.id,
+            policy_name: policy.name.clone(),
+            passed: rule_results.iter().all(|rule| rule.passed),
+            rule_results,
+        }
+    }
+
+    /// Evaluates `policy` against `release` as if it were assigned to the release's component,
+    /// without storing the policy or changing the release. Nothing is known about the component
+    /// or the attestations here, so component rules fail and attestation rules pass; use
+    /// `dry_run_evaluate_all` to supply them.
+    pub fn dry_run_evaluate(&self, policy: &Policy, release: &SDLCRelease) -> Result<PolicyEvaluationReport, PolicyEnforcementError> {
+        self.dry_run_evaluate_all(std::slice::from_ref(policy), release, None, &[], &[])
+    }
+
+    /// Like `dry_run_evaluate`, for several policies at once and with the release's component,
+    /// attestations and their ancestors at hand.
+    pub fn dry_run_evaluate_all(&self, policies: &[Policy], release: &SDLCRelease, component: Option<&SDLCComponent>, attestations: &[Attestation], ancestors: &[Attestation]) -> Result<PolicyEvaluationReport, PolicyEnforcementError> {
+        if let Some(policy) = policies.iter().find(|policy| policy.rules.is_empty()) {
+            return Err(PolicyEnforcementError::EmptyPolicy(policy.id));
+        }
+        let evaluated_at = Utc::now();
+        let policy_results: Vec<PolicyResult> = policies.iter()
+            .map(|policy| self.evaluate_policy_at(policy, release, component, attestations, ancestors, evaluated_at))
+            .collect();
+        Ok(PolicyEvaluationReport {
+            release_id: release.id,
+            evaluated_at,
+            overall: policy_results.iter().all(|result| result.passed),
+            policy_results,
+        })
+    }
+
+    /// Returns every violation of `policy` by `component`. An empty list means it passes.
+    pub fn evaluate_component(&self, policy: &Policy, component: &SDLCComponent) -> Vec<PolicyViolation> {
+        policy.rules.iter().flat_map(|rule| self.evaluate_component_rule(rule, component.id(), Some(component))).collect()
+    }
+
+    /// Returns every violation of `policy` by `attestation`. An empty list means it passes.
+    pub fn evaluate_attestation(&self, policy: &Policy, attestation: &Attestation) -> Vec<PolicyViolation> {
+        self.evaluate_attestation_at(policy, attestation, Utc::now())
+    }
+
+    /// Like `evaluate_attestation`, with the attestation's age measured at `now`.
+    pub fn evaluate_attestation_at(&self, policy: &Policy, attestation: &Attestation, now: DateTime<Utc>) -> Vec<PolicyViolation> {
+        policy.rules.iter().flat_map(|rule| self.evaluate_attestation_rule(rule, attestation, now)).collect()
+    }
+
+    fn evaluate_attestation_rule(&self, rule: &PolicyRule, attestation: &Attestation, now: DateTime<Utc>) -> Vec<PolicyViolation> {
+        match rule {
+            PolicyRule::MaxAge(max_age) => {
+                // A window too large for chrono cannot be exceeded.
+                let Ok(max_age) = chrono::Duration::from_std(*max_age) else {
+                    return Vec::new();
+                };
+                if now - attestation.timestamp > max_age {
+                    vec![PolicyViolation::AttestationTooOld { attestation_id: attestation.id, timestamp: attestation.timestamp }]
+                } else {
+                    Vec::new()
+                }
+            }
+            PolicyRule::ApprovedIdentities(approved) => {
+                if attestation.signatures.is_empty() {
+                    return vec![PolicyViolation::UnsignedAttestation { attestation_id: attestation.id }];
+                }
+                attestation.signatures.iter()
+                    .filter(|signature| !approved.contains(&signature.signer))
+                    .map(|signature| PolicyViolation::UnapprovedSigner { attestation_id: attestation.id, signer: signature.signer.clone() })
+                    .collect()
+            }
+            PolicyRule::RequiredClaims(required) => required.iter()
+                .filter_map(|(claim, expected)| {
+                    let actual = claim_value(attestation, claim);
+                    (actual.as_ref() != Some(expected)).then(|| PolicyViolation::ClaimMismatch {
+                        attestation_id: attestation.id,
+                        claim: claim.clone(),
+                        expected: expected.clone(),
+                        actual,
+                    })
+                })
+                .collect(),
+            PolicyRule::ForbiddenClaims(forbidden) => forbidden.iter()
+                .filter(|(claim, value)| claim_value(attestation, claim).as_ref() == Some(*value))
+                .map(|(claim, value)| PolicyViolation::ForbiddenClaim {
+                    attestation_id: attestation.id,
+                    claim: claim.clone(),
+                    value: value.clone(),
+                })
+                .collect(),
+            PolicyRule::NoRevokedAttestations => revocation(attestation).into_iter().collect(),
+            PolicyRule::ActiveForPhases(_)
+            | PolicyRule::VulnerabilityThreshold(..)
+            | PolicyRule::MaxVulnerabilityAge(_)
+            | PolicyRule::BlockedPackages(_)
+            | PolicyRule::RequiresSBOM(_)
+            | PolicyRule::RequireReproducibleBuild
+            | PolicyRule::BlockSuspendedReleases
+            | PolicyRule::RequireApprovedSourceReview
+            | PolicyRule::RequireHealthyRuntime
+            | PolicyRule::RequireTeamOwnership(_)
+            | PolicyRule::MinVersion(_)
+            | PolicyRule::RequireReleaseNotes
+            | PolicyRule::PhaseCompletedWithin(..) => Vec::new(),
+        }
+    }
+
+    fn evaluate_rule(&self, rule: &PolicyRule, release: &SDLCRelease, now: DateTime<Utc>) -> Vec<PolicyViolation> {
+        let details = release.phase_details.as_ref();
+        match rule {
+            PolicyRule::RequiresSBOM(required) => {
+                let found = details.and_then(|details| details.package_details.as_ref()).and_then(|package| package.sbom.as_ref()).map(|sbom| sbom.format);
+                if found == Some(*required) {
+                    Vec::new()
+                } else {
+                    vec![PolicyViolation::MissingSBOM { required: *required, found }]
+                }
+            }
+            PolicyRule::RequireReproducibleBuild => match details.and_then(|details| details.build_details.as_ref()) {
+                Some(build) if build.reproducible => Vec::new(),
+                Some(build) => vec![PolicyViolation::NonReproducibleBuild {
+                    build_id: Some(build.build_id.clone()),
+                    message: format!("Build {} is not reproducible", build.build_id),
+                }],
+                None => vec![PolicyViolation::NonReproducibleBuild {
+                    build_id: None,
+                    message: "Release has no build details to check for reproducibility".to_string(),
+                }],
+            },
+            PolicyRule::BlockSuspendedReleases => match &release.state {
+                ReleaseState::Suspended { reason, suspended_by, .. } => vec![PolicyViolation::ReleaseSuspended {
+                    reason: reason.clone(),
+                    suspended_by: suspended_by.clone(),
+                }],
+                _ => Vec::new(),
+            },
+            PolicyRule::RequireApprovedSourceReview => {
+                let review_status = details.and_then(|details| details.source_details.as_ref()).map(|source| source.review_status.clone());
+                if matches!(review_status, Some(SourceReviewStatus::Approved { .. })) {
+                    Vec::new()
+                } else {
+                    vec![PolicyViolation::UnapprovedSourceReview { review_status }]
+                }
+            }
+            PolicyRule::MinVersion(requirement) => match semver::Version::parse(&release.version) {
+                Ok(version) if requirement.matches(&version) => Vec::new(),
+                _ => vec![PolicyViolation::VersionRequirementNotMet {
+                    version: release.version.clone(),
+                    requirement: requirement.to_string(),
+                }],
+            },
+            PolicyRule::RequireReleaseNotes => {
+                let has_notes = release.release_notes.as_deref().is_some_and(|notes| !notes.trim().is_empty());
+                // Custom phases are not ordered against Package, so they never need notes.
+                let packaged = release.phase >= SDLCPhase::Package;
+                if has_notes || !packaged {
+                    Vec::new()
+                } else {
+                    vec![PolicyViolation::MissingReleaseNotes { phase: release.phase.clone() }]
+                }
+            }
+            PolicyRule::PhaseCompletedWithin(phase, limit) => {
+                // A limit too large for chrono cannot be exceeded.
+                let Ok(max) = chrono::Duration::from_std(*limit) else {
+                    return Vec::new();
+                };
+                release.phase_stays(phase).into_iter()
+                    .filter_map(|(entered_at, left_at)| {
+                        let took = left_at.unwrap_or(now) - entered_at;
+                        (took > max).then(|| PolicyViolation::PhaseTookTooLong {
+                            phase: phase.clone(),
+                            entered_at,
+                            left_at,
+                            took: took.to_std().unwrap_or_default(),
+                            limit: *limit,
+                        })
+                    })
+                    .collect()
+            }
+            _ => {
+                // A release without runtime details has no known vulnerabilities.
+                let Some(runtime) = details.and_then(|details| details.runtime_details.as_ref()) else {
+                    return Vec::new();
+                };
+                self.evaluate_runtime_rule(rule, runtime, now)
+            }
+        }
+    }
+
+    fn evaluate_runtime_rule(&self, rule: &PolicyRule, runtime: &RuntimeDetails, now: DateTime<Utc>) -> Vec<PolicyViolation> {
+        match rule {
+            PolicyRule::VulnerabilityThreshold(level, max_count) => {
+                let found = runtime.vulnerabilities_above(*level).len();
+                if found > *max_count as usize {
+                    vec![PolicyViolation::VulnerabilityThresholdExceeded { level: *level, max_count: *max_count, found }]
+                } else {
+                    Vec::new()
+                }
+            }
+            PolicyRule::MaxVulnerabilityAge(max_age) => {
+                // A window too large for chrono cannot be exceeded.
+                let Ok(max_age) = chrono::Duration::from_std(*max_age) else {
+                    return Vec::new();
+                };
+                runtime.vulnerabilities.iter()
+                    .filter(|vulnerability| now - vulnerability.discovered_at > max_age)
+                    .map(|vulnerability| PolicyViolation::VulnerabilityTooOld {
+                        vulnerability_id: vulnerability.id.clone(),
+                        discovered_at: vulnerability.discovered_at,
+                    })
+                    .collect()
+            }
+            PolicyRule::BlockedPackages(blocked) => runtime.vulnerabilities.iter()
+                .filter_map(|vulnerability| {
+                    let package = blocked_package(vulnerability, blocked)?;
+                    Some(PolicyViolation::BlockedPackage {
+                        vulnerability_id: vulnerability.id.clone(),
+                        package: package.to_string(),
+                    })
+                })
+                .collect(),
+            PolicyRule::RequireHealthyRuntime => match &runtime.health_status {
+                HealthStatus::Unhealthy { details } => vec![PolicyViolation::UnhealthyRuntime {
+                    runtime_id: runtime.runtime_id.clone(),
+                    details: details.clone(),
+                }],
+                _ => Vec::new(),
+            },
+            PolicyRule::MaxAge(_) | PolicyRule::ApprovedIdentities(_) | PolicyRule::RequiredClaims(_)
+            | PolicyRule::ForbiddenClaims(_)
+            | PolicyRule::RequiresSBOM(_)
+            | PolicyRule::RequireReproducibleBuild
+            | PolicyRule::BlockSuspendedReleases
+            | PolicyRule::RequireApprovedSourceReview
+            | PolicyRule::RequireTeamOwnership(_)
+            | PolicyRule::MinVersion(_)
+            | PolicyRule::RequireReleaseNotes
+            | PolicyRule::PhaseCompletedWithin(..)
+            | PolicyRule::NoRevokedAttestations
+            | PolicyRule::ActiveForPhases(_) => Vec::new(),
+        }
+    }
+
+    /// Attestations the release's attestations build on only have to be trusted, not match the
+    /// other attestation rules.
+    fn evaluate_ancestor_rule(&self, rule: &PolicyRule, ancestor: &Attestation) -> Vec<PolicyViolation> {
+        match rule {
+            PolicyRule::NoRevokedAttestations => revocation(ancestor).into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn evaluate_component_rule(&self, rule: &PolicyRule, component_id: Uuid, component: Option<&SDLCComponent>) -> Vec<PolicyViolation> {
+        match rule {
+            PolicyRule::RequireTeamOwnership(team_id) => {
+                let owned = component.is_some_and(|component| component.teams().iter().any(|team| team.id == *team_id));
+                if owned {
+                    Vec::new()
+                } else {
+                    vec![PolicyViolation::MissingTeamOwnership { component_id, team_id: *team_id }]
+                }
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Whether the policy's `ActiveForPhases` rules, if any, all list `phase`.
+fn active_in(policy: &Policy, phase: &SDLCPhase) -> bool {
+    policy.rules.iter().all(|rule| match rule {
+        PolicyRule::ActiveForPhases(phases) => phases.contains(phase),
+        _ => true,
+    })
+}
+
+/// The violation of `NoRevokedAttestations` by the attestation, if it was revoked.
+fn revocation(attestation: &Attestation) -> Option<PolicyViolation> {
+    match &attestation.status {
+        AttestationStatus::Active => None,
+        AttestationStatus::Revoked { reason, revoked_by, .. } => Some(PolicyViolation::RevokedAttestation {
+            attestation_id: attestation.id,
+            reason: reason.clone(),
+            revoked_by: revoked_by.clone(),
+        }),
+    }
+}
+
+/// A duration as hours and minutes, like `73h 5m`.
+fn hours(duration: &Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    format!("{}h {}m", minutes / 60, minutes % 60)
+}
+
+/// The affected package if it is blocked. A blocked entry without a version, like
+/// `pkg:npm/lodash`, also blocks every version of it, like `pkg:npm/lodash@4.17.20`.
+fn blocked_package<'a>(vulnerability: &'a Vulnerability, blocked: &[String]) -> Option<&'a str> {
+    let package = vulnerability.affected_package.as_deref()?;
+    let unversioned = package.rsplit_once('@').map_or(package, |(name, _)| name);
+    blocked.iter().any(|entry| entry == package || entry == unversioned).then_some(package)
+}
+
+/// The claim as a string, with numbers and booleans in their JSON form, like `3` or `true`.
+fn claim_value(attestation: &Attestation, claim: &str) -> Option<String> {
+    attestation.claims.get(claim).map(|value| value.as_str().map_or_else(|| value.to_string(), str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::attestation::{ClaimSet, Subject, SubjectType};
+    use crate::model::phase::{BuildDetails, PackageDetails, PhaseDetails, SBOMReference, SourceDetails};
+    use crate::model::sdlc_component::{Project, TeamReference};
+    use crate::model::SDLCPhase;
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn vulnerability(id: &str, discovered_at: DateTime<Utc>, affected_package: Option<&str>) -> Vulnerability {
+        Vulnerability {
+            id: id.to_string(),
+            cve_id: None,
+            severity: VulnerabilityLevel::Medium,
+            cvss_score: None,
+            cvss_vector: None,
+            affected_package: affected_package.map(str::to_string),
+            fix_available: false,
+            description: String::new(),
+            discovered_at,
+        }
+    }
+
+    fn release_with(vulnerabilities: Vec<Vulnerability>) -> SDLCRelease {
+        let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        let mut details = PhaseDetails::new();
+        details.runtime_details = Some(RuntimeDetails {
+            runtime_id: "runtime-1".to_string(),
+            last_heartbeat: Utc::now(),
+            vulnerabilities,
+            health_status: HealthStatus::Unknown,
+            last_successful_health_check: None,
+        });
+        release.phase_details = Some(details);
+        release
+    }
+
+    fn policy_with(rule: PolicyRule) -> Policy {
+        let mut policy = Policy::new("Runtime Policy".to_string(), vec!["Runtime".to_string()]);
+        policy.add_rule(rule);
+        policy
+    }
+
+    #[test]
+    fn test_max_vulnerability_age() {
+        let service = PolicyEnforcementService::new();
+        let now = Utc::now();
+        let window = chrono::Duration::days(30);
+        let policy = policy_with(PolicyRule::MaxVulnerabilityAge(Duration::from_secs(30 * 24 * 60 * 60)));
+
+        let within = release_with(vec![vulnerability("CVE-2024-0001", now - window, None)]);
+        assert!(service.evaluate_at(&policy, &within, now).is_empty());
+
+        let discovered_at = now - window - chrono::Duration::seconds(1);
+        let past = release_with(vec![vulnerability("CVE-2024-0002", discovered_at, None)]);
+        assert_eq!(
+            service.evaluate_at(&policy, &past, now),
+            vec![PolicyViolation::VulnerabilityTooOld { vulnerability_id: "CVE-2024-0002".to_string(), discovered_at }]
+        );
+    }
+
+    #[test]
+    fn test_blocked_packages() {
+        let service = PolicyEnforcementService::new();
+        let policy = policy_with(PolicyRule::BlockedPackages(vec![
+            "pkg:npm/event-stream".to_string(),
+            "pkg:pypi/ctx@0.2.6".to_string(),
+        ]));
+
+        let allowed = release_with(vec![
+            vulnerability("CVE-2024-0003", Utc::now(), Some("pkg:npm/left-pad@1.3.0")),
+            vulnerability("CVE-2024-0004", Utc::now(), Some("pkg:pypi/ctx@0.2.2")),
+            vulnerability("CVE-2024-0005", Utc::now(), None),
+        ]);
+        assert!(service.evaluate(&policy, &allowed).is_empty());
+
+        let blocked = release_with(vec![
+            vulnerability("CVE-2018-1000851", Utc::now(), Some("pkg:npm/event-stream@3.3.6")),
+            vulnerability("CVE-2022-0001", Utc::now(), Some("pkg:pypi/ctx@0.2.6")),
+        ]);
+        let violations = service.evaluate(&policy, &blocked);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(
+            violations[0],
+            PolicyViolation::BlockedPackage {
+                vulnerability_id: "CVE-2018-1000851".to_string(),
+                package: "pkg:npm/event-stream@3.3.6".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_attestation_rules() {
+        let service = PolicyEnforcementService::new();
+        let now = Utc::now();
+        let subject = Subject { type_: SubjectType::Artifact, name: "app.tar.gz".to_string(), digest: "sha256:1234".to_string() };
+        let mut attestation = Attestation::new(subject, ClaimSet::from(HashMap::from([("builder".to_string(), serde_json::json!("ci"))])));
+        attestation.timestamp = now - chrono::Duration::hours(2);
+
+        let mut policy = policy_with(PolicyRule::MaxAge(Duration::from_secs(60 * 60)));
+        policy.add_rule(PolicyRule::ApprovedIdentities(vec!["ci@example.com".to_string()]));
+        policy.add_rule(PolicyRule::RequiredClaims(HashMap::from([("builder".to_string(), "ci".to_string())])));
+        assert_eq!(
+            service.evaluate_attestation_at(&policy, &attestation, now),
+            vec![
+                PolicyViolation::AttestationTooOld { attestation_id: attestation.id, timestamp: attestation.timestamp },
+                PolicyViolation::UnsignedAttestation { attestation_id: attestation.id },
+            ]
+        );
+
+        attestation.timestamp = now;
+        attestation.add_signature("ci@example.com".to_string(), "sig".to_string());
+        assert!(service.evaluate_attestation_at(&policy, &attestation, now).is_empty());
+
+        attestation.add_signature("mallory@example.com".to_string(), "sig".to_string());
+        attestation.claims.insert("builder".to_string(), serde_json::json!("laptop"));
+        assert_eq!(
+            service.evaluate_attestation_at(&policy, &attestation, now),
+            vec![
+                PolicyViolation::UnapprovedSigner { attestation_id: attestation.id, signer: "mallory@example.com".to_string() },
+                PolicyViolation::ClaimMismatch {
+                    attestation_id: attestation.id,
+                    claim: "builder".to_string(),
+                    expected: "ci".to_string(),
+                    actual: Some("laptop".to_string()),
+                },
+            ]
+        );
+    }
+
+    fn attestation_with(claims: serde_json::Value) -> Attestation {
+        let subject = Subject { type_: SubjectType::Artifact, name: "app.tar.gz".to_string(), digest: "sha256:1234".to_string() };
+        Attestation::new(subject, serde_json::from_value(claims).unwrap())
+    }
+
+    fn claims(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(claim, value)| (claim.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_required_claims() {
+        let service = PolicyEnforcementService::new();
+        let attestation = attestation_with(serde_json::json!({ "builder": "ci", "level": 3, "hermetic": true }));
+
+        let policy = policy_with(PolicyRule::RequiredClaims(claims(&[("builder", "ci"), ("level", "3"), ("hermetic", "true")])));
+        assert!(service.evaluate_attestation(&policy, &attestation).is_empty());
+
+        let policy = policy_with(PolicyRule::RequiredClaims(claims(&[("builder", "ci"), ("reviewer", "alice")])));
+        assert_eq!(
+            service.evaluate_attestation(&policy, &attestation),
+            vec![PolicyViolation::ClaimMismatch {
+                attestation_id: attestation.id,
+                claim: "reviewer".to_string(),
+                expected: "alice".to_string(),
+                actual: None,
+            }]
+        );
+
+        let policy = policy_with(PolicyRule::RequiredClaims(claims(&[("level", "4")])));
+        let violations = service.evaluate_attestation(&policy, &attestation);
+        assert_eq!(
+            violations,
+            vec![PolicyViolation::ClaimMismatch {
+                attestation_id: attestation.id,
+                claim: "level".to_string(),
+                expected: "4".to_string(),
+                actual: Some("3".to_string()),
+            }]
+        );
+        assert_eq!(violations[0].to_string(), format!("Attestation {} has the claim level=3, 4 expected", attestation.id));
+
+        let policy = policy_with(PolicyRule::RequiredClaims(HashMap::new()));
+        assert!(service.evaluate_attestation(&policy, &attestation_with(serde_json::json!({}))).is_empty());
+    }
+
+    #[test]
+    fn test_forbidden_claims() {
+        let service = PolicyEnforcementService::new();
+        let policy = policy_with(PolicyRule::ForbiddenClaims(claims(&[("builder", "laptop"), ("hermetic", "false")])));
+
+        assert!(service.evaluate_attestation(&policy, &attestation_with(serde_json::json!({ "builder": "ci", "hermetic": true }))).is_empty());
+        assert!(service.evaluate_attestation(&policy, &attestation_with(serde_json::json!({}))).is_empty());
+
+        let attestation = attestation_with(serde_json::json!({ "builder": "ci", "hermetic": false }));
+        assert_eq!(
+            service.evaluate_attestation(&policy, &attestation),
+            vec![PolicyViolation::ForbiddenClaim {
+                attestation_id: attestation.id,
+                claim: "hermetic".to_string(),
+                value: "false".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_requires_sbom() {
+        let service = PolicyEnforcementService::new();
+        let policy = policy_with(PolicyRule::RequiresSBOM(SBOMFormat::CycloneDX15));
+        let mut release = release_with(Vec::new());
+        let package = |sbom| PackageDetails {
+            artifact_hash: "sha256:feed".to_string(),
+            artifact_url: "https://example.com/app.tar.gz".to_string(),
+            sbom,
+        };
+        let sbom = |format| SBOMReference {
+            format,
+            url: "https://example.com/app.cdx.json".to_string(),
+            digest: "sha256:5b0m".to_string(),
+            generated_at: Utc::now(),
+        };
+
+        release.phase_details.as_mut().unwrap().package_details = Some(package(None));
+        assert_eq!(
+            service.evaluate(&policy, &release),
+            vec![PolicyViolation::MissingSBOM { required: SBOMFormat::CycloneDX15, found: None }]
+        );
+
+        release.phase_details.as_mut().unwrap().package_details = Some(package(Some(sbom(SBOMFormat::Spdx2))));
+        assert_eq!(
+            service.evaluate(&policy, &release),
+            vec![PolicyViolation::MissingSBOM { required: SBOMFormat::CycloneDX15, found: Some(SBOMFormat::Spdx2) }]
+        );
+
+        release.phase_details.as_mut().unwrap().package_details = Some(package(Some(sbom(SBOMFormat::CycloneDX15))));
+        assert!(service.evaluate(&policy, &release).is_empty());
+    }
+
+    #[test]
+    fn test_require_reproducible_build() {
+        let service = PolicyEnforcementService::new();
+        let policy = policy_with(PolicyRule::RequireReproducibleBuild);
+        let mut release = release_with(Vec::new());
+        assert_eq!(
+            service.evaluate(&policy, &release),
+            vec![PolicyViolation::NonReproducibleBuild {
+                build_id: None,
+                message: "Release has no build details to check for reproducibility".to_string(),
+            }]
+        );
+
+        let build = |reproducible| BuildDetails {
+            build_id: "build-1".to_string(),
+            build_timestamp: Utc::now(),
+            reproducible,
+            build_tool: Some("bazel 7.1.0".to_string()),
+            build_environment: None,
+        };
+        release.phase_details.as_mut().unwrap().build_details = Some(build(false));
+        assert_eq!(
+            service.evaluate(&policy, &release),
+            vec![PolicyViolation::NonReproducibleBuild {
+                build_id: Some("build-1".to_string()),
+                message: "Build build-1 is not reproducible".to_string(),
+            }]
+        );
+
+        release.phase_details.as_mut().unwrap().build_details = Some(build(true));
+        assert!(service.evaluate(&policy, &release).is_empty());
+    }
+
+    #[test]
+    fn test_block_suspended_releases() {
+        let service = PolicyEnforcementService::new();
+        let policy = policy_with(PolicyRule::BlockSuspendedReleases);
+        let mut release = release_with(Vec::new());
+        release.state = ReleaseState::Deployed { environment: "production".to_string(), deployment_time: Utc::now() };
+        assert!(service.evaluate(&policy, &release).is_empty());
+
+        release.suspend("Incident INC-42".to_string(), "oncall".to_string()).unwrap();
+        assert_eq!(
+            service.evaluate(&policy, &release),
+            vec![PolicyViolation::ReleaseSuspended {
+                reason: "Incident INC-42".to_string(),
+                suspended_by: "oncall".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_require_approved_source_review() {
+        let service = PolicyEnforcementService::new();
+        let policy = policy_with(PolicyRule::RequireApprovedSourceReview);
+        let mut release = release_with(Vec::new());
+        assert_eq!(service.evaluate(&policy, &release), vec![PolicyViolation::UnapprovedSourceReview { review_status: None }]);
+
+        let source = |review_status| SourceDetails {
+            commit_hash: "abcdef123456".to_string(),
+            branch: "main".to_string(),
+            pr_url: None,
+            review_status,
+        };
+        let changes_requested = SourceReviewStatus::ChangesRequested { reviewer: "reviewer1".to_string(), comment: "Missing tests".to_string() };
+        release.phase_details.as_mut().unwrap().source_details = Some(source(changes_requested.clone()));
+        assert_eq!(
+            service.evaluate(&policy, &release),
+            vec![PolicyViolation::UnapprovedSourceReview { review_status: Some(changes_requested) }]
+        );
+
+        let approved = SourceReviewStatus::Approved { approver: "reviewer1".to_string(), approved_at: Utc::now() };
+        release.phase_details.as_mut().unwrap().source_details = Some(source(approved));
+        assert!(service.evaluate(&policy, &release).is_empty());
+    }
+
+    #[test]
+    fn test_require_healthy_runtime() {
+        let service = PolicyEnforcementService::new();
+        let policy = policy_with(PolicyRule::RequireHealthyRuntime);
+        let mut release = release_with(Vec::new());
+        assert!(release.update_health_status(HealthStatus::Healthy).is_err());
+
+        release.phase = SDLCPhase::Runtime;
+        release.state = ReleaseState::Deployed { environment: "production".to_string(), deployment_time: Utc::now() };
+        assert!(service.evaluate(&policy, &release).is_empty());
+
+        release.update_health_status(HealthStatus::Degraded { details: "High latency".to_string() }).unwrap();
+        assert!(service.evaluate(&policy, &release).is_empty());
+
+        release.update_health_status(HealthStatus::Unhealthy { details: "Liveness probe failing".to_string() }).unwrap();
+        assert_eq!(
+            service.evaluate(&policy, &release),
+            vec![PolicyViolation::UnhealthyRuntime {
+                runtime_id: "runtime-1".to_string(),
+                details: "Liveness probe failing".to_string(),
+            }]
+        );
+
+        release.record_heartbeat().unwrap();
+        let runtime = release.phase_details.as_ref().unwrap().runtime_details.as_ref().unwrap();
+        assert_eq!(runtime.health_status, HealthStatus::Healthy);
+        assert_eq!(runtime.last_successful_health_check, Some(runtime.last_heartbeat));
+        assert!(service.evaluate(&policy, &release).is_empty());
+    }
+
+    #[test]
+    fn test_require_team_ownership() {
+        let service = PolicyEnforcementService::new();
+        let payments = TeamReference { id: Uuid::new_v4(), name: "payments".to_string(), contact_email: "payments@example.com".to_string() };
+        let policy = policy_with(PolicyRule::RequireTeamOwnership(payments.id));
+        let mut project = Project {
+            id: Uuid::new_v4(),
+            name: "payments-api".to_string(),
+            repository_url: None,
+            owner: None,
+            components: Vec::new(),
+            teams: Vec::new(),
+        };
+        let violation = PolicyViolation::MissingTeamOwnership { component_id: project.id, team_id: payments.id };
+        assert_eq!(service.evaluate_component(&policy, &SDLCComponent::Project(project.clone())), vec![violation.clone()]);
+
+        project.teams.push(TeamReference { id: Uuid::new_v4(), name: "platform".to_string(), contact_email: "platform@example.com".to_string() });
+        assert_eq!(service.evaluate_component(&policy, &SDLCComponent::Project(project.clone())), vec![violation.clone()]);
+
+        project.teams.push(payments);
+        let component = SDLCComponent::Project(project);
+        assert!(service.evaluate_component(&policy, &component).is_empty());
+
+        // Release-only evaluation cannot see the component; the per-rule report can.
+        let mut release = release_with(Vec::new());
+        release.component_id = component.id();
+        assert!(service.evaluate(&policy, &release).is_empty());
+        assert!(service.evaluate_policy(&policy, &release, Some(&component), &[]).passed);
+        let unregistered = service.evaluate_policy(&policy, &release, None, &[]);
+        assert!(!unregistered.passed);
+        assert_eq!(unregistered.rule_results[0].details, violation.to_string());
+    }
+
+    #[test]
+    fn test_min_version() {
+        let service = PolicyEnforcementService::new();
+        let policy = policy_with(PolicyRule::MinVersion(semver::VersionReq::parse(">=1.2.0").unwrap()));
+        let mut release = release_with(Vec::new());
+        assert_eq!(
+            service.evaluate(&policy, &release),
+            vec![PolicyViolation::VersionRequirementNotMet { version: "1.0.0".to_string(), requirement: ">=1.2.0".to_string() }]
+        );
+
+        release.version = "1.10.0".to_string();
+        assert!(service.evaluate(&policy, &release).is_empty());
+
+        // Pre-releases only satisfy requirements that name them.
+        release.version = "2.0.0-rc.1".to_string();
+        assert_eq!(service.evaluate(&policy, &release).len(), 1);
+    }
+
+    #[test]
+    fn test_require_release_notes() {
+        let service = PolicyEnforcementService::new();
+        let policy = policy_with(PolicyRule::RequireReleaseNotes);
+        let mut release = release_with(Vec::new());
+        release.phase = SDLCPhase::Build;
+        assert!(service.evaluate(&policy, &release).is_empty());
+
+        release.phase = SDLCPhase::Package;
+        assert_eq!(service.evaluate(&policy, &release), vec![PolicyViolation::MissingReleaseNotes { phase: SDLCPhase::Package }]);
+        release.set_release_notes("  ".to_string());
+        assert_eq!(service.evaluate(&policy, &release).len(), 1);
+        release.set_release_notes("Adds SSO".to_string());
+        assert!(service.evaluate(&policy, &release).is_empty());
+
+        release.release_notes = None;
+        release.phase = SDLCPhase::Custom("Certification".to_string());
+        assert!(service.evaluate(&policy, &release).is_empty());
+    }
+
+    /// A release that entered Source review at `entered` and, after `hours` hours, moved on to Build.
+    fn reviewed_release(entered: DateTime<Utc>, hours: i64) -> SDLCRelease {
+        let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+        release.complete_development().unwrap();
+        release.start_source_review("reviewer1".to_string()).unwrap();
+        let approved = SourceReviewStatus::Approved { approver: "reviewer1".to_string(), approved_at: Utc::now() };
+        release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, approved).unwrap();
+        let timestamps = [entered, entered, entered + chrono::Duration::hours(1), entered + chrono::Duration::hours(hours)];
+        for (record, timestamp) in release.transition_history.iter_mut().zip(timestamps) {
+            record.timestamp = timestamp;
+        }
+        release
+    }
+
+    #[test]
+    fn test_phase_completed_within() {
+        let service = PolicyEnforcementService::new();
+        let limit = Duration::from_secs(72 * 60 * 60);
+        let policy = policy_with(PolicyRule::PhaseCompletedWithin(SDLCPhase::Source, limit));
+        let entered = Utc::now() - chrono::Duration::days(7);
+
+        let release = reviewed_release(entered, 73);
+        let violation = PolicyViolation::PhaseTookTooLong {
+            phase: SDLCPhase::Source,
+            entered_at: entered,
+            left_at: Some(entered + chrono::Duration::hours(73)),
+            took: Duration::from_secs(73 * 60 * 60),
+            limit,
+        };
+        assert_eq!(service.evaluate(&policy, &release), vec![violation.clone()]);
+        let result = service.evaluate_policy(&policy, &release, None, &[]);
+        assert!(!result.passed);
+        assert_eq!(result.rule_results[0].details, "The release spent 73h 0m in the Source phase, at most 72h 0m allowed");
+        assert_eq!(result.rule_results[0].details, violation.to_string());
+
+        assert!(service.evaluate(&policy, &reviewed_release(entered, 71)).is_empty());
+        // Other phases are not limited.
+        let development = policy_with(PolicyRule::PhaseCompletedWithin(SDLCPhase::Development, Duration::from_secs(60)));
+        assert!(service.evaluate(&development, &reviewed_release(entered, 73)).is_empty());
+    }
+
+    #[test]
+    fn test_phase_completed_within_counts_the_current_stay() {
+        let service = PolicyEnforcementService::new();
+        let policy = policy_with(PolicyRule::PhaseCompletedWithin(SDLCPhase::Development, Duration::from_secs(60 * 60)));
+        let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+
+        // The release was created in Development and has been there since.
+        let now = release.created_at + chrono::Duration::minutes(90);
+        assert_eq!(
+            service.evaluate_at(&policy, &release, now),
+            vec![PolicyViolation::PhaseTookTooLong {
+                phase: SDLCPhase::Development,
+                entered_at: release.created_at,
+                left_at: None,
+                took: Duration::from_secs(90 * 60),
+                limit: Duration::from_secs(60 * 60),
+            }]
+        );
+        assert!(service.evaluate_at(&policy, &release, release.created_at + chrono::Duration::minutes(30)).is_empty());
+    }
+
+    #[test]
+    fn test_phase_completed_within_skips_releases_without_history() {
+        let service = PolicyEnforcementService::new();
+        let policy = policy_with(PolicyRule::PhaseCompletedWithin(SDLCPhase::Source, Duration::from_secs(60)));
+        let mut release = reviewed_release(Utc::now() - chrono::Duration::days(7), 73);
+        release.transition_history.clear();
+
+        let result = service.evaluate_policy(&policy, &release, None, &[]);
+        assert!(result.passed);
+        assert_eq!(result.rule_results[0].details, "No history available; rule skipped");
+    }
+
+    #[test]
+    fn test_no_revoked_attestations() {
+        let service = PolicyEnforcementService::new();
+        let policy = policy_with(PolicyRule::NoRevokedAttestations);
+        let release = release_with(Vec::new());
+        let attestation = attestation_with(serde_json::json!({}));
+        let mut revoked = attestation_with(serde_json::json!({}));
+        revoked.status = AttestationStatus::Revoked {
+            reason: "Signing key leaked".to_string(),
+            revoked_at: Utc::now(),
+            revoked_by: "security@example.com".to_string(),
+        };
+        let violation = PolicyViolation::RevokedAttestation {
+            attestation_id: revoked.id,
+            reason: "Signing key leaked".to_string(),
+            revoked_by: "security@example.com".to_string(),
+        };
+
+        assert!(service.evaluate(&policy, &release).is_empty());
+        assert!(service.evaluate_attestation(&policy, &attestation).is_empty());
+        assert_eq!(service.evaluate_attestation(&policy, &revoked), vec![violation.clone()]);
+
+        // A revoked ancestor fails the rule like a revoked attestation about the release.
+        let result = service.evaluate_policy_at(&policy, &release, None, std::slice::from_ref(&attestation), std::slice::from_ref(&revoked), Utc::now());
+        assert!(!result.passed);
+        assert_eq!(result.rule_results[0].details, violation.to_string());
+        // Other attestation rules ignore ancestors.
+        let claims = policy_with(PolicyRule::RequiredClaims(claims(&[("builder", "ci")])));
+        assert!(service.evaluate_policy_at(&claims, &release, None, &[], std::slice::from_ref(&revoked), Utc::now()).passed);
+    }
+
+    #[test]
+    fn test_active_for_phases() {
+        let service = PolicyEnforcementService::new();
+        let mut policy = policy_with(PolicyRule::MaxAge(Duration::from_secs(24 * 60 * 60)));
+        policy.add_rule(PolicyRule::ActiveForPhases(vec![SDLCPhase::Source]));
+        policy.add_rule(PolicyRule::RequireApprovedSourceReview);
+        let mut stale = attestation_with(serde_json::json!({}));
+        stale.timestamp = Utc::now() - chrono::Duration::days(2);
+        let mut release = release_with(Vec::new());
+
+        release.phase = SDLCPhase::Source;
+        assert_eq!(service.evaluate(&policy, &release), vec![PolicyViolation::UnapprovedSourceReview { review_status: None }]);
+        let result = service.evaluate_policy(&policy, &release, None, std::slice::from_ref(&stale));
+        assert_eq!(result.rule_results.iter().map(|rule| rule.passed).collect::<Vec<_>>(), vec![false, true, false]);
+
+        // Outside Source every rule passes, however stale the attestation.
+        release.phase = SDLCPhase::Build;
+        assert!(service.evaluate(&policy, &release).is_empty());
+        let result = service.evaluate_policy(&policy, &release, None, std::slice::from_ref(&stale));
+        assert!(result.passed);
+        assert!(result.rule_results.iter().all(|rule| rule.details == "Policy is not active in the Build phase; rule skipped"));
+
+        // Custom phases match by name, and every ActiveForPhases rule has to list the phase.
+        release.phase = SDLCPhase::Custom("Certification".to_string());
+        let mut certification = policy_with(PolicyRule::RequireApprovedSourceReview);
+        certification.add_rule(PolicyRule::ActiveForPhases(vec![SDLCPhase::Custom("Certification".to_string()), SDLCPhase::Build]));
+        assert_eq!(service.evaluate(&certification, &release).len(), 1);
+        certification.add_rule(PolicyRule::ActiveForPhases(vec![SDLCPhase::Build]));
+        assert!(service.evaluate(&certification, &release).is_empty());
+    }
+}
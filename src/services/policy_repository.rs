@@ -2,15 +2,27 @@ use async_trait::async_trait;
 use schemars::JsonSchema;
 use utoipa::ToSchema;
 use uuid::Uuid;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
 
-use crate::model::Policy;
+use crate::model::{Policy, SDLCPhase};
+
+use super::release_repository::ReleaseRepository;
 
 #[async_trait]
 pub trait PolicyRepository: Send + Sync {
     async fn store_policy(&self, policy: Policy) -> Result<(), PolicyRepositoryError>;
     async fn get_policy(&self, id: &Uuid) -> Result<Option<Policy>, PolicyRepositoryError>;
+    async fn list_policies(&self) -> Result<Vec<Policy>, PolicyRepositoryError>;
     async fn get_policies_for_component(&self, component_id: &Uuid) -> Result<Vec<Policy>, PolicyRepositoryError>;
     async fn get_latest_policy_for_component(&self, component_id: &Uuid) -> Result<Option<Policy>, PolicyRepositoryError>;
+    /// Policies whose `applies_to` names the phase, whichever components they are assigned to.
+    async fn get_policies_by_phase(&self, phase: &SDLCPhase) -> Result<Vec<Policy>, PolicyRepositoryError>;
+    /// Removes the policy and its assignments to components. Fails with `HasDependents` if
+    /// releases have the policy in `SDLCRelease::applied_policies`, unless `force` is set, in
+    /// which case it is removed from those releases as well.
+    async fn delete_policy(&self, id: &Uuid, force: bool) -> Result<(), PolicyRepositoryError>;
 }
 
 #[derive(Debug, thiserror::Error, JsonSchema, ToSchema)]
@@ -19,5 +31,166 @@ pub enum PolicyRepositoryError {
     StorageError(String),
     #[error("Failed to retrieve policy: {0}")]
     RetrievalError(String),
+    #[error("Policy {0} not found")]
+    NotFound(Uuid),
+    #[error("Policy was applied to releases {0:?}")]
+    HasDependents(Vec<Uuid>),
+}
+
+
+// Example in-memory implementation for testing. Policies are linked to components with
+// `assign_policy`; the most recently assigned policy is a component's latest. Releases are only
+// checked for a deleted policy if the repository they are stored in is set with `with_releases`.
+#[derive(Clone)]
+pub struct InMemoryPolicyRepository {
+    policies: Arc<RwLock<HashMap<Uuid, Policy>>>,
+    assignments: Arc<RwLock<HashMap<Uuid, Vec<Uuid>>>>,
+    releases: Option<Arc<dyn ReleaseRepository>>,
 }
 
+impl Default for InMemoryPolicyRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryPolicyRepository {
+    pub fn new() -> Self {
+        Self {
+            policies: Arc::new(RwLock::new(HashMap::new())),
+            assignments: Arc::new(RwLock::new(HashMap::new())),
+            releases: None,
+        }
+    }
+
+    /// Looks for releases the policy was applied to in `releases` when a policy is deleted.
+    pub fn with_releases(mut self, releases: Arc<dyn ReleaseRepository>) -> Self {
+        self.releases = Some(releases);
+        self
+    }
+
+    pub async fn assign_policy(&self, component_id: Uuid, policy_id: Uuid) {
+        let mut assignments = self.assignments.write().await;
+        assignments.entry(component_id).or_default().push(policy_id);
+    }
+}
+
+#[async_trait]
+impl PolicyRepository for InMemoryPolicyRepository {
+    async fn store_policy(&self, policy: Policy) -> Result<(), PolicyRepositoryError> {
+        let mut policies = self.policies.write().await;
+        policies.insert(policy.id, policy);
+        Ok(())
+    }
+
+    async fn get_policy(&self, id: &Uuid) -> Result<Option<Policy>, PolicyRepositoryError> {
+        let policies = self.policies.read().await;
+        Ok(policies.get(id).cloned())
+    }
+
+    async fn list_policies(&self) -> Result<Vec<Policy>, PolicyRepositoryError> {
+        let policies = self.policies.read().await;
+        Ok(policies.values().cloned().collect())
+    }
+
+    async fn get_policies_for_component(&self, component_id: &Uuid) -> Result<Vec<Policy>, PolicyRepositoryError> {
+        let policies = self.policies.read().await;
+        let assignments = self.assignments.read().await;
+        Ok(assignments.get(component_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|policy_id| policies.get(policy_id).cloned())
+            .collect())
+    }
+
+    async fn get_latest_policy_for_component(&self, component_id: &Uuid) -> Result<Option<Policy>, PolicyRepositoryError> {
+        Ok(self.get_policies_for_component(component_id).await?.pop())
+    }
+
+    async fn get_policies_by_phase(&self, phase: &SDLCPhase) -> Result<Vec<Policy>, PolicyRepositoryError> {
+        let policies = self.policies.read().await;
+        Ok(policies.values()
+            .filter(|policy| policy.applies_to.iter().any(|name| name == phase.name()))
+            .cloned()
+            .collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete_policy(&self, id: &Uuid, force: bool) -> Result<(), PolicyRepositoryError> {
+        let mut policies = self.policies.write().await;
+        if !policies.contains_key(id) {
+            return Err(PolicyRepositoryError::NotFound(*id));
+        }
+        if let Some(releases) = &self.releases {
+            let dependents: Vec<_> = releases.list_releases().await
+                .map_err(|err| PolicyRepositoryError::RetrievalError(err.to_string()))?
+                .into_iter()
+                .filter(|release| release.applied_policies.contains(id))
+                .collect();
+            if !dependents.is_empty() && !force {
+                let mut release_ids: Vec<Uuid> = dependents.iter().map(|release| release.id).collect();
+                release_ids.sort();
+                return Err(PolicyRepositoryError::HasDependents(release_ids));
+            }
+            for mut release in dependents {
+                release.applied_policies.remove(id);
+                let revision = release.revision;
+                releases.update_release(release, revision).await
+                    .map_err(|err| PolicyRepositoryError::StorageError(err.to_string()))?;
+            }
+        }
+        policies.remove(id);
+        for assigned in self.assignments.write().await.values_mut() {
+            assigned.retain(|policy_id| policy_id != id);
+        }
+        tracing::info!("Deleted policy");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::contract_tests::define_service_contract_tests;
+
+    #[tokio::test]
+    async fn test_latest_policy_for_component() {
+        let repository = InMemoryPolicyRepository::new();
+        let component_id = Uuid::new_v4();
+        let first = Policy::new("baseline".to_string(), vec!["Build".to_string()]);
+        let second = Policy::new("hardened".to_string(), vec!["Build".to_string()]);
+        repository.store_policy(first.clone()).await.unwrap();
+        repository.store_policy(second.clone()).await.unwrap();
+        repository.assign_policy(component_id, first.id).await;
+        repository.assign_policy(component_id, second.id).await;
+
+        assert_eq!(repository.get_policies_for_component(&component_id).await.unwrap().len(), 2);
+        let latest = repository.get_latest_policy_for_component(&component_id).await.unwrap().unwrap();
+        assert_eq!(latest.name, "hardened");
+        assert!(repository.get_latest_policy_for_component(&Uuid::new_v4()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_policies_by_phase() {
+        let repository = InMemoryPolicyRepository::new();
+        let source = Policy::new("review".to_string(), vec!["Source".to_string()]);
+        let both = Policy::new("provenance".to_string(), vec!["Source".to_string(), "Build".to_string()]);
+        let custom = Policy::new("certification".to_string(), vec!["Certification".to_string()]);
+        for policy in [&source, &both, &custom] {
+            repository.store_policy(policy.clone()).await.unwrap();
+        }
+        // Assignments to components do not matter.
+        repository.assign_policy(Uuid::new_v4(), both.id).await;
+
+        let mut names: Vec<String> = repository.get_policies_by_phase(&SDLCPhase::Source).await.unwrap().into_iter().map(|policy| policy.name).collect();
+        names.sort();
+        assert_eq!(names, vec!["provenance", "review"]);
+        let build = repository.get_policies_by_phase(&SDLCPhase::Build).await.unwrap();
+        assert_eq!(build.iter().map(|policy| policy.id).collect::<Vec<_>>(), vec![both.id]);
+        let certification = repository.get_policies_by_phase(&SDLCPhase::Custom("Certification".to_string())).await.unwrap();
+        assert_eq!(certification.iter().map(|policy| policy.id).collect::<Vec<_>>(), vec![custom.id]);
+        assert!(repository.get_policies_by_phase(&SDLCPhase::Runtime).await.unwrap().is_empty());
+    }
+
+    define_service_contract_tests!(InMemoryPolicyRepository::new);
+}
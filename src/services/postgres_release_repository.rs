@@ -0,0 +1,200 @@
+use async_trait::async_trait;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::{Postgres, Transaction};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use crate::model::SDLCRelease;
+
+use super::release_repository::{ReleaseFilter, ReleaseRepository, ReleaseRepositoryError};
+
+const SCHEMA: [&str; 3] = [
+    "CREATE TABLE IF NOT EXISTS releases (
+        id UUID PRIMARY KEY,
+        component_id UUID NOT NULL,
+        revision BIGINT NOT NULL,
+        release JSONB NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS releases_component_id ON releases (component_id)",
+    // Serves the `@>` containment queries the phase and tag filters compile to.
+    "CREATE INDEX IF NOT EXISTS releases_release ON releases USING GIN (release jsonb_path_ops)",
+];
+
+/// Keeps every release as a JSONB document in the `releases` table. Phase and tag filters run
+/// in the database; the remaining criteria of a `ReleaseFilter` are applied after loading.
+#[derive(Clone)]
+pub struct PostgresReleaseRepository {
+    pool: PgPool,
+}
+
+impl PostgresReleaseRepository {
+    /// Connects to the database at `url`, e.g. `postgres://sdlccp@localhost/sdlccp`.
+    pub async fn connect(url: &str) -> Result<Self, ReleaseRepositoryError> {
+        let pool = PgPoolOptions::new().connect(url).await.map_err(storage_error)?;
+        Self::from_pool(pool).await
+    }
+
+    /// Uses `pool`, creating the `releases` table and its indexes first if they do not exist.
+    pub async fn from_pool(pool: PgPool) -> Result<Self, ReleaseRepositoryError> {
+        for statement in SCHEMA {
+            sqlx::query(statement).execute(&pool).await.map_err(storage_error)?;
+        }
+        Ok(Self { pool })
+    }
+
+    /// Writes `release` with the revision after the one it replaces. With `expected_revision`,
+    /// only replaces a release that is still at that revision.
+    async fn write_release(&self, release: SDLCRelease, expected_revision: Option<u64>) -> Result<SDLCRelease, ReleaseRepositoryError> {
+        let mut transaction = self.pool.begin().await.map_err(storage_error)?;
+        let release = write_in(&mut transaction, release, expected_revision).await?;
+        transaction.commit().await.map_err(storage_error)?;
+        Ok(release)
+    }
+
+    /// The releases whose document contains `document`, narrowed to one component if given.
+    async fn containing(&self, document: serde_json::Value, component_id: Option<Uuid>) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let releases: Vec<Json<SDLCRelease>> = sqlx::query_scalar(
+            "SELECT release FROM releases WHERE release @> $1 AND ($2::uuid IS NULL OR component_id = $2)",
+        )
+        .bind(Json(document))
+        .bind(component_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(retrieval_error)?;
+        Ok(releases.into_iter().map(|Json(release)| release).collect())
+    }
+}
+
+#[async_trait]
+impl ReleaseRepository for PostgresReleaseRepository {
+    #[tracing::instrument(skip_all, fields(release_id = %release.id))]
+    async fn store_release(&self, release: SDLCRelease) -> Result<(), ReleaseRepositoryError> {
+        let stored = self.write_release(release, None).await?;
+        tracing::debug!(revision = stored.revision, "Stored release");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(count = releases.len()))]
+    async fn store_releases(&self, releases: Vec<SDLCRelease>) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let mut transaction = self.pool.begin().await.map_err(storage_error)?;
+        let mut stored = Vec::with_capacity(releases.len());
+        for release in releases {
+            stored.push(write_in(&mut transaction, release, None).await?);
+        }
+        transaction.commit().await.map_err(storage_error)?;
+        tracing::debug!("Stored releases");
+        Ok(stored)
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %release.id, expected_revision))]
+    async fn update_release(&self, release: SDLCRelease, expected_revision: u64) -> Result<SDLCRelease, ReleaseRepositoryError> {
+        let stored = self.write_release(release, Some(expected_revision)).await?;
+        tracing::debug!(revision = stored.revision, "Updated release");
+        Ok(stored)
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %id))]
+    async fn get_release(&self, id: &Uuid) -> Result<Option<SDLCRelease>, ReleaseRepositoryError> {
+        let release: Option<Json<SDLCRelease>> = sqlx::query_scalar("SELECT release FROM releases WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(retrieval_error)?;
+        Ok(release.map(|Json(release)| release))
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %id))]
+    async fn delete_release(&self, id: &Uuid) -> Result<(), ReleaseRepositoryError> {
+        let deleted = sqlx::query("DELETE FROM releases WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(storage_error)?;
+        if deleted.rows_affected() == 0 {
+            return Err(ReleaseRepositoryError::NotFound);
+        }
+        tracing::debug!("Deleted release");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases(&self) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        self.containing(serde_json::json!({}), None).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases_for_component(&self, component_id: &Uuid) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        self.containing(serde_json::json!({}), Some(*component_id)).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases_by_tag(&self, tag: &str) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        self.containing(serde_json::json!({ "tags": [tag] }), None).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases_filtered(&self, filter: ReleaseFilter) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let mut document = serde_json::Map::new();
+        if let Some(phase) = &filter.phase {
+            document.insert("phase".to_string(), serde_json::to_value(phase).map_err(retrieval_error)?);
+        }
+        if let Some(tag) = &filter.tag {
+            document.insert("tags".to_string(), serde_json::json!([tag]));
+        }
+        let releases = self.containing(document.into(), filter.component_id).await?;
+        let mut matching: Vec<SDLCRelease> = releases.into_iter().filter(|release| filter.matches(release)).collect();
+        matching.sort_by_key(|release| (release.created_at, release.id));
+        Ok(matching)
+    }
+}
+
+/// `write_release` within `transaction`.
+async fn write_in(transaction: &mut Transaction<'_, Postgres>, mut release: SDLCRelease, expected_revision: Option<u64>) -> Result<SDLCRelease, ReleaseRepositoryError> {
+    let actual: Option<i64> = sqlx::query_scalar("SELECT revision FROM releases WHERE id = $1 FOR UPDATE")
+        .bind(release.id)
+        .fetch_optional(&mut **transaction)
+        .await
+        .map_err(storage_error)?;
+    match (actual.map(|revision| revision as u64), expected_revision) {
+        (None, Some(_)) => return Err(ReleaseRepositoryError::NotFound),
+        (Some(actual), Some(expected)) if actual != expected => {
+            return Err(ReleaseRepositoryError::RevisionMismatch { expected, actual });
+        }
+        (Some(actual), _) => release.revision = actual + 1,
+        (None, None) => {}
+    }
+    sqlx::query(
+        "INSERT INTO releases (id, component_id, revision, release) VALUES ($1, $2, $3, $4)
+         ON CONFLICT (id) DO UPDATE SET component_id = excluded.component_id, revision = excluded.revision, release = excluded.release",
+    )
+    .bind(release.id)
+    .bind(release.component_id)
+    .bind(release.revision as i64)
+    .bind(Json(&release))
+    .execute(&mut **transaction)
+    .await
+    .map_err(storage_error)?;
+    Ok(release)
+}
+
+fn storage_error(err: impl ToString) -> ReleaseRepositoryError {
+    ReleaseRepositoryError::StorageError(err.to_string())
+}
+
+fn retrieval_error(err: impl ToString) -> ReleaseRepositoryError {
+    ReleaseRepositoryError::RetrievalError(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::contract_tests::define_service_contract_tests;
+
+    async fn repository() -> PostgresReleaseRepository {
+        let url = std::env::var("SDLCCP_TEST_POSTGRES_URL").expect("SDLCCP_TEST_POSTGRES_URL is not set");
+        PostgresReleaseRepository::connect(&url).await.unwrap()
+    }
+
+    // Needs a database to write to, like `test_postgres_repository`.
+    define_service_contract_tests!(async repository, #[ignore]);
+}
@@ -0,0 +1,384 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::model::{SDLCPhase, SDLCRelease};
+
+use super::event_sourcing_release_repository::ReleaseEvent;
+
+#[async_trait]
+pub trait ReleaseRepository: Send + Sync {
+    /// Stores the release, replacing any release with the same ID and bumping its revision.
+    async fn store_release(&self, release: SDLCRelease) -> Result<(), ReleaseRepositoryError>;
+    /// Stores every release like `store_release`, all or none of them.
+    async fn store_releases(&self, releases: Vec<SDLCRelease>) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError>;
+    /// Replaces a stored release only if it is still at `expected_revision`, and returns it with
+    /// its new revision. Fails with `RevisionMismatch` if someone else changed it in between.
+    async fn update_release(&self, release: SDLCRelease, expected_revision: u64) -> Result<SDLCRelease, ReleaseRepositoryError>;
+    async fn get_release(&self, id: &Uuid) -> Result<Option<SDLCRelease>, ReleaseRepositoryError>;
+    /// Removes the release. Fails with `NotFound` if it is not stored.
+    async fn delete_release(&self, id: &Uuid) -> Result<(), ReleaseRepositoryError>;
+    async fn list_releases(&self) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError>;
+    async fn list_releases_for_component(&self, component_id: &Uuid) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError>;
+    async fn list_releases_by_tag(&self, tag: &str) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError>;
+    /// The releases matching every criterion of `filter`, oldest first.
+    async fn list_releases_filtered(&self, filter: ReleaseFilter) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError>;
+
+    /// At most `max_results` releases matching `query`, best match first. Releases do not know
+    /// their component's name, so the caller passes the IDs of the components whose name matched
+    /// as `component_ids`. See `search_score` for how matches are ranked.
+    async fn search_releases(&self, query: &str, component_ids: &HashSet<Uuid>, max_results: usize) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let releases = self.list_releases().await?;
+        Ok(best_matches(releases.iter(), query, component_ids, max_results))
+    }
+
+    /// Every change made to the release, oldest first. `None` for repositories that only keep
+    /// the latest state, which is all but `EventSourcingReleaseRepository`.
+    async fn event_log(&self, _id: &Uuid) -> Result<Option<Vec<ReleaseEvent>>, ReleaseRepositoryError> {
+        Ok(None)
+    }
+
+    /// Every release the release depends on, directly or transitively, nearest first and
+    /// without duplicates. Dependencies that are not stored are skipped.
+    async fn dependency_graph(&self, id: &Uuid) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let release = self.get_release(id).await?.ok_or(ReleaseRepositoryError::NotFound)?;
+        let mut seen: HashSet<Uuid> = HashSet::from([release.id]);
+        let mut pending: VecDeque<Uuid> = release.dependencies.into_iter().collect();
+        let mut dependencies = Vec::new();
+        while let Some(dependency_id) = pending.pop_front() {
+            if !seen.insert(dependency_id) {
+                continue;
+            }
+            match self.get_release(&dependency_id).await? {
+                Some(dependency) => {
+                    pending.extend(dependency.dependencies.iter().copied());
+                    dependencies.push(dependency);
+                }
+                None => tracing::warn!(%dependency_id, "Dependency release is missing, skipping it"),
+            }
+        }
+        Ok(dependencies)
+    }
+}
+
+#[derive(Debug, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum ReleaseRepositoryError {
+    #[error("Failed to store release: {0}")]
+    StorageError(String),
+    #[error("Failed to retrieve release: {0}")]
+    RetrievalError(String),
+    #[error("Release not found")]
+    NotFound,
+    #[error("Release is at revision {actual}, not {expected}")]
+    RevisionMismatch { expected: u64, actual: u64 },
+}
+
+/// Criteria for listing releases. All set fields must match.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReleaseFilter {
+    /// Only return releases in the phase of this name, e.g. `Build`.
+    #[serde(default, deserialize_with = "phase_by_name")]
+    #[param(value_type = Option<String>)]
+    pub phase: Option<SDLCPhase>,
+    /// Only return releases in the state of this name, e.g. `Deployed`.
+    pub state: Option<String>,
+    /// Only return releases of this component.
+    pub component_id: Option<Uuid>,
+    /// Only return releases created at or after this time.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only return releases created before this time.
+    pub created_before: Option<DateTime<Utc>>,
+    /// Only return releases carrying this tag.
+    pub tag: Option<String>,
+}
+
+impl ReleaseFilter {
+    pub fn matches(&self, release: &SDLCRelease) -> bool {
+        self.phase.as_ref().is_none_or(|phase| &release.phase == phase)
+            && self.state.as_deref().is_none_or(|state| release.state_name() == state)
+            && self.component_id.is_none_or(|component_id| release.component_id == component_id)
+            && self.created_after.is_none_or(|created_after| release.created_at >= created_after)
+            && self.created_before.is_none_or(|created_before| release.created_at < created_before)
+            && self.tag.as_ref().is_none_or(|tag| release.tags.contains(tag))
+    }
+}
+
+/// How many of the searched fields of `release` match `query`: its version, its component (one
+/// of `component_ids`), its commit hash (by prefix) and any of its tags. Zero if none does.
+pub fn search_score(release: &SDLCRelease, query: &str, component_ids: &HashSet<Uuid>) -> usize {
+    [
+        release.version.contains(query),
+        component_ids.contains(&release.component_id),
+        release.commit_hash.as_deref().is_some_and(|hash| hash.starts_with(query)),
+        release.tags.iter().any(|tag| tag.contains(query)),
+    ]
+    .into_iter()
+    .filter(|matched| *matched)
+    .count()
+}
+
+/// The `max_results` releases with the highest `search_score`, newest first among equals.
+fn best_matches<'a>(releases: impl Iterator<Item = &'a SDLCRelease>, query: &str, component_ids: &HashSet<Uuid>, max_results: usize) -> Vec<SDLCRelease> {
+    let mut scored: Vec<(usize, &SDLCRelease)> = releases
+        .map(|release| (search_score(release, query, component_ids), release))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    scored.sort_by(|(score, release), (other_score, other)| {
+        other_score.cmp(score).then_with(|| (other.created_at, other.id).cmp(&(release.created_at, release.id)))
+    });
+    scored.into_iter().take(max_results).map(|(_, release)| release.clone()).collect()
+}
+
+/// Reads a phase from its name, so `?phase=Build` works alongside custom phases.
+fn phase_by_name<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<SDLCPhase>, D::Error> {
+    Ok(Option::<String>::deserialize(deserializer)?.map(|name| SDLCPhase::from_name(&name)))
+}
+
+// Example in-memory implementation for testing
+#[derive(Clone)]
+pub struct InMemoryReleaseRepository {
+    releases: Arc<RwLock<HashMap<Uuid, SDLCRelease>>>,
+    /// Tag to the IDs of the releases carrying it. Always locked after `releases`.
+    tag_index: Arc<RwLock<HashMap<String, HashSet<Uuid>>>>,
+}
+
+impl Default for InMemoryReleaseRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryReleaseRepository {
+    pub fn new() -> Self {
+        Self {
+            releases: Arc::new(RwLock::new(HashMap::new())),
+            tag_index: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+#[async_trait]
+impl ReleaseRepository for InMemoryReleaseRepository {
+    #[tracing::instrument(skip_all, fields(release_id = %release.id))]
+    async fn store_release(&self, release: SDLCRelease) -> Result<(), ReleaseRepositoryError> {
+        let mut releases = self.releases.write().await;
+        let mut tag_index = self.tag_index.write().await;
+        let stored = replace_release(&mut releases, &mut tag_index, release);
+        tracing::debug!(revision = stored.revision, "Stored release");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(count = batch.len()))]
+    async fn store_releases(&self, batch: Vec<SDLCRelease>) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        // Held for the whole batch, so nobody sees part of it.
+        let mut releases = self.releases.write().await;
+        let mut tag_index = self.tag_index.write().await;
+        let stored: Vec<SDLCRelease> = batch.into_iter().map(|release| replace_release(&mut releases, &mut tag_index, release)).collect();
+        tracing::debug!("Stored releases");
+        Ok(stored)
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %release.id, expected_revision))]
+    async fn update_release(&self, release: SDLCRelease, expected_revision: u64) -> Result<SDLCRelease, ReleaseRepositoryError> {
+        let mut releases = self.releases.write().await;
+        let mut tag_index = self.tag_index.write().await;
+        let actual = releases.get(&release.id).ok_or(ReleaseRepositoryError::NotFound)?.revision;
+        if actual != expected_revision {
+            return Err(ReleaseRepositoryError::RevisionMismatch { expected: expected_revision, actual });
+        }
+        let stored = replace_release(&mut releases, &mut tag_index, release);
+        tracing::debug!(revision = stored.revision, "Updated release");
+        Ok(stored)
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %id))]
+    async fn get_release(&self, id: &Uuid) -> Result<Option<SDLCRelease>, ReleaseRepositoryError> {
+        let releases = self.releases.read().await;
+        Ok(releases.get(id).cloned())
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %id))]
+    async fn delete_release(&self, id: &Uuid) -> Result<(), ReleaseRepositoryError> {
+        let mut releases = self.releases.write().await;
+        let mut tag_index = self.tag_index.write().await;
+        let release = releases.remove(id).ok_or(ReleaseRepositoryError::NotFound)?;
+        for tag in &release.tags {
+            if let Some(ids) = tag_index.get_mut(tag) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    tag_index.remove(tag);
+                }
+            }
+        }
+        tracing::debug!("Deleted release");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases(&self) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let releases = self.releases.read().await;
+        Ok(releases.values().cloned().collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases_for_component(&self, component_id: &Uuid) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let releases = self.releases.read().await;
+        Ok(releases.values()
+            .filter(|release| &release.component_id == component_id)
+            .cloned()
+            .collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases_by_tag(&self, tag: &str) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let releases = self.releases.read().await;
+        let tag_index = self.tag_index.read().await;
+        Ok(tag_index.get(tag)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| releases.get(id).cloned())
+            .collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases_filtered(&self, filter: ReleaseFilter) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let releases = self.releases.read().await;
+        let mut matching: Vec<SDLCRelease> = releases.values()
+            .filter(|release| filter.matches(release))
+            .cloned()
+            .collect();
+        matching.sort_by_key(|release| (release.created_at, release.id));
+        Ok(matching)
+    }
+
+    #[tracing::instrument(skip(self, component_ids))]
+    async fn search_releases(&self, query: &str, component_ids: &HashSet<Uuid>, max_results: usize) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let releases = self.releases.read().await;
+        Ok(best_matches(releases.values(), query, component_ids, max_results))
+    }
+}
+
+/// Stores `release` with the revision after the one it replaces, keeping the tag index in step.
+fn replace_release(releases: &mut HashMap<Uuid, SDLCRelease>, tag_index: &mut HashMap<String, HashSet<Uuid>>, mut release: SDLCRelease) -> SDLCRelease {
+    if let Some(previous) = releases.get(&release.id) {
+        release.revision = previous.revision + 1;
+        for tag in previous.tags.difference(&release.tags) {
+            if let Some(ids) = tag_index.get_mut(tag) {
+                ids.remove(&release.id);
+                if ids.is_empty() {
+                    tag_index.remove(tag);
+                }
+            }
+        }
+    }
+    for tag in &release.tags {
+        tag_index.entry(tag.clone()).or_default().insert(release.id);
+    }
+    releases.insert(release.id, release.clone());
+    release
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::contract_tests::define_service_contract_tests;
+
+    #[tokio::test]
+    async fn test_store_replaces_existing_release() {
+        let repository = InMemoryReleaseRepository::new();
+        let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        repository.store_release(release.clone()).await.unwrap();
+
+        release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+        repository.store_release(release.clone()).await.unwrap();
+
+        let stored = repository.get_release(release.id()).await.unwrap().unwrap();
+        assert_eq!(stored.state_name(), "InProgress");
+        assert_eq!(repository.list_releases().await.unwrap().len(), 1);
+        assert!(repository.get_release(&Uuid::new_v4()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_releases_for_component() {
+        let repository = InMemoryReleaseRepository::new();
+        let component_id = Uuid::new_v4();
+        repository.store_release(SDLCRelease::new(component_id, "1.0.0".to_string(), "developer1".to_string()).unwrap()).await.unwrap();
+        repository.store_release(SDLCRelease::new(component_id, "1.1.0".to_string(), "developer1".to_string()).unwrap()).await.unwrap();
+        repository.store_release(SDLCRelease::new(Uuid::new_v4(), "0.1.0".to_string(), "developer2".to_string()).unwrap()).await.unwrap();
+
+        assert_eq!(repository.list_releases_for_component(&component_id).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_dependency_graph_is_transitive_and_deduplicated() {
+        let repository = InMemoryReleaseRepository::new();
+        let new_release = || SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        let (mut app, mut library, mut runtime, shared) = (new_release(), new_release(), new_release(), new_release());
+        // app -> library -> shared, app -> runtime -> shared, and a cycle back to app.
+        library.add_dependency(shared.id);
+        runtime.add_dependency(shared.id);
+        runtime.add_dependency(app.id);
+        app.add_dependency(library.id);
+        app.add_dependency(runtime.id);
+        app.add_dependency(Uuid::new_v4());
+        for release in [&app, &library, &runtime, &shared] {
+            repository.store_release(release.clone()).await.unwrap();
+        }
+
+        let ids: Vec<Uuid> = repository.dependency_graph(&app.id).await.unwrap().into_iter().map(|release| release.id).collect();
+        assert_eq!(ids, vec![library.id, runtime.id, shared.id]);
+        assert!(repository.dependency_graph(&shared.id).await.unwrap().is_empty());
+        assert!(matches!(repository.dependency_graph(&Uuid::new_v4()).await, Err(ReleaseRepositoryError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_list_releases_by_tag_follows_tag_changes() {
+        let repository = InMemoryReleaseRepository::new();
+        let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        release.add_tag("lts").unwrap();
+        release.add_tag("hotfix").unwrap();
+        repository.store_release(release.clone()).await.unwrap();
+        repository.store_release(SDLCRelease::new(Uuid::new_v4(), "2.0.0".to_string(), "developer1".to_string()).unwrap()).await.unwrap();
+
+        assert_eq!(repository.list_releases_by_tag("lts").await.unwrap().len(), 1);
+        assert_eq!(repository.list_releases_by_tag("hotfix").await.unwrap()[0].id, release.id);
+
+        release.remove_tag("hotfix");
+        repository.store_release(release.clone()).await.unwrap();
+        assert!(repository.list_releases_by_tag("hotfix").await.unwrap().is_empty());
+        assert_eq!(repository.list_releases_by_tag("lts").await.unwrap().len(), 1);
+        assert!(repository.list_releases_by_tag("unknown").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_release_checks_revision() {
+        let repository = InMemoryReleaseRepository::new();
+        let release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        repository.store_release(release.clone()).await.unwrap();
+
+        let mut first = release.clone();
+        first.start_development("ci".to_string(), vec!["feature x".to_string()]).unwrap();
+        let stored = repository.update_release(first, 0).await.unwrap();
+        assert_eq!(stored.revision, 1);
+
+        let mut second = release.clone();
+        second.add_tag("reviewed").unwrap();
+        assert!(matches!(
+            repository.update_release(second, 0).await,
+            Err(ReleaseRepositoryError::RevisionMismatch { expected: 0, actual: 1 })
+        ));
+        assert_eq!(repository.get_release(release.id()).await.unwrap().unwrap().state_name(), "InProgress");
+
+        let missing = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        assert!(matches!(repository.update_release(missing, 0).await, Err(ReleaseRepositoryError::NotFound)));
+    }
+
+    define_service_contract_tests!(InMemoryReleaseRepository::new);
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::{
+    body::Body,
+    http::{header, HeaderName, HeaderValue, Request},
+    response::Response,
+};
+use tower::{Layer, Service};
+use uuid::Uuid;
+
+use super::api_error::ApiError;
+
+/// Response header carrying the ID of the request.
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Identifies one request, added to request extensions by `RequestIdLayer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestId(pub Uuid);
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Gives every request a fresh `RequestId`, returns it in the `X-Request-ID` header and writes it
+/// into the `request_id` field of `ApiError` bodies.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestIdLayer;
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestIdService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for RequestIdService<S>
+where
+    S: Service<Request<Body>, Response = Response>,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<Body>) -> Self::Future {
+        let request_id = RequestId(Uuid::new_v4());
+        request.extensions_mut().insert(request_id);
+        // Only spans that declare a `request_id` field, like the server's request span, keep it.
+        tracing::Span::current().record("request_id", tracing::field::display(request_id));
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let mut response = response.await?;
+            if let Some(error) = response.extensions_mut().remove::<ApiError>() {
+                let error = error.with_request_id(&request_id);
+                // The body was rendered without the ID, so its length no longer applies.
+                response.headers_mut().remove(header::CONTENT_LENGTH);
+                *response.body_mut() = Body::from(serde_json::to_vec(&error).unwrap_or_default());
+            }
+            if let Ok(value) = HeaderValue::from_str(&request_id.to_string()) {
+                response.headers_mut().insert(X_REQUEST_ID, value);
+            }
+            Ok(response)
+        })
+    }
+}
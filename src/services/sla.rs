@@ -0,0 +1,90 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::model::{SDLCRelease, SLAStatus};
+
+use super::release_repository::{ReleaseRepository, ReleaseRepositoryError};
+
+/// A release that was not deployed within its `DeploymentSLA`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct SLABreach {
+    pub release_id: Uuid,
+    pub component_id: Uuid,
+    pub version: String,
+    /// When the release was due to be deployed, if it was released with its SLA already set.
+    pub estimated_deploy_time: Option<DateTime<Utc>>,
+    #[schema(value_type = DurationSchema)]
+    pub overdue_by: Duration,
+}
+
+/// Every release with an SLA that is at risk or breached at `now`, with its status.
+pub async fn releases_off_track_at(releases: &dyn ReleaseRepository, now: DateTime<Utc>) -> Result<Vec<(SDLCRelease, SLAStatus)>, ReleaseRepositoryError> {
+    Ok(releases.list_releases().await?
+        .into_iter()
+        .filter(|release| release.sla.is_some())
+        .map(|release| {
+            let status = release.sla_status_at(now);
+            (release, status)
+        })
+        .filter(|(_, status)| *status != SLAStatus::OnTrack)
+        .collect())
+}
+
+/// Every release past its deployment SLA at `now`, longest overdue first.
+pub async fn sla_breaches_at(releases: &dyn ReleaseRepository, now: DateTime<Utc>) -> Result<Vec<SLABreach>, ReleaseRepositoryError> {
+    let mut breaches: Vec<SLABreach> = releases_off_track_at(releases, now).await?
+        .into_iter()
+        .filter_map(|(release, status)| match status {
+            SLAStatus::Breached { overdue_by } => Some(SLABreach {
+                release_id: release.id,
+                component_id: release.component_id,
+                version: release.version,
+                estimated_deploy_time: release.estimated_deploy_time,
+                overdue_by,
+            }),
+            _ => None,
+        })
+        .collect();
+    breaches.sort_by_key(|breach| std::cmp::Reverse(breach.overdue_by));
+    Ok(breaches)
+}
+
+/// Logs a warning for every release at risk of missing its deployment SLA and an error for
+/// every release that missed it.
+pub async fn report_sla_status(releases: &dyn ReleaseRepository, now: DateTime<Utc>) {
+    let off_track = match releases_off_track_at(releases, now).await {
+        Ok(off_track) => off_track,
+        Err(error) => {
+            tracing::warn!(%error, "Failed to list releases for the SLA check");
+            return;
+        },
+    };
+    for (release, status) in off_track {
+        match status {
+            SLAStatus::AtRisk { percentage_elapsed } => {
+                tracing::warn!(release_id = %release.id, version = %release.version, percentage_elapsed, "Release at risk of missing its deployment SLA");
+            },
+            SLAStatus::Breached { overdue_by } => {
+                tracing::error!(release_id = %release.id, version = %release.version, overdue_secs = overdue_by.as_secs(), "Release missed its deployment SLA");
+            },
+            SLAStatus::OnTrack => {},
+        }
+    }
+}
+
+/// Spawns a task that reports the SLA status of `releases` every `interval`, starting right
+/// away. Abort the returned handle to stop it.
+pub fn start_sla_monitor(releases: Arc<dyn ReleaseRepository>, interval: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(interval);
+        loop {
+            ticks.tick().await;
+            report_sla_status(releases.as_ref(), Utc::now()).await;
+        }
+    })
+}
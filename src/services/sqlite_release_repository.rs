@@ -0,0 +1,201 @@
+use async_trait::async_trait;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
+use sqlx::{Sqlite, Transaction};
+use sqlx::types::Json;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::model::SDLCRelease;
+
+use super::release_repository::{ReleaseFilter, ReleaseRepository, ReleaseRepositoryError};
+
+const SCHEMA: [&str; 2] = [
+    "CREATE TABLE IF NOT EXISTS releases (
+        id BLOB PRIMARY KEY NOT NULL,
+        component_id BLOB NOT NULL,
+        revision INTEGER NOT NULL,
+        release TEXT NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS releases_component_id ON releases (component_id)",
+];
+
+/// Keeps every release as a JSON document in the `releases` table, next to the columns it is
+/// looked up by. Filters the table cannot answer are applied after loading.
+#[derive(Clone)]
+pub struct SqliteReleaseRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteReleaseRepository {
+    /// Opens the database at `url`, e.g. `sqlite://releases.db`, creating the file if it is missing.
+    pub async fn connect(url: &str) -> Result<Self, ReleaseRepositoryError> {
+        let options = SqliteConnectOptions::from_str(url).map_err(storage_error)?.create_if_missing(true);
+        let pool = SqlitePool::connect_with(options).await.map_err(storage_error)?;
+        Self::from_pool(pool).await
+    }
+
+    /// Uses `pool`, creating the `releases` table first if it does not exist.
+    pub async fn from_pool(pool: SqlitePool) -> Result<Self, ReleaseRepositoryError> {
+        for statement in SCHEMA {
+            sqlx::query(statement).execute(&pool).await.map_err(storage_error)?;
+        }
+        Ok(Self { pool })
+    }
+
+    /// Writes `release` with the revision after the one it replaces. With `expected_revision`,
+    /// only replaces a release that is still at that revision.
+    async fn write_release(&self, release: SDLCRelease, expected_revision: Option<u64>) -> Result<SDLCRelease, ReleaseRepositoryError> {
+        let mut transaction = self.pool.begin().await.map_err(storage_error)?;
+        let release = write_in(&mut transaction, release, expected_revision).await?;
+        transaction.commit().await.map_err(storage_error)?;
+        Ok(release)
+    }
+}
+
+#[async_trait]
+impl ReleaseRepository for SqliteReleaseRepository {
+    #[tracing::instrument(skip_all, fields(release_id = %release.id))]
+    async fn store_release(&self, release: SDLCRelease) -> Result<(), ReleaseRepositoryError> {
+        let stored = self.write_release(release, None).await?;
+        tracing::debug!(revision = stored.revision, "Stored release");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(count = releases.len()))]
+    async fn store_releases(&self, releases: Vec<SDLCRelease>) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let mut transaction = self.pool.begin().await.map_err(storage_error)?;
+        let mut stored = Vec::with_capacity(releases.len());
+        for release in releases {
+            stored.push(write_in(&mut transaction, release, None).await?);
+        }
+        transaction.commit().await.map_err(storage_error)?;
+        tracing::debug!("Stored releases");
+        Ok(stored)
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %release.id, expected_revision))]
+    async fn update_release(&self, release: SDLCRelease, expected_revision: u64) -> Result<SDLCRelease, ReleaseRepositoryError> {
+        let stored = self.write_release(release, Some(expected_revision)).await?;
+        tracing::debug!(revision = stored.revision, "Updated release");
+        Ok(stored)
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %id))]
+    async fn get_release(&self, id: &Uuid) -> Result<Option<SDLCRelease>, ReleaseRepositoryError> {
+        let release: Option<Json<SDLCRelease>> = sqlx::query_scalar("SELECT release FROM releases WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(retrieval_error)?;
+        Ok(release.map(|Json(release)| release))
+    }
+
+    #[tracing::instrument(skip_all, fields(release_id = %id))]
+    async fn delete_release(&self, id: &Uuid) -> Result<(), ReleaseRepositoryError> {
+        let deleted = sqlx::query("DELETE FROM releases WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(storage_error)?;
+        if deleted.rows_affected() == 0 {
+            return Err(ReleaseRepositoryError::NotFound);
+        }
+        tracing::debug!("Deleted release");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases(&self) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let releases: Vec<Json<SDLCRelease>> = sqlx::query_scalar("SELECT release FROM releases")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(retrieval_error)?;
+        Ok(releases.into_iter().map(|Json(release)| release).collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases_for_component(&self, component_id: &Uuid) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let releases: Vec<Json<SDLCRelease>> = sqlx::query_scalar("SELECT release FROM releases WHERE component_id = ?")
+            .bind(component_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(retrieval_error)?;
+        Ok(releases.into_iter().map(|Json(release)| release).collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases_by_tag(&self, tag: &str) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let releases: Vec<Json<SDLCRelease>> = sqlx::query_scalar(
+            "SELECT release FROM releases WHERE EXISTS (SELECT 1 FROM json_each(release, '$.tags') WHERE value = ?)",
+        )
+        .bind(tag)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(retrieval_error)?;
+        Ok(releases.into_iter().map(|Json(release)| release).collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_releases_filtered(&self, filter: ReleaseFilter) -> Result<Vec<SDLCRelease>, ReleaseRepositoryError> {
+        let releases = match &filter.component_id {
+            Some(component_id) => self.list_releases_for_component(component_id).await?,
+            None => self.list_releases().await?,
+        };
+        let mut matching: Vec<SDLCRelease> = releases.into_iter().filter(|release| filter.matches(release)).collect();
+        matching.sort_by_key(|release| (release.created_at, release.id));
+        Ok(matching)
+    }
+}
+
+/// `write_release` within `transaction`.
+async fn write_in(transaction: &mut Transaction<'_, Sqlite>, mut release: SDLCRelease, expected_revision: Option<u64>) -> Result<SDLCRelease, ReleaseRepositoryError> {
+    let actual: Option<i64> = sqlx::query_scalar("SELECT revision FROM releases WHERE id = ?")
+        .bind(release.id)
+        .fetch_optional(&mut **transaction)
+        .await
+        .map_err(storage_error)?;
+    match (actual.map(|revision| revision as u64), expected_revision) {
+        (None, Some(_)) => return Err(ReleaseRepositoryError::NotFound),
+        (Some(actual), Some(expected)) if actual != expected => {
+            return Err(ReleaseRepositoryError::RevisionMismatch { expected, actual });
+        }
+        (Some(actual), _) => release.revision = actual + 1,
+        (None, None) => {}
+    }
+    sqlx::query(
+        "INSERT INTO releases (id, component_id, revision, release) VALUES (?, ?, ?, ?)
+         ON CONFLICT (id) DO UPDATE SET component_id = excluded.component_id, revision = excluded.revision, release = excluded.release",
+    )
+    .bind(release.id)
+    .bind(release.component_id)
+    .bind(release.revision as i64)
+    .bind(Json(&release))
+    .execute(&mut **transaction)
+    .await
+    .map_err(storage_error)?;
+    Ok(release)
+}
+
+fn storage_error(err: impl ToString) -> ReleaseRepositoryError {
+    ReleaseRepositoryError::StorageError(err.to_string())
+}
+
+fn retrieval_error(err: impl ToString) -> ReleaseRepositoryError {
+    ReleaseRepositoryError::RetrievalError(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::contract_tests::define_service_contract_tests;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    /// A fresh in-memory database. Every connection to `sqlite::memory:` opens its own database,
+    /// so the pool keeps just one.
+    async fn repository() -> SqliteReleaseRepository {
+        let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        SqliteReleaseRepository::from_pool(pool).await.unwrap()
+    }
+
+    define_service_contract_tests!(async repository);
+}
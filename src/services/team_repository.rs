@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::model::sdlc_component::TeamReference;
+
+#[async_trait]
+pub trait TeamRepository: Send + Sync {
+    async fn store_team(&self, team: TeamReference) -> Result<(), TeamRepositoryError>;
+    async fn get_team(&self, id: &Uuid) -> Result<Option<TeamReference>, TeamRepositoryError>;
+    async fn list_teams(&self) -> Result<Vec<TeamReference>, TeamRepositoryError>;
+    async fn list_teams_for_component(&self, component_id: &Uuid) -> Result<Vec<TeamReference>, TeamRepositoryError>;
+    async fn delete_team(&self, id: &Uuid) -> Result<(), TeamRepositoryError>;
+}
+
+#[derive(Debug, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum TeamRepositoryError {
+    #[error("Failed to store team: {0}")]
+    StorageError(String),
+    #[error("Failed to retrieve team: {0}")]
+    RetrievalError(String),
+    #[error("Team not found")]
+    NotFound,
+}
+
+// Example in-memory implementation for testing. Teams are linked to components with
+// `assign_team`, like policies are with `InMemoryPolicyRepository::assign_policy`.
+#[derive(Clone)]
+pub struct InMemoryTeamRepository {
+    teams: Arc<RwLock<HashMap<Uuid, TeamReference>>>,
+    assignments: Arc<RwLock<HashMap<Uuid, Vec<Uuid>>>>,
+}
+
+impl Default for InMemoryTeamRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryTeamRepository {
+    pub fn new() -> Self {
+        Self {
+            teams: Arc::new(RwLock::new(HashMap::new())),
+            assignments: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub async fn assign_team(&self, component_id: Uuid, team_id: Uuid) {
+        let mut assignments = self.assignments.write().await;
+        let teams = assignments.entry(component_id).or_default();
+        if !teams.contains(&team_id) {
+            teams.push(team_id);
+        }
+    }
+}
+
+#[async_trait]
+impl TeamRepository for InMemoryTeamRepository {
+    async fn store_team(&self, team: TeamReference) -> Result<(), TeamRepositoryError> {
+        let mut teams = self.teams.write().await;
+        teams.insert(team.id, team);
+        Ok(())
+    }
+
+    async fn get_team(&self, id: &Uuid) -> Result<Option<TeamReference>, TeamRepositoryError> {
+        let teams = self.teams.read().await;
+        Ok(teams.get(id).cloned())
+    }
+
+    async fn list_teams(&self) -> Result<Vec<TeamReference>, TeamRepositoryError> {
+        let teams = self.teams.read().await;
+        Ok(teams.values().cloned().collect())
+    }
+
+    async fn list_teams_for_component(&self, component_id: &Uuid) -> Result<Vec<TeamReference>, TeamRepositoryError> {
+        let teams = self.teams.read().await;
+        let assignments = self.assignments.read().await;
+        Ok(assignments.get(component_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|team_id| teams.get(team_id).cloned())
+            .collect())
+    }
+
+    async fn delete_team(&self, id: &Uuid) -> Result<(), TeamRepositoryError> {
+        let mut teams = self.teams.write().await;
+        match teams.remove(id) {
+            Some(_) => Ok(()),
+            None => Err(TeamRepositoryError::NotFound),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::contract_tests::define_service_contract_tests;
+
+    fn team(name: &str) -> TeamReference {
+        TeamReference {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            contact_email: format!("{}@example.com", name),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_teams_for_component() {
+        let repository = InMemoryTeamRepository::new();
+        let payments = team("payments");
+        let platform = team("platform");
+        repository.store_team(payments.clone()).await.unwrap();
+        repository.store_team(platform.clone()).await.unwrap();
+        let component_id = Uuid::new_v4();
+        repository.assign_team(component_id, payments.id).await;
+        repository.assign_team(component_id, payments.id).await;
+
+        assert_eq!(repository.list_teams().await.unwrap().len(), 2);
+        assert_eq!(repository.list_teams_for_component(&component_id).await.unwrap(), vec![payments.clone()]);
+        assert!(repository.list_teams_for_component(&Uuid::new_v4()).await.unwrap().is_empty());
+
+        repository.delete_team(&payments.id).await.unwrap();
+        assert!(repository.list_teams_for_component(&component_id).await.unwrap().is_empty());
+        assert!(matches!(repository.delete_team(&payments.id).await, Err(TeamRepositoryError::NotFound)));
+    }
+
+    define_service_contract_tests!(InMemoryTeamRepository::new);
+}
@@ -0,0 +1,271 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::model::{phase::{FeatureListError, FeatureListLimits, RolloutStrategy, SBOMReference, SourceReviewStatus}, SDLCRelease};
+
+/// Emitted whenever a release moves from one state to another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct TransitionEvent {
+    pub release_id: Uuid,
+    pub from_state: String,
+    pub to_state: String,
+    pub timestamp: DateTime<Utc>,
+    /// The transition that was applied, when the event comes from a `TransitionRequest`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<TransitionAction>,
+}
+
+impl TransitionEvent {
+    /// Builds the event for `release`, which has just left `from_state`.
+    pub fn new(release: &SDLCRelease, from_state: &str) -> Self {
+        Self {
+            release_id: release.id,
+            from_state: from_state.to_string(),
+            to_state: release.state_name().to_string(),
+            timestamp: Utc::now(),
+            action: None,
+        }
+    }
+}
+
+/// Fans transition events out to every watcher, whichever API they are watching through.
+#[derive(Clone)]
+pub struct TransitionBroadcaster(Arc<broadcast::Sender<TransitionEvent>>);
+
+impl TransitionBroadcaster {
+    /// `capacity` is how many events a slow watcher may fall behind before it misses some.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self(Arc::new(sender))
+    }
+
+    /// Publishes `event`. Having nobody watching is not an error.
+    pub fn publish(&self, event: TransitionEvent) {
+        let _ = self.0.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<TransitionEvent> {
+        self.0.subscribe()
+    }
+}
+
+/// A transition to apply to a release, named after the `SDLCRelease` method that performs it.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TransitionRequest {
+    StartDevelopment {
+        started_by: String,
+        #[serde(default)]
+        feature_list: Vec<String>,
+    },
+    CompleteDevelopment,
+    StartSourceReview { started_by: String },
+    CompleteSourceReview {
+        commit_hash: String,
+        #[serde(default)]
+        branch: String,
+        #[serde(default)]
+        pr_url: Option<String>,
+        review_status: SourceReviewStatus,
+    },
+    StartBuild { started_by: String },
+    CompleteBuild {
+        build_id: String,
+        #[serde(default)]
+        reproducible: bool,
+        #[serde(default)]
+        build_tool: Option<String>,
+    },
+    StartPackaging { started_by: String },
+    CompletePackaging {
+        artifact_hash: String,
+        artifact_url: String,
+        #[serde(default)]
+        sbom: Option<SBOMReference>,
+    },
+    RequestApproval { requester: String, required_approvers: Vec<String> },
+    RecordApproval {
+        approver: String,
+        #[serde(default)]
+        comment: Option<String>,
+    },
+    Release { release_notes: String },
+    StartDeployment {
+        environment: String,
+        #[serde(default)]
+        rollout_strategy: RolloutStrategy,
+    },
+    UpdateCanaryPercentage { percentage: u8 },
+    CompleteDeployment,
+    Revoke { reason: String },
+    Fail { reason: String },
+    Retry,
+    EnterCustomState { name: String },
+    ExitCustomState { reason: String },
+}
+
+/// The kind of a `TransitionRequest`, without its arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionAction {
+    StartDevelopment,
+    CompleteDevelopment,
+    StartSourceReview,
+    CompleteSourceReview,
+    StartBuild,
+    CompleteBuild,
+    StartPackaging,
+    CompletePackaging,
+    RequestApproval,
+    RecordApproval,
+    Release,
+    StartDeployment,
+    UpdateCanaryPercentage,
+    CompleteDeployment,
+    Revoke,
+    Fail,
+    Retry,
+    EnterCustomState,
+    ExitCustomState,
+    /// Restoring a `ReleaseSnapshot`. Never requested through a `TransitionRequest`.
+    Rollback,
+}
+
+impl TransitionRequest {
+    pub fn action(&self) -> TransitionAction {
+        match self {
+            TransitionRequest::StartDevelopment { .. } => TransitionAction::StartDevelopment,
+            TransitionRequest::CompleteDevelopment => TransitionAction::CompleteDevelopment,
+            TransitionRequest::StartSourceReview { .. } => TransitionAction::StartSourceReview,
+            TransitionRequest::CompleteSourceReview { .. } => TransitionAction::CompleteSourceReview,
+            TransitionRequest::StartBuild { .. } => TransitionAction::StartBuild,
+            TransitionRequest::CompleteBuild { .. } => TransitionAction::CompleteBuild,
+            TransitionRequest::StartPackaging { .. } => TransitionAction::StartPackaging,
+            TransitionRequest::CompletePackaging { .. } => TransitionAction::CompletePackaging,
+            TransitionRequest::RequestApproval { .. } => TransitionAction::RequestApproval,
+            TransitionRequest::RecordApproval { .. } => TransitionAction::RecordApproval,
+            TransitionRequest::Release { .. } => TransitionAction::Release,
+            TransitionRequest::StartDeployment { .. } => TransitionAction::StartDeployment,
+            TransitionRequest::UpdateCanaryPercentage { .. } => TransitionAction::UpdateCanaryPercentage,
+            TransitionRequest::CompleteDeployment => TransitionAction::CompleteDeployment,
+            TransitionRequest::Revoke { .. } => TransitionAction::Revoke,
+            TransitionRequest::Fail { .. } => TransitionAction::Fail,
+            TransitionRequest::Retry => TransitionAction::Retry,
+            TransitionRequest::EnterCustomState { .. } => TransitionAction::EnterCustomState,
+            TransitionRequest::ExitCustomState { .. } => TransitionAction::ExitCustomState,
+        }
+    }
+
+    /// Applies the transition to `release` and returns the event describing it.
+    pub fn apply(self, release: &mut SDLCRelease) -> Result<TransitionEvent, TransitionError> {
+        self.apply_with_limits(release, FeatureListLimits::default())
+    }
+
+    /// Like [`apply`](Self::apply), but checks a new feature list against `limits`.
+    pub fn apply_with_limits(self, release: &mut SDLCRelease, limits: FeatureListLimits) -> Result<TransitionEvent, TransitionError> {
+        let action = self.action();
+        self.perform(release, limits).map(|event| TransitionEvent { action: Some(action), ..event })
+    }
+
+    fn perform(self, release: &mut SDLCRelease, limits: FeatureListLimits) -> Result<TransitionEvent, TransitionError> {
+        let from_state = release.state_name().to_string();
+        // The release methods would reject most transitions from a custom state anyway, but not
+        // with a message that tells the caller how to get the release going again.
+        if let crate::model::ReleaseState::Custom(name) = &release.state {
+            if !matches!(self, TransitionRequest::EnterCustomState { .. } | TransitionRequest::ExitCustomState { .. }) {
+                return Err(TransitionError::InvalidTransition(format!("The release is in the custom state {name}; exit it first.")));
+            }
+        }
+        match self {
+            TransitionRequest::StartDevelopment { started_by, feature_list } => {
+                return release.start_development_with_limits(started_by, feature_list, limits).map(|()| TransitionEvent::new(release, &from_state));
+            },
+            TransitionRequest::CompleteDevelopment => release.complete_development(),
+            TransitionRequest::StartSourceReview { started_by } => release.start_source_review(started_by),
+            TransitionRequest::CompleteSourceReview { commit_hash, branch, pr_url, review_status } => {
+                release.complete_source_review(commit_hash, branch, pr_url, review_status)
+            },
+            TransitionRequest::StartBuild { started_by } => release.start_build(started_by),
+            TransitionRequest::CompleteBuild { build_id, reproducible, build_tool } => {
+                return release.complete_build(build_id, reproducible, build_tool).map(|()| TransitionEvent::new(release, &from_state));
+            },
+            TransitionRequest::StartPackaging { started_by } => release.start_packaging(started_by),
+            TransitionRequest::CompletePackaging { artifact_hash, artifact_url, sbom } => release.complete_packaging(artifact_hash, artifact_url, sbom),
+            TransitionRequest::RequestApproval { requester, required_approvers } => {
+                return release.request_approval(requester, required_approvers).map(|()| TransitionEvent::new(release, &from_state));
+            },
+            TransitionRequest::RecordApproval { approver, comment } => {
+                return release.record_approval(approver, comment).map(|()| TransitionEvent::new(release, &from_state));
+            },
+            TransitionRequest::Release { release_notes } => release.release(release_notes),
+            TransitionRequest::StartDeployment { environment, rollout_strategy } => release.start_deployment(environment, rollout_strategy),
+            TransitionRequest::UpdateCanaryPercentage { percentage } => {
+                return release.update_canary_percentage(percentage).map(|()| TransitionEvent::new(release, &from_state));
+            },
+            TransitionRequest::CompleteDeployment => release.complete_deployment(),
+            TransitionRequest::Revoke { reason } => release.revoke(reason),
+            TransitionRequest::Fail { reason } => {
+                return release.fail(reason).map(|()| TransitionEvent::new(release, &from_state));
+            },
+            TransitionRequest::Retry => {
+                return release.retry().map(|()| TransitionEvent::new(release, &from_state));
+            },
+            TransitionRequest::EnterCustomState { name } => {
+                return release.enter_custom_state(name).map(|()| TransitionEvent::new(release, &from_state));
+            },
+            TransitionRequest::ExitCustomState { reason } => {
+                return release.exit_custom_state(reason).map(|()| TransitionEvent::new(release, &from_state));
+            },
+        }
+        .map_err(TransitionError::InvalidTransition)?;
+        Ok(TransitionEvent::new(release, &from_state))
+    }
+}
+
+#[derive(Debug, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum TransitionError {
+    #[error("Release not found")]
+    ReleaseNotFound,
+    #[error("Invalid transition: {0}")]
+    InvalidTransition(String),
+    #[error("Failed to store release: {0}")]
+    StorageError(String),
+    #[error("Invalid feature list: {0}")]
+    FeatureListError(FeatureListError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_apply_publishes_to_subscribers() {
+        let broadcaster = TransitionBroadcaster::new(8);
+        let mut receiver = broadcaster.subscribe();
+        let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+
+        let event = TransitionRequest::StartDevelopment { started_by: "developer1".to_string(), feature_list: vec!["feature x".to_string()] }
+            .apply(&mut release)
+            .unwrap();
+        broadcaster.publish(event.clone());
+
+        assert_eq!(receiver.recv().await.unwrap(), event);
+        assert_eq!(event.from_state, "Draft");
+        assert_eq!(event.to_state, "InProgress");
+        assert_eq!(event.action, Some(TransitionAction::StartDevelopment));
+    }
+
+    #[test]
+    fn test_apply_rejects_invalid_transition() {
+        let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        let result = TransitionRequest::CompleteDeployment.apply(&mut release);
+        assert!(matches!(result, Err(TransitionError::InvalidTransition(_))));
+        assert_eq!(release.state_name(), "Draft");
+    }
+}
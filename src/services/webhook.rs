@@ -0,0 +1,243 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::header::CONTENT_TYPE;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
+
+use super::transitions::{TransitionAction, TransitionBroadcaster, TransitionEvent};
+
+/// Webhooks are subscribed to the transitions that trigger them.
+pub type WebhookEventType = TransitionAction;
+
+/// Header carrying `sha256=<hex HMAC-SHA256 of the body>`, keyed with the subscription's secret.
+pub const SIGNATURE_HEADER: &str = "X-SDLCCP-Signature";
+
+/// How often a delivery that failed with a `5xx` status or a connection error is retried.
+pub const MAX_RETRIES: u32 = 3;
+
+/// Delay before the first retry; it doubles with every further retry.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// How long a single delivery attempt may take.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub url: String,
+    #[schema(value_type = Vec<TransitionAction>)]
+    pub events: Vec<WebhookEventType>,
+    /// Never returned by the API once registered.
+    #[serde(skip_serializing, default)]
+    #[schema(write_only)]
+    pub secret: String,
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+impl WebhookSubscription {
+    pub fn new(url: String, events: Vec<WebhookEventType>, secret: String) -> Self {
+        Self { id: Uuid::new_v4(), url, events, secret, active: true }
+    }
+
+    pub fn matches(&self, event: &TransitionEvent) -> bool {
+        self.active && event.action.is_some_and(|action| self.events.contains(&action))
+    }
+}
+
+/// The JSON body posted to a subscription's URL.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct WebhookPayload {
+    #[schema(value_type = TransitionAction)]
+    pub event_type: WebhookEventType,
+    pub release_id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    /// The `TransitionEvent` that triggered the webhook.
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+}
+
+#[async_trait]
+pub trait WebhookService: Send + Sync {
+    async fn register_subscription(&self, subscription: WebhookSubscription) -> Result<(), WebhookError>;
+    async fn deactivate_subscription(&self, id: &Uuid) -> Result<(), WebhookError>;
+    async fn list_subscriptions(&self) -> Result<Vec<WebhookSubscription>, WebhookError>;
+    /// Starts delivering `event` to every matching subscription, without waiting for the deliveries.
+    async fn notify(&self, event: &TransitionEvent) -> Result<(), WebhookError>;
+}
+
+#[derive(Debug, Serialize, thiserror::Error, JsonSchema, ToSchema)]
+pub enum WebhookError {
+    #[error("Failed to store webhook subscription: {0}")]
+    StorageError(String),
+    #[error("Failed to retrieve webhook subscriptions: {0}")]
+    RetrievalError(String),
+    #[error("Invalid webhook URL: {0}")]
+    InvalidUrl(String),
+    #[error("Webhook subscription not found")]
+    NotFound,
+}
+
+/// Computes the `X-SDLCCP-Signature` value of `body` for a subscription with `secret`.
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Notifies `webhooks` of every event published on `transitions`, until the broadcaster is dropped.
+pub fn spawn_webhook_dispatcher(webhooks: Arc<dyn WebhookService>, transitions: &TransitionBroadcaster) -> JoinHandle<()> {
+    let mut receiver = transitions.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => {
+                    if let Err(err) = webhooks.notify(&event).await {
+                        tracing::error!(release_id = %event.release_id, "Failed to notify webhooks: {}", err);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    tracing::warn!(missed, "Webhook dispatcher fell behind, transitions were not delivered");
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+// Example in-memory implementation for testing
+#[derive(Clone)]
+pub struct InMemoryWebhookService {
+    subscriptions: Arc<RwLock<HashMap<Uuid, WebhookSubscription>>>,
+    client: reqwest::Client,
+    max_retries: u32,
+    initial_backoff: Duration,
+}
+
+impl Default for InMemoryWebhookService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryWebhookService {
+    pub fn new() -> Self {
+        Self {
+            subscriptions: Arc::new(RwLock::new(HashMap::new())),
+            client: reqwest::Client::builder()
+                .timeout(DELIVERY_TIMEOUT)
+                .build()
+                .expect("the TLS backend is available"),
+            max_retries: MAX_RETRIES,
+            initial_backoff: INITIAL_BACKOFF,
+        }
+    }
+
+    /// Overrides how often and how soon failed deliveries are retried.
+    pub fn with_retry(mut self, max_retries: u32, initial_backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff = initial_backoff;
+        self
+    }
+}
+
+#[async_trait]
+impl WebhookService for InMemoryWebhookService {
+    async fn register_subscription(&self, subscription: WebhookSubscription) -> Result<(), WebhookError> {
+        let url = url::Url::parse(&subscription.url).map_err(|err| WebhookError::InvalidUrl(err.to_string()))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(WebhookError::InvalidUrl(format!("unsupported scheme {}", url.scheme())));
+        }
+        let mut subscriptions = self.subscriptions.write().await;
+        subscriptions.insert(subscription.id, subscription);
+        Ok(())
+    }
+
+    async fn deactivate_subscription(&self, id: &Uuid) -> Result<(), WebhookError> {
+        let mut subscriptions = self.subscriptions.write().await;
+        let subscription = subscriptions.get_mut(id).ok_or(WebhookError::NotFound)?;
+        subscription.active = false;
+        Ok(())
+    }
+
+    async fn list_subscriptions(&self) -> Result<Vec<WebhookSubscription>, WebhookError> {
+        let subscriptions = self.subscriptions.read().await;
+        Ok(subscriptions.values().cloned().collect())
+    }
+
+    async fn notify(&self, event: &TransitionEvent) -> Result<(), WebhookError> {
+        let Some(event_type) = event.action else {
+            return Ok(());
+        };
+        let payload = WebhookPayload {
+            event_type,
+            release_id: event.release_id,
+            timestamp: event.timestamp,
+            payload: serde_json::to_value(event).map_err(|err| WebhookError::RetrievalError(err.to_string()))?,
+        };
+        let body = serde_json::to_vec(&payload).map_err(|err| WebhookError::RetrievalError(err.to_string()))?;
+        let subscriptions = self.subscriptions.read().await;
+        for subscription in subscriptions.values().filter(|subscription| subscription.matches(event)) {
+            let delivery = Delivery {
+                client: self.client.clone(),
+                url: subscription.url.clone(),
+                signature: sign_payload(&subscription.secret, &body),
+                body: body.clone(),
+            };
+            tokio::spawn(delivery.send(self.max_retries, self.initial_backoff));
+        }
+        Ok(())
+    }
+}
+
+struct Delivery {
+    client: reqwest::Client,
+    url: String,
+    signature: String,
+    body: Vec<u8>,
+}
+
+impl Delivery {
+    /// Posts the payload, retrying with exponential backoff while the receiver answers with a
+    /// `5xx` status or cannot be reached. Any other error status is given up on right away.
+    async fn send(self, max_retries: u32, mut backoff: Duration) {
+        let mut attempt = 0;
+        loop {
+            let result = self.client.post(&self.url)
+                .header(CONTENT_TYPE, "application/json")
+                .header(SIGNATURE_HEADER, &self.signature)
+                .body(self.body.clone())
+                .send()
+                .await;
+            match result {
+                Ok(response) if response.status().is_success() => return,
+                Ok(response) if !response.status().is_server_error() => {
+                    tracing::warn!(status = %response.status(), url = %self.url, "Webhook delivery was rejected");
+                    return;
+                }
+                Ok(response) => tracing::warn!(status = %response.status(), url = %self.url, attempt, "Webhook delivery failed"),
+                Err(err) => tracing::warn!(url = %self.url, attempt, "Webhook delivery failed: {}", err),
+            }
+            if attempt >= max_retries {
+                tracing::error!(url = %self.url, "Giving up on webhook delivery");
+                return;
+            }
+            attempt += 1;
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::services::api_error::ApiError;
+use crate::services::attestation::AttestationError;
+use crate::services::attestation::InMemoryAttestationService;
+use crate::services::audit::InMemoryAuditLogService;
+use crate::services::auth::{CurrentUser, NAMESPACE_READ};
+use crate::services::export::InMemoryExportService;
+use crate::services::controlplane::{namespace_router, release_router, ControlPlaneError, NamespaceStore};
+use crate::services::policy_repository::{InMemoryPolicyRepository, PolicyRepositoryError};
+use crate::services::release_repository::InMemoryReleaseRepository;
+use crate::services::request_id::{RequestIdLayer, X_REQUEST_ID};
+use crate::services::namespace::InMemoryNamespaceManager;
+use crate::services::transitions::TransitionBroadcaster;
+use std::sync::Arc;
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Request, StatusCode};
+use axum::response::IntoResponse;
+use axum::{routing, Router};
+use tokio::sync::Mutex;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+/// Sends `request` and checks the parts every error response shares: a JSON body whose
+/// `request_id` matches the `X-Request-ID` header.
+async fn error_body(router: Router, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+    let response = router.layer(RequestIdLayer).oneshot(request).await.unwrap();
+    let status = response.status();
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    let request_id = response.headers()[X_REQUEST_ID].to_str().unwrap().to_string();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["request_id"], request_id);
+    assert!(body["message"].is_string());
+    (status, body)
+}
+
+/// A router whose only route fails with `error`, for error sources without an HTTP endpoint yet.
+async fn returned_error(error: ApiError) -> (StatusCode, serde_json::Value) {
+    let router = Router::new().route("/", routing::get(move || async move { Err::<(), _>(error) }));
+    error_body(router, Request::get("/").body(Body::empty()).unwrap()).await
+}
+
+fn release_api() -> Router {
+    release_router(
+        Arc::new(InMemoryReleaseRepository::new()),
+        TransitionBroadcaster::new(16),
+        Arc::new(InMemoryAuditLogService::new()),
+    )
+}
+
+#[tokio::test]
+async fn test_namespace_error_shape() {
+    let store: Arc<NamespaceStore> = Arc::new(Mutex::new(InMemoryNamespaceManager::new()));
+    let exports = Arc::new(InMemoryExportService::new(store.clone(), Arc::new(InMemoryReleaseRepository::new()), Arc::new(InMemoryPolicyRepository::new()), Arc::new(InMemoryAttestationService::new())));
+    let router = namespace_router(store, exports, Arc::new(InMemoryAuditLogService::new()));
+    let reader = CurrentUser { sub: "alice".to_string(), roles: vec![NAMESPACE_READ.to_string()] };
+
+    let (status, body) = error_body(router, Request::get("/missing").extension(reader).body(Body::empty()).unwrap()).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "namespace_not_found");
+    assert_eq!(body["message"], "Namespace not found");
+    assert!(body["details"].is_null());
+}
+
+#[tokio::test]
+async fn test_transition_error_shape() {
+    let request = Request::post(format!("/{}/transitions", Uuid::new_v4()))
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"action":"complete_deployment"}"#))
+        .unwrap();
+
+    let (status, body) = error_body(release_api(), request).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "release_not_found");
+}
+
+#[tokio::test]
+async fn test_tag_error_shape() {
+    let (status, body) = error_body(
+        release_api(),
+        Request::put(format!("/{}/tags/ok", Uuid::new_v4())).body(Body::empty()).unwrap(),
+    ).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "release_not_found");
+
+    let (status, body) = returned_error(ApiError::from(crate::model::TagError::InvalidTag("not a tag".to_string()))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "invalid_tag");
+    assert_eq!(body["details"]["tag"], "not a tag");
+}
+
+#[tokio::test]
+async fn test_policy_repository_error_shape() {
+    let (status, body) = returned_error(ApiError::from(PolicyRepositoryError::RetrievalError("disk full".to_string()))).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(body["code"], "policy_retrieval_failed");
+    assert_eq!(body["message"], "Failed to retrieve policy: disk full");
+}
+
+#[tokio::test]
+async fn test_attestation_error_shape() {
+    let (status, body) = returned_error(ApiError::from(AttestationError::VerificationError("bad signature".to_string()))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "attestation_verification_failed");
+
+    let (status, body) = returned_error(ApiError::from(AttestationError::Revoked(Uuid::nil()))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "attestation_revoked");
+}
+
+#[tokio::test]
+async fn test_control_plane_error_shape() {
+    let (status, body) = returned_error(ApiError::from(ControlPlaneError::NoPolicyFound)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "no_policy_found");
+    assert_eq!(body["message"], "No policy found for component");
+}
+
+#[tokio::test]
+async fn test_error_without_request_id_layer_has_null_request_id() {
+    let response = ApiError::from(ControlPlaneError::NoPolicyFound).into_response();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(body["request_id"].is_null());
+    assert_eq!(body["code"], "no_policy_found");
+}
@@ -0,0 +1,335 @@
+use crate::model::attestation::{claims, Attestation, ClaimSet, ClaimValidationError, Subject, SubjectKind, SubjectType};
+use crate::model::claim_schema::ClaimSchemaRegistry;
+use crate::model::sbom::CYCLONEDX_PREDICATE_TYPE;
+use crate::model::slsa::SLSA_PROVENANCE_V02;
+use crate::model::SlsaProvenanceBuilder;
+use crate::services::attestation::{start_cleanup_task, AttestationFilter, AttestationService, CleanupStats, InMemoryAttestationService};
+use crate::services::api_error::ApiError;
+use crate::services::attestation::AttestationError;
+use crate::services::audit::InMemoryAuditLogService;
+use crate::services::controlplane::{attestation_router, attestation_schema_router};
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+fn attestation(type_: SubjectType, signer: &str, timestamp: DateTime<Utc>, claim: &str) -> Attestation {
+    let subject = Subject { type_, name: "app".to_string(), digest: "sha256:1234".to_string() };
+    let mut claims = ClaimSet::new();
+    claims.set_bool(claim, true);
+    let mut attestation = Attestation::new(subject, claims);
+    attestation.timestamp = timestamp;
+    attestation.add_signature(signer.to_string(), "c2lnbmF0dXJl".to_string());
+    attestation
+}
+
+/// Three attestations that each differ from the others in every filterable field.
+async fn seeded_service(now: DateTime<Utc>) -> (InMemoryAttestationService, Vec<Attestation>) {
+    let container = SubjectType::Container {
+        registry: "ghcr.io".to_string(),
+        repository: "sdlcsec/app".to_string(),
+        digest: "sha256:1234".to_string(),
+    };
+    let attestations = vec![
+        attestation(SubjectType::Commit, "alice", now - Duration::days(10), "reviewed"),
+        attestation(SubjectType::Artifact, "bob", now - Duration::days(5), "scanned"),
+        attestation(container, "carol", now, "signed"),
+    ];
+    let service = InMemoryAttestationService::new();
+    for attestation in &attestations {
+        service.store_attestation(attestation.clone()).await.unwrap();
+    }
+    (service, attestations)
+}
+
+async fn ids(service: &InMemoryAttestationService, filter: AttestationFilter) -> Vec<Uuid> {
+    let mut ids: Vec<Uuid> = service.list_attestations(filter).await.unwrap().iter().map(|att| att.id).collect();
+    ids.sort();
+    ids
+}
+
+#[tokio::test]
+async fn test_list_attestations_by_subject_type() {
+    let (service, attestations) = seeded_service(Utc::now()).await;
+    let filter = AttestationFilter { subject_type: Some(SubjectKind::Container), ..Default::default() };
+    assert_eq!(ids(&service, filter).await, vec![attestations[2].id]);
+}
+
+#[tokio::test]
+async fn test_list_attestations_by_signer() {
+    let (service, attestations) = seeded_service(Utc::now()).await;
+    let filter = AttestationFilter { signer: Some("bob".to_string()), ..Default::default() };
+    assert_eq!(ids(&service, filter).await, vec![attestations[1].id]);
+}
+
+#[tokio::test]
+async fn test_list_attestations_by_time_range() {
+    let now = Utc::now();
+    let (service, attestations) = seeded_service(now).await;
+
+    let filter = AttestationFilter { from: Some(now - Duration::days(5)), ..Default::default() };
+    let mut expected = vec![attestations[1].id, attestations[2].id];
+    expected.sort();
+    assert_eq!(ids(&service, filter).await, expected);
+
+    let filter = AttestationFilter { to: Some(now - Duration::days(5)), ..Default::default() };
+    let mut expected = vec![attestations[0].id, attestations[1].id];
+    expected.sort();
+    assert_eq!(ids(&service, filter).await, expected);
+}
+
+#[tokio::test]
+async fn test_list_attestations_by_claim_key() {
+    let (service, attestations) = seeded_service(Utc::now()).await;
+    let filter = AttestationFilter { has_claim_key: Some("reviewed".to_string()), ..Default::default() };
+    assert_eq!(ids(&service, filter).await, vec![attestations[0].id]);
+}
+
+#[tokio::test]
+async fn test_list_attestations_combines_filters() {
+    let now = Utc::now();
+    let (service, attestations) = seeded_service(now).await;
+
+    assert_eq!(ids(&service, AttestationFilter::default()).await.len(), 3);
+
+    let filter = AttestationFilter {
+        subject_type: Some(SubjectKind::Artifact),
+        signer: Some("bob".to_string()),
+        from: Some(now - Duration::days(6)),
+        to: Some(now - Duration::days(4)),
+        has_claim_key: Some("scanned".to_string()),
+    };
+    assert_eq!(ids(&service, filter).await, vec![attestations[1].id]);
+
+    // Every field must match: the right signer with the wrong subject type matches nothing.
+    let filter = AttestationFilter {
+        subject_type: Some(SubjectKind::Commit),
+        signer: Some("bob".to_string()),
+        ..Default::default()
+    };
+    assert!(ids(&service, filter).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_attestation_router_applies_query_filters() {
+    let (service, attestations) = seeded_service(Utc::now()).await;
+
+    let response = attestation_router(Arc::new(service), Arc::new(InMemoryAuditLogService::new()))
+        .oneshot(
+            Request::get("/?subject_type=Container&signer=carol&has_claim_key=signed")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let found: Vec<Attestation> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, attestations[2].id);
+}
+
+#[tokio::test]
+async fn test_create_attestation_endpoint_validates_well_known_claims() {
+    let service = Arc::new(InMemoryAttestationService::new());
+    let router = attestation_router(service.clone(), Arc::new(InMemoryAuditLogService::new()));
+    let post = |claims: serde_json::Value| {
+        let request = serde_json::json!({
+            "subject": Subject { type_: SubjectType::Artifact, name: "app".to_string(), digest: "sha256:1234".to_string() },
+            "expiration": null,
+            "signatures": [],
+            "claims": claims,
+            "parent_attestations": [],
+        });
+        Request::post("/").header("content-type", "application/json").body(Body::from(request.to_string())).unwrap()
+    };
+
+    let valid = serde_json::json!({
+        claims::CLAIM_BUILD_TYPE: "https://slsa.dev/build/v1",
+        claims::CLAIM_SOURCE_COMMIT: "a".repeat(40),
+        "custom": 42,
+    });
+    let response = router.clone().oneshot(post(valid)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let created: Attestation = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert!(service.get_attestation(&created.id).await.unwrap().is_some());
+
+    let invalid = serde_json::json!({
+        claims::CLAIM_BUILDER_ID: "",
+        claims::CLAIM_VULNERABILITY_SCAN_RESULT: { "passed": true, "critical": -1 },
+    });
+    let response = router.oneshot(post(invalid)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body["code"], "invalid_claims");
+    let errors: Vec<ClaimValidationError> = serde_json::from_value(body["details"]["claims"].clone()).unwrap();
+    assert_eq!(
+        errors,
+        vec![
+            ClaimValidationError { claim: "builderId".to_string(), reason: "must not be empty".to_string() },
+            ClaimValidationError {
+                claim: "vulnerabilityScanResult".to_string(),
+                reason: "severity counts must be non-negative integers".to_string(),
+            },
+        ]
+    );
+    assert_eq!(service.list_attestations(AttestationFilter::default()).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_prune_expired_attestations() {
+    let now = Utc::now();
+    let service = Arc::new(InMemoryAttestationService::new().with_deduplication(false));
+    let expirations = [Some(now - Duration::days(1)); 5].into_iter().chain([Some(now + Duration::days(1)), Some(now + Duration::hours(1)), None]);
+    for expiration in expirations {
+        let mut attestation = attestation(SubjectType::Artifact, "alice", now - Duration::days(2), "scanned");
+        attestation.expiration = expiration;
+        service.store_attestation(attestation).await.unwrap();
+    }
+
+    assert_eq!(service.prune_expired().await, 5);
+    assert_eq!(service.list_attestations(AttestationFilter::default()).await.unwrap().len(), 3);
+    assert_eq!(service.prune_expired().await, 0);
+
+    let response = attestation_router(service.clone(), Arc::new(InMemoryAuditLogService::new()))
+        .oneshot(Request::get("/cleanup-stats").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let stats: CleanupStats = serde_json::from_slice(&body).unwrap();
+    assert_eq!(stats.total_pruned, 5);
+    assert!(stats.last_run.is_some_and(|last_run| last_run >= now));
+}
+
+#[tokio::test]
+async fn test_cleanup_task_prunes_periodically() {
+    let service = Arc::new(InMemoryAttestationService::new());
+    let mut expired = attestation(SubjectType::Commit, "alice", Utc::now(), "reviewed");
+    expired.expiration = Some(Utc::now() - Duration::seconds(1));
+    service.store_attestation(expired).await.unwrap();
+
+    let task = start_cleanup_task(service.clone(), std::time::Duration::from_millis(10));
+    for _ in 0..100 {
+        if service.cleanup_stats().await.unwrap().total_pruned == 1 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    task.abort();
+    assert_eq!(service.cleanup_stats().await.unwrap().total_pruned, 1);
+    assert!(service.list_attestations(AttestationFilter::default()).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_duplicate_attestations_are_rejected() {
+    let service = InMemoryAttestationService::new();
+    let original = attestation(SubjectType::Artifact, "alice", Utc::now(), "scanned");
+    service.store_attestation(original.clone()).await.unwrap();
+
+    // A new ID, timestamp and signer do not make it a different attestation.
+    let duplicate = attestation(SubjectType::Artifact, "bob", Utc::now() + Duration::minutes(5), "scanned");
+    assert_eq!(duplicate.content_hash(), original.content_hash());
+    let err = service.store_attestation(duplicate.clone()).await.unwrap_err();
+    assert!(matches!(err, AttestationError::Duplicate { existing_id } if existing_id == original.id));
+    let response = ApiError::from(err);
+    assert_eq!(response.code, "duplicate_attestation");
+    assert_eq!(response.details.unwrap()["existing_id"], original.id.to_string());
+
+    // Other claims, or storing the same attestation again, are fine.
+    service.store_attestation(attestation(SubjectType::Artifact, "alice", Utc::now(), "signed")).await.unwrap();
+    service.store_attestation(original.clone()).await.unwrap();
+    assert_eq!(service.list_attestations(AttestationFilter::default()).await.unwrap().len(), 2);
+    let found = service.find_by_content_hash(&original.content_hash()).await.unwrap().unwrap();
+    assert_eq!(found.id, original.id);
+    assert!(service.find_by_content_hash("0000").await.unwrap().is_none());
+
+    // Once the original is revoked, it may be reissued.
+    service.revoke_attestation(&original.id, "Signing key leaked".to_string(), "security@example.com".to_string()).await.unwrap();
+    service.store_attestation(duplicate.clone()).await.unwrap();
+    assert_eq!(service.find_by_content_hash(&original.content_hash()).await.unwrap().unwrap().id, duplicate.id);
+}
+
+#[tokio::test]
+async fn test_deduplication_can_be_turned_off() {
+    let service = InMemoryAttestationService::new().with_deduplication(false);
+    for signer in ["alice", "bob"] {
+        service.store_attestation(attestation(SubjectType::Artifact, signer, Utc::now(), "scanned")).await.unwrap();
+    }
+    assert_eq!(service.list_attestations(AttestationFilter::default()).await.unwrap().len(), 2);
+}
+
+#[test]
+fn test_content_hash_ignores_claim_order() {
+    let subject = Subject { type_: SubjectType::Artifact, name: "app".to_string(), digest: "sha256:1234".to_string() };
+    let mut first = ClaimSet::new();
+    let mut second = ClaimSet::new();
+    for (claim, value) in [("builder", "ci"), ("level", "3"), ("runner", "linux")] {
+        first.set_string(claim, value.to_string());
+    }
+    for (claim, value) in [("runner", "linux"), ("builder", "ci"), ("level", "3")] {
+        second.set_string(claim, value.to_string());
+    }
+    let first = Attestation::new(subject.clone(), first);
+    let mut other_subject = Attestation::new(subject, second.clone());
+    assert_eq!(first.content_hash(), other_subject.content_hash());
+    assert_eq!(first.content_hash().len(), 64);
+
+    other_subject.subject.digest = "sha256:5678".to_string();
+    assert_ne!(first.content_hash(), other_subject.content_hash());
+}
+
+#[tokio::test]
+async fn test_malformed_slsa_provenance_is_rejected() {
+    let service = InMemoryAttestationService::new();
+    let provenance = SlsaProvenanceBuilder::new("app.tar.gz", "sha256:5678")
+        .with_builder_id("https://github.com/actions/runner")
+        .with_build_type("https://github.com/slsa-framework/slsa-github-generator/generic@v1")
+        .add_material("git+https://github.com/sdlcsec/app", Default::default())
+        .build();
+    service.store_attestation(provenance.clone()).await.unwrap();
+
+    let mut malformed = provenance;
+    malformed.id = Uuid::new_v4();
+    malformed.claims.insert("builder".to_string(), serde_json::json!({ "version": "2.1" }));
+    malformed.claims.insert("materials".to_string(), serde_json::json!([{ "uri": 42 }]));
+    malformed.claims.insert("buildType".to_string(), serde_json::Value::Null);
+    let err = service.store_attestation(malformed.clone()).await.unwrap_err();
+    let AttestationError::InvalidClaims(errors) = &err else { panic!("unexpected error {err}") };
+    let mut errors = errors.clone();
+    errors.sort_by(|a, b| a.claim.cmp(&b.claim));
+    assert_eq!(
+        errors,
+        vec![
+            ClaimValidationError { claim: "buildType".to_string(), reason: "must be a string".to_string() },
+            ClaimValidationError { claim: "builder.id".to_string(), reason: "is required".to_string() },
+            ClaimValidationError { claim: "materials[0].uri".to_string(), reason: "must be a string".to_string() },
+        ]
+    );
+    let response = ApiError::from(err);
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.code, "invalid_claims");
+
+    // Without a schema for its predicate type, only the well-known claims are checked.
+    let service = InMemoryAttestationService::new().with_claim_schemas(ClaimSchemaRegistry::new());
+    let err = service.store_attestation(malformed.clone()).await.unwrap_err();
+    assert!(matches!(err, AttestationError::InvalidClaims(ref errors) if errors.len() == 1 && errors[0].claim == "buildType"), "unexpected error {err}");
+    malformed.claims.insert("buildType".to_string(), serde_json::json!("https://example.com/build/v1"));
+    service.store_attestation(malformed).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_attestation_schemas_lists_registered_predicate_types() {
+    let mut registry = ClaimSchemaRegistry::with_builtin_schemas();
+    registry.register_schema("https://example.com/review/v1", schemars::schema_for!(bool));
+    let response = attestation_schema_router(Arc::new(InMemoryAttestationService::new().with_claim_schemas(registry)))
+        .oneshot(Request::get("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let predicate_types: Vec<String> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(predicate_types, vec![CYCLONEDX_PREDICATE_TYPE, "https://example.com/review/v1", SLSA_PROVENANCE_V02]);
+}
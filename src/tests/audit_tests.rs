@@ -0,0 +1,80 @@
+use crate::services::audit::{AuditAction, AuditEvent, AuditLogService, InMemoryAuditLogService};
+use crate::services::auth::{CurrentUser, NAMESPACE_WRITE};
+use crate::services::controlplane::{audit_router, namespace_router, NamespaceStore};
+use crate::services::attestation::InMemoryAttestationService;
+use crate::services::export::InMemoryExportService;
+use crate::services::policy_repository::InMemoryPolicyRepository;
+use crate::services::release_repository::InMemoryReleaseRepository;
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn test_namespace_mutations_are_queryable_from_audit_router() {
+    let audit = Arc::new(InMemoryAuditLogService::new());
+    let store = Arc::new(NamespaceStore::default());
+    let exports = Arc::new(InMemoryExportService::new(store.clone(), Arc::new(InMemoryReleaseRepository::new()), Arc::new(InMemoryPolicyRepository::new()), Arc::new(InMemoryAttestationService::new())));
+    let namespaces = namespace_router(store, exports, audit.clone());
+
+    for path in ["team-a", "team-b"] {
+        let response = namespaces
+            .clone()
+            .oneshot(
+                Request::post("/")
+                    .header("content-type", "application/json")
+                    .extension(CurrentUser { sub: "alice".to_string(), roles: vec![NAMESPACE_WRITE.to_string()] })
+                    .body(Body::from(format!(r#"{{"namespace":"{}"}}"#, path)))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    let response = namespaces
+        .oneshot(
+            Request::delete("/team-a")
+                .extension(CurrentUser { sub: "bob".to_string(), roles: vec![NAMESPACE_WRITE.to_string()] })
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = audit_router(audit)
+        .oneshot(
+            Request::get("/?actor=alice&action=CreateNamespace")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let events: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|event| event["actor"] == "alice" && event["resource_type"] == "namespace"));
+    assert_eq!(events[0]["metadata"]["path"], "team-a");
+}
+
+#[tokio::test]
+async fn test_audit_router_filters_by_time_range() {
+    let audit = Arc::new(InMemoryAuditLogService::new());
+    audit
+        .record(AuditEvent::new("alice".to_string(), AuditAction::CreatePolicy, uuid::Uuid::new_v4(), "policy"))
+        .await
+        .unwrap();
+
+    let response = audit_router(audit)
+        .oneshot(
+            Request::get("/?from=2999-01-01T00:00:00Z")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let events: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+    assert!(events.is_empty());
+}
@@ -0,0 +1,76 @@
+use crate::services::audit::InMemoryAuditLogService;
+use crate::services::auth::{CurrentUser, NAMESPACE_READ, NAMESPACE_WRITE};
+use crate::services::controlplane::{namespace_router, NamespaceStore};
+use crate::services::attestation::InMemoryAttestationService;
+use crate::services::export::InMemoryExportService;
+use crate::services::policy_repository::InMemoryPolicyRepository;
+use crate::services::release_repository::InMemoryReleaseRepository;
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+fn router() -> Router {
+    let store = Arc::new(NamespaceStore::default());
+    let exports = Arc::new(InMemoryExportService::new(store.clone(), Arc::new(InMemoryReleaseRepository::new()), Arc::new(InMemoryPolicyRepository::new()), Arc::new(InMemoryAttestationService::new())));
+    namespace_router(store, exports, Arc::new(InMemoryAuditLogService::new()))
+}
+
+fn user(roles: &[&str]) -> CurrentUser {
+    CurrentUser {
+        sub: "alice".to_string(),
+        roles: roles.iter().map(|role| role.to_string()).collect(),
+    }
+}
+
+fn create_request(user: Option<CurrentUser>) -> Request<Body> {
+    let mut request = Request::post("/").header("content-type", "application/json");
+    if let Some(user) = user {
+        request = request.extension(user);
+    }
+    request.body(Body::from(r#"{"namespace":"team-a"}"#)).unwrap()
+}
+
+#[tokio::test]
+async fn test_namespace_router_requires_authentication() {
+    let response = router().oneshot(create_request(None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+
+    let response = router().oneshot(Request::get("/").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_namespace_router_enforces_roles_per_method() {
+    let router = router();
+
+    // Readers can list but not create or delete
+    let reader = user(&[NAMESPACE_READ]);
+    let response = router.clone().oneshot(create_request(Some(reader.clone()))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = router
+        .clone()
+        .oneshot(Request::get("/").extension(reader.clone()).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Writers can create and delete but not read
+    let writer = user(&[NAMESPACE_WRITE]);
+    let response = router.clone().oneshot(create_request(Some(writer.clone()))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = router
+        .clone()
+        .oneshot(Request::get("/team-a").extension(writer.clone()).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = router
+        .clone()
+        .oneshot(Request::delete("/team-a").extension(writer).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,266 @@
+use crate::model::sdlc_component::{ContainerImage, MergeConflict, MergeError, Project, SDLCComponent, TeamReference, Unmanaged};
+use crate::model::SDLCRelease;
+use crate::services::audit::{AuditAction, AuditFilter, AuditLogService, InMemoryAuditLogService};
+use crate::services::auth::CurrentUser;
+use crate::services::component_repository::{ComponentRepository, InMemoryComponentRepository};
+use crate::services::controlplane::component_router;
+use std::collections::HashMap;
+use std::sync::Arc;
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_release_resolves_component_from_router() {
+    let audit = Arc::new(InMemoryAuditLogService::new());
+    let router = component_router(Arc::new(InMemoryComponentRepository::new()), audit.clone());
+    let component = SDLCComponent::Project(Project {
+        id: Uuid::new_v4(),
+        name: "Test Project".to_string(),
+        repository_url: Some("https://github.com/test/project".to_string()),
+        owner: Some("Test Owner".to_string()),
+        components: Vec::new(),
+        teams: Vec::new(),
+    });
+
+    // Register the component
+    let response = router
+        .clone()
+        .oneshot(
+            Request::post("/")
+                .header("content-type", "application/json")
+                .extension(CurrentUser { sub: "developer1".to_string(), roles: Vec::new() })
+                .body(Body::from(serde_json::to_vec(&component).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // Create a release that references the component by ID
+    let release = SDLCRelease::new(component.id(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+
+    // Resolve the release's component through the API
+    let response = router
+        .clone()
+        .oneshot(
+            Request::get(format!("/{}", release.component_id()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let resolved: SDLCComponent = serde_json::from_slice(&body).unwrap();
+    assert_eq!(resolved.id(), component.id());
+    assert_eq!(resolved.name(), "Test Project");
+
+    // Once deleted, the component can no longer be resolved
+    let response = router
+        .clone()
+        .oneshot(
+            Request::delete(format!("/{}", release.component_id()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = router
+        .oneshot(
+            Request::get(format!("/{}", release.component_id()))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Both mutations were recorded in the audit trail
+    let events = audit.query(AuditFilter::default()).await.unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].action, AuditAction::CreateComponent);
+    assert_eq!(events[0].actor, "developer1");
+    assert_eq!(events[0].resource_id, component.id());
+    assert_eq!(events[1].action, AuditAction::DeleteComponent);
+    assert_eq!(events[1].actor, "anonymous");
+}
+
+#[tokio::test]
+async fn test_release_resolve_component() {
+    use crate::services::component_repository::{ComponentRepository, InMemoryComponentRepository};
+
+    let repository = InMemoryComponentRepository::new();
+    let component = SDLCComponent::Project(Project {
+        id: Uuid::new_v4(),
+        name: "Test Project".to_string(),
+        repository_url: None,
+        owner: None,
+        components: Vec::new(),
+        teams: Vec::new(),
+    });
+    repository.store_component(component.clone()).await.unwrap();
+
+    let release = SDLCRelease::new(component.id(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    let resolved = release.resolve_component(&repository).await.unwrap().unwrap();
+    assert_eq!(resolved.name(), "Test Project");
+}
+
+fn unmanaged(repository_url: Option<&str>, package_url: Option<&str>, metadata: &[(&str, &str)]) -> Unmanaged {
+    Unmanaged {
+        id: Uuid::new_v4(),
+        name: "openssl".to_string(),
+        repository_url: repository_url.map(str::to_string),
+        package_url: package_url.map(str::to_string),
+        metadata: metadata.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect(),
+    }
+}
+
+fn conflict(key: &str, our_value: &str, their_value: &str) -> MergeConflict {
+    MergeConflict { key: key.to_string(), our_value: our_value.to_string(), their_value: their_value.to_string() }
+}
+
+#[test]
+fn test_merge_unmanaged_fills_gaps_without_conflicts() {
+    let ours = unmanaged(None, Some("pkg:generic/openssl@3.0.0"), &[("license", "Apache-2.0")]);
+    let theirs = unmanaged(Some("https://github.com/openssl/openssl"), None, &[("license", "Apache-2.0"), ("cpe", "cpe:2.3:a:openssl")]);
+    let mut merged = SDLCComponent::Unmanaged(ours.clone());
+    merged.merge(&SDLCComponent::Unmanaged(theirs)).unwrap();
+
+    let SDLCComponent::Unmanaged(merged) = merged else { unreachable!() };
+    assert_eq!(merged.id, ours.id);
+    assert_eq!(merged.repository_url.as_deref(), Some("https://github.com/openssl/openssl"));
+    assert_eq!(merged.package_url.as_deref(), Some("pkg:generic/openssl@3.0.0"));
+    assert_eq!(merged.metadata.len(), 2);
+    assert_eq!(merged.metadata["cpe"], "cpe:2.3:a:openssl");
+}
+
+#[test]
+fn test_merge_unmanaged_reports_every_conflict_and_keeps_our_values() {
+    let ours = unmanaged(Some("https://github.com/openssl/openssl"), Some("pkg:generic/openssl@3.0.0"), &[("owner", "platform"), ("tier", "1")]);
+    let mut theirs = unmanaged(Some("https://git.example.com/mirror/openssl"), Some("pkg:generic/openssl@3.0.0"), &[("tier", "2"), ("owner", "security"), ("cpe", "cpe:2.3:a:openssl")]);
+    theirs.name = "OpenSSL".to_string();
+    let mut merged = SDLCComponent::Unmanaged(ours);
+
+    let err = merged.merge(&SDLCComponent::Unmanaged(theirs)).unwrap_err();
+    assert_eq!(
+        err,
+        MergeError::Conflicts(vec![
+            conflict("name", "openssl", "OpenSSL"),
+            conflict("repository_url", "https://github.com/openssl/openssl", "https://git.example.com/mirror/openssl"),
+            conflict("metadata.owner", "platform", "security"),
+            conflict("metadata.tier", "1", "2"),
+        ])
+    );
+    assert_eq!(err.to_string(), "4 fields differ between the components");
+
+    // Everything that did not conflict was still merged in.
+    let SDLCComponent::Unmanaged(merged) = merged else { unreachable!() };
+    assert_eq!(merged.name, "openssl");
+    assert_eq!(merged.repository_url.as_deref(), Some("https://github.com/openssl/openssl"));
+    assert_eq!(merged.metadata["owner"], "platform");
+    assert_eq!(merged.metadata["tier"], "1");
+    assert_eq!(merged.metadata["cpe"], "cpe:2.3:a:openssl");
+}
+
+#[test]
+fn test_merge_projects_deduplicates_components_and_teams() {
+    let (shared, ours_only, theirs_only) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let payments = TeamReference { id: Uuid::new_v4(), name: "payments".to_string(), contact_email: "payments@example.com".to_string() };
+    let platform = TeamReference { id: Uuid::new_v4(), name: "platform".to_string(), contact_email: "platform@example.com".to_string() };
+    let project = |components: Vec<Uuid>, teams: Vec<TeamReference>, owner: Option<&str>| Project {
+        id: Uuid::new_v4(),
+        name: "payments-api".to_string(),
+        repository_url: None,
+        owner: owner.map(str::to_string),
+        components,
+        teams,
+    };
+    let mut merged = SDLCComponent::Project(project(vec![shared, ours_only], vec![payments.clone()], None));
+    let theirs = SDLCComponent::Project(project(vec![theirs_only, shared], vec![platform.clone(), payments.clone()], Some("alice")));
+    merged.merge(&theirs).unwrap();
+
+    let SDLCComponent::Project(merged) = merged else { unreachable!() };
+    assert_eq!(merged.components, vec![shared, ours_only, theirs_only]);
+    assert_eq!(merged.teams, vec![payments, platform]);
+    assert_eq!(merged.owner.as_deref(), Some("alice"));
+
+    let mut ours = SDLCComponent::Project(project(Vec::new(), Vec::new(), Some("bob")));
+    assert_eq!(ours.merge(&theirs), Err(MergeError::Conflicts(vec![conflict("owner", "bob", "alice")])));
+}
+
+#[test]
+fn test_merge_containers_and_incompatible_types() {
+    let image = |digest: Option<&str>, metadata: HashMap<String, String>| ContainerImage {
+        id: Uuid::new_v4(),
+        name: "api".to_string(),
+        registry: "ghcr.io".to_string(),
+        repository: "example/api".to_string(),
+        tag: Some("1.0.0".to_string()),
+        digest: digest.map(str::to_string),
+        base_image: None,
+        metadata,
+    };
+    let mut merged = SDLCComponent::Container(image(None, HashMap::new()));
+    let theirs = image(Some("sha256:abc"), HashMap::from([("arch".to_string(), "arm64".to_string())]));
+    merged.merge(&SDLCComponent::Container(theirs.clone())).unwrap();
+    let SDLCComponent::Container(merged_image) = &merged else { unreachable!() };
+    assert_eq!(merged_image.digest.as_deref(), Some("sha256:abc"));
+    assert_eq!(merged_image.metadata["arch"], "arm64");
+
+    let mut moved = theirs.clone();
+    moved.registry = "docker.io".to_string();
+    moved.digest = Some("sha256:def".to_string());
+    assert_eq!(
+        merged.merge(&SDLCComponent::Container(moved)),
+        Err(MergeError::Conflicts(vec![conflict("registry", "ghcr.io", "docker.io"), conflict("digest", "sha256:abc", "sha256:def")]))
+    );
+
+    let err = merged.merge(&SDLCComponent::Unmanaged(unmanaged(None, None, &[]))).unwrap_err();
+    assert_eq!(err, MergeError::IncompatibleTypes { ours: "Container", theirs: "Unmanaged" });
+}
+
+#[tokio::test]
+async fn test_merge_component_endpoint() {
+    let repository = Arc::new(InMemoryComponentRepository::new());
+    let audit = Arc::new(InMemoryAuditLogService::new());
+    let router = component_router(repository.clone(), audit.clone());
+    let ours = unmanaged(None, None, &[("owner", "platform")]);
+    repository.store_component(SDLCComponent::Unmanaged(ours.clone())).await.unwrap();
+    let merge = |id: Uuid, other: SDLCComponent| {
+        Request::post(format!("/{}/merge", id))
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&other).unwrap()))
+            .unwrap()
+    };
+
+    let theirs = unmanaged(Some("https://github.com/openssl/openssl"), None, &[("owner", "platform")]);
+    let response = router.clone().oneshot(merge(ours.id, SDLCComponent::Unmanaged(theirs.clone()))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let stored = repository.get_component(&ours.id).await.unwrap().unwrap();
+    let SDLCComponent::Unmanaged(stored) = stored else { unreachable!() };
+    assert_eq!(stored.repository_url.as_deref(), Some("https://github.com/openssl/openssl"));
+    let events = audit.query(AuditFilter { action: Some(AuditAction::MergeComponent), ..Default::default() }).await.unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].metadata["merged_from"], theirs.id.to_string());
+
+    // A conflicting merge stores nothing and says what differs.
+    let conflicting = unmanaged(None, Some("pkg:generic/openssl@3.0.0"), &[("owner", "security")]);
+    let response = router.clone().oneshot(merge(ours.id, SDLCComponent::Unmanaged(conflicting))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body["code"], "merge_conflicts");
+    assert_eq!(body["details"]["conflicts"], serde_json::json!([{ "key": "metadata.owner", "our_value": "platform", "their_value": "security" }]));
+    let SDLCComponent::Unmanaged(unchanged) = repository.get_component(&ours.id).await.unwrap().unwrap() else { unreachable!() };
+    assert_eq!(unchanged.package_url, None);
+
+    let project = SDLCComponent::Project(Project { id: Uuid::new_v4(), name: "openssl".to_string(), repository_url: None, owner: None, components: Vec::new(), teams: Vec::new() });
+    let response = router.clone().oneshot(merge(ours.id, project)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let response = router.oneshot(merge(Uuid::new_v4(), SDLCComponent::Unmanaged(theirs))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,528 @@
+use crate::model::attestation::{ClaimSet, Subject, SubjectType};
+use crate::model::phase::{RolloutStrategy, SourceReviewStatus};
+use crate::model::policy::{PolicyRule, Vulnerability, VulnerabilityLevel};
+use crate::model::sdlc_component::{Project, TeamReference};
+use crate::model::{Attestation, Policy, SDLCComponent, SDLCPhase, SDLCRelease};
+use crate::services::attestation::{AttestationService, InMemoryAttestationService};
+use crate::services::compliance::{ComplianceBaseline, ComplianceReport, DriftReport, InMemoryComplianceBaselineService};
+use crate::services::audit::{AuditAction, AuditFilter, AuditLogService, InMemoryAuditLogService};
+use crate::services::auth::{CurrentUser, NAMESPACE_WRITE, POLICY_WRITE};
+use crate::services::component_repository::{ComponentRepository, InMemoryComponentRepository};
+use crate::services::controlplane::{compliance_baseline_router, policy_evaluation_router, policy_router, ControlPlane, ControlPlaneError, InMemoryControlPlane};
+use crate::services::policy_enforcement::PolicyEvaluationReport;
+use crate::services::policy_repository::{InMemoryPolicyRepository, PolicyRepository};
+use crate::services::release_repository::{InMemoryReleaseRepository, ReleaseRepository};
+use crate::services::transitions::TransitionRequest;
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const ARTIFACT_HASH: &str = "sha256:9f86d081884c7d65";
+
+struct Fixture {
+    policies: Arc<InMemoryPolicyRepository>,
+    attestations: Arc<InMemoryAttestationService>,
+    releases: Arc<InMemoryReleaseRepository>,
+    components: Arc<InMemoryComponentRepository>,
+    control_plane: Arc<InMemoryControlPlane>,
+}
+
+fn fixture() -> Fixture {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let policies = Arc::new(InMemoryPolicyRepository::new().with_releases(releases.clone()));
+    let components = Arc::new(InMemoryComponentRepository::new());
+    // The tests store attestations that only differ in who signed them.
+    let attestations = Arc::new(InMemoryAttestationService::new().with_deduplication(false));
+    let control_plane = Arc::new(InMemoryControlPlane::new(policies.clone(), attestations.clone(), releases.clone(), components.clone()));
+    Fixture { policies, attestations, releases, components, control_plane }
+}
+
+/// A release taken through every phase up to `Deployed`, so it has runtime details.
+async fn deployed_release(releases: &InMemoryReleaseRepository, component_id: Uuid) -> SDLCRelease {
+    let mut release = SDLCRelease::new(component_id, "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    let lifecycle = [
+        TransitionRequest::StartDevelopment { started_by: "developer1".to_string(), feature_list: vec!["feature x".to_string()] },
+        TransitionRequest::CompleteDevelopment,
+        TransitionRequest::StartSourceReview { started_by: "reviewer1".to_string() },
+        TransitionRequest::CompleteSourceReview {
+            commit_hash: "abcdef123456".to_string(),
+            branch: "main".to_string(),
+            pr_url: None,
+            review_status: SourceReviewStatus::Approved { approver: "reviewer1".to_string(), approved_at: Utc::now() },
+        },
+        TransitionRequest::StartBuild { started_by: "builder1".to_string() },
+        TransitionRequest::CompleteBuild { build_id: "build-1".to_string(), reproducible: false, build_tool: None },
+        TransitionRequest::StartPackaging { started_by: "packager1".to_string() },
+        TransitionRequest::CompletePackaging { artifact_hash: ARTIFACT_HASH.to_string(), artifact_url: "https://example.com/app.tar.gz".to_string(), sbom: None },
+        TransitionRequest::Release { release_notes: "First release".to_string() },
+        TransitionRequest::StartDeployment { environment: "production".to_string(), rollout_strategy: RolloutStrategy::AllAtOnce },
+        TransitionRequest::CompleteDeployment,
+    ];
+    for transition in lifecycle {
+        transition.apply(&mut release).unwrap();
+    }
+    releases.store_release(release.clone()).await.unwrap();
+    release
+}
+
+fn build_attestation(signer: &str) -> Attestation {
+    let subject = Subject { type_: SubjectType::Artifact, name: "app.tar.gz".to_string(), digest: ARTIFACT_HASH.to_string() };
+    let mut attestation = Attestation::new(subject, ClaimSet::from(HashMap::from([("builder".to_string(), serde_json::json!("ci"))])));
+    attestation.add_signature(signer.to_string(), "MEUCIQD".to_string());
+    attestation
+}
+
+fn release_policy() -> Policy {
+    let mut policy = Policy::new("Release Policy".to_string(), vec!["Runtime".to_string()]);
+    policy.add_rule(PolicyRule::ApprovedIdentities(vec!["ci@example.com".to_string()]));
+    policy.add_rule(PolicyRule::RequiredClaims(HashMap::from([("builder".to_string(), "ci".to_string())])));
+    policy.add_rule(PolicyRule::VulnerabilityThreshold(VulnerabilityLevel::High, 0));
+    policy
+}
+
+#[tokio::test]
+async fn test_release_passes_until_an_attestation_violates_policy() {
+    let fixture = fixture();
+    let component_id = Uuid::new_v4();
+    let release = deployed_release(&fixture.releases, component_id).await;
+    let policy = release_policy();
+    fixture.control_plane.store_policy(policy.clone()).await.unwrap();
+    fixture.policies.assign_policy(component_id, policy.id).await;
+    fixture.control_plane.store_attestation(build_attestation("ci@example.com")).await.unwrap();
+    // Attestations about other artifacts do not count.
+    let mut unrelated = build_attestation("mallory@example.com");
+    unrelated.subject.digest = "sha256:other".to_string();
+    fixture.control_plane.store_attestation(unrelated).await.unwrap();
+
+    assert!(fixture.control_plane.apply_policy_to_release(&release.id, &component_id).await.unwrap());
+
+    fixture.control_plane.store_attestation(build_attestation("mallory@example.com")).await.unwrap();
+    assert!(!fixture.control_plane.apply_policy_to_release(&release.id, &component_id).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_every_policy_of_the_component_must_pass() {
+    let fixture = fixture();
+    let component_id = Uuid::new_v4();
+    let mut release = deployed_release(&fixture.releases, component_id).await;
+    let lenient = Policy::new("Lenient".to_string(), vec!["Runtime".to_string()]);
+    let strict = release_policy();
+    for policy in [&lenient, &strict] {
+        fixture.control_plane.store_policy(policy.clone()).await.unwrap();
+        fixture.policies.assign_policy(component_id, policy.id).await;
+    }
+    fixture.control_plane.store_attestation(build_attestation("ci@example.com")).await.unwrap();
+    assert!(fixture.control_plane.apply_policy_to_release(&release.id, &component_id).await.unwrap());
+
+    let runtime = release.phase_details.as_mut().unwrap().runtime_details.as_mut().unwrap();
+    runtime.vulnerabilities.push(Vulnerability {
+        id: "CVE-2024-3094".to_string(),
+        cve_id: Some("CVE-2024-3094".to_string()),
+        severity: VulnerabilityLevel::Critical,
+        cvss_score: Some(10.0),
+        cvss_vector: None,
+        affected_package: Some("pkg:deb/debian/xz-utils@5.6.0".to_string()),
+        fix_available: true,
+        description: "Backdoor in xz".to_string(),
+        discovered_at: Utc::now(),
+    });
+    fixture.releases.store_release(release.clone()).await.unwrap();
+
+    assert!(!fixture.control_plane.apply_policy_to_release(&release.id, &component_id).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_revoking_an_ancestor_attestation_fails_the_release() {
+    let fixture = fixture();
+    let component_id = Uuid::new_v4();
+    let release = deployed_release(&fixture.releases, component_id).await;
+    let mut policy = Policy::new("Trusted chain".to_string(), vec!["Runtime".to_string()]);
+    policy.add_rule(PolicyRule::NoRevokedAttestations);
+    fixture.control_plane.store_policy(policy.clone()).await.unwrap();
+    fixture.policies.assign_policy(component_id, policy.id).await;
+    // The source attestation is about another subject; the build attestation about the release builds on it.
+    let mut source = build_attestation("ci@example.com");
+    source.subject = Subject { type_: SubjectType::Commit, name: "app".to_string(), digest: "abcdef123456".to_string() };
+    let mut build = build_attestation("ci@example.com");
+    build.parent_attestations.push(source.id);
+    for attestation in [&source, &build] {
+        fixture.attestations.store_attestation(attestation.clone()).await.unwrap();
+    }
+    assert!(fixture.control_plane.apply_policy_to_release(&release.id, &component_id).await.unwrap());
+
+    fixture.attestations.revoke_attestation(&source.id, "Signing key leaked".to_string(), "security@example.com".to_string()).await.unwrap();
+    let report = fixture.control_plane.evaluate_release(&release.id, None).await.unwrap();
+    assert!(!report.overall);
+    assert_eq!(
+        report.policy_results[0].rule_results[0].details,
+        format!("Attestation {} was revoked by security@example.com: Signing key leaked", source.id)
+    );
+}
+
+#[tokio::test]
+async fn test_list_policies_by_phase() {
+    let fixture = fixture();
+    let source = Policy::new("Review".to_string(), vec!["Source".to_string()]);
+    let custom = Policy::new("Certification".to_string(), vec!["Certification".to_string(), "Runtime".to_string()]);
+    for policy in [&source, &custom] {
+        fixture.control_plane.store_policy(policy.clone()).await.unwrap();
+    }
+    let router = policy_router(fixture.policies.clone(), Arc::new(InMemoryAuditLogService::new()));
+    let list = |uri: &'static str| {
+        let router = router.clone();
+        async move {
+            let response = router.oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap();
+            let status = response.status();
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+        }
+    };
+
+    let (status, body) = list("/?phase=Source").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 1);
+    assert_eq!(body[0]["id"], source.id.to_string());
+    let (_, body) = list("/?phase=Certification").await;
+    assert_eq!(body[0]["id"], custom.id.to_string());
+    let (_, body) = list("/?phase=Build").await;
+    assert_eq!(body, serde_json::json!([]));
+    let (_, body) = list("/").await;
+    assert_eq!(body.as_array().unwrap().len(), 2);
+
+    let (status, body) = list("/?phase=").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "invalid_phase");
+}
+
+#[tokio::test]
+async fn test_applied_policy_is_only_deleted_with_force() {
+    let fixture = fixture();
+    let component_id = Uuid::new_v4();
+    let release = deployed_release(&fixture.releases, component_id).await;
+    let policy = release_policy();
+    fixture.control_plane.store_policy(policy.clone()).await.unwrap();
+    fixture.policies.assign_policy(component_id, policy.id).await;
+    let response = policy_evaluation_router(fixture.control_plane.clone())
+        .oneshot(Request::post(format!("/{}/apply-policy/{}", release.id, policy.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let applied = fixture.releases.get_release(&release.id).await.unwrap().unwrap();
+    assert_eq!(applied.applied_policies, HashSet::from([policy.id]));
+
+    let audit = Arc::new(InMemoryAuditLogService::new());
+    let router = policy_router(fixture.policies.clone(), audit.clone());
+    let delete_as = |uri: String, user: Option<CurrentUser>| {
+        let router = router.clone();
+        async move {
+            let mut request = Request::delete(uri).body(Body::empty()).unwrap();
+            if let Some(user) = user {
+                request.extensions_mut().insert(user);
+            }
+            let response = router.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap_or_default())
+        }
+    };
+    let user = |roles: &[&str]| CurrentUser { sub: "policy-admin".to_string(), roles: roles.iter().map(|role| role.to_string()).collect() };
+    let delete = |uri: String| delete_as(uri, Some(user(&[POLICY_WRITE])));
+
+    // Deleting a policy requires the policy write role
+    let (status, _) = delete_as(format!("/{}?force=true", policy.id), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = delete_as(format!("/{}?force=true", policy.id), Some(user(&[NAMESPACE_WRITE]))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(fixture.policies.get_policy(&policy.id).await.unwrap().is_some());
+
+    let (status, body) = delete(format!("/{}", policy.id)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "policy_has_dependents");
+    assert_eq!(body["details"]["release_ids"], serde_json::json!([release.id]));
+    assert!(fixture.policies.get_policy(&policy.id).await.unwrap().is_some());
+
+    let (status, _) = delete(format!("/{}?force=true", policy.id)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(fixture.policies.get_policy(&policy.id).await.unwrap().is_none());
+    assert!(fixture.policies.get_policies_for_component(&component_id).await.unwrap().is_empty());
+    let release = fixture.releases.get_release(&release.id).await.unwrap().unwrap();
+    assert!(release.applied_policies.is_empty());
+
+    let (status, body) = delete(format!("/{}", policy.id)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "policy_not_found");
+
+    // Only the successful deletion was recorded, together with the force flag
+    let events = audit.query(AuditFilter::default()).await.unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].action, AuditAction::DeletePolicy);
+    assert_eq!(events[0].actor, "policy-admin");
+    assert_eq!(events[0].resource_id, policy.id);
+    assert_eq!(events[0].metadata["force"], serde_json::json!(true));
+}
+
+#[tokio::test]
+async fn test_unknown_release_and_component_without_policy() {
+    let fixture = fixture();
+
+    let result = fixture.control_plane.apply_policy_to_release(&Uuid::new_v4(), &Uuid::new_v4()).await;
+    assert!(matches!(result, Err(ControlPlaneError::ReleaseNotFound)));
+
+    let release = deployed_release(&fixture.releases, Uuid::new_v4()).await;
+    let result = fixture.control_plane.apply_policy_to_release(&release.id, &release.component_id).await;
+    assert!(matches!(result, Err(ControlPlaneError::NoPolicyFound)));
+}
+
+#[tokio::test]
+async fn test_policy_report_identifies_the_failing_rule() {
+    let fixture = fixture();
+    let component_id = Uuid::new_v4();
+    let release = deployed_release(&fixture.releases, component_id).await;
+    let mut policy = Policy::new("Supply Chain Policy".to_string(), vec!["Build".to_string()]);
+    policy.add_rule(PolicyRule::VulnerabilityThreshold(VulnerabilityLevel::High, 0));
+    policy.add_rule(PolicyRule::RequireReproducibleBuild);
+    fixture.control_plane.store_policy(policy.clone()).await.unwrap();
+    fixture.policies.assign_policy(component_id, policy.id).await;
+    let router = policy_evaluation_router(fixture.control_plane.clone());
+
+    let response = router
+        .clone()
+        .oneshot(Request::get(format!("/{}/policy-report", release.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let report: PolicyEvaluationReport = serde_json::from_slice(&body).unwrap();
+    assert_eq!(report.release_id, release.id);
+    assert!(!report.overall);
+    assert_eq!(report.policy_results.len(), 1);
+    let result = &report.policy_results[0];
+    assert_eq!((result.policy_id, result.policy_name.as_str()), (policy.id, "Supply Chain Policy"));
+    assert!(!result.passed);
+    let outcomes: Vec<(&str, bool)> = result.rule_results.iter().map(|rule| (rule.rule_description.as_str(), rule.passed)).collect();
+    assert_eq!(outcomes, vec![("At most 0 vulnerabilities at or above High", true), ("The build is reproducible", false)]);
+    assert_eq!(result.rule_results[1].details, "Build build-1 is not reproducible");
+
+    let response = router
+        .clone()
+        .oneshot(Request::post(format!("/{}/apply-policy/{}", release.id, Uuid::new_v4())).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_team_ownership_is_checked_against_the_registered_component() {
+    let fixture = fixture();
+    let team = TeamReference { id: Uuid::new_v4(), name: "payments".to_string(), contact_email: "payments@example.com".to_string() };
+    let mut project = Project {
+        id: Uuid::new_v4(),
+        name: "payments-api".to_string(),
+        repository_url: None,
+        owner: None,
+        components: Vec::new(),
+        teams: Vec::new(),
+    };
+    let release = deployed_release(&fixture.releases, project.id).await;
+    let mut policy = Policy::new("Ownership Policy".to_string(), vec!["Runtime".to_string()]);
+    policy.add_rule(PolicyRule::RequireTeamOwnership(team.id));
+    fixture.control_plane.store_policy(policy.clone()).await.unwrap();
+    fixture.policies.assign_policy(project.id, policy.id).await;
+
+    // The component is not registered yet, so nobody owns it.
+    assert!(!fixture.control_plane.evaluate_release(&release.id, None).await.unwrap().overall);
+
+    fixture.components.store_component(SDLCComponent::Project(project.clone())).await.unwrap();
+    assert!(!fixture.control_plane.evaluate_release(&release.id, None).await.unwrap().overall);
+
+    project.teams.push(team);
+    fixture.components.store_component(SDLCComponent::Project(project)).await.unwrap();
+    assert!(fixture.control_plane.evaluate_release(&release.id, None).await.unwrap().overall);
+}
+
+async fn dry_run(router: &axum::Router, release_id: Uuid, request: serde_json::Value) -> (StatusCode, serde_json::Value) {
+    let response = router
+        .clone()
+        .oneshot(
+            Request::post(format!("/{}/policy-dry-run", release_id))
+                .header("content-type", "application/json")
+                .body(Body::from(request.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_policy_dry_run_has_no_side_effects() {
+    let fixture = fixture();
+    let component_id = Uuid::new_v4();
+    let mut release = SDLCRelease::new(component_id, "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    release.start_source_review("reviewer1".to_string()).unwrap();
+    release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, SourceReviewStatus::Approved { approver: "reviewer1".to_string(), approved_at: Utc::now() }).unwrap();
+    release.start_build("builder1".to_string()).unwrap();
+    release.complete_build("build-1".to_string(), false, None).unwrap();
+    release.start_packaging("packager1".to_string()).unwrap();
+    fixture.releases.store_release(release.clone()).await.unwrap();
+    let stored_revision = fixture.releases.get_release(&release.id).await.unwrap().unwrap().revision;
+    let mut policy = Policy::new("Reproducibility".to_string(), vec!["Package".to_string()]);
+    policy.add_rule(PolicyRule::RequireReproducibleBuild);
+    let router = policy_evaluation_router(fixture.control_plane.clone());
+
+    let (status, report) = dry_run(&router, release.id, serde_json::json!({ "policy": policy })).await;
+    assert_eq!(status, StatusCode::OK);
+    let report: PolicyEvaluationReport = serde_json::from_value(report).unwrap();
+    assert!(!report.overall);
+    assert_eq!(report.policy_results[0].rule_results[0].details, "Build build-1 is not reproducible");
+    // Neither the policy nor the release were stored or changed.
+    assert!(fixture.control_plane.get_policy(&policy.id).await.unwrap().is_none());
+    assert_eq!(fixture.releases.get_release(&release.id).await.unwrap().unwrap().revision, stored_revision);
+
+    release.phase_details.as_mut().unwrap().build_details.as_mut().unwrap().reproducible = true;
+    fixture.releases.store_release(release.clone()).await.unwrap();
+    let (status, report) = dry_run(&router, release.id, serde_json::json!({ "policy": policy })).await;
+    assert_eq!(status, StatusCode::OK);
+    let report: PolicyEvaluationReport = serde_json::from_value(report).unwrap();
+    assert!(report.overall);
+
+    // With `apply_to_all` the component's policies count too.
+    let strict = release_policy();
+    fixture.control_plane.store_policy(strict.clone()).await.unwrap();
+    fixture.policies.assign_policy(component_id, strict.id).await;
+    let (_, report) = dry_run(&router, release.id, serde_json::json!({ "policy": policy, "apply_to_all": true })).await;
+    let report: PolicyEvaluationReport = serde_json::from_value(report).unwrap();
+    let evaluated: Vec<Uuid> = report.policy_results.iter().map(|result| result.policy_id).collect();
+    assert_eq!(evaluated, vec![policy.id, strict.id]);
+
+    let empty = Policy::new("Empty".to_string(), Vec::new());
+    let (status, error) = dry_run(&router, release.id, serde_json::json!({ "policy": empty })).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error["code"], "invalid_policy");
+}
+
+#[tokio::test]
+async fn test_compliance_report_summarizes_each_phase() {
+    let fixture = fixture();
+    let project = Project {
+        id: Uuid::new_v4(),
+        name: "payments-api".to_string(),
+        repository_url: None,
+        owner: None,
+        components: Vec::new(),
+        teams: Vec::new(),
+    };
+    fixture.components.store_component(SDLCComponent::Project(project.clone())).await.unwrap();
+    let mut release = SDLCRelease::new(project.id, "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    let attestation = build_attestation("mallory@example.com").for_release(release.id);
+    release.add_phase_attestation("Development".to_string(), attestation.id);
+    fixture.attestations.store_attestation(attestation).await.unwrap();
+    fixture.releases.store_release(release.clone()).await.unwrap();
+    let mut development = Policy::new("Fast Development".to_string(), vec!["Development".to_string()]);
+    development.add_rule(PolicyRule::PhaseCompletedWithin(SDLCPhase::Development, Duration::from_secs(3600)));
+    let mut source = Policy::new("Trusted Signers".to_string(), vec!["Source".to_string()]);
+    source.add_rule(PolicyRule::ApprovedIdentities(vec!["ci@example.com".to_string()]));
+    for policy in [&development, &source] {
+        fixture.control_plane.store_policy(policy.clone()).await.unwrap();
+    }
+    let router = policy_evaluation_router(fixture.control_plane.clone());
+
+    let response = router.clone().oneshot(Request::get(format!("/{}/compliance-report", release.id)).body(Body::empty()).unwrap()).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let report: ComplianceReport = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(report.component_name, "payments-api");
+    assert_eq!(report.version, "1.0.0");
+    let [development_summary, source_summary] = report.phase_summaries.as_slice() else {
+        panic!("expected two phases, got {:?}", report.phase_summaries);
+    };
+    assert_eq!(development_summary.phase, SDLCPhase::Development);
+    assert!(development_summary.attestations_present);
+    assert!(development_summary.phase_started_at.is_some() && development_summary.phase_completed_at.is_some());
+    assert_eq!(development_summary.policies_evaluated.len(), 1);
+    assert_eq!(development_summary.policies_evaluated[0].policy_results[0].policy_id, development.id);
+    assert!(development_summary.passed());
+    assert_eq!(source_summary.phase, SDLCPhase::Source);
+    assert!(!source_summary.attestations_present);
+    assert_eq!(source_summary.phase_started_at, development_summary.phase_completed_at);
+    assert_eq!(source_summary.phase_completed_at, None);
+    assert_eq!(source_summary.policies_evaluated[0].policy_results[0].policy_id, source.id);
+    assert!(!source_summary.passed());
+
+    let response = router
+        .clone()
+        .oneshot(Request::get(format!("/{}/compliance-report", release.id)).header("accept", "text/html,application/xhtml+xml").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/html; charset=utf-8");
+    let html = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+    assert!(html.contains("<table>"), "{html}");
+    assert!(html.contains("<td>Fast Development</td><td>passed</td>"), "{html}");
+    assert!(html.contains("<td>Trusted Signers</td><td>failed</td>"), "{html}");
+
+    let response = router.oneshot(Request::get(format!("/{}/compliance-report", Uuid::new_v4())).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_compliance_drift_lists_newly_failing_phases() {
+    let fixture = fixture();
+    let mut release = deployed_release(&fixture.releases, Uuid::new_v4()).await;
+    let mut policy = Policy::new("No Known Vulnerabilities".to_string(), vec!["Runtime".to_string()]);
+    policy.add_rule(PolicyRule::VulnerabilityThreshold(VulnerabilityLevel::High, 0));
+    fixture.control_plane.store_policy(policy).await.unwrap();
+    let audit = Arc::new(InMemoryAuditLogService::new());
+    let router = compliance_baseline_router(Arc::new(InMemoryComplianceBaselineService::new(fixture.control_plane.clone())), audit.clone());
+
+    let response = router.clone().oneshot(Request::get(format!("/{}/compliance-baseline/drift", release.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = router.clone().oneshot(Request::post(format!("/{}/compliance-baseline", release.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let baseline: ComplianceBaseline = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(baseline.release_id, release.id);
+    assert!(baseline.phase_results.values().all(|passed| *passed));
+    assert_eq!(baseline.phase_results.get("Runtime"), Some(&true));
+    let events = audit.query(AuditFilter { action: Some(AuditAction::CaptureComplianceBaseline), ..Default::default() }).await.unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].actor, "anonymous");
+    assert_eq!(events[0].resource_id, release.id);
+
+    let runtime = release.phase_details.as_mut().unwrap().runtime_details.as_mut().unwrap();
+    runtime.vulnerabilities.push(Vulnerability {
+        id: "CVE-2024-3094".to_string(),
+        cve_id: Some("CVE-2024-3094".to_string()),
+        severity: VulnerabilityLevel::Critical,
+        cvss_score: Some(10.0),
+        cvss_vector: None,
+        affected_package: Some("pkg:deb/debian/xz-utils@5.6.0".to_string()),
+        fix_available: true,
+        description: "Backdoor in xz".to_string(),
+        discovered_at: Utc::now(),
+    });
+    fixture.releases.store_release(release.clone()).await.unwrap();
+
+    let response = router.clone().oneshot(Request::get(format!("/{}/compliance-baseline/drift", release.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let drift: DriftReport = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(drift.new_failures, vec!["Runtime".to_string()]);
+    assert_eq!(drift.baseline_captured_at, baseline.captured_at);
+
+    let response = router.oneshot(Request::post(format!("/{}/compliance-baseline", Uuid::new_v4())).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(audit.query(AuditFilter::default()).await.unwrap().len(), 1);
+}
@@ -0,0 +1,256 @@
+use crate::model::attestation::{ClaimSet, Subject, SubjectType};
+use crate::model::{Attestation, Policy, SDLCRelease};
+use crate::services::attestation::{AttestationService, InMemoryAttestationService};
+use crate::services::audit::InMemoryAuditLogService;
+use crate::services::auth::{CurrentUser, NAMESPACE_READ, NAMESPACE_WRITE};
+use crate::services::controlplane::{namespace_router, NamespaceStore};
+use crate::services::export::{ImportCounts, ImportReport, InMemoryExportService, NamespaceExport};
+use crate::services::namespace::{NamespaceQuota, NamespaceService};
+use crate::services::policy_repository::{InMemoryPolicyRepository, PolicyRepository};
+use crate::services::release_repository::{InMemoryReleaseRepository, ReleaseRepository};
+use std::io::Read;
+use std::sync::Arc;
+use axum::body::{to_bytes, Body, Bytes};
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use flate2::read::GzDecoder;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct Instance {
+    namespaces: Arc<NamespaceStore>,
+    releases: Arc<InMemoryReleaseRepository>,
+    policies: Arc<InMemoryPolicyRepository>,
+    attestations: Arc<InMemoryAttestationService>,
+    router: Router,
+}
+
+fn instance() -> Instance {
+    let namespaces = Arc::new(NamespaceStore::default());
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let policies = Arc::new(InMemoryPolicyRepository::new());
+    let attestations = Arc::new(InMemoryAttestationService::new());
+    let exports = Arc::new(InMemoryExportService::new(namespaces.clone(), releases.clone(), policies.clone(), attestations.clone()));
+    let operator = CurrentUser { sub: "operator".to_string(), roles: vec![NAMESPACE_READ.to_string(), NAMESPACE_WRITE.to_string()] };
+    let router = namespace_router(namespaces.clone(), exports, Arc::new(InMemoryAuditLogService::new()))
+        .layer(axum::Extension(operator));
+    Instance { namespaces, releases, policies, attestations, router }
+}
+
+async fn send(router: &Router, request: Request<Body>) -> (StatusCode, Bytes) {
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    (status, to_bytes(response.into_body(), usize::MAX).await.unwrap())
+}
+
+fn import_request(uri: &str, archive: Bytes) -> Request<Body> {
+    Request::post(uri).body(Body::from(archive)).unwrap()
+}
+
+#[tokio::test]
+async fn test_export_and_import_between_instances() {
+    let source = instance();
+    for path in ["org/payments/api", "org/payments/worker", "org/platform", "other"] {
+        source.namespaces.lock().await.create_namespace(path).await.unwrap();
+    }
+    let release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    source.releases.store_release(release.clone()).await.unwrap();
+    source.policies.store_policy(Policy::new("Release Policy".to_string(), vec!["Deploy".to_string()])).await.unwrap();
+    let subject = Subject { type_: SubjectType::Artifact, name: "app.tar.gz".to_string(), digest: "sha256:abc".to_string() };
+    source.attestations.store_attestation(Attestation::new(subject, ClaimSet::new())).await.unwrap();
+
+    let (status, archive) = send(&source.router, Request::get("/org/export?compressed=true").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    let mut json = String::new();
+    GzDecoder::new(archive.as_ref()).read_to_string(&mut json).unwrap();
+    let export: NamespaceExport = serde_json::from_str(&json).unwrap();
+    assert_eq!(export.namespace_path, "org");
+    assert_eq!(export.namespaces.len(), 2);
+
+    let target = instance();
+    let (status, body) = send(&target.router, import_request("/migrated/import", archive.clone())).await;
+    assert_eq!(status, StatusCode::OK);
+    let created = |created| ImportCounts { created, skipped: 0, failed: 0 };
+    assert_eq!(serde_json::from_slice::<ImportReport>(&body).unwrap(), ImportReport {
+        namespaces: created(4),
+        releases: created(1),
+        policies: created(1),
+        attestations: created(1),
+    });
+    assert_eq!(
+        target.namespaces.lock().await.list_all_namespaces().await,
+        vec!["migrated", "migrated/payments", "migrated/payments/api", "migrated/payments/worker", "migrated/platform"],
+    );
+    assert_eq!(target.releases.get_release(&release.id).await.unwrap().unwrap().version, "1.0.0");
+
+    // Importing again conflicts unless told to skip what exists.
+    let (status, _) = send(&target.router, import_request("/migrated/import", archive.clone())).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, body) = send(&target.router, import_request("/migrated/import?conflict_strategy=skip", archive)).await;
+    assert_eq!(status, StatusCode::OK);
+    let report: ImportReport = serde_json::from_slice(&body).unwrap();
+    assert_eq!(report.namespaces, ImportCounts { created: 0, skipped: 4, failed: 0 });
+    assert_eq!(report.releases, ImportCounts { created: 0, skipped: 1, failed: 0 });
+
+    // Uncompressed archives import too, and overwrite replaces existing items.
+    let (status, json) = send(&source.router, Request::get("/export").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(&target.router, import_request("/import?conflict_strategy=overwrite", json)).await;
+    assert_eq!(status, StatusCode::OK);
+    let report: ImportReport = serde_json::from_slice(&body).unwrap();
+    assert_eq!(report.namespaces, ImportCounts { created: 6, skipped: 0, failed: 0 });
+    assert_eq!(report.releases, created(1));
+}
+
+#[tokio::test]
+async fn test_export_rejects_missing_namespace_and_bad_archive() {
+    let instance = instance();
+    let (status, _) = send(&instance.router, Request::get("/missing/export").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let response = instance.router.clone().oneshot(import_request("/import", Bytes::from_static(b"not json"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+}
+
+#[tokio::test]
+async fn test_namespace_subtree_endpoint() {
+    let instance = instance();
+    for path in ["org/team/service/api", "org/team/worker", "other"] {
+        instance.namespaces.lock().await.create_namespace(path).await.unwrap();
+    }
+    let subtree = |uri: &str| {
+        let router = instance.router.clone();
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        async move {
+            let (status, body) = send(&router, request).await;
+            (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+        }
+    };
+
+    let (status, json) = subtree("/org/subtree?max_depth=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json, serde_json::json!({ "namespaces": ["org/team", "org/team/service", "org/team/worker"], "truncated": true }));
+
+    let (_, json) = subtree("/org/subtree").await;
+    assert_eq!(json["namespaces"].as_array().unwrap().len(), 4);
+    assert_eq!(json["truncated"], false);
+    let (_, json) = subtree("/org/subtree?max_depth=3").await;
+    assert_eq!(json["truncated"], false);
+    let (_, json) = subtree("/subtree?max_depth=1").await;
+    assert_eq!(json["namespaces"], serde_json::json!(["org", "other"]));
+
+    let (status, json) = subtree("/missing/subtree").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(json["code"], "namespace_not_found");
+}
+
+#[tokio::test]
+async fn test_namespace_stats_endpoint() {
+    let instance = instance();
+    for path in ["org/team/service/api", "org/team/worker", "org/other"] {
+        instance.namespaces.lock().await.create_namespace(path).await.unwrap();
+    }
+
+    let (status, body) = send(&instance.router, Request::get("/org/stats").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    let stats: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(stats["path"], "org");
+    assert_eq!(stats["direct_children"], 2);
+    assert_eq!(stats["total_descendants"], 5);
+    assert_eq!(stats["max_depth"], 3);
+    assert!(stats["created_at"].is_string());
+
+    let (status, _) = send(&instance.router, Request::get("/org/missing/stats").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_namespace_search_endpoint() {
+    let instance = instance();
+    for path in ["org/team-a/api", "org/team-b", "other"] {
+        instance.namespaces.lock().await.create_namespace(path).await.unwrap();
+    }
+
+    let (status, body) = send(&instance.router, Request::get("/search?q=team").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    let mut namespaces: Vec<String> = serde_json::from_value(serde_json::from_slice::<serde_json::Value>(&body).unwrap()["namespaces"].clone()).unwrap();
+    namespaces.sort();
+    assert_eq!(namespaces, vec!["org/team-a", "org/team-b"]);
+
+    let (status, _) = send(&instance.router, Request::get("/search").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_namespace_quota_endpoints() {
+    let instance = instance();
+    instance.namespaces.lock().await.create_namespace("org/team-a").await.unwrap();
+
+    let (status, body) = send(&instance.router, Request::get("/org/quota").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(serde_json::from_slice::<NamespaceQuota>(&body).unwrap(), NamespaceQuota::default());
+
+    let quota = serde_json::json!({ "max_children": 2, "max_total_descendants": null, "max_depth": 1 });
+    let request = Request::put("/org/quota")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(quota.to_string()))
+        .unwrap();
+    let (status, _) = send(&instance.router, request).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send(&instance.router, Request::get("/org/quota").body(Body::empty()).unwrap()).await;
+    assert_eq!(serde_json::from_slice::<serde_json::Value>(&body).unwrap(), quota);
+
+    let create = |namespace: &str| {
+        Request::post("/")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::json!({ "namespace": namespace }).to_string()))
+            .unwrap()
+    };
+    let (status, _) = send(&instance.router, create("org/team-b")).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(&instance.router, create("org/team-c")).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error["code"], "namespace_quota_exceeded");
+    assert_eq!(error["details"], serde_json::json!({ "limit": 2, "actual": 3, "quota_type": "max_children" }));
+    let (status, _) = send(&instance.router, create("org/team-a/api")).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let request = Request::put("/org/missing/quota")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(quota.to_string()))
+        .unwrap();
+    let (status, _) = send(&instance.router, request).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_namespace_copy_endpoint() {
+    let instance = instance();
+    instance.namespaces.lock().await.create_namespace("templates/service/ci").await.unwrap();
+    let copy = |uri: &str, destination: &str| {
+        Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::json!({ "destination": destination }).to_string()))
+            .unwrap()
+    };
+
+    let (status, _) = send(&instance.router, copy("/templates/service/copy", "teams/payments")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let namespaces = instance.namespaces.lock().await.list_all_namespaces_recursive("teams", None).await.unwrap();
+    assert_eq!(namespaces, vec!["teams/payments", "teams/payments/ci"]);
+
+    let (status, _) = send(&instance.router, copy("/templates/service/copy", "teams/payments")).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, body) = send(&instance.router, copy("/templates/copy", "templates/service/nested")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(serde_json::from_slice::<serde_json::Value>(&body).unwrap()["code"], "invalid_namespace_path");
+    let (status, _) = send(&instance.router, copy("/templates/missing/copy", "teams/other")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(&instance.router, Request::post("/templates/copy").body(Body::from("{}")).unwrap()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(&instance.router, Request::post("/templates").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+}
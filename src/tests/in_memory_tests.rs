@@ -0,0 +1,32 @@
+use crate::model::attestation::{ClaimSet, Subject, SubjectType};
+use crate::model::{Attestation, Policy, SDLCRelease};
+use crate::services::attestation::{AttestationFilter, AttestationService, InMemoryAttestationService};
+use crate::services::component_repository::{ComponentRepository, InMemoryComponentRepository};
+use crate::services::policy_repository::{InMemoryPolicyRepository, PolicyRepository};
+use crate::services::release_repository::{InMemoryReleaseRepository, ReleaseRepository};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_default_stores_start_empty() {
+    assert!(InMemoryAttestationService::default().list_attestations(AttestationFilter::default()).await.unwrap().is_empty());
+    assert!(InMemoryPolicyRepository::default().list_policies().await.unwrap().is_empty());
+    assert!(InMemoryReleaseRepository::default().list_releases().await.unwrap().is_empty());
+    assert!(InMemoryComponentRepository::default().list_components().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_clones_share_state() {
+    let attestations = InMemoryAttestationService::default();
+    let subject = Subject { type_: SubjectType::Artifact, name: "app.tar.gz".to_string(), digest: "sha256:1234".to_string() };
+    attestations.clone().store_attestation(Attestation::new(subject, ClaimSet::new())).await.unwrap();
+    assert_eq!(attestations.list_attestations(AttestationFilter::default()).await.unwrap().len(), 1);
+
+    let policies = InMemoryPolicyRepository::default();
+    policies.clone().store_policy(Policy::new("Release Policy".to_string(), vec!["Build".to_string()])).await.unwrap();
+    assert_eq!(policies.list_policies().await.unwrap().len(), 1);
+
+    let releases = InMemoryReleaseRepository::default();
+    let clone = releases.clone();
+    releases.store_release(SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap()).await.unwrap();
+    assert_eq!(clone.list_releases().await.unwrap().len(), 1);
+}
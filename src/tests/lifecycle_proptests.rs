@@ -0,0 +1,120 @@
+use crate::model::phase::{RolloutStrategy, SourceReviewStatus};
+use crate::model::{ReleaseState, SDLCRelease};
+use chrono::Utc;
+use crate::services::transitions::TransitionRequest;
+use proptest::prelude::*;
+use uuid::Uuid;
+
+/// Any transition, with arbitrary payloads.
+fn transition() -> impl Strategy<Value = TransitionRequest> {
+    prop_oneof![
+        (any::<String>(), prop::collection::vec(any::<String>(), 0..4))
+            .prop_map(|(started_by, feature_list)| TransitionRequest::StartDevelopment { started_by, feature_list }),
+        Just(TransitionRequest::CompleteDevelopment),
+        any::<String>().prop_map(|started_by| TransitionRequest::StartSourceReview { started_by }),
+        (any::<String>(), any::<bool>()).prop_map(|(commit_hash, approved)| TransitionRequest::CompleteSourceReview {
+            commit_hash,
+            branch: "main".to_string(),
+            pr_url: None,
+            review_status: if approved {
+                SourceReviewStatus::Approved { approver: "reviewer1".to_string(), approved_at: Utc::now() }
+            } else {
+                SourceReviewStatus::ChangesRequested { reviewer: "reviewer1".to_string(), comment: "Needs work".to_string() }
+            },
+        }),
+        any::<String>().prop_map(|started_by| TransitionRequest::StartBuild { started_by }),
+        any::<String>().prop_map(|build_id| TransitionRequest::CompleteBuild { build_id, reproducible: false, build_tool: None }),
+        any::<String>().prop_map(|started_by| TransitionRequest::StartPackaging { started_by }),
+        (any::<String>(), any::<String>())
+            .prop_map(|(artifact_hash, artifact_url)| TransitionRequest::CompletePackaging { artifact_hash, artifact_url, sbom: None }),
+        any::<String>().prop_map(|release_notes| TransitionRequest::Release { release_notes }),
+        (any::<String>(), any::<Option<u8>>()).prop_map(|(environment, canary)| TransitionRequest::StartDeployment {
+            environment,
+            rollout_strategy: canary.map_or(RolloutStrategy::AllAtOnce, |percentage| RolloutStrategy::Canary { percentage, increment_interval_secs: 60 }),
+        }),
+        any::<u8>().prop_map(|percentage| TransitionRequest::UpdateCanaryPercentage { percentage }),
+        Just(TransitionRequest::CompleteDeployment),
+        any::<String>().prop_map(|reason| TransitionRequest::Revoke { reason }),
+        any::<String>().prop_map(|reason| TransitionRequest::Fail { reason }),
+        Just(TransitionRequest::Retry),
+        "[A-Za-z]{1,12}".prop_map(|name| TransitionRequest::EnterCustomState { name }),
+        any::<String>().prop_map(|reason| TransitionRequest::ExitCustomState { reason }),
+    ]
+}
+
+fn transitions() -> impl Strategy<Value = Vec<TransitionRequest>> {
+    prop::collection::vec(transition(), 1..=20)
+}
+
+/// Every transition from Draft to Deployed in order, so random suffixes also reach the late states
+/// that random sequences alone almost never get to.
+fn deployed_release() -> SDLCRelease {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    let lifecycle = [
+        TransitionRequest::StartDevelopment { started_by: "developer1".to_string(), feature_list: vec!["feature x".to_string()] },
+        TransitionRequest::CompleteDevelopment,
+        TransitionRequest::StartSourceReview { started_by: "reviewer1".to_string() },
+        TransitionRequest::CompleteSourceReview {
+            commit_hash: "abcdef123456".to_string(),
+            branch: "main".to_string(),
+            pr_url: None,
+            review_status: SourceReviewStatus::Approved { approver: "reviewer1".to_string(), approved_at: Utc::now() },
+        },
+        TransitionRequest::StartBuild { started_by: "builder1".to_string() },
+        TransitionRequest::CompleteBuild { build_id: "build-1".to_string(), reproducible: false, build_tool: None },
+        TransitionRequest::StartPackaging { started_by: "packager1".to_string() },
+        TransitionRequest::CompletePackaging { artifact_hash: "sha256:1234".to_string(), artifact_url: "https://example.com/app.tar.gz".to_string(), sbom: None },
+        TransitionRequest::Release { release_notes: "First release".to_string() },
+        TransitionRequest::StartDeployment { environment: "production".to_string(), rollout_strategy: RolloutStrategy::AllAtOnce },
+        TransitionRequest::CompleteDeployment,
+    ];
+    for transition in lifecycle {
+        transition.apply(&mut release).unwrap();
+    }
+    release
+}
+
+/// Applies `transitions` in order and checks the invariants that hold after every step.
+fn check_sequence(mut release: SDLCRelease, transitions: Vec<TransitionRequest>) -> Result<(), TestCaseError> {
+    for transition in transitions {
+        let was_revoked = matches!(release.state, ReleaseState::Revoked { .. });
+        let is_revoke = matches!(transition, TransitionRequest::Revoke { .. });
+        let description = format!("{:?}", transition);
+        let applied = transition.apply(&mut release).is_ok();
+
+        if was_revoked {
+            prop_assert!(!applied, "{} succeeded on a revoked release", description);
+        }
+        if applied {
+            prop_assert!(release.validate().is_ok(), "{} left the release in {} / {}", description, release.phase_name(), release.state_name());
+        }
+        if applied && is_revoke {
+            prop_assert_eq!(release.state_name(), "Revoked");
+        }
+    }
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn test_random_transitions_keep_release_valid(transitions in transitions()) {
+        let release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+        check_sequence(release, transitions)?;
+    }
+
+    #[test]
+    fn test_random_transitions_after_deployment_keep_release_valid(transitions in transitions()) {
+        check_sequence(deployed_release(), transitions)?;
+    }
+
+    #[test]
+    fn test_revoked_release_rejects_every_transition(reason in any::<String>(), transition in transition()) {
+        let mut release = deployed_release();
+        TransitionRequest::Revoke { reason }.apply(&mut release).unwrap();
+
+        prop_assert!(transition.apply(&mut release).is_err());
+        prop_assert_eq!(release.state_name(), "Revoked");
+    }
+}
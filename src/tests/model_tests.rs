@@ -1,12 +1,18 @@
 use crate::model::*;
-use attestation::{Attestation, Subject, SubjectType};
+use attestation::{Attestation, ClaimSet, SBOMFormat, Subject, SubjectType};
 use chrono::Utc;
-use phase::{PhaseDetails, RuntimeDetails};
+use phase::{FeatureListError, FeatureListLimits, HealthStatus, PhaseDetails, RolloutStrategy, RuntimeDetails, SourceReviewStatus};
 use policy::{Policy, PolicyRule, Vulnerability, VulnerabilityLevel};
-use sdlc_component::{Project, SDLCComponent, Unmanaged};
+use sdlc_component::{ContainerImage, Project, SDLCComponent, Unmanaged};
 use std::collections::HashMap;
+use crate::services::policy_enforcement::PolicyEnforcementService;
+use crate::services::transitions::{TransitionAction, TransitionError, TransitionRequest};
 use uuid::Uuid;
 
+fn approved_review() -> SourceReviewStatus {
+    SourceReviewStatus::Approved { approver: "reviewer1".to_string(), approved_at: Utc::now() }
+}
+
 #[test]
 fn test_sdlc_release_lifecycle() {
     let project = Project {
@@ -15,20 +21,22 @@ fn test_sdlc_release_lifecycle() {
         repository_url: Some("https://github.com/test/project".to_string()),
         owner: Some("Test Owner".to_string()),
         components: Vec::new(),
+        teams: Vec::new(),
     };
     let component = SDLCComponent::Project(project);
+    assert_eq!(component.name(), "Test Project");
     
     // Initialize a new release
     let mut release = SDLCRelease::new(
-        component,
+        component.id(),
         "1.0.0".to_string(),
         "developer1".to_string()
-    );
+    ).unwrap();
 
     assert_eq!(release.phase, SDLCPhase::Development);
     assert_eq!(release.state, ReleaseState::Draft);
     assert_eq!(release.created_by, "developer1");
-    assert_eq!(release.component_name(), "Test Project");
+    assert_eq!(release.component_id(), &component.id());
 
     // Start development
     release.start_development("developer2".to_string(), vec!["feature x".to_string(), "feature y".to_string()]).unwrap();
@@ -49,7 +57,7 @@ fn test_sdlc_release_lifecycle() {
     assert!(matches!(release.state, ReleaseState::InProgress { .. }));
 
     // Complete source review
-    release.complete_source_review("abcdef123456".to_string()).unwrap();
+    release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, approved_review()).unwrap();
     assert_eq!(release.phase, SDLCPhase::Build);
     assert!(matches!(release.state, ReleaseState::Draft));
     assert_eq!(release.commit_hash, Some("abcdef123456".to_string()));
@@ -60,7 +68,7 @@ fn test_sdlc_release_lifecycle() {
     assert!(matches!(release.state, ReleaseState::InProgress { .. }));
 
     // Complete build
-    release.complete_build("build123".to_string()).unwrap();
+    release.complete_build("build123".to_string(), false, None).unwrap();
     assert_eq!(release.phase, SDLCPhase::Package);
     assert!(matches!(release.state, ReleaseState::Draft));
 
@@ -70,7 +78,7 @@ fn test_sdlc_release_lifecycle() {
     assert!(matches!(release.state, ReleaseState::InProgress { .. }));
 
     // Complete packaging
-    release.complete_packaging("123abc456def".to_string(), "https://example.com/artifacts/project1-1.0.0.tar.gz".to_string()).unwrap();
+    release.complete_packaging("123abc456def".to_string(), "https://example.com/artifacts/project1-1.0.0.tar.gz".to_string(), None).unwrap();
     assert_eq!(release.phase, SDLCPhase::Deploy);
     assert!(matches!(release.state, ReleaseState::Releasable { .. }));
 
@@ -78,9 +86,10 @@ fn test_sdlc_release_lifecycle() {
     release.release("Version 1.0.0 release notes".to_string()).unwrap();
     assert_eq!(release.phase, SDLCPhase::Deploy);
     assert!(matches!(release.state, ReleaseState::Released { .. }));
+    assert_eq!(release.release_notes.as_deref(), Some("Version 1.0.0 release notes"));
 
     // Start deployment
-    release.start_deployment("production".to_string()).unwrap();
+    release.start_deployment("production".to_string(), RolloutStrategy::AllAtOnce).unwrap();
     assert_eq!(release.phase, SDLCPhase::Deploy);
     assert!(matches!(release.state, ReleaseState::InProgress { .. }));
 
@@ -93,6 +102,23 @@ fn test_sdlc_release_lifecycle() {
     release.revoke("Critical bug found".to_string()).unwrap();
     assert_eq!(release.phase, SDLCPhase::Runtime);
     assert!(matches!(release.state, ReleaseState::Revoked { .. }));
+    // The notes outlive the Released state they were published in.
+    assert_eq!(release.release_notes.as_deref(), Some("Version 1.0.0 release notes"));
+}
+
+#[test]
+fn test_release_notes_can_be_written_before_release() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    assert_eq!(release.release_notes, None);
+    release.set_release_notes("Draft notes".to_string());
+    assert_eq!(release.release_notes.as_deref(), Some("Draft notes"));
+    assert!(release.transition_history.is_empty());
+
+    // Releases stored before the field existed load without notes.
+    let mut json = serde_json::to_value(&release).unwrap();
+    json.as_object_mut().unwrap().remove("release_notes");
+    let loaded: SDLCRelease = serde_json::from_value(json).unwrap();
+    assert_eq!(loaded.release_notes, None);
 }
 
 #[test]
@@ -105,18 +131,19 @@ fn test_sdlc_release_lifecycle_unmanaged() {
         metadata: HashMap::new(),
     };
     let component = SDLCComponent::Unmanaged(unmanaged);
+    assert_eq!(component.name(), "Unmanaged Component");
     
     // Initialize a new release
     let mut release = SDLCRelease::new(
-        component,
+        component.id(),
         "1.0.0".to_string(),
         "integrator1".to_string()
-    );
+    ).unwrap();
 
     assert_eq!(release.phase, SDLCPhase::Development);
     assert_eq!(release.state, ReleaseState::Draft);
     assert_eq!(release.created_by, "integrator1");
-    assert_eq!(release.component_name(), "Unmanaged Component");
+    assert_eq!(release.component_id(), &component.id());
 
     // For unmanaged components, we might skip some phases or handle them differently
     // Here's an example of a simplified lifecycle:
@@ -137,7 +164,7 @@ fn test_sdlc_release_lifecycle_unmanaged() {
     assert!(matches!(release.state, ReleaseState::InProgress { .. }));
 
     // Complete source review
-    release.complete_source_review("verify123".to_string()).unwrap();
+    release.complete_source_review("verify123".to_string(), "main".to_string(), None, approved_review()).unwrap();
     assert_eq!(release.phase, SDLCPhase::Build);
     assert!(matches!(release.state, ReleaseState::Draft));
 
@@ -152,7 +179,7 @@ fn test_sdlc_release_lifecycle_unmanaged() {
     assert!(matches!(release.state, ReleaseState::Released { .. }));
 
     // Start deployment
-    release.start_deployment("production".to_string()).unwrap();
+    release.start_deployment("production".to_string(), RolloutStrategy::AllAtOnce).unwrap();
     assert_eq!(release.phase, SDLCPhase::Deploy);
     assert!(matches!(release.state, ReleaseState::InProgress { .. }));
 
@@ -162,6 +189,396 @@ fn test_sdlc_release_lifecycle_unmanaged() {
     assert!(matches!(release.state, ReleaseState::Deployed { .. }));
 }
 
+#[test]
+fn test_sdlc_release_lifecycle_container() {
+    let base_image = SDLCComponent::Container(ContainerImage {
+        id: Uuid::new_v4(),
+        name: "Distroless Base".to_string(),
+        registry: "gcr.io".to_string(),
+        repository: "distroless/static".to_string(),
+        tag: Some("nonroot".to_string()),
+        digest: None,
+        base_image: None,
+        metadata: HashMap::new(),
+    });
+    let component = SDLCComponent::Container(ContainerImage {
+        id: Uuid::new_v4(),
+        name: "Payments API Image".to_string(),
+        registry: "ghcr.io".to_string(),
+        repository: "example/payments-api".to_string(),
+        tag: Some("1.0.0".to_string()),
+        digest: Some("sha256:1234567890abcdef".to_string()),
+        base_image: Some(Box::new(base_image)),
+        metadata: HashMap::from([("os".to_string(), "linux".to_string())]),
+    });
+    assert_eq!(component.name(), "Payments API Image");
+    if let SDLCComponent::Container(image) = &component {
+        assert_eq!(image.base_image.as_ref().unwrap().name(), "Distroless Base");
+    }
+
+    let mut release = SDLCRelease::new(
+        component.id(),
+        "1.0.0".to_string(),
+        "developer1".to_string()
+    ).unwrap();
+    assert_eq!(release.component_id(), &component.id());
+
+    release.start_development("developer1".to_string(), vec!["distroless base".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    release.start_source_review("reviewer1".to_string()).unwrap();
+    release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, approved_review()).unwrap();
+    release.start_build("builder1".to_string()).unwrap();
+    release.complete_build("build123".to_string(), false, None).unwrap();
+    release.start_packaging("packager1".to_string()).unwrap();
+    release.complete_packaging("sha256:1234567890abcdef".to_string(), "ghcr.io/example/payments-api:1.0.0".to_string(), None).unwrap();
+    assert_eq!(release.phase, SDLCPhase::Deploy);
+    assert!(matches!(release.state, ReleaseState::Releasable { .. }));
+
+    release.release("Payments API image 1.0.0".to_string()).unwrap();
+    release.start_deployment("production".to_string(), RolloutStrategy::AllAtOnce).unwrap();
+    release.complete_deployment().unwrap();
+    assert_eq!(release.phase, SDLCPhase::Runtime);
+    assert!(matches!(release.state, ReleaseState::Deployed { .. }));
+    assert!(release.validate().is_ok());
+
+    let json = serde_json::to_string(&component).unwrap();
+    let decoded: SDLCComponent = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.name(), "Payments API Image");
+}
+
+#[test]
+fn test_custom_phase_between_build_and_package() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    release.start_source_review("reviewer1".to_string()).unwrap();
+    release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, approved_review()).unwrap();
+    release.start_build("builder1".to_string()).unwrap();
+    release.complete_build("build123".to_string(), false, None).unwrap();
+    assert_eq!(release.phase, SDLCPhase::Package);
+
+    release.start_custom_phase("SecurityAudit".to_string(), "auditor1".to_string()).unwrap();
+    assert_eq!(release.phase, SDLCPhase::Custom("SecurityAudit".to_string()));
+    assert!(matches!(release.state, ReleaseState::InProgress { .. }));
+    assert!(release.validate().is_ok());
+    let audit = release.phase_details.as_ref().unwrap().custom_details["SecurityAudit"].clone().unwrap();
+    assert_eq!(audit["started_by"], "auditor1");
+
+    // A custom phase cannot skip ahead to deployment.
+    assert!(release.complete_custom_phase(SDLCPhase::Deploy).is_err());
+
+    release.complete_custom_phase(SDLCPhase::Package).unwrap();
+    assert_eq!(release.phase, SDLCPhase::Package);
+    assert!(matches!(release.state, ReleaseState::Draft));
+    assert!(release.validate().is_ok());
+    assert!(release.validate_phase_completeness().is_ok());
+    let audit = release.phase_details.as_ref().unwrap().custom_details["SecurityAudit"].clone().unwrap();
+    assert!(audit.get("completed_at").is_some());
+
+    release.start_packaging("packager1".to_string()).unwrap();
+    release.complete_packaging("123abc456def".to_string(), "https://example.com/artifacts/project1-1.0.0.tar.gz".to_string(), None).unwrap();
+    assert_eq!(release.phase, SDLCPhase::Deploy);
+
+    assert!(release.complete_custom_phase(SDLCPhase::Package).is_err());
+    assert!(release.start_custom_phase("SecurityAudit".to_string(), "auditor1".to_string()).is_err());
+}
+
+#[test]
+fn test_custom_state_blocks_transitions_until_exited() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    assert!(release.enter_custom_state(" ".to_string()).is_err());
+
+    release.enter_custom_state("AwaitingLegalSignOff".to_string()).unwrap();
+    assert_eq!(release.state, ReleaseState::Custom("AwaitingLegalSignOff".to_string()));
+    assert_eq!(release.phase, SDLCPhase::Development);
+    assert!(release.validate().is_ok());
+
+    // Advancing the phase is blocked while the release is parked.
+    let err = TransitionRequest::CompleteDevelopment.apply(&mut release).unwrap_err();
+    assert!(matches!(err, TransitionError::InvalidTransition(message) if message.contains("AwaitingLegalSignOff")));
+    assert!(release.complete_development().is_err());
+    assert_eq!(release.phase, SDLCPhase::Development);
+
+    let event = TransitionRequest::ExitCustomState { reason: "Legal signed off".to_string() }.apply(&mut release).unwrap();
+    assert_eq!(event.from_state, "AwaitingLegalSignOff");
+    assert_eq!(event.to_state, "Draft");
+    assert_eq!(release.phase, SDLCPhase::Development);
+    assert!(release.exit_custom_state("Again".to_string()).is_err());
+
+    let custom: Vec<_> = release.transition_history.iter().filter(|record| record.action.is_some()).collect();
+    assert_eq!(custom.len(), 2);
+    assert_eq!(custom[0].action, Some(TransitionAction::EnterCustomState));
+    assert_eq!(custom[0].from_state, "InProgress");
+    assert_eq!(custom[1].action, Some(TransitionAction::ExitCustomState));
+    assert_eq!(custom[1].reason.as_deref(), Some("Legal signed off"));
+
+    // The phase starts over from Draft.
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    assert_eq!(release.phase, SDLCPhase::Source);
+    assert!(release.validate().is_ok());
+}
+
+#[test]
+fn test_custom_state_of_deployed_release() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    release.start_source_review("reviewer1".to_string()).unwrap();
+    release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, approved_review()).unwrap();
+    release.start_build("builder1".to_string()).unwrap();
+    release.complete_build("build123".to_string(), false, None).unwrap();
+    release.start_packaging("packager1".to_string()).unwrap();
+    release.complete_packaging("123abc456def".to_string(), "https://example.com/artifacts/project1-1.0.0.tar.gz".to_string(), None).unwrap();
+    release.release("Version 1.0.0 release notes".to_string()).unwrap();
+    release.start_deployment("production".to_string(), RolloutStrategy::AllAtOnce).unwrap();
+    release.complete_deployment().unwrap();
+
+    release.enter_custom_state("UnderInvestigation".to_string()).unwrap();
+    assert!(release.revoke("Compromised".to_string()).is_err());
+    // Runtime has no draft, so the release goes back to being deployed.
+    release.exit_custom_state("Nothing found".to_string()).unwrap();
+    assert!(matches!(release.state, ReleaseState::Deployed { .. }));
+    assert!(release.validate().is_ok());
+
+    release.revoke("Compromised".to_string()).unwrap();
+    assert!(release.enter_custom_state("UnderInvestigation".to_string()).is_err());
+}
+
+#[test]
+fn test_suspend_and_resume_deployed_release() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    assert!(release.suspend("Incident".to_string(), "oncall".to_string()).is_err());
+
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    release.start_source_review("reviewer1".to_string()).unwrap();
+    release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, approved_review()).unwrap();
+    release.start_build("builder1".to_string()).unwrap();
+    release.complete_build("build123".to_string(), false, None).unwrap();
+    release.start_packaging("packager1".to_string()).unwrap();
+    release.complete_packaging("123abc456def".to_string(), "https://example.com/artifacts/project1-1.0.0.tar.gz".to_string(), None).unwrap();
+    release.release("Version 1.0.0 release notes".to_string()).unwrap();
+    release.start_deployment("production".to_string(), RolloutStrategy::AllAtOnce).unwrap();
+    release.complete_deployment().unwrap();
+    let deployed = release.state.clone();
+    let history_len = release.transition_history.len();
+    assert!(release.resume("oncall".to_string()).is_err());
+
+    release.suspend("Incident INC-42".to_string(), "oncall".to_string()).unwrap();
+    assert_eq!(release.phase, SDLCPhase::Runtime);
+    assert!(matches!(&release.state, ReleaseState::Suspended { reason, suspended_by, .. }
+        if reason == "Incident INC-42" && suspended_by == "oncall"));
+    assert!(release.validate().is_ok());
+
+    release.resume("incident-lead".to_string()).unwrap();
+    assert_eq!(release.state, deployed);
+    assert!(release.validate().is_ok());
+
+    let records = &release.transition_history[history_len..];
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].from_state, "Deployed");
+    assert_eq!(records[0].to_state.name(), "Suspended");
+    assert_eq!(records[0].reason.as_deref(), Some("Incident INC-42"));
+    assert_eq!(records[0].changed_by.as_deref(), Some("oncall"));
+    assert_eq!(records[1].from_state, "Suspended");
+    assert_eq!(records[1].to_state, deployed);
+    assert_eq!(records[1].changed_by.as_deref(), Some("incident-lead"));
+}
+
+#[test]
+fn test_failed_build_is_retried() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    release.start_source_review("reviewer1".to_string()).unwrap();
+    release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, approved_review()).unwrap();
+    release.start_build("builder1".to_string()).unwrap();
+
+    release.fail("Compiler crashed".to_string()).unwrap();
+    assert!(matches!(&release.state, ReleaseState::Failed { reason, phase, .. }
+        if reason == "Compiler crashed" && *phase == SDLCPhase::Build));
+    assert!(release.validate().is_ok());
+    // Completing the build needs an explicit retry first.
+    assert!(release.complete_build("build123".to_string(), false, None).is_err());
+
+    release.retry().unwrap();
+    assert_eq!(release.phase, SDLCPhase::Build);
+    assert_eq!(release.state, ReleaseState::Draft);
+    assert!(release.phase_details.as_ref().unwrap().build_details.is_none());
+
+    release.start_build("builder1".to_string()).unwrap();
+    release.complete_build("build124".to_string(), false, None).unwrap();
+    assert_eq!(release.phase, SDLCPhase::Package);
+    assert_eq!(release.phase_details.as_ref().unwrap().build_details.as_ref().unwrap().build_id, "build124");
+}
+
+#[test]
+fn test_deployment_failures_are_recorded_in_history() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    release.start_source_review("reviewer1".to_string()).unwrap();
+    release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, approved_review()).unwrap();
+    release.start_build("builder1".to_string()).unwrap();
+    release.complete_build("build123".to_string(), false, None).unwrap();
+    release.start_packaging("packager1".to_string()).unwrap();
+    release.complete_packaging("123abc456def".to_string(), "https://example.com/artifacts/project1-1.0.0.tar.gz".to_string(), None).unwrap();
+    release.release("Version 1.0.0 release notes".to_string()).unwrap();
+
+    for attempt in 1..=3 {
+        release.start_deployment("production".to_string(), RolloutStrategy::AllAtOnce).unwrap();
+        release.fail(format!("Health check failed on attempt {}", attempt)).unwrap();
+        assert!(release.validate().is_ok());
+        release.retry().unwrap();
+        assert!(release.validate().is_ok());
+        assert!(release.phase_details.as_ref().unwrap().deploy_details.is_none());
+    }
+    release.start_deployment("production".to_string(), RolloutStrategy::AllAtOnce).unwrap();
+    release.complete_deployment().unwrap();
+    assert!(matches!(release.state, ReleaseState::Deployed { .. }));
+
+    let failures: Vec<&str> = release.transition_history.iter()
+        .filter_map(|record| match &record.to_state {
+            ReleaseState::Failed { reason, phase, .. } if *phase == SDLCPhase::Deploy => Some(reason.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(failures, [
+        "Health check failed on attempt 1",
+        "Health check failed on attempt 2",
+        "Health check failed on attempt 3",
+    ]);
+}
+
+#[test]
+fn test_retry_requires_failed_state() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    assert!(release.retry().is_err());
+    // Only work in progress can fail.
+    assert!(release.fail("Nothing started".to_string()).is_err());
+
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    assert!(release.retry().is_err());
+    assert!(matches!(release.state, ReleaseState::InProgress { .. }));
+}
+
+#[test]
+fn test_release_requires_every_approval() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    assert!(release.request_approval("packager1".to_string(), vec!["alice".to_string()]).is_err());
+
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    release.start_source_review("reviewer1".to_string()).unwrap();
+    release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, approved_review()).unwrap();
+    release.start_build("builder1".to_string()).unwrap();
+    release.complete_build("build123".to_string(), false, None).unwrap();
+    release.start_packaging("packager1".to_string()).unwrap();
+    release.complete_packaging("123abc456def".to_string(), "https://example.com/artifacts/project1-1.0.0.tar.gz".to_string(), None).unwrap();
+    assert!(release.request_approval("packager1".to_string(), Vec::new()).is_err());
+
+    release.request_approval("packager1".to_string(), vec!["alice".to_string(), "bob".to_string()]).unwrap();
+    assert!(matches!(release.state, ReleaseState::PolicyCheckPending { .. }));
+    assert!(release.validate().is_ok());
+    assert!(!release.check_approval_complete());
+    assert!(release.release("Version 1.0.0 release notes".to_string()).is_err());
+
+    assert!(release.record_approval("mallory".to_string(), None).is_err());
+    release.record_approval("bob".to_string(), Some("LGTM".to_string())).unwrap();
+    assert!(release.record_approval("bob".to_string(), None).is_err());
+    assert!(matches!(release.state, ReleaseState::PolicyCheckPending { .. }));
+    let pending = release.pending_approval.as_ref().unwrap();
+    assert_eq!(pending.obtained_approvals.len(), 1);
+    assert_eq!(pending.obtained_approvals[0].comment.as_deref(), Some("LGTM"));
+
+    release.record_approval("alice".to_string(), None).unwrap();
+    assert!(matches!(&release.state, ReleaseState::Releasable { approved_by, .. } if approved_by == "bob, alice"));
+    assert!(release.pending_approval.is_none());
+    assert!(release.record_approval("alice".to_string(), None).is_err());
+    release.release("Version 1.0.0 release notes".to_string()).unwrap();
+}
+
+#[test]
+fn test_source_review_changes_requested_then_approved() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    let review_status = |release: &SDLCRelease| release.phase_details.as_ref().unwrap().source_details.as_ref().unwrap().review_status.clone();
+
+    release.start_source_review("reviewer1".to_string()).unwrap();
+    assert!(matches!(review_status(&release), SourceReviewStatus::InReview { reviewer, .. } if reviewer == "reviewer1"));
+    assert!(release.complete_source_review("abcdef".to_string(), "feature-x".to_string(), None, review_status(&release)).is_err());
+
+    let changes_requested = SourceReviewStatus::ChangesRequested { reviewer: "reviewer1".to_string(), comment: "Missing tests".to_string() };
+    release.complete_source_review("abcdef".to_string(), "feature-x".to_string(), Some("https://example.com/pr/1".to_string()), changes_requested.clone()).unwrap();
+    assert_eq!(release.phase, SDLCPhase::Source);
+    assert_eq!(release.state, ReleaseState::Draft);
+    assert_eq!(release.commit_hash, None);
+    assert_eq!(review_status(&release), changes_requested);
+
+    release.start_source_review("reviewer2".to_string()).unwrap();
+    assert!(matches!(review_status(&release), SourceReviewStatus::InReview { reviewer, .. } if reviewer == "reviewer2"));
+    let source = release.phase_details.as_ref().unwrap().source_details.clone().unwrap();
+    assert_eq!(source.branch, "feature-x");
+    assert_eq!(source.pr_url.as_deref(), Some("https://example.com/pr/1"));
+
+    let approved = SourceReviewStatus::Approved { approver: "reviewer2".to_string(), approved_at: Utc::now() };
+    release.complete_source_review("123456".to_string(), "feature-x".to_string(), Some("https://example.com/pr/1".to_string()), approved.clone()).unwrap();
+    assert_eq!(release.phase, SDLCPhase::Build);
+    assert_eq!(release.commit_hash.as_deref(), Some("123456"));
+    assert_eq!(review_status(&release), approved);
+    assert!(release.validate_phase_completeness().is_ok());
+}
+
+#[test]
+fn test_canary_deployment_increments_to_full_traffic() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    release.start_source_review("reviewer1".to_string()).unwrap();
+    release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, approved_review()).unwrap();
+    release.start_build("builder1".to_string()).unwrap();
+    release.complete_build("build123".to_string(), false, None).unwrap();
+    release.start_packaging("packager1".to_string()).unwrap();
+    release.complete_packaging("123abc456def".to_string(), "https://example.com/artifacts/project1-1.0.0.tar.gz".to_string(), None).unwrap();
+    release.release("Version 1.0.0 release notes".to_string()).unwrap();
+    assert!(release.update_canary_percentage(20).is_err());
+
+    let canary = RolloutStrategy::Canary { percentage: 10, increment_interval_secs: 300 };
+    release.start_deployment("production".to_string(), canary).unwrap();
+    assert!(release.complete_deployment().is_err());
+    assert!(release.update_canary_percentage(10).is_err());
+    assert!(release.update_canary_percentage(101).is_err());
+
+    for percentage in [25, 50, 75, 100] {
+        release.update_canary_percentage(percentage).unwrap();
+        assert_eq!(release.state, ReleaseState::PartiallyDeployed { percentage, environment: "production".to_string() });
+        assert!(release.validate().is_ok());
+        assert!(release.validate_phase_completeness().is_ok());
+        if percentage < 100 {
+            assert!(release.complete_deployment().is_err());
+        }
+    }
+    let deploy_details = release.phase_details.as_ref().unwrap().deploy_details.clone().unwrap();
+    assert_eq!(deploy_details.rollout_strategy, RolloutStrategy::Canary { percentage: 100, increment_interval_secs: 300 });
+
+    release.complete_deployment().unwrap();
+    assert!(matches!(&release.state, ReleaseState::Deployed { environment, .. } if environment == "production"));
+    assert!(release.update_canary_percentage(100).is_err());
+}
+
+#[test]
+fn test_canary_percentage_requires_canary_strategy() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.phase = SDLCPhase::Deploy;
+    release.state = ReleaseState::Released { release_notes: "Notes".to_string(), release_time: Utc::now() };
+    release.start_deployment("staging".to_string(), RolloutStrategy::BlueGreen { active_slot: "blue".to_string() }).unwrap();
+    assert!(release.update_canary_percentage(50).is_err());
+    assert!(matches!(release.state, ReleaseState::InProgress { .. }));
+}
+
 #[test]
 fn test_policy_checks() {
     // Create a policy
@@ -181,7 +598,7 @@ fn test_policy_checks() {
             name: "app-1.0.0.jar".to_string(),
             digest: "sha256:1234567890abcdef".to_string(),
         },
-        HashMap::new(),
+        ClaimSet::new(),
     );
     attestation.add_signature("trusted_developer".to_string(), "signature123".to_string());
 
@@ -203,10 +620,10 @@ fn test_vulnerability_detection() {
     let component = SDLCComponent::Unmanaged(unmanaged);
     
     let mut release = SDLCRelease::new(
-        component,
+        component.id(),
         "1.0.0".to_string(),
         "integrator1".to_string()
-    );
+    ).unwrap();
 
     // Set the release to Runtime phase and Deployed state
     release.phase = SDLCPhase::Runtime;
@@ -222,7 +639,12 @@ fn test_vulnerability_detection() {
     // Simulate detection of a new vulnerability
     let vulnerability = Vulnerability {
         id: "CVE-2023-12345".to_string(),
+        cve_id: Some("CVE-2023-12345".to_string()),
         severity: VulnerabilityLevel::High,
+        cvss_score: Some(8.1),
+        cvss_vector: Some("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:H/I:H/A:H".to_string()),
+        affected_package: Some("pkg:generic/unmanaged@1.0.0".to_string()),
+        fix_available: false,
         description: "Critical vulnerability detected".to_string(),
         discovered_at: Utc::now(),
     };
@@ -234,22 +656,130 @@ fn test_vulnerability_detection() {
     assert!(!check_runtime_policy(&policy, &updated_release), "Release should fail policy after vulnerability detection");
 }
 
+#[test]
+fn test_vulnerability_levels_are_totally_ordered() {
+    let mut levels = vec![VulnerabilityLevel::Critical, VulnerabilityLevel::Low, VulnerabilityLevel::High, VulnerabilityLevel::Medium];
+    levels.sort();
+    assert_eq!(levels, vec![VulnerabilityLevel::Low, VulnerabilityLevel::Medium, VulnerabilityLevel::High, VulnerabilityLevel::Critical]);
+    assert_eq!(levels.iter().max(), Some(&VulnerabilityLevel::Critical));
+
+    assert_eq!(VulnerabilityLevel::from_cvss_score(0.0), VulnerabilityLevel::Low);
+    assert_eq!(VulnerabilityLevel::from_cvss_score(3.9), VulnerabilityLevel::Low);
+    assert_eq!(VulnerabilityLevel::from_cvss_score(4.0), VulnerabilityLevel::Medium);
+    assert_eq!(VulnerabilityLevel::from_cvss_score(6.9), VulnerabilityLevel::Medium);
+    assert_eq!(VulnerabilityLevel::from_cvss_score(7.0), VulnerabilityLevel::High);
+    assert_eq!(VulnerabilityLevel::from_cvss_score(8.9), VulnerabilityLevel::High);
+    assert_eq!(VulnerabilityLevel::from_cvss_score(9.0), VulnerabilityLevel::Critical);
+    assert_eq!(VulnerabilityLevel::from_cvss_score(10.0), VulnerabilityLevel::Critical);
+}
+
+#[test]
+fn test_vulnerabilities_above_level() {
+    let vulnerability = |id: &str, score: f32| Vulnerability {
+        id: id.to_string(),
+        cve_id: None,
+        severity: VulnerabilityLevel::from_cvss_score(score),
+        cvss_score: Some(score),
+        cvss_vector: None,
+        affected_package: None,
+        fix_available: true,
+        description: String::new(),
+        discovered_at: Utc::now(),
+    };
+    let runtime_details = RuntimeDetails {
+        runtime_id: "runtime-1".to_string(),
+        last_heartbeat: Utc::now(),
+        vulnerabilities: vec![vulnerability("low", 2.0), vulnerability("high", 7.5), vulnerability("critical", 9.8)],
+        health_status: HealthStatus::Unknown,
+        last_successful_health_check: None,
+    };
+
+    let ids = |level| runtime_details.vulnerabilities_above(level).iter().map(|v| v.id.as_str()).collect::<Vec<_>>();
+    assert_eq!(ids(VulnerabilityLevel::High), vec!["high", "critical"]);
+    assert_eq!(ids(VulnerabilityLevel::Critical), vec!["critical"]);
+    assert_eq!(ids(VulnerabilityLevel::Low).len(), 3);
+}
+
+#[test]
+fn test_vulnerability_deserializes_without_cvss_fields() {
+    let vulnerability: Vulnerability = serde_json::from_value(serde_json::json!({
+        "id": "GHSA-xxxx-yyyy-zzzz",
+        "severity": "Medium",
+        "description": "Older payload",
+        "discovered_at": "2024-01-01T00:00:00Z",
+    }))
+    .unwrap();
+    assert_eq!(vulnerability.cvss_score, None);
+    assert!(!vulnerability.fix_available);
+}
+
+#[test]
+fn test_subject_canonical_digest() {
+    let subject = |type_| Subject { type_, name: "subject".to_string(), digest: "sha256:subject".to_string() };
+
+    assert_eq!(subject(SubjectType::Artifact).canonical_digest(), "sha256:subject");
+    let container = subject(SubjectType::Container {
+        registry: "ghcr.io".to_string(),
+        repository: "sdlcsec/app".to_string(),
+        digest: "sha256:image".to_string(),
+    });
+    assert_eq!(container.canonical_digest(), "sha256:image");
+    let tag = subject(SubjectType::GitTag {
+        repository_url: "https://github.com/sdlcsec/app".to_string(),
+        tag_name: "v1.0.0".to_string(),
+        commit_hash: "abc123".to_string(),
+    });
+    assert_eq!(tag.canonical_digest(), "abc123");
+    let sbom = subject(SubjectType::SBOMDocument {
+        format: SBOMFormat::CycloneDX15,
+        document_url: "https://example.com/sbom.json".to_string(),
+    });
+    assert_eq!(sbom.canonical_digest(), "sha256:subject");
+
+    let json = serde_json::to_value(&sbom).unwrap();
+    assert_eq!(json["type_"]["SBOMDocument"]["format"], "CycloneDX15");
+    // Formats stored before they were versioned still load.
+    assert_eq!(serde_json::from_str::<SBOMFormat>("\"CycloneDX\"").unwrap(), SBOMFormat::CycloneDX14);
+}
+
 // Helper functions (these would typically be in a separate module)
 
+#[test]
+fn test_release_version_must_be_semver() {
+    let component_id = Uuid::new_v4();
+    let release = SDLCRelease::new(component_id, "not-a-version".to_string(), "developer1".to_string());
+    assert_eq!(release.unwrap_err(), ValidationError::InvalidSemver("not-a-version".to_string()));
+    for invalid in ["", "1.0", "v1.0.0"] {
+        assert!(SDLCRelease::new(component_id, invalid.to_string(), "developer1".to_string()).is_err(), "{invalid:?}");
+    }
+    assert!(SDLCRelease::new(component_id, "1.0.0-rc.1+build.5".to_string(), "developer1".to_string()).is_ok());
+
+    let unchecked = SDLCRelease::new_unchecked(component_id, "foo".to_string(), "developer1".to_string());
+    assert_eq!(unchecked.version, "foo");
+    assert!(unchecked.clone_for_hotfix("foo-hotfix".to_string(), "developer2".to_string()).is_err());
+}
+
+#[test]
+fn test_release_tags() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.add_tag("lts").unwrap();
+    release.add_tag("Hotfix-2").unwrap();
+    release.add_tag("lts").unwrap();
+    assert_eq!(release.list_tags(), vec!["Hotfix-2", "lts"]);
+
+    for invalid in ["", "with space", "under_score", &"a".repeat(65)] {
+        assert_eq!(release.add_tag(invalid), Err(TagError::InvalidTag(invalid.to_string())));
+    }
+    assert!(release.add_tag(&"a".repeat(64)).is_ok());
+
+    release.remove_tag("lts");
+    release.remove_tag("never-added");
+    assert!(!release.has_tag("lts"));
+    assert!(release.has_tag("Hotfix-2"));
+}
+
 fn check_policy(policy: &Policy, attestation: &Attestation) -> bool {
-    policy.rules.iter().all(|rule| {
-        match rule {
-            PolicyRule::ApprovedIdentities(approved) => {
-                attestation.signatures.iter().any(|sig| approved.contains(&sig.signer))
-            },
-            PolicyRule::MaxAge(max_age) => {
-                let age = Utc::now() - attestation.timestamp;
-                age <= chrono::Duration::from_std(*max_age).unwrap_or_else(|_| chrono::Duration::zero())
-            },
-            // Add checks for other rule types as needed
-            _ => true, // Assume other rules pass for now
-        }
-    })
+    PolicyEnforcementService::new().evaluate_attestation(policy, attestation).is_empty()
 }
 
 fn check_runtime_policy(policy: &Policy, release: &SDLCRelease) -> bool {
@@ -258,10 +788,7 @@ fn check_runtime_policy(policy: &Policy, release: &SDLCRelease) -> bool {
             match rule {
                 PolicyRule::VulnerabilityThreshold(level, max_count) => {
                     if let Some(PhaseDetails { runtime_details: Some(runtime_details), .. }) = &release.phase_details {
-                        let count = runtime_details.vulnerabilities.iter()
-                            .filter(|v| v.severity >= *level)
-                            .count();
-                        count <= *max_count as usize
+                        runtime_details.vulnerabilities_above(*level).len() <= *max_count as usize
                     } else {
                         true // No runtime details available, assume it passes
                     }
@@ -275,6 +802,83 @@ fn check_runtime_policy(policy: &Policy, release: &SDLCRelease) -> bool {
     }
 }
 
+#[test]
+fn test_validate_phase_completeness() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    assert!(release.validate_phase_completeness().is_ok());
+
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    assert!(release.validate_phase_completeness().is_ok());
+    // Hand-assembled: stored before empty feature lists were rejected.
+    release.phase_details.as_mut().unwrap().development_details.as_mut().unwrap().feature_list.clear();
+    let errors = release.validate_phase_completeness().unwrap_err();
+    assert_eq!(errors.iter().map(|error| error.field()).collect::<Vec<_>>(), vec!["phase_details.development_details.feature_list"]);
+
+    // Hand-assembled: packaged without a commit, build or usable artifact URL.
+    release.phase = SDLCPhase::Deploy;
+    release.state = ReleaseState::Draft;
+    release.phase_details = Some(PhaseDetails {
+        package_details: Some(phase::PackageDetails { artifact_hash: String::new(), artifact_url: "not a url".to_string(), sbom: None }),
+        ..PhaseDetails::new()
+    });
+    let errors = release.validate_phase_completeness().unwrap_err();
+    assert_eq!(
+        errors.iter().map(|error| error.field()).collect::<Vec<_>>(),
+        vec![
+            "commit_hash",
+            "phase_details.build_details",
+            "phase_details.package_details.artifact_url",
+            "phase_details.package_details.artifact_hash",
+        ]
+    );
+
+    // Every transition fills in what the next phase checks for.
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    release.start_source_review("reviewer1".to_string()).unwrap();
+    release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, approved_review()).unwrap();
+    release.start_build("builder1".to_string()).unwrap();
+    release.complete_build("build-1".to_string(), false, None).unwrap();
+    release.start_packaging("packager1".to_string()).unwrap();
+    release.complete_packaging("sha256:1234".to_string(), "https://example.com/app.tar.gz".to_string(), None).unwrap();
+    release.release("notes".to_string()).unwrap();
+    release.start_deployment("production".to_string(), RolloutStrategy::AllAtOnce).unwrap();
+    assert!(release.validate_phase_completeness().is_ok());
+    release.complete_deployment().unwrap();
+    assert!(release.validate_phase_completeness().is_ok());
+}
+
+#[test]
+fn test_phase_ordering() {
+    assert!(SDLCPhase::Build > SDLCPhase::Source);
+    assert!(SDLCPhase::Development < SDLCPhase::Runtime);
+    let (x, y) = (SDLCPhase::Custom("X".to_string()), SDLCPhase::Custom("Y".to_string()));
+    let x_before_y = x < y;
+    assert!(!x_before_y);
+    assert_eq!(x.partial_cmp(&y), None);
+    assert_eq!(x.partial_cmp(&SDLCPhase::Build), None);
+    assert_eq!(phase::phase_order(&x), None);
+
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    assert!(release.can_advance_to(&SDLCPhase::Source));
+    assert!(!release.can_advance_to(&SDLCPhase::Development));
+    assert!(!release.can_advance_to(&x));
+
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    assert!(release.validate().is_ok());
+
+    // A hand-written history that went back without a rollback.
+    let mut backwards = release.transition_history.last().unwrap().clone();
+    std::mem::swap(&mut backwards.from_phase, &mut backwards.to_phase);
+    release.transition_history.push(backwards.clone());
+    assert!(release.validate().is_err());
+    backwards.action = Some(TransitionAction::Rollback);
+    *release.transition_history.last_mut().unwrap() = backwards;
+    assert!(release.validate().is_ok());
+}
+
 fn update_release_vulnerabilities(mut release: SDLCRelease, vulnerabilities: Vec<Vulnerability>) -> SDLCRelease {
     if let Some(PhaseDetails { runtime_details: Some(runtime_details), .. }) = &mut release.phase_details {
         runtime_details.vulnerabilities.extend(vulnerabilities);
@@ -288,6 +892,8 @@ fn update_release_vulnerabilities(mut release: SDLCRelease, vulnerabilities: Vec
                 runtime_id: Uuid::new_v4().to_string(),
                 last_heartbeat: Utc::now(),
                 vulnerabilities,
+                health_status: HealthStatus::Unknown,
+                last_successful_health_check: None,
             }),
             development_details: None,
             source_details: None,
@@ -295,4 +901,179 @@ fn update_release_vulnerabilities(mut release: SDLCRelease, vulnerabilities: Vec
         });
     }
     release
-}
\ No newline at end of file
+}
+/// Serializes `value`, reads it back and checks that nothing was lost on the way.
+fn assert_round_trips<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> serde_json::Value {
+    let json = serde_json::to_value(value).unwrap();
+    let parsed: T = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), json);
+    json
+}
+
+#[test]
+fn test_attestation_round_trips_through_json() {
+    let subject_types = [
+        SubjectType::Commit,
+        SubjectType::Artifact,
+        SubjectType::Deployment,
+        SubjectType::Container { registry: "ghcr.io".to_string(), repository: "sdlcsec/app".to_string(), digest: "sha256:1234".to_string() },
+        SubjectType::GitTag { repository_url: "https://github.com/sdlcsec/app".to_string(), tag_name: "v1.0.0".to_string(), commit_hash: "abc123".to_string() },
+        SubjectType::SBOMDocument { format: SBOMFormat::CycloneDX15, document_url: "https://example.com/bom.json".to_string() },
+    ];
+    for type_ in subject_types {
+        let subject = Subject { type_, name: "app".to_string(), digest: "sha256:1234".to_string() };
+        let mut attestation = Attestation::new(subject, ClaimSet::from(HashMap::from([
+            ("builder".to_string(), serde_json::json!("ci")),
+            ("scan".to_string(), serde_json::json!({ "passed": true, "high": 0 })),
+        ])));
+        attestation.expiration = Some(Utc::now());
+        attestation.parent_attestations.push(Uuid::new_v4());
+        attestation.add_signature("ci@example.com".to_string(), "c2lnbmF0dXJl".to_string());
+
+        let json = assert_round_trips(&attestation);
+        assert_eq!(json["signatures"][0]["signer"], "ci@example.com");
+    }
+}
+
+#[test]
+fn test_claim_set_accessors_and_json_shape() {
+    let mut claims = ClaimSet::from(HashMap::from([
+        ("level".to_string(), serde_json::json!(3)),
+        ("negative".to_string(), serde_json::json!(-1)),
+    ]));
+    claims.set_string("builder", "ci".to_string());
+    claims.set_bool("hermetic", true);
+
+    assert_eq!(claims.get_string("builder"), Some("ci"));
+    assert_eq!(claims.get_bool("hermetic"), Some(true));
+    assert_eq!(claims.get_u64("level"), Some(3));
+    // Claims of another shape, or missing ones, read as `None`.
+    assert_eq!(claims.get_u64("negative"), None);
+    assert_eq!(claims.get_string("level"), None);
+    assert_eq!(claims.get_bool("missing"), None);
+
+    // Serialized as the bare object, the same as the map it replaced.
+    let json = serde_json::to_value(&claims).unwrap();
+    assert_eq!(json, serde_json::json!({ "level": 3, "negative": -1, "builder": "ci", "hermetic": true }));
+    assert_eq!(serde_json::from_value::<ClaimSet>(json).unwrap(), claims);
+
+    let mut keys: Vec<String> = claims.into_iter().map(|(key, _)| key).collect();
+    keys.sort();
+    assert_eq!(keys, vec!["builder", "hermetic", "level", "negative"]);
+}
+
+#[test]
+fn test_policy_round_trips_through_json() {
+    use std::time::Duration;
+
+    let mut policy = Policy::new("Everything".to_string(), vec!["Deploy".to_string()]);
+    policy.parent_policies.push(Uuid::new_v4());
+    for rule in [
+        PolicyRule::MaxAge(Duration::from_millis(90_500)),
+        PolicyRule::ApprovedIdentities(vec!["ci@example.com".to_string()]),
+        PolicyRule::RequiredClaims(HashMap::from([("builder".to_string(), "ci".to_string())])),
+        PolicyRule::ForbiddenClaims(HashMap::from([("builder".to_string(), "laptop".to_string())])),
+        PolicyRule::VulnerabilityThreshold(VulnerabilityLevel::Critical, 0),
+        PolicyRule::MaxVulnerabilityAge(Duration::from_secs(30 * 24 * 60 * 60)),
+        PolicyRule::BlockedPackages(vec!["pkg:npm/event-stream".to_string()]),
+        PolicyRule::RequiresSBOM(SBOMFormat::Spdx3),
+        PolicyRule::RequireReproducibleBuild,
+        PolicyRule::BlockSuspendedReleases,
+        PolicyRule::RequireApprovedSourceReview,
+        PolicyRule::RequireHealthyRuntime,
+        PolicyRule::RequireTeamOwnership(Uuid::new_v4()),
+        PolicyRule::MinVersion(semver::VersionReq::parse(">=1.2, <2").unwrap()),
+        PolicyRule::RequireReleaseNotes,
+        PolicyRule::PhaseCompletedWithin(SDLCPhase::Source, Duration::from_secs(72 * 60 * 60)),
+        PolicyRule::NoRevokedAttestations,
+        PolicyRule::ActiveForPhases(vec![SDLCPhase::Source, SDLCPhase::Custom("Certification".to_string())]),
+    ] {
+        policy.add_rule(rule);
+    }
+
+    let json = assert_round_trips(&policy);
+    // `std::time::Duration` keeps serde's own `{secs, nanos}` form, which schemas and clients already expect.
+    assert_eq!(json["rules"][0], serde_json::json!({ "MaxAge": { "secs": 90, "nanos": 500_000_000 } }));
+}
+
+#[test]
+fn test_vulnerability_round_trips_through_json() {
+    for severity in [VulnerabilityLevel::Low, VulnerabilityLevel::Medium, VulnerabilityLevel::High, VulnerabilityLevel::Critical] {
+        let vulnerability = Vulnerability {
+            id: "GHSA-xxxx-yyyy-zzzz".to_string(),
+            cve_id: Some("CVE-2024-0001".to_string()),
+            severity,
+            cvss_score: Some(7.5),
+            cvss_vector: Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:N/A:N".to_string()),
+            affected_package: Some("pkg:cargo/openssl@0.10.55".to_string()),
+            fix_available: true,
+            description: "Information disclosure".to_string(),
+            discovered_at: Utc::now(),
+        };
+        assert_round_trips(&vulnerability);
+    }
+}
+
+#[test]
+fn test_policy_ids_are_uuids() {
+    let stored = serde_json::json!({
+        "id": "67E55044-10B1-426F-9247-BB680E5FE0C8",
+        "name": "Legacy",
+        "rules": [],
+        "parent_policies": ["9d2f3a51b8c74a0f9e6d1c2b3a4f5e6d"],
+        "applies_to": ["Build"],
+    });
+    let policy: Policy = serde_json::from_value(stored).unwrap();
+    let json = serde_json::to_value(&policy).unwrap();
+    assert_eq!(json["id"], "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    assert_eq!(json["parent_policies"], serde_json::json!(["9d2f3a51-b8c7-4a0f-9e6d-1c2b3a4f5e6d"]));
+
+    let invalid = serde_json::json!({ "id": "security-baseline", "name": "Legacy", "rules": [], "parent_policies": [], "applies_to": [] });
+    assert!(serde_json::from_value::<Policy>(invalid).is_err());
+}
+
+#[test]
+fn test_start_development_rejects_empty_feature_list() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+
+    let err = release.start_development("developer1".to_string(), Vec::new()).unwrap_err();
+
+    assert!(matches!(err, TransitionError::FeatureListError(FeatureListError::EmptyList)));
+    assert_eq!(release.state, ReleaseState::Draft);
+}
+
+#[test]
+fn test_start_development_rejects_too_many_features() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    let features = (0..=phase::MAX_FEATURE_LIST_SIZE).map(|i| format!("feature {i}")).collect();
+
+    let err = release.start_development("developer1".to_string(), features).unwrap_err();
+
+    assert!(matches!(err, TransitionError::FeatureListError(FeatureListError::TooManyItems { limit: phase::MAX_FEATURE_LIST_SIZE })));
+    assert_eq!(release.state, ReleaseState::Draft);
+}
+
+#[test]
+fn test_start_development_rejects_long_feature_description() {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    // Counted in characters: 500 two-byte characters still fit.
+    let features = vec!["é".repeat(phase::MAX_FEATURE_DESCRIPTION_LEN), "x".repeat(phase::MAX_FEATURE_DESCRIPTION_LEN + 1)];
+
+    let err = release.start_development("developer1".to_string(), features).unwrap_err();
+
+    assert!(matches!(err, TransitionError::FeatureListError(FeatureListError::ItemTooLong { index: 1, limit: phase::MAX_FEATURE_DESCRIPTION_LEN })));
+    assert_eq!(release.state, ReleaseState::Draft);
+}
+
+#[test]
+fn test_start_development_with_custom_limits() {
+    let limits = FeatureListLimits { max_items: 2, max_item_len: 10 };
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    let features = vec!["login".to_string(), "logout".to_string(), "signup".to_string()];
+
+    let err = release.start_development_with_limits("developer1".to_string(), features, limits).unwrap_err();
+    assert!(matches!(err, TransitionError::FeatureListError(FeatureListError::TooManyItems { limit: 2 })));
+
+    release.start_development_with_limits("developer1".to_string(), vec!["login".to_string(), "logout".to_string()], limits).unwrap();
+    assert!(matches!(release.state, ReleaseState::InProgress { .. }));
+}
@@ -0,0 +1,65 @@
+use crate::services::attestation::InMemoryAttestationService;
+use crate::services::audit::InMemoryAuditLogService;
+use crate::services::auth::{CurrentUser, NAMESPACE_READ, NAMESPACE_WRITE};
+use crate::services::controlplane::{namespace_router, NamespaceStore};
+use crate::services::export::InMemoryExportService;
+use crate::services::namespace::{NamespaceEvent, NamespaceEventType};
+use crate::services::policy_repository::InMemoryPolicyRepository;
+use crate::services::release_repository::InMemoryReleaseRepository;
+use std::sync::Arc;
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+/// The namespace router, called by an operator holding both namespace roles.
+fn router() -> Router {
+    let namespaces = Arc::new(NamespaceStore::default());
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let exports = Arc::new(InMemoryExportService::new(
+        namespaces.clone(),
+        releases,
+        Arc::new(InMemoryPolicyRepository::new()),
+        Arc::new(InMemoryAttestationService::new()),
+    ));
+    let operator = CurrentUser { sub: "operator".to_string(), roles: vec![NAMESPACE_READ.to_string(), NAMESPACE_WRITE.to_string()] };
+    namespace_router(namespaces, exports, Arc::new(InMemoryAuditLogService::new())).layer(axum::Extension(operator))
+}
+
+#[tokio::test]
+async fn test_namespace_events_endpoint_streams_changes() {
+    let router = router();
+    let response = router.clone().oneshot(Request::get("/events").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+    let mut stream = response.into_body();
+
+    for path in ["org/payments", "org/platform"] {
+        let request = Request::post("/")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::json!({ "namespace": path }).to_string()))
+            .unwrap();
+        assert_eq!(router.clone().oneshot(request).await.unwrap().status(), StatusCode::OK);
+    }
+
+    let mut created = Vec::new();
+    while created.len() < 3 {
+        let frame = tokio::time::timeout(std::time::Duration::from_millis(100), stream.frame())
+            .await
+            .expect("namespace event was not streamed within 100ms")
+            .unwrap()
+            .unwrap();
+        let frame = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+        assert!(frame.starts_with("event: namespace\n"), "unexpected frame: {}", frame);
+        let data = frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+        let event: NamespaceEvent = serde_json::from_str(data).unwrap();
+        created.push(event.event_type);
+    }
+    // `org` was created implicitly along with `org/payments`, and announced first.
+    assert_eq!(created, vec![
+        NamespaceEventType::Created("org".to_string()),
+        NamespaceEventType::Created("org/payments".to_string()),
+        NamespaceEventType::Created("org/platform".to_string()),
+    ]);
+}
@@ -0,0 +1,96 @@
+use crate::services::controlplane::ControlPlaneAPIDoc;
+use utoipa::openapi::{schema::Schema, OpenApi as OpenApiDocument, RefOr};
+use utoipa::OpenApi;
+
+/// Every `$ref` in `value`, with the `#/components/schemas/` prefix stripped.
+fn schema_refs(value: &serde_json::Value, refs: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(object) => {
+            if let Some(serde_json::Value::String(reference)) = object.get("$ref") {
+                refs.push(reference.trim_start_matches("#/components/schemas/").to_string());
+            }
+            object.values().for_each(|value| schema_refs(value, refs));
+        }
+        serde_json::Value::Array(values) => values.iter().for_each(|value| schema_refs(value, refs)),
+        _ => {}
+    }
+}
+
+#[test]
+fn test_openapi_document_round_trips_through_json() {
+    let openapi = ControlPlaneAPIDoc::openapi();
+    let json = serde_json::to_value(&openapi).unwrap();
+    let parsed: OpenApiDocument = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), json);
+
+    let schemas = &openapi.components.as_ref().unwrap().schemas;
+    for name in ["Policy", "PolicyRule", "VulnerabilityLevel", "Vulnerability", "Attestation", "Subject", "SubjectType", "Signature"] {
+        let schema = schemas.get(name).unwrap_or_else(|| panic!("{name} is not registered"));
+        assert!(matches!(schema, RefOr::T(Schema::Object(_) | Schema::OneOf(_))), "{name} is not an inline object or enum schema");
+    }
+
+    let mut refs = Vec::new();
+    schema_refs(&json, &mut refs);
+    let dangling: Vec<&String> = refs.iter().filter(|name| !schemas.contains_key(name.as_str())).collect();
+    assert!(dangling.is_empty(), "references to unregistered schemas: {dangling:?}");
+}
+
+#[test]
+fn test_durations_are_documented_as_serde_writes_them() {
+    let json = serde_json::to_value(ControlPlaneAPIDoc::openapi()).unwrap();
+    let schemas = &json["components"]["schemas"];
+    assert_eq!(schemas["DurationSchema"]["required"], serde_json::json!(["secs", "nanos"]));
+
+    let rules = schemas["PolicyRule"]["oneOf"].as_array().unwrap();
+    for rule in ["MaxAge", "MaxVulnerabilityAge"] {
+        let variant = rules.iter().find_map(|variant| variant["properties"].get(rule)).unwrap();
+        assert_eq!(variant["$ref"], "#/components/schemas/DurationSchema", "{rule}");
+    }
+}
+
+#[test]
+fn test_every_namespace_endpoint_is_documented() {
+    let openapi = ControlPlaneAPIDoc::openapi();
+    let mut operations: Vec<(String, String)> = openapi
+        .paths
+        .paths
+        .iter()
+        .filter(|(path, _)| path.starts_with("/namespaces"))
+        .flat_map(|(path, item)| item.operations.values().map(move |operation| (path.clone(), operation.operation_id.clone().unwrap())))
+        .collect();
+    operations.sort();
+    let expected = [
+        ("/namespaces", "create_namespace"),
+        ("/namespaces", "list_namespaces"),
+        ("/namespaces/events", "watch_namespaces"),
+        ("/namespaces/search", "search_namespaces"),
+        ("/namespaces/{path}", "delete_namespace"),
+        ("/namespaces/{path}", "get_namespace"),
+        ("/namespaces/{path}/copy", "copy_namespace"),
+        ("/namespaces/{path}/export", "export_namespace"),
+        ("/namespaces/{path}/import", "import_namespace"),
+        ("/namespaces/{path}/quota", "get_namespace_quota"),
+        ("/namespaces/{path}/quota", "set_namespace_quota"),
+        ("/namespaces/{path}/stats", "get_namespace_stats"),
+        ("/namespaces/{path}/subtree", "list_namespace_subtree"),
+    ];
+    let mut expected: Vec<(String, String)> = expected.iter().map(|(path, id)| (path.to_string(), id.to_string())).collect();
+    expected.sort();
+    assert_eq!(operations, expected);
+
+    let search = &openapi.paths.paths["/namespaces/search"].operations.values().next().unwrap();
+    let parameters = search.parameters.as_ref().unwrap();
+    assert_eq!(parameters.len(), 1);
+    assert_eq!(parameters[0].name, "q");
+}
+
+#[test]
+fn test_server_url_can_be_replaced() {
+    let servers = ControlPlaneAPIDoc::openapi().servers.unwrap();
+    assert_eq!(servers.len(), 1);
+    assert_eq!(servers[0].url, "/api/v1alpha1");
+
+    let servers = ControlPlaneAPIDoc::openapi_with_server_url("https://sdlccp.example.com/api/v1alpha1").servers.unwrap();
+    assert_eq!(servers[0].url, "https://sdlccp.example.com/api/v1alpha1");
+    assert_eq!(servers[0].description.as_deref(), Some("Local"));
+}
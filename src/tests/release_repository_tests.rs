@@ -0,0 +1,186 @@
+use crate::model::phase::{RolloutStrategy, SourceReviewStatus};
+use crate::model::{ReleaseState, SDLCPhase, SDLCRelease};
+use crate::services::event_sourcing_release_repository::{EventSourcingReleaseRepository, ReleaseEventKind};
+use crate::services::release_repository::{InMemoryReleaseRepository, ReleaseFilter, ReleaseRepository, ReleaseRepositoryError};
+use chrono::Utc;
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn ids(releases: &[SDLCRelease]) -> HashSet<Uuid> {
+    releases.iter().map(|release| release.id).collect()
+}
+
+/// The behavior every `ReleaseRepository` shares. Listings are only checked for the releases
+/// stored here, so a backend may run it against a database that already holds others.
+async fn check_repository(repository: Arc<dyn ReleaseRepository>) {
+    let component_id = Uuid::new_v4();
+    let tag = format!("suite-{}", Uuid::new_v4());
+    let mut release = SDLCRelease::new(component_id, "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.tags.insert(tag.clone());
+    let mut other = SDLCRelease::new(component_id, "1.1.0".to_string(), "developer1".to_string()).unwrap();
+    other.phase = SDLCPhase::Build;
+    let unrelated = SDLCRelease::new(Uuid::new_v4(), "0.1.0".to_string(), "developer2".to_string()).unwrap();
+    for stored in [&release, &other, &unrelated] {
+        repository.store_release(stored.clone()).await.unwrap();
+    }
+
+    let stored = repository.get_release(&release.id).await.unwrap().unwrap();
+    assert_eq!(stored.version, "1.0.0");
+    assert_eq!(stored.revision, release.revision);
+    assert!(repository.get_release(&Uuid::new_v4()).await.unwrap().is_none());
+    assert!(ids(&repository.list_releases().await.unwrap()).is_superset(&ids(&[release.clone(), other.clone(), unrelated.clone()])));
+
+    // Storing again replaces the release and bumps its revision.
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    repository.store_release(release.clone()).await.unwrap();
+    let stored = repository.get_release(&release.id).await.unwrap().unwrap();
+    assert_eq!(stored.state_name(), "InProgress");
+    assert_eq!(stored.revision, release.revision + 1);
+
+    // Updates only go through at the expected revision.
+    let updated = repository.update_release(stored.clone(), stored.revision).await.unwrap();
+    assert_eq!(updated.revision, stored.revision + 1);
+    assert!(matches!(
+        repository.update_release(stored.clone(), stored.revision).await,
+        Err(ReleaseRepositoryError::RevisionMismatch { expected, actual }) if expected == stored.revision && actual == updated.revision
+    ));
+    let missing = SDLCRelease::new(component_id, "9.9.9".to_string(), "developer1".to_string()).unwrap();
+    assert!(matches!(repository.update_release(missing, 0).await, Err(ReleaseRepositoryError::NotFound)));
+
+    assert_eq!(ids(&repository.list_releases_for_component(&component_id).await.unwrap()), ids(&[release.clone(), other.clone()]));
+    assert_eq!(ids(&repository.list_releases_by_tag(&tag).await.unwrap()), ids(&[release.clone()]));
+
+    let by_component = repository.list_releases_filtered(ReleaseFilter { component_id: Some(component_id), ..Default::default() }).await.unwrap();
+    let expected_order: Vec<Uuid> = if (release.created_at, release.id) <= (other.created_at, other.id) { vec![release.id, other.id] } else { vec![other.id, release.id] };
+    assert_eq!(by_component.iter().map(|release| release.id).collect::<Vec<_>>(), expected_order);
+    let by_phase = repository.list_releases_filtered(ReleaseFilter { component_id: Some(component_id), phase: Some(SDLCPhase::Build), ..Default::default() }).await.unwrap();
+    assert_eq!(ids(&by_phase), ids(&[other.clone()]));
+    let by_tag = repository.list_releases_filtered(ReleaseFilter { tag: Some(tag.clone()), state: Some("InProgress".to_string()), ..Default::default() }).await.unwrap();
+    assert_eq!(ids(&by_tag), ids(&[release.clone()]));
+
+    // A batch is stored like single releases.
+    let batch = vec![
+        SDLCRelease::new(component_id, "2.0.0".to_string(), "developer1".to_string()).unwrap(),
+        repository.get_release(&other.id).await.unwrap().unwrap(),
+    ];
+    let stored = repository.store_releases(batch.clone()).await.unwrap();
+    assert_eq!(stored.iter().map(|release| release.revision).collect::<Vec<_>>(), vec![batch[0].revision, batch[1].revision + 1]);
+    assert_eq!(repository.get_release(&batch[0].id).await.unwrap().unwrap().version, "2.0.0");
+    assert_eq!(repository.get_release(&other.id).await.unwrap().unwrap().revision, batch[1].revision + 1);
+
+    // Removing a tag takes the release out of the tag listing.
+    let mut untagged = repository.get_release(&release.id).await.unwrap().unwrap();
+    untagged.tags.clear();
+    repository.update_release(untagged.clone(), untagged.revision).await.unwrap();
+    assert!(repository.list_releases_by_tag(&tag).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_in_memory_repository() {
+    check_repository(Arc::new(InMemoryReleaseRepository::new())).await;
+}
+
+#[tokio::test]
+async fn test_event_sourcing_repository() {
+    check_repository(Arc::new(EventSourcingReleaseRepository::new())).await;
+}
+
+/// Waits until the clock has moved on, so events stored before and after compare unequal.
+async fn tick() -> chrono::DateTime<Utc> {
+    let now = Utc::now();
+    tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    now
+}
+
+#[tokio::test]
+async fn test_event_sourcing_repository_answers_point_in_time_queries() {
+    let repository = EventSourcingReleaseRepository::new();
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    let before_creation = tick().await;
+    repository.store_release(release.clone()).await.unwrap();
+
+    release.start_development("developer1".to_string(), vec!["feature x".to_string()]).unwrap();
+    release.complete_development().unwrap();
+    release.start_source_review("reviewer1".to_string()).unwrap();
+    let approved = SourceReviewStatus::Approved { approver: "reviewer1".to_string(), approved_at: Utc::now() };
+    release.complete_source_review("abcdef123456".to_string(), "main".to_string(), None, approved).unwrap();
+    repository.store_release(release.clone()).await.unwrap();
+    let in_build = tick().await;
+
+    release.add_tag("candidate").unwrap();
+    repository.store_release(release.clone()).await.unwrap();
+    release.start_build("builder1".to_string()).unwrap();
+    release.complete_build("build-1".to_string(), true, None).unwrap();
+    release.start_packaging("packager1".to_string()).unwrap();
+    release.complete_packaging("sha256:0123".to_string(), "https://example.com/app-1.0.0.tar.gz".to_string(), None).unwrap();
+    release.release("First release".to_string()).unwrap();
+    release.start_deployment("production".to_string(), RolloutStrategy::AllAtOnce).unwrap();
+    release.complete_deployment().unwrap();
+    repository.store_release(release.clone()).await.unwrap();
+    let deployed = tick().await;
+
+    repository.delete_release(&release.id).await.unwrap();
+
+    assert!(repository.get_release_at(release.id, before_creation).await.unwrap().is_none());
+    let then = repository.get_release_at(release.id, in_build).await.unwrap().unwrap();
+    assert_eq!(then.phase, SDLCPhase::Build);
+    assert_eq!(then.state, ReleaseState::Draft);
+    assert_eq!(then.commit_hash.as_deref(), Some("abcdef123456"));
+    assert!(then.tags.is_empty());
+    let then = repository.get_release_at(release.id, deployed).await.unwrap().unwrap();
+    assert_eq!(then.phase, SDLCPhase::Runtime);
+    assert!(matches!(then.state, ReleaseState::Deployed { .. }));
+    assert!(then.tags.contains("candidate"));
+    assert_eq!(then.revision, 3);
+
+    // Deleted now, but the journal still has every step.
+    assert!(repository.get_release(&release.id).await.unwrap().is_none());
+    assert!(repository.list_releases().await.unwrap().is_empty());
+    // One event per transition, rather than a copy of the release per store.
+    let log = repository.get_event_log(release.id).await;
+    assert!(matches!(log[0].kind, ReleaseEventKind::Created { .. }));
+    let transitions: Vec<&str> = log.iter()
+        .filter_map(|event| match &event.kind {
+            ReleaseEventKind::Transitioned(record) => Some(record.to_state.name()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(transitions.len(), 11);
+    assert_eq!(transitions.last(), Some(&"Deployed"));
+    assert!(log.iter().any(|event| matches!(&event.kind, ReleaseEventKind::Updated { changes } if changes.contains_key("tags"))));
+    assert!(matches!(log.last().unwrap().kind, ReleaseEventKind::Deleted));
+    assert_eq!(log.iter().map(|event| event.sequence).collect::<Vec<_>>(), (0..log.len() as u64).collect::<Vec<_>>());
+    assert_eq!(repository.event_log(&release.id).await.unwrap().unwrap().len(), log.len());
+    assert!(matches!(repository.delete_release(&release.id).await, Err(ReleaseRepositoryError::NotFound)));
+}
+
+#[tokio::test]
+async fn test_latest_state_repositories_keep_no_event_log() {
+    let repository = InMemoryReleaseRepository::new();
+    let release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    repository.store_release(release.clone()).await.unwrap();
+    assert!(repository.event_log(&release.id).await.unwrap().is_none());
+}
+
+#[cfg(feature = "backend-sqlite")]
+#[tokio::test]
+async fn test_sqlite_repository() {
+    use crate::services::sqlite_release_repository::SqliteReleaseRepository;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    // Every connection to `sqlite::memory:` opens its own database, so the pool keeps just one.
+    let pool = SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+    check_repository(Arc::new(SqliteReleaseRepository::from_pool(pool).await.unwrap())).await;
+}
+
+/// Needs a database to write to: `SDLCCP_TEST_POSTGRES_URL=postgres://... cargo test --features backend-postgres -- --ignored`.
+#[cfg(feature = "backend-postgres")]
+#[tokio::test]
+#[ignore]
+async fn test_postgres_repository() {
+    use crate::services::postgres_release_repository::PostgresReleaseRepository;
+
+    let url = std::env::var("SDLCCP_TEST_POSTGRES_URL").expect("SDLCCP_TEST_POSTGRES_URL is not set");
+    check_repository(Arc::new(PostgresReleaseRepository::connect(&url).await.unwrap())).await;
+}
@@ -0,0 +1,1067 @@
+use crate::model::{ReleaseDiff, ReleaseSnapshot, SDLCRelease};
+use crate::model::attestation::{Attestation, ClaimSet, Subject, SubjectType};
+use crate::services::attestation::{AttestationService, InMemoryAttestationService};
+use crate::services::audit::InMemoryAuditLogService;
+use crate::services::auth::CurrentUser;
+use crate::model::phase::{FeatureListLimits, PackageDetails, PhaseDetails, RolloutStrategy, SourceReviewStatus};
+use crate::model::sbom::{CycloneDxBom, SpdxDocument};
+use crate::model::sdlc_component::{SDLCComponent, Unmanaged};
+use crate::services::component_repository::{ComponentRepository, InMemoryComponentRepository};
+use crate::services::controlplane::{release_attestation_router, release_router, release_search_router, sbom_router, BatchCreateReleasesResponse, ReleaseDetailResponse, ReleaseEventsResponse, ReleaseListResponse, ReleaseSearchResponse};
+use crate::model::{DeploymentSLA, ReleaseState, SDLCPhase, SLAStatus};
+use crate::services::event_sourcing_release_repository::{EventSourcingReleaseRepository, ReleaseEventKind};
+use crate::services::release_repository::{InMemoryReleaseRepository, ReleaseRepository};
+use crate::services::sla::SLABreach;
+use crate::services::transitions::{TransitionAction, TransitionBroadcaster, TransitionEvent, TransitionRequest};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Request, StatusCode};
+use axum::{Extension, Router};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+fn router() -> Router {
+    release_router(
+        Arc::new(InMemoryReleaseRepository::new()),
+        TransitionBroadcaster::new(16),
+        Arc::new(InMemoryAuditLogService::new()),
+    )
+}
+
+async fn create_release(router: &Router) -> SDLCRelease {
+    let response = router
+        .clone()
+        .oneshot(
+            Request::post("/")
+                .header("content-type", "application/json")
+                .extension(CurrentUser { sub: "developer1".to_string(), roles: Vec::new() })
+                .body(Body::from(serde_json::json!({ "component_id": Uuid::new_v4(), "version": "1.0.0" }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+fn transition_request(release_id: &Uuid, transition: serde_json::Value) -> Request<Body> {
+    Request::post(format!("/{}/transitions", release_id))
+        .header("content-type", "application/json")
+        .body(Body::from(transition.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_watch_streams_transitions() {
+    let router = router();
+    let release = create_release(&router).await;
+    assert_eq!(release.created_by, "developer1");
+
+    let response = router
+        .clone()
+        .oneshot(Request::get(format!("/{}/watch", release.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+    let mut stream = response.into_body();
+
+    let response = router
+        .clone()
+        .oneshot(transition_request(&release.id, serde_json::json!({ "action": "start_development", "started_by": "developer1", "feature_list": ["login"] })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let frame = tokio::time::timeout(Duration::from_millis(100), stream.frame())
+        .await
+        .expect("transition event was not streamed within 100ms")
+        .unwrap()
+        .unwrap();
+    let frame = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+    assert!(frame.starts_with("event: transition\n"), "unexpected frame: {}", frame);
+    let data = frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+    let event: TransitionEvent = serde_json::from_str(data).unwrap();
+    assert_eq!(event.release_id, release.id);
+    assert_eq!(event.from_state, "Draft");
+    assert_eq!(event.to_state, "InProgress");
+}
+
+#[tokio::test]
+async fn test_create_release_rejects_non_semver_version() {
+    let response = router()
+        .oneshot(
+            Request::post("/")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::json!({ "component_id": Uuid::new_v4(), "version": "foo" }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body["code"], "invalid_version");
+    assert_eq!(body["details"]["version"], "foo");
+}
+
+#[tokio::test]
+async fn test_invalid_transition_and_unknown_release() {
+    let router = router();
+    let release = create_release(&router).await;
+
+    let response = router
+        .clone()
+        .oneshot(transition_request(&release.id, serde_json::json!({ "action": "complete_deployment" })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = router
+        .clone()
+        .oneshot(Request::get(format!("/{}", release.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let stored: SDLCRelease = serde_json::from_slice(&body).unwrap();
+    assert_eq!(stored.state_name(), "Draft");
+
+    let response = router
+        .clone()
+        .oneshot(Request::get(format!("/{}/watch", Uuid::new_v4())).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+async fn list_release_page(router: &Router, uri: &str) -> ReleaseListResponse {
+    let response = router.clone().oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn list_releases(router: &Router, uri: &str) -> Vec<SDLCRelease> {
+    list_release_page(router, uri).await.releases
+}
+
+#[tokio::test]
+async fn test_tag_release_and_list_by_tag() {
+    let router = router();
+    let release = create_release(&router).await;
+    create_release(&router).await;
+
+    let response = router
+        .clone()
+        .oneshot(Request::put(format!("/{}/tags/security-patch", release.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let tagged: SDLCRelease = serde_json::from_slice(&body).unwrap();
+    assert!(tagged.has_tag("security-patch"));
+
+    let releases = list_releases(&router, "/?tag=security-patch").await;
+    assert_eq!(releases.len(), 1);
+    assert_eq!(releases[0].id, release.id);
+    assert_eq!(list_releases(&router, "/").await.len(), 2);
+
+    let response = router
+        .clone()
+        .oneshot(Request::put(format!("/{}/tags/not_valid", release.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = router
+        .clone()
+        .oneshot(Request::put(format!("/{}/tags/lts", Uuid::new_v4())).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = router
+        .clone()
+        .oneshot(Request::delete(format!("/{}/tags/security-patch", release.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(list_releases(&router, "/?tag=security-patch").await.is_empty());
+}
+
+/// Four releases of two components created a day apart, in different phases and states.
+async fn filterable_releases() -> (Router, Vec<SDLCRelease>) {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let router = release_router(releases.clone(), TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()));
+    let (api, worker) = (Uuid::new_v4(), Uuid::new_v4());
+    let start = Utc::now() - chrono::Duration::days(10);
+    let mut stored = Vec::new();
+    for (day, component_id, phase, state) in [
+        (0, api, SDLCPhase::Development, ReleaseState::Draft),
+        (1, api, SDLCPhase::Build, ReleaseState::Draft),
+        (2, worker, SDLCPhase::Build, ReleaseState::InProgress { started_by: "builder1".to_string(), started_at: start }),
+        (3, worker, SDLCPhase::Runtime, ReleaseState::Deployed { environment: "production".to_string(), deployment_time: start }),
+    ] {
+        let mut release = SDLCRelease::new(component_id, format!("1.{}.0", day), "developer1".to_string()).unwrap();
+        release.created_at = start + chrono::Duration::days(day);
+        release.phase = phase;
+        release.state = state;
+        releases.store_release(release.clone()).await.unwrap();
+        stored.push(release);
+    }
+    (router, stored)
+}
+
+fn versions(releases: &[SDLCRelease]) -> Vec<&str> {
+    releases.iter().map(|release| release.version.as_str()).collect()
+}
+
+#[tokio::test]
+async fn test_list_releases_by_each_filter() {
+    let (router, releases) = filterable_releases().await;
+
+    assert_eq!(versions(&list_releases(&router, "/").await), vec!["1.0.0", "1.1.0", "1.2.0", "1.3.0"]);
+    assert_eq!(versions(&list_releases(&router, "/?phase=Build").await), vec!["1.1.0", "1.2.0"]);
+    assert!(list_releases(&router, "/?phase=Hardening").await.is_empty());
+    assert_eq!(versions(&list_releases(&router, "/?state=Deployed").await), vec!["1.3.0"]);
+    let uri = format!("/?component_id={}", releases[2].component_id);
+    assert_eq!(versions(&list_releases(&router, &uri).await), vec!["1.2.0", "1.3.0"]);
+    let uri = format!("/?created_after={}", releases[2].created_at.to_rfc3339_opts(chrono::SecondsFormat::Micros, true));
+    assert_eq!(versions(&list_releases(&router, &uri).await), vec!["1.2.0", "1.3.0"]);
+    let uri = format!("/?created_before={}", releases[2].created_at.to_rfc3339_opts(chrono::SecondsFormat::Micros, true));
+    assert_eq!(versions(&list_releases(&router, &uri).await), vec!["1.0.0", "1.1.0"]);
+}
+
+#[tokio::test]
+async fn test_list_releases_by_combined_filters() {
+    let (router, releases) = filterable_releases().await;
+
+    let uri = format!("/?phase=Build&component_id={}", releases[0].component_id);
+    assert_eq!(versions(&list_releases(&router, &uri).await), vec!["1.1.0"]);
+    let uri = format!("/?phase=Build&state=InProgress&created_before={}", releases[3].created_at.to_rfc3339_opts(chrono::SecondsFormat::Micros, true));
+    assert_eq!(versions(&list_releases(&router, &uri).await), vec!["1.2.0"]);
+    assert!(list_releases(&router, "/?phase=Development&state=Deployed").await.is_empty());
+
+    let response = router.oneshot(Request::get("/?phase=Build&component_id=not-a-uuid").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_list_releases_page_by_page() {
+    let (router, _) = filterable_releases().await;
+
+    let first = list_release_page(&router, "/?limit=3").await;
+    assert_eq!(versions(&first.releases), vec!["1.0.0", "1.1.0", "1.2.0"]);
+    let cursor = first.next_cursor.unwrap();
+    let second = list_release_page(&router, &format!("/?limit=3&cursor={}", cursor)).await;
+    assert_eq!(versions(&second.releases), vec!["1.3.0"]);
+    assert_eq!(second.next_cursor, None);
+
+    let filtered = list_release_page(&router, "/?phase=Build&limit=1").await;
+    assert_eq!(versions(&filtered.releases), vec!["1.1.0"]);
+    let filtered = list_release_page(&router, &format!("/?phase=Build&limit=1&cursor={}", filtered.next_cursor.unwrap())).await;
+    assert_eq!(versions(&filtered.releases), vec!["1.2.0"]);
+    assert_eq!(filtered.next_cursor, None);
+
+    let response = router.oneshot(Request::get(format!("/?cursor={}", Uuid::new_v4())).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "invalid_cursor");
+}
+
+fn patch_request(release_id: &Uuid, etag: Option<&str>, transition: serde_json::Value) -> Request<Body> {
+    let mut request = Request::patch(format!("/{}/phase-transition", release_id)).header("content-type", "application/json");
+    if let Some(etag) = etag {
+        request = request.header(header::IF_MATCH, etag);
+    }
+    request.body(Body::from(transition.to_string())).unwrap()
+}
+
+async fn release_etag(router: &Router, release_id: &Uuid) -> String {
+    let response = router
+        .clone()
+        .oneshot(Request::get(format!("/{}", release_id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    response.headers()[header::ETAG].to_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_patch_phase_transition() {
+    let router = router();
+    let release = create_release(&router).await;
+    let etag = release_etag(&router, &release.id).await;
+    assert_eq!(etag, "\"0\"");
+
+    let start = serde_json::json!({ "action": "start_development", "started_by": "developer1", "feature_list": ["login"] });
+    let response = router.clone().oneshot(patch_request(&release.id, None, start.clone())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+
+    let response = router.clone().oneshot(patch_request(&release.id, Some(&etag), start)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::ETAG], "\"1\"");
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let updated: SDLCRelease = serde_json::from_slice(&body).unwrap();
+    assert_eq!(updated.state_name(), "InProgress");
+    assert_eq!(updated.revision, 1);
+
+    let response = router
+        .clone()
+        .oneshot(patch_request(&release.id, Some("\"1\""), serde_json::json!({ "action": "release", "release_notes": "too early" })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_concurrent_patch_with_same_etag_is_rejected() {
+    let router = router();
+    let release = create_release(&router).await;
+    // The CI pipeline and a reviewer both read the release before either changes it.
+    let pipeline_etag = release_etag(&router, &release.id).await;
+    let reviewer_etag = release_etag(&router, &release.id).await;
+
+    let response = router
+        .clone()
+        .oneshot(patch_request(&release.id, Some(&pipeline_etag), serde_json::json!({ "action": "start_development", "started_by": "ci", "feature_list": ["login"] })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = router
+        .clone()
+        .oneshot(patch_request(&release.id, Some(&reviewer_etag), serde_json::json!({ "action": "revoke", "reason": "not approved" })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error["code"], "revision_mismatch");
+    assert_eq!(error["details"]["actual"], 1);
+
+    let response = router
+        .clone()
+        .oneshot(Request::get(format!("/{}", release.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let stored: SDLCRelease = serde_json::from_slice(&body).unwrap();
+    assert_eq!(stored.state_name(), "InProgress");
+}
+
+#[tokio::test]
+async fn test_transition_of_incomplete_release_is_rejected() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let router = release_router(releases.clone(), TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()));
+    // Put straight into the Package phase, skipping the build that would have recorded its details.
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.phase = SDLCPhase::Package;
+    release.state = ReleaseState::Draft;
+    release.commit_hash = Some("abcdef123456".to_string());
+    releases.store_release(release.clone()).await.unwrap();
+
+    let response = router
+        .oneshot(transition_request(&release.id, serde_json::json!({ "action": "start_packaging", "started_by": "packager1" })))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "incomplete_phase_details");
+    assert_eq!(body["details"][0]["field"], "phase_details.build_details");
+    assert_eq!(releases.get_release(&release.id).await.unwrap().unwrap().state_name(), "Draft");
+}
+
+#[tokio::test]
+async fn test_invalid_feature_list_is_rejected() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let router = release_router(releases.clone(), TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()))
+        .layer(Extension(FeatureListLimits { max_items: 2, max_item_len: 10 }));
+    let release = create_release(&router).await;
+
+    let response = router
+        .clone()
+        .oneshot(transition_request(&release.id, serde_json::json!({ "action": "start_development", "started_by": "developer1" })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "invalid_feature_list");
+
+    let features = serde_json::json!(["login", "logout", "signup"]);
+    let response = router
+        .clone()
+        .oneshot(transition_request(&release.id, serde_json::json!({ "action": "start_development", "started_by": "developer1", "feature_list": features })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["details"], serde_json::json!({ "TooManyItems": { "limit": 2 } }));
+
+    let etag = release_etag(&router, &release.id).await;
+    let start = serde_json::json!({ "action": "start_development", "started_by": "developer1", "feature_list": ["single sign-on"] });
+    let response = router.clone().oneshot(patch_request(&release.id, Some(&etag), start)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["details"], serde_json::json!({ "ItemTooLong": { "index": 0, "limit": 10 } }));
+    assert_eq!(releases.get_release(&release.id).await.unwrap().unwrap().state_name(), "Draft");
+
+    let response = router
+        .oneshot(transition_request(&release.id, serde_json::json!({ "action": "start_development", "started_by": "developer1", "feature_list": ["login"] })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_get_attestations_for_release() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let attestations = Arc::new(InMemoryAttestationService::new());
+    let router = release_router(releases.clone(), TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()))
+        .merge(release_attestation_router(releases, attestations.clone()));
+    let release = create_release(&router).await;
+    let subject = |digest: &str| Subject { type_: SubjectType::Artifact, name: "app.tar.gz".to_string(), digest: digest.to_string() };
+    let build = Attestation::new(subject("sha256:1111"), ClaimSet::new()).for_release(release.id);
+    let scan = Attestation::new(subject("sha256:2222"), ClaimSet::new()).for_release(release.id);
+    let other_release = Attestation::new(subject("sha256:3333"), ClaimSet::new()).for_release(Uuid::new_v4());
+    for attestation in [&build, &scan, &other_release] {
+        attestations.store_attestation(attestation.clone()).await.unwrap();
+    }
+
+    let response = router.clone().oneshot(Request::get(format!("/{}/attestations", release.id)).body(Body::empty()).unwrap()).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let found: Vec<Attestation> = serde_json::from_slice(&body).unwrap();
+    let mut found: Vec<Uuid> = found.iter().map(|attestation| attestation.id).collect();
+    let mut expected = vec![build.id, scan.id];
+    found.sort();
+    expected.sort();
+    assert_eq!(found, expected);
+
+    let response = router.oneshot(Request::get(format!("/{}/attestations", Uuid::new_v4())).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_get_release_phase_attestations_after_full_lifecycle() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let attestations = Arc::new(InMemoryAttestationService::new());
+    let router = release_attestation_router(releases.clone(), attestations.clone());
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    let lifecycle = [
+        TransitionRequest::StartDevelopment { started_by: "developer1".to_string(), feature_list: vec!["feature x".to_string()] },
+        TransitionRequest::CompleteDevelopment,
+        TransitionRequest::StartSourceReview { started_by: "reviewer1".to_string() },
+        TransitionRequest::CompleteSourceReview {
+            commit_hash: "abcdef123456".to_string(),
+            branch: "main".to_string(),
+            pr_url: None,
+            review_status: SourceReviewStatus::Approved { approver: "reviewer1".to_string(), approved_at: Utc::now() },
+        },
+        TransitionRequest::StartBuild { started_by: "builder1".to_string() },
+        TransitionRequest::CompleteBuild { build_id: "build-1".to_string(), reproducible: false, build_tool: None },
+        TransitionRequest::StartPackaging { started_by: "packager1".to_string() },
+        TransitionRequest::CompletePackaging { artifact_hash: "sha256:1234".to_string(), artifact_url: "https://example.com/app.tar.gz".to_string(), sbom: None },
+        TransitionRequest::Release { release_notes: "First release".to_string() },
+        TransitionRequest::StartDeployment { environment: "production".to_string(), rollout_strategy: RolloutStrategy::AllAtOnce },
+        TransitionRequest::CompleteDeployment,
+    ];
+    for transition in lifecycle {
+        let action = transition.action();
+        transition.apply(&mut release).unwrap();
+        // One attestation per transition; the last one in a phase is the one kept.
+        let subject = Subject { type_: SubjectType::Artifact, name: format!("{action:?}"), digest: format!("sha256:{}", Uuid::new_v4().simple()) };
+        let attestation = Attestation::new(subject, ClaimSet::new()).for_release(release.id);
+        release.add_phase_attestation(release.phase_name().to_string(), attestation.id);
+        attestations.store_attestation(attestation).await.unwrap();
+    }
+    releases.store_release(release.clone()).await.unwrap();
+
+    let response = router.clone().oneshot(Request::get(format!("/{}/phase-attestations", release.id)).body(Body::empty()).unwrap()).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let found: HashMap<String, Attestation> = serde_json::from_slice(&body).unwrap();
+    let mut phases: Vec<&str> = found.keys().map(String::as_str).collect();
+    phases.sort();
+    assert_eq!(phases, vec!["Build", "Deploy", "Development", "Package", "Runtime", "Source"]);
+    for (phase, attestation) in &found {
+        assert_eq!(attestation.id, release.phase_attestations[phase]);
+    }
+
+    // A phase attestation that is no longer stored.
+    release.add_phase_attestation("Runtime".to_string(), Uuid::new_v4());
+    releases.store_release(release.clone()).await.unwrap();
+    let response = router.oneshot(Request::get(format!("/{}/phase-attestations", release.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn test_lineage_follows_three_generation_hotfix_chain() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let router = release_router(releases.clone(), TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()));
+
+    let mut original = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    original.commit_hash = Some("abcdef123456".to_string());
+    let hotfix = original.clone_for_hotfix("1.0.1".to_string(), "developer2".to_string()).unwrap();
+    let second_hotfix = hotfix.clone_for_hotfix("1.0.2".to_string(), "developer3".to_string()).unwrap();
+    for release in [&original, &hotfix, &second_hotfix] {
+        releases.store_release(release.clone()).await.unwrap();
+    }
+
+    assert_eq!(second_hotfix.parent_release_id, Some(hotfix.id));
+    assert_eq!(second_hotfix.dependencies, vec![hotfix.id]);
+    assert_eq!(second_hotfix.commit_hash.as_deref(), Some("abcdef123456"));
+    assert_eq!(second_hotfix.component_id, original.component_id);
+    assert_eq!(second_hotfix.phase, SDLCPhase::Source);
+    assert_eq!(second_hotfix.state, ReleaseState::Draft);
+    assert!(second_hotfix.phase_attestations.is_empty());
+
+    let response = router
+        .clone()
+        .oneshot(Request::get(format!("/{}/lineage", second_hotfix.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let lineage: Vec<SDLCRelease> = serde_json::from_slice(&body).unwrap();
+    let versions: Vec<&str> = lineage.iter().map(|release| release.version.as_str()).collect();
+    assert_eq!(versions, vec!["1.0.2", "1.0.1", "1.0.0"]);
+    assert_eq!(lineage[2].parent_release_id, None);
+
+    let response = router
+        .clone()
+        .oneshot(Request::get(format!("/{}/lineage", Uuid::new_v4())).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_sbom_lists_transitive_dependencies() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let components = Arc::new(InMemoryComponentRepository::new());
+    let router = sbom_router(releases.clone(), components.clone());
+    let library = SDLCComponent::Unmanaged(Unmanaged {
+        id: Uuid::new_v4(),
+        name: "serde".to_string(),
+        repository_url: None,
+        package_url: Some("pkg:cargo/serde@1.0.209".to_string()),
+        metadata: Default::default(),
+    });
+    components.store_component(library.clone()).await.unwrap();
+
+    let mut app = SDLCRelease::new(Uuid::new_v4(), "2.0.0".to_string(), "developer1".to_string()).unwrap();
+    let mut serde = SDLCRelease::new(library.id(), "1.0.209".to_string(), "developer1".to_string()).unwrap();
+    let mut client = SDLCRelease::new(Uuid::new_v4(), "0.3.0".to_string(), "developer1".to_string()).unwrap();
+    let transitive = SDLCRelease::new(Uuid::new_v4(), "0.1.0".to_string(), "developer1".to_string()).unwrap();
+    serde.phase_details = Some(PhaseDetails {
+        package_details: Some(PackageDetails {
+            artifact_hash: "sha256:2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae".to_string(),
+            artifact_url: "https://artifacts.example.com/serde-1.0.209.crate".to_string(),
+            sbom: None,
+        }),
+        ..PhaseDetails::new()
+    });
+    client.add_dependency(transitive.id);
+    client.add_dependency(serde.id);
+    app.add_dependency(serde.id);
+    app.add_dependency(client.id);
+    for release in [&app, &serde, &client, &transitive] {
+        releases.store_release(release.clone()).await.unwrap();
+    }
+
+    let response = router.clone().oneshot(Request::get(format!("/{}/sbom", app.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/vnd.cyclonedx+json; version=1.4");
+    let bom: CycloneDxBom = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(bom.spec_version, "1.4");
+    assert_eq!(bom.metadata.component.bom_ref, app.id.to_string());
+    assert_eq!(bom.components.len(), 3);
+    let serde_component = bom.components.iter().find(|component| component.name == "serde").unwrap();
+    assert_eq!(serde_component.purl.as_deref(), Some("pkg:cargo/serde@1.0.209"));
+    assert_eq!(serde_component.hashes[0].alg, "SHA-256");
+    let client_dependencies = &bom.dependencies.iter().find(|dependency| dependency.ref_ == client.id.to_string()).unwrap().depends_on;
+    assert_eq!(client_dependencies, &vec![transitive.id.to_string(), serde.id.to_string()]);
+
+    let response = router.clone().oneshot(Request::get(format!("/{}/sbom?format=spdx", app.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/spdx+json");
+    let document: SpdxDocument = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(document.spdx_version, "SPDX-2.3");
+    assert_eq!(document.packages.len(), 4);
+    // DESCRIBES the release, plus one DEPENDS_ON per dependency edge.
+    assert_eq!(document.relationships.len(), 5);
+
+    let response = router.oneshot(Request::get(format!("/{}/sbom", Uuid::new_v4())).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_get_release_notes() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let router = release_router(releases.clone(), TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()));
+    let release = create_release(&router).await;
+    let get_notes = |id: Uuid| Request::get(format!("/{}/release-notes", id)).body(Body::empty()).unwrap();
+
+    let response = router.clone().oneshot(get_notes(release.id)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body, serde_json::json!({ "release_id": release.id, "release_notes": null }));
+
+    let mut release = releases.get_release(&release.id).await.unwrap().unwrap();
+    release.set_release_notes("Fixes the login page".to_string());
+    releases.store_release(release.clone()).await.unwrap();
+    let response = router.clone().oneshot(get_notes(release.id)).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body["release_notes"], "Fixes the login page");
+
+    let response = router.oneshot(get_notes(Uuid::new_v4())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_rollback_to_snapshot_after_packaging() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let router = release_router(releases.clone(), TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()));
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    let until_packaged = [
+        TransitionRequest::StartDevelopment { started_by: "developer1".to_string(), feature_list: vec!["feature x".to_string()] },
+        TransitionRequest::CompleteDevelopment,
+        TransitionRequest::StartSourceReview { started_by: "reviewer1".to_string() },
+        TransitionRequest::CompleteSourceReview {
+            commit_hash: "abcdef123456".to_string(),
+            branch: "main".to_string(),
+            pr_url: None,
+            review_status: SourceReviewStatus::Approved { approver: "reviewer1".to_string(), approved_at: Utc::now() },
+        },
+        TransitionRequest::StartBuild { started_by: "builder1".to_string() },
+        TransitionRequest::CompleteBuild { build_id: "build-1".to_string(), reproducible: false, build_tool: None },
+        TransitionRequest::StartPackaging { started_by: "packager1".to_string() },
+        TransitionRequest::CompletePackaging { artifact_hash: "sha256:1234".to_string(), artifact_url: "https://example.com/app.tar.gz".to_string(), sbom: None },
+    ];
+    for transition in until_packaged {
+        transition.apply(&mut release).unwrap();
+    }
+    let packaged = release.snapshot();
+    assert_eq!(packaged.phase, SDLCPhase::Deploy);
+    assert!(release.rollback_to(packaged.clone()).is_err());
+
+    let until_deployed = [
+        TransitionRequest::Release { release_notes: "First release".to_string() },
+        TransitionRequest::StartDeployment { environment: "production".to_string(), rollout_strategy: RolloutStrategy::AllAtOnce },
+        TransitionRequest::CompleteDeployment,
+    ];
+    for transition in until_deployed {
+        transition.apply(&mut release).unwrap();
+    }
+    assert_eq!(release.state_name(), "Deployed");
+    assert_eq!(release.snapshots.len(), release.transition_history.len());
+    assert_eq!(release.release_notes.as_deref(), Some("First release"));
+
+    release.rollback_to(packaged.clone()).unwrap();
+    assert_eq!(release.phase, SDLCPhase::Deploy);
+    assert_eq!(release.state, packaged.state);
+    assert!(release.phase_details.as_ref().unwrap().runtime_details.is_none());
+    let rollback = release.transition_history.last().unwrap();
+    assert_eq!(rollback.action, Some(TransitionAction::Rollback));
+    assert_eq!(rollback.from_state, "Deployed");
+    releases.store_release(release.clone()).await.unwrap();
+
+    let response = router
+        .clone()
+        .oneshot(Request::get(format!("/{}/snapshots", release.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let snapshots: Vec<ReleaseSnapshot> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(snapshots.len(), release.transition_history.len());
+    assert_eq!(snapshots.first().unwrap().state.name(), "Draft");
+    assert_eq!(snapshots.last().unwrap().phase, SDLCPhase::Runtime);
+
+    let response = router
+        .oneshot(Request::get(format!("/{}/snapshots", Uuid::new_v4())).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_diff_of_hotfix_against_original() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let router = release_router(releases.clone(), TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()));
+    let mut original = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    original.commit_hash = Some("abcdef123456".to_string());
+    let mut hotfix = original.clone_for_hotfix("1.0.1".to_string(), "developer2".to_string()).unwrap();
+    hotfix.commit_hash = Some("fedcba654321".to_string());
+    for release in [&original, &hotfix] {
+        releases.store_release(release.clone()).await.unwrap();
+    }
+
+    let response = router
+        .clone()
+        .oneshot(Request::get(format!("/{}/diff/{}", original.id, hotfix.id)).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let diff: ReleaseDiff = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        diff,
+        ReleaseDiff {
+            same_component: true,
+            version_changed: true,
+            phase_changed: true,
+            state_changed: false,
+            added_dependencies: vec![original.id],
+            removed_dependencies: Vec::new(),
+            build_id_changed: false,
+            commit_hash_changed: true,
+            artifact_hash_changed: false,
+        }
+    );
+
+    let response = router
+        .oneshot(Request::get(format!("/{}/diff/{}", original.id, Uuid::new_v4())).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+async fn search(router: &Router, uri: &str) -> Vec<SDLCRelease> {
+    let response = router.clone().oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice::<ReleaseSearchResponse>(&body).unwrap().releases
+}
+
+#[tokio::test]
+async fn test_search_releases_ranks_by_matching_fields() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let components = Arc::new(InMemoryComponentRepository::new());
+    let router = release_search_router(releases.clone(), components.clone());
+    let component = |name: &str| SDLCComponent::Unmanaged(Unmanaged {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        repository_url: None,
+        package_url: None,
+        metadata: Default::default(),
+    });
+    let (orchestrator, billing) = (component("orchestrator"), component("billing"));
+    components.store_component(orchestrator.clone()).await.unwrap();
+    components.store_component(billing.clone()).await.unwrap();
+
+    // 20 releases: three have an "rc" version, two belong to the orchestrator, and one of them
+    // is both. The rest match nothing.
+    let started = Utc::now();
+    let mut created: Vec<(Uuid, String)> = vec![
+        (orchestrator.id(), "1.0.0".to_string()),
+        (billing.id(), "2.0.0-rc.1".to_string()),
+        (orchestrator.id(), "1.1.0-rc.1".to_string()),
+        (billing.id(), "2.0.0-rc.2".to_string()),
+    ];
+    created.extend((0..16).map(|patch| (billing.id(), format!("1.0.{}", patch))));
+    for (minutes, (component_id, version)) in created.into_iter().enumerate() {
+        let mut release = SDLCRelease::new(component_id, version, "developer1".to_string()).unwrap();
+        release.created_at = started + chrono::Duration::minutes(minutes as i64);
+        releases.store_release(release).await.unwrap();
+    }
+    assert_eq!(releases.list_releases().await.unwrap().len(), 20);
+
+    // The release matching on both fields comes first, then the rest newest first.
+    let found = search(&router, "/search?q=rc").await;
+    assert_eq!(versions(&found), vec!["1.1.0-rc.1", "2.0.0-rc.2", "2.0.0-rc.1", "1.0.0"]);
+    assert_eq!(versions(&search(&router, "/search?q=rc&limit=2").await), vec!["1.1.0-rc.1", "2.0.0-rc.2"]);
+    assert_eq!(versions(&search(&router, "/search?q=orchestrator").await), vec!["1.1.0-rc.1", "1.0.0"]);
+    assert!(search(&router, "/search?q=nightly").await.is_empty());
+
+    let response = router.oneshot(Request::get("/search?q=%20").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_search_releases_by_commit_hash_and_tag() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let router = release_search_router(releases.clone(), Arc::new(InMemoryComponentRepository::new()));
+    let mut hashed = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    hashed.commit_hash = Some("9f86d081884c7d65".to_string());
+    let mut tagged = SDLCRelease::new(Uuid::new_v4(), "1.1.0".to_string(), "developer1".to_string()).unwrap();
+    tagged.tags.insert("hotfix-9f86".to_string());
+    releases.store_release(hashed.clone()).await.unwrap();
+    releases.store_release(tagged.clone()).await.unwrap();
+
+    // Commit hashes only match by prefix, tags anywhere.
+    assert_eq!(versions(&search(&router, "/search?q=9f86d").await), vec!["1.0.0"]);
+    assert_eq!(versions(&search(&router, "/search?q=884c").await), Vec::<&str>::new());
+    assert_eq!(search(&router, "/search?q=9f86").await.len(), 2);
+    assert_eq!(versions(&search(&router, "/search?q=hotfix").await), vec!["1.1.0"]);
+}
+
+async fn create_batch(router: &Router, versions: &[&str], fail_fast: bool) -> (StatusCode, BatchCreateReleasesResponse) {
+    let releases: Vec<serde_json::Value> = versions.iter().map(|version| serde_json::json!({ "component_id": Uuid::new_v4(), "version": version })).collect();
+    let response = router
+        .clone()
+        .oneshot(
+            Request::post("/batch")
+                .header("content-type", "application/json")
+                .extension(CurrentUser { sub: "ci".to_string(), roles: Vec::new() })
+                .body(Body::from(serde_json::json!({ "releases": releases, "fail_fast": fail_fast }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_else(|_| panic!("unexpected body: {}", String::from_utf8_lossy(&body))))
+}
+
+#[tokio::test]
+async fn test_batch_create_releases() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let router = release_router(releases.clone(), TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()));
+
+    let (status, response) = create_batch(&router, &["1.0.0", "2.0.0", "3.0.0"], true).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(versions(&response.created), vec!["1.0.0", "2.0.0", "3.0.0"]);
+    assert!(response.errors.is_empty());
+    assert!(response.created.iter().all(|release| release.created_by == "ci"));
+    assert_eq!(releases.list_releases().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_batch_create_releases_partial_failure() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let router = release_router(releases.clone(), TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()));
+    let batch = ["1.0.0", "latest", "2.0.0", "v3"];
+
+    // Without fail_fast the valid releases are created and every invalid one is reported.
+    let (status, response) = create_batch(&router, &batch, false).await;
+    assert_eq!(status, StatusCode::MULTI_STATUS);
+    assert_eq!(versions(&response.created), vec!["1.0.0", "2.0.0"]);
+    assert_eq!(response.errors.iter().map(|error| error.index).collect::<Vec<_>>(), vec![1, 3]);
+    assert!(response.errors[0].error.contains("latest"));
+    assert_eq!(releases.list_releases().await.unwrap().len(), 2);
+
+    // With fail_fast the first invalid release stops the batch and nothing is created.
+    let (status, response) = create_batch(&router, &batch, true).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(response.created.is_empty());
+    assert_eq!(response.errors.iter().map(|error| error.index).collect::<Vec<_>>(), vec![1]);
+    assert_eq!(releases.list_releases().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_batch_create_releases_rejects_oversized_batch() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let router = release_router(releases.clone(), TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()));
+    let items: Vec<serde_json::Value> = (0..101).map(|patch| serde_json::json!({ "component_id": Uuid::new_v4(), "version": format!("1.0.{}", patch) })).collect();
+    let response = router
+        .oneshot(
+            Request::post("/batch")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::json!({ "releases": items }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(releases.list_releases().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_get_release_events() {
+    let router = release_router(
+        Arc::new(EventSourcingReleaseRepository::new()),
+        TransitionBroadcaster::new(16),
+        Arc::new(InMemoryAuditLogService::new()),
+    );
+    let release = create_release(&router).await;
+    let response = router
+        .clone()
+        .oneshot(transition_request(&release.id, serde_json::json!({ "action": "start_development", "started_by": "developer1", "feature_list": ["login"] })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = router.clone().oneshot(Request::get(format!("/{}/events", release.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let events: ReleaseEventsResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(events.release_id, release.id);
+    assert!(matches!(events.events[0].kind, ReleaseEventKind::Created { .. }));
+    assert!(matches!(&events.events[1].kind, ReleaseEventKind::Transitioned(record) if record.from_state == "Draft"));
+
+    let response = router.oneshot(Request::get(format!("/{}/events", Uuid::new_v4())).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // The default store only keeps the latest state.
+    let router = self::router();
+    let release = create_release(&router).await;
+    let response = router.oneshot(Request::get(format!("/{}/events", release.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_IMPLEMENTED);
+}
+
+fn released_at(release_time: chrono::DateTime<Utc>, sla: Option<DeploymentSLA>) -> SDLCRelease {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.phase = SDLCPhase::Deploy;
+    release.state = ReleaseState::Released { release_notes: "notes".to_string(), release_time };
+    release.sla = sla;
+    release
+}
+
+#[test]
+fn test_sla_status_follows_time_since_release() {
+    let release_time = Utc::now();
+    let sla = DeploymentSLA { max_time_to_deploy: Duration::from_secs(4 * 3600), notify_at_percent: 75 };
+    let release = released_at(release_time, Some(sla));
+    let after = |minutes: i64| release_time + chrono::Duration::minutes(minutes);
+
+    assert_eq!(release.sla_status_at(after(60)), SLAStatus::OnTrack);
+    assert_eq!(release.sla_status_at(after(180)), SLAStatus::AtRisk { percentage_elapsed: 75 });
+    assert_eq!(release.sla_status_at(after(240)), SLAStatus::AtRisk { percentage_elapsed: 100 });
+    assert_eq!(release.sla_status_at(after(270)), SLAStatus::Breached { overdue_by: Duration::from_secs(30 * 60) });
+
+    // The clock keeps running while the release is being deployed and stops once it is deployed
+    let mut deploying = release.clone();
+    deploying.transition_history.push(crate::model::TransitionRecord {
+        from_phase: SDLCPhase::Deploy,
+        from_state: "Draft".to_string(),
+        to_phase: SDLCPhase::Deploy,
+        to_state: release.state.clone(),
+        timestamp: release_time,
+        action: None,
+        reason: None,
+        changed_by: None,
+    });
+    deploying.start_deployment("production".to_string(), RolloutStrategy::AllAtOnce).unwrap();
+    assert!(matches!(deploying.sla_status_at(after(270)), SLAStatus::Breached { .. }));
+    deploying.state = ReleaseState::Deployed { environment: "production".to_string(), deployment_time: after(270) };
+    assert_eq!(deploying.sla_status_at(after(600)), SLAStatus::OnTrack);
+    assert_eq!(released_at(release_time, None).sla_status_at(after(600)), SLAStatus::OnTrack);
+
+    let mut releasable = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    releasable.sla = Some(sla);
+    releasable.state = ReleaseState::Releasable { approved_by: "alice".to_string(), approved_at: release_time };
+    releasable.release("notes".to_string()).unwrap();
+    let ReleaseState::Released { release_time, .. } = releasable.state else { panic!("not released") };
+    assert_eq!(releasable.estimated_deploy_time, Some(release_time + chrono::Duration::hours(4)));
+}
+
+#[tokio::test]
+async fn test_sla_breaches_lists_overdue_releases() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let sla = DeploymentSLA { max_time_to_deploy: Duration::from_secs(3600), notify_at_percent: 80 };
+    let overdue = released_at(Utc::now() - chrono::Duration::hours(3), Some(sla));
+    let barely_overdue = released_at(Utc::now() - chrono::Duration::hours(2), Some(sla));
+    let on_time = released_at(Utc::now(), Some(sla));
+    let without_sla = released_at(Utc::now() - chrono::Duration::hours(3), None);
+    for release in [&barely_overdue, &overdue, &on_time, &without_sla] {
+        releases.store_release(release.clone()).await.unwrap();
+    }
+    let router = release_router(releases, TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()));
+
+    let response = router.oneshot(Request::get("/sla-breaches").body(Body::empty()).unwrap()).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let breaches: Vec<SLABreach> = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    let ids: Vec<Uuid> = breaches.iter().map(|breach| breach.release_id).collect();
+    assert_eq!(ids, vec![overdue.id, barely_overdue.id]);
+    assert!(breaches[0].overdue_by >= Duration::from_secs(2 * 3600));
+}
+
+/// A release created in Development at `start` that then entered each of the given phases the
+/// given number of hours after `start`.
+fn release_with_history<const N: usize>(start: chrono::DateTime<Utc>, entered_at_hours: [(SDLCPhase, i64); N]) -> SDLCRelease {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    release.created_at = start;
+    let mut from_phase = SDLCPhase::Development;
+    for (to_phase, hours) in entered_at_hours {
+        release.transition_history.push(crate::model::TransitionRecord {
+            from_phase: from_phase.clone(),
+            from_state: "Approved".to_string(),
+            to_phase: to_phase.clone(),
+            to_state: ReleaseState::Draft,
+            timestamp: start + chrono::Duration::hours(hours),
+            action: None,
+            reason: None,
+            changed_by: None,
+        });
+        from_phase = to_phase;
+    }
+    release.phase = from_phase;
+    release
+}
+
+#[test]
+fn test_phase_durations_follow_transition_history() {
+    let start = Utc::now() - chrono::Duration::days(1);
+    let release = release_with_history(start, [(SDLCPhase::Source, 2), (SDLCPhase::Build, 5), (SDLCPhase::Deploy, 6)]);
+
+    assert_eq!(release.phase_duration(&SDLCPhase::Development), Some(chrono::Duration::hours(2)));
+    assert_eq!(release.phase_duration(&SDLCPhase::Source), Some(chrono::Duration::hours(3)));
+    assert_eq!(release.phase_duration(&SDLCPhase::Build), Some(chrono::Duration::hours(1)));
+    // Still in Deploy, and never in Package
+    assert_eq!(release.phase_duration(&SDLCPhase::Deploy), None);
+    assert_eq!(release.phase_duration(&SDLCPhase::Package), None);
+    assert_eq!(release.total_duration_to_deploy(), Some(chrono::Duration::hours(6)));
+    assert_eq!(release.phase_durations().len(), 3);
+
+    let fresh = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    assert_eq!(fresh.phase_duration(&SDLCPhase::Development), None);
+    assert_eq!(fresh.total_duration_to_deploy(), None);
+    assert!(fresh.phase_durations().is_empty());
+}
+
+#[test]
+fn test_phase_duration_spans_first_entry_to_last_exit() {
+    let start = Utc::now() - chrono::Duration::days(1);
+    let release = release_with_history(
+        start,
+        [(SDLCPhase::Source, 2), (SDLCPhase::Development, 4), (SDLCPhase::Source, 5), (SDLCPhase::Build, 8)],
+    );
+
+    // Entered at creation, rolled back into at 4h and last left at 5h
+    assert_eq!(release.phase_duration(&SDLCPhase::Development), Some(chrono::Duration::hours(5)));
+    // First entered at 2h and last left at 8h, counting the hour back in Development
+    assert_eq!(release.phase_duration(&SDLCPhase::Source), Some(chrono::Duration::hours(6)));
+    assert_eq!(release.phase_duration(&SDLCPhase::Build), None);
+}
+
+#[tokio::test]
+async fn test_release_endpoints_report_phase_durations() {
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let release = release_with_history(Utc::now() - chrono::Duration::days(1), [(SDLCPhase::Source, 1), (SDLCPhase::Build, 3), (SDLCPhase::Deploy, 4)]);
+    releases.store_release(release.clone()).await.unwrap();
+    let router = release_router(releases, TransitionBroadcaster::new(16), Arc::new(InMemoryAuditLogService::new()));
+
+    let response = router.clone().oneshot(Request::get(format!("/{}/phase-durations", release.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let durations: HashMap<String, i64> = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(durations, HashMap::from([
+        ("Development".to_string(), 3600),
+        ("Source".to_string(), 2 * 3600),
+        ("Build".to_string(), 3600),
+    ]));
+
+    let response = router.clone().oneshot(Request::get(format!("/{}", release.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let detail: ReleaseDetailResponse = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(detail.release.id, release.id);
+    assert_eq!(detail.phase_durations, durations);
+    assert_eq!(detail.total_duration_to_deploy_secs, Some(4 * 3600));
+
+    let response = router.oneshot(Request::get(format!("/{}/phase-durations", Uuid::new_v4())).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
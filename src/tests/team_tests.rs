@@ -0,0 +1,63 @@
+use crate::model::sdlc_component::TeamReference;
+use crate::services::audit::{AuditAction, AuditFilter, AuditLogService, InMemoryAuditLogService};
+use crate::services::controlplane::team_router;
+use crate::services::team_repository::{InMemoryTeamRepository, TeamRepository};
+use std::sync::Arc;
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+fn json_request(method: &str, uri: String, team: &TeamReference) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(team).unwrap()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_team_crud() {
+    let audit = Arc::new(InMemoryAuditLogService::new());
+    let teams = Arc::new(InMemoryTeamRepository::new());
+    let router = team_router(teams.clone(), audit.clone());
+    let mut team = TeamReference {
+        id: Uuid::new_v4(),
+        name: "payments".to_string(),
+        contact_email: "payments@example.com".to_string(),
+    };
+
+    let response = router.clone().oneshot(json_request("POST", "/".to_string(), &team)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    team.contact_email = "payments-oncall@example.com".to_string();
+    let response = router.clone().oneshot(json_request("PUT", format!("/{}", team.id), &team)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = router.clone().oneshot(json_request("PUT", format!("/{}", Uuid::new_v4()), &team)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = router.clone().oneshot(Request::get(format!("/{}", team.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(serde_json::from_slice::<TeamReference>(&body).unwrap(), team);
+
+    // Listing by component only returns the teams assigned to it.
+    let component_id = Uuid::new_v4();
+    teams.assign_team(component_id, team.id).await;
+    let response = router.clone().oneshot(Request::get(format!("/?component_id={}", component_id)).body(Body::empty()).unwrap()).await.unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(serde_json::from_slice::<Vec<TeamReference>>(&body).unwrap(), vec![team.clone()]);
+    let response = router.clone().oneshot(Request::get(format!("/?component_id={}", Uuid::new_v4())).body(Body::empty()).unwrap()).await.unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(serde_json::from_slice::<Vec<TeamReference>>(&body).unwrap().is_empty());
+
+    let response = router.clone().oneshot(Request::delete(format!("/{}", team.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(teams.get_team(&team.id).await.unwrap().is_none());
+    let response = router.oneshot(Request::get(format!("/{}", team.id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let actions: Vec<AuditAction> = audit.query(AuditFilter::default()).await.unwrap().into_iter().map(|event| event.action).collect();
+    assert_eq!(actions, vec![AuditAction::CreateTeam, AuditAction::UpdateTeam, AuditAction::DeleteTeam]);
+}
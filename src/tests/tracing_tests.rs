@@ -0,0 +1,72 @@
+use crate::model::attestation::{Attestation, ClaimSet, Subject, SubjectType};
+use crate::model::{Policy, SDLCRelease};
+use crate::services::attestation::{AttestationService, InMemoryAttestationService};
+use crate::services::component_repository::InMemoryComponentRepository;
+use crate::services::controlplane::{ControlPlane, InMemoryControlPlane};
+use crate::services::namespace::{InMemoryNamespaceManager, NamespaceService};
+use crate::services::policy_repository::InMemoryPolicyRepository;
+use crate::services::release_repository::{InMemoryReleaseRepository, ReleaseRepository};
+use std::sync::Arc;
+use uuid::Uuid;
+use tracing_test::traced_test;
+
+#[tokio::test]
+#[traced_test]
+async fn test_namespace_lifecycle_emits_spans() {
+    let mut manager = InMemoryNamespaceManager::new();
+    manager.create_namespace("org/team").await.unwrap();
+    manager.list_namespaces("org").await.unwrap();
+    manager.search_namespaces("team").await.unwrap();
+    manager.drill_down("org").await.unwrap();
+    manager.delete_namespace("org/team").await.unwrap();
+    assert!(manager.delete_namespace("org/team").await.is_err());
+
+    assert!(logs_contain("create_namespace{path=\"org/team\"}"));
+    assert!(logs_contain("Created namespace namespace=\"org/team\""));
+    assert!(logs_contain("delete_namespace{path=\"org/team\"}"));
+    assert!(logs_contain("Deleted namespace namespace=\"org/team\""));
+    assert!(logs_contain("error=NotFound"));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_attestation_lifecycle_emits_spans() {
+    let service = InMemoryAttestationService::new();
+    let attestation = Attestation::new(
+        Subject {
+            type_: SubjectType::Artifact,
+            name: "app-1.0.0.jar".to_string(),
+            digest: "sha256:1234567890abcdef".to_string(),
+        },
+        ClaimSet::new(),
+    );
+    let id = attestation.id;
+    service.store_attestation(attestation.clone()).await.unwrap();
+    service.get_attestation(&id).await.unwrap();
+    service.verify_attestation(&attestation).await.unwrap();
+
+    assert!(logs_contain(&format!("store_attestation{{attestation_id={}}}", id)));
+    assert!(logs_contain("Stored attestation subject=app-1.0.0.jar"));
+}
+
+#[tokio::test]
+#[traced_test]
+async fn test_release_evaluation_emits_spans() {
+    let policies = Arc::new(InMemoryPolicyRepository::new());
+    let releases = Arc::new(InMemoryReleaseRepository::new());
+    let control_plane = InMemoryControlPlane::new(policies.clone(), Arc::new(InMemoryAttestationService::new()), releases.clone(), Arc::new(InMemoryComponentRepository::new()));
+    let release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    releases.store_release(release.clone()).await.unwrap();
+    let policy = Policy::new("Release Policy".to_string(), vec!["Deploy".to_string()]);
+    control_plane.store_policy(policy.clone()).await.unwrap();
+    policies.assign_policy(release.component_id, policy.id).await;
+
+    assert!(control_plane.evaluate_release(&release.id, None).await.unwrap().overall);
+
+    assert!(logs_contain(&format!("store_release{{release_id={}}}: sdlc_cp_api::services::release_repository: Stored release revision=", release.id)));
+    assert!(logs_contain(&format!("store_policy{{policy_id={}}}: sdlc_cp_api::services::controlplane: Stored policy rules=0", policy.id)));
+    assert!(logs_contain(&format!(
+        "evaluate_release{{release_id={} policy_id=None component_id={}}}: sdlc_cp_api::services::controlplane: Evaluated release policies=1",
+        release.id, release.component_id,
+    )));
+}
@@ -0,0 +1,123 @@
+use crate::model::SDLCRelease;
+use crate::services::audit::InMemoryAuditLogService;
+use crate::services::controlplane::webhook_router;
+use crate::services::transitions::{TransitionAction, TransitionBroadcaster, TransitionEvent, TransitionRequest};
+use crate::services::webhook::{sign_payload, spawn_webhook_dispatcher, InMemoryWebhookService, WebhookPayload, WebhookService, WebhookSubscription, SIGNATURE_HEADER};
+use std::sync::Arc;
+use std::time::Duration;
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use tower::ServiceExt;
+use uuid::Uuid;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request as ReceivedRequest, ResponseTemplate};
+
+fn start_development_event() -> TransitionEvent {
+    let mut release = SDLCRelease::new(Uuid::new_v4(), "1.0.0".to_string(), "developer1".to_string()).unwrap();
+    TransitionRequest::StartDevelopment { started_by: "developer1".to_string(), feature_list: vec!["feature x".to_string()] }
+        .apply(&mut release)
+        .unwrap()
+}
+
+/// Waits until the mock server has received `count` requests, since deliveries run in the background.
+async fn received_requests(server: &MockServer, count: usize) -> Vec<ReceivedRequest> {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let requests = server.received_requests().await.unwrap();
+            if requests.len() >= count {
+                return requests;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("webhook was not delivered in time")
+}
+
+#[tokio::test]
+async fn test_transition_is_posted_with_signature() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST")).and(path("/hook"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    let webhooks = Arc::new(InMemoryWebhookService::new());
+    let subscription = WebhookSubscription::new(format!("{}/hook", server.uri()), vec![TransitionAction::StartDevelopment], "s3cret".to_string());
+    webhooks.register_subscription(subscription).await.unwrap();
+    let transitions = TransitionBroadcaster::new(16);
+    spawn_webhook_dispatcher(webhooks, &transitions);
+
+    let event = start_development_event();
+    transitions.publish(event.clone());
+
+    let requests = received_requests(&server, 1).await;
+    let request = &requests[0];
+    assert_eq!(request.headers.get(SIGNATURE_HEADER).unwrap().to_str().unwrap(), sign_payload("s3cret", &request.body));
+    let payload: WebhookPayload = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(payload.event_type, TransitionAction::StartDevelopment);
+    assert_eq!(payload.release_id, event.release_id);
+    assert_eq!(serde_json::from_value::<TransitionEvent>(payload.payload).unwrap(), event);
+}
+
+#[tokio::test]
+async fn test_delivery_is_retried_on_server_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+    let webhooks = InMemoryWebhookService::new().with_retry(3, Duration::from_millis(10));
+    webhooks.register_subscription(WebhookSubscription::new(server.uri(), vec![TransitionAction::StartDevelopment], "s3cret".to_string())).await.unwrap();
+
+    webhooks.notify(&start_development_event()).await.unwrap();
+
+    assert_eq!(received_requests(&server, 3).await.len(), 3);
+}
+
+#[tokio::test]
+async fn test_only_active_subscriptions_for_the_action_match() {
+    let event = start_development_event();
+    let mut subscription = WebhookSubscription::new("https://ci.example.com/hook".to_string(), vec![TransitionAction::StartDevelopment], "s3cret".to_string());
+    assert!(subscription.matches(&event));
+    assert!(!subscription.matches(&TransitionEvent { action: Some(TransitionAction::CompleteBuild), ..event.clone() }));
+    assert!(!subscription.matches(&TransitionEvent { action: None, ..event.clone() }));
+    subscription.active = false;
+    assert!(!subscription.matches(&event));
+}
+
+#[tokio::test]
+async fn test_webhook_endpoints() {
+    let webhooks = Arc::new(InMemoryWebhookService::new());
+    let router = webhook_router(webhooks.clone(), Arc::new(InMemoryAuditLogService::new()));
+    let register = |url: &str| {
+        Request::post("/")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::json!({"url": url, "events": ["start_build", "fail"], "secret": "s3cret"}).to_string()))
+            .unwrap()
+    };
+
+    let response = router.clone().oneshot(register("ftp://ci.example.com/hook")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = router.clone().oneshot(register("https://ci.example.com/hook")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = router.clone().oneshot(Request::get("/").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    let listed = body.as_array().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0]["events"], serde_json::json!(["start_build", "fail"]));
+    assert!(listed[0].get("secret").is_none(), "the secret must not be returned");
+
+    let id = listed[0]["id"].as_str().unwrap();
+    let response = router.clone().oneshot(Request::delete(format!("/{}", id)).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(!webhooks.list_subscriptions().await.unwrap()[0].active);
+    let response = router.oneshot(Request::delete(format!("/{}", Uuid::new_v4())).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}